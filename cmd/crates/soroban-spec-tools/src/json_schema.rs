@@ -0,0 +1,321 @@
+//! JSON Schemas of the JSON values that [`Spec::from_json`] accepts for each type of a
+//! contract spec, for form generators and request validators.
+use serde_json::{json, Map, Value};
+use stellar_xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use crate::{Error, Spec};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl Spec {
+    /// A schema document with the contract's types under `$defs` and, under `functions`, the
+    /// schemas of the arguments and return value of each function.
+    ///
+    /// With `function`, the document is instead the schema of that function's arguments, as an
+    /// object keyed by argument name, with the types it uses under `$defs`.
+    pub fn json_schema(&self, function: Option<&str>) -> Result<Value, Error> {
+        let defs = self.json_schema_defs()?;
+        if let Some(function) = function {
+            let mut schema = args_schema(self.find_function(function)?);
+            schema["$schema"] = json!(DRAFT);
+            schema["$defs"] = Value::Object(defs);
+            return Ok(schema);
+        }
+        let functions = self
+            .find_functions()?
+            .filter(|f| !f.name.to_string_lossy().starts_with("__"))
+            .map(|f| {
+                let returns = f
+                    .outputs
+                    .first()
+                    .map_or(json!({ "type": "null" }), type_schema);
+                (
+                    f.name.to_string_lossy(),
+                    with_description(
+                        json!({ "args": args_schema(f), "returns": returns }),
+                        &f.doc.to_string_lossy(),
+                    ),
+                )
+            })
+            .collect::<Map<_, _>>();
+        Ok(json!({
+            "$schema": DRAFT,
+            "$defs": defs,
+            "functions": functions,
+        }))
+    }
+
+    /// The schemas of the contract's types, by name.
+    pub fn json_schema_defs(&self) -> Result<Map<String, Value>, Error> {
+        Ok(self
+            .0
+            .as_ref()
+            .ok_or(Error::MissingSpec)?
+            .iter()
+            .filter_map(|entry| {
+                let (name, schema) = match entry {
+                    ScSpecEntry::UdtStructV0(strukt) => (&strukt.name, struct_schema(strukt)),
+                    ScSpecEntry::UdtUnionV0(union) => (&union.name, union_schema(union)),
+                    ScSpecEntry::UdtEnumV0(enum_) => (&enum_.name, enum_schema(enum_)),
+                    ScSpecEntry::UdtErrorEnumV0(enum_) => (&enum_.name, error_enum_schema(enum_)),
+                    ScSpecEntry::FunctionV0(_) => return None,
+                };
+                Some((name.to_string_lossy(), schema))
+            })
+            .collect())
+    }
+}
+
+/// The schema of a value of `type_`.
+pub fn type_schema(type_: &ScType) -> Value {
+    match type_ {
+        ScType::Bool => json!({ "type": "boolean" }),
+        ScType::Void => json!({ "type": "null" }),
+        ScType::U32 => json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX }),
+        ScType::I32 => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        ScType::U64 | ScType::Timepoint | ScType::Duration => {
+            json!({ "type": "integer", "minimum": 0, "maximum": u64::MAX })
+        }
+        ScType::I64 => json!({ "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX }),
+        // Numbers beyond 64 bits are passed as strings
+        ScType::U128 => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        ScType::I128 => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        ScType::U256 => json!({ "type": "string", "pattern": "^(0x[0-9a-fA-F]+|[0-9]+)$" }),
+        ScType::I256 => json!({ "type": "string", "pattern": "^-?(0x[0-9a-fA-F]+|[0-9]+)$" }),
+        ScType::String => json!({ "type": "string" }),
+        ScType::Symbol => {
+            json!({ "type": "string", "pattern": "^[a-zA-Z0-9_]*$", "maxLength": 32 })
+        }
+        ScType::Address => json!({ "type": "string", "pattern": "^[GC][A-Z2-7]{55}$" }),
+        ScType::Bytes => json!({ "type": "string", "pattern": "^([0-9a-fA-F]{2})*$" }),
+        ScType::BytesN(bytes) => json!({
+            "type": "string",
+            "pattern": "^[0-9a-fA-F]*$",
+            "maxLength": bytes.n * 2,
+        }),
+        ScType::Option(option) => {
+            json!({ "anyOf": [type_schema(&option.value_type), { "type": "null" }] })
+        }
+        ScType::Result(result) => type_schema(&result.ok_type),
+        ScType::Vec(vec) => json!({ "type": "array", "items": type_schema(&vec.element_type) }),
+        ScType::Set(set) => json!({
+            "type": "array",
+            "items": type_schema(&set.element_type),
+            "uniqueItems": true,
+        }),
+        // Keys are parsed from the object's property names
+        ScType::Map(map) => json!({
+            "type": "object",
+            "propertyNames": type_schema(&map.key_type),
+            "additionalProperties": type_schema(&map.value_type),
+        }),
+        ScType::Tuple(tuple) => tuple_schema(tuple.value_types.iter()),
+        ScType::Udt(udt) => json!({ "$ref": format!("#/$defs/{}", udt.name.to_string_lossy()) }),
+        ScType::Val | ScType::Error => json!({}),
+    }
+}
+
+fn args_schema(ScSpecFunctionV0 { inputs, doc, .. }: &ScSpecFunctionV0) -> Value {
+    let properties = inputs
+        .iter()
+        .map(|input| {
+            (
+                input.name.to_string_lossy(),
+                with_description(type_schema(&input.type_), &input.doc.to_string_lossy()),
+            )
+        })
+        .collect::<Map<_, _>>();
+    // Options may be left out
+    let required = inputs
+        .iter()
+        .filter(|input| !matches!(input.type_, ScType::Option(_)))
+        .map(|input| input.name.to_string_lossy())
+        .collect::<Vec<_>>();
+    with_description(
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        }),
+        &doc.to_string_lossy(),
+    )
+}
+
+fn struct_schema(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> Value {
+    // Tuple structs, with fields named by position, are passed as arrays
+    let schema = if fields.iter().any(|f| f.name.to_string_lossy() == "0") {
+        tuple_schema(fields.iter().map(|f| &f.type_))
+    } else {
+        let properties = fields
+            .iter()
+            .map(|f| {
+                (
+                    f.name.to_string_lossy(),
+                    with_description(type_schema(&f.type_), &f.doc.to_string_lossy()),
+                )
+            })
+            .collect::<Map<_, _>>();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": fields.iter().map(|f| f.name.to_string_lossy()).collect::<Vec<_>>(),
+            "additionalProperties": false,
+        })
+    };
+    with_title(schema, name.to_string_lossy(), &doc.to_string_lossy())
+}
+
+/// Cases without values are passed as their name, and cases with values as an object with
+/// the name as only key, e.g. `"Stopped"` or `{ "Running": 3 }`.
+fn union_schema(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> Value {
+    let cases = cases
+        .iter()
+        .map(|case| match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => with_description(
+                json!({ "const": case.name.to_string_lossy() }),
+                &case.doc.to_string_lossy(),
+            ),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                let name = case.name.to_string_lossy();
+                let values = match case.type_.as_slice() {
+                    [type_] => type_schema(type_),
+                    types => tuple_schema(types.iter()),
+                };
+                with_description(
+                    json!({
+                        "type": "object",
+                        "properties": { &name: values },
+                        "required": [name],
+                        "additionalProperties": false,
+                    }),
+                    &case.doc.to_string_lossy(),
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+    with_title(
+        json!({ "oneOf": cases }),
+        name.to_string_lossy(),
+        &doc.to_string_lossy(),
+    )
+}
+
+fn enum_schema(
+    ScSpecUdtEnumV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtEnumV0,
+) -> Value {
+    int_enum_schema(
+        name.to_string_lossy(),
+        &doc.to_string_lossy(),
+        cases.iter().map(|case| {
+            (
+                case.value,
+                case.name.to_string_lossy(),
+                case.doc.to_string_lossy(),
+            )
+        }),
+    )
+}
+
+fn error_enum_schema(
+    ScSpecUdtErrorEnumV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtErrorEnumV0,
+) -> Value {
+    int_enum_schema(
+        name.to_string_lossy(),
+        &doc.to_string_lossy(),
+        cases.iter().map(|case| {
+            (
+                case.value,
+                case.name.to_string_lossy(),
+                case.doc.to_string_lossy(),
+            )
+        }),
+    )
+}
+
+fn int_enum_schema(
+    name: String,
+    doc: &str,
+    cases: impl Iterator<Item = (u32, String, String)>,
+) -> Value {
+    let cases = cases
+        .map(|(value, name, doc)| with_description(json!({ "const": value, "title": name }), &doc))
+        .collect::<Vec<_>>();
+    with_title(json!({ "type": "integer", "oneOf": cases }), name, doc)
+}
+
+fn tuple_schema<'a>(types: impl ExactSizeIterator<Item = &'a ScType>) -> Value {
+    let len = types.len();
+    json!({
+        "type": "array",
+        "prefixItems": types.map(type_schema).collect::<Vec<_>>(),
+        "minItems": len,
+        "maxItems": len,
+    })
+}
+
+fn with_title(mut schema: Value, title: String, doc: &str) -> Value {
+    schema["title"] = json!(title);
+    with_description(schema, doc)
+}
+
+pub(crate) fn with_description(mut schema: Value, doc: &str) -> Value {
+    if !doc.is_empty() {
+        schema["description"] = json!(doc);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::{ScSpecTypeOption, ScSpecTypeTuple, ScSpecTypeUdt};
+
+    #[test]
+    fn nested_types() {
+        let type_ = ScType::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(ScType::Tuple(Box::new(ScSpecTypeTuple {
+                value_types: vec![
+                    ScType::U32,
+                    ScType::Udt(ScSpecTypeUdt {
+                        name: "Point".try_into().unwrap(),
+                    }),
+                ]
+                .try_into()
+                .unwrap(),
+            }))),
+        }));
+        assert_eq!(
+            type_schema(&type_),
+            json!({
+                "anyOf": [
+                    {
+                        "type": "array",
+                        "prefixItems": [
+                            { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+                            { "$ref": "#/$defs/Point" },
+                        ],
+                        "minItems": 2,
+                        "maxItems": 2,
+                    },
+                    { "type": "null" },
+                ]
+            })
+        );
+    }
+}
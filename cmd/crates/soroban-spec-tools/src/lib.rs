@@ -13,6 +13,8 @@ use stellar_xdr::{
     ScVec, StringM, UInt128Parts, UInt256Parts, Uint256, VecM,
 };
 
+pub mod json_schema;
+pub mod open_rpc;
 pub mod utils;
 
 #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,77 @@
+//! [OpenRPC](https://spec.open-rpc.org) documents describing a contract's functions as methods.
+use serde_json::{json, Value};
+use stellar_xdr::ScSpecTypeDef as ScType;
+
+use crate::{
+    json_schema::{type_schema, with_description},
+    Error, Spec,
+};
+
+const VERSION: &str = "1.2.6";
+
+impl Spec {
+    /// An OpenRPC document with a method per function, taking its arguments by name, and the
+    /// contract's types under `components.schemas`.
+    pub fn open_rpc(&self, title: &str, version: &str) -> Result<Value, Error> {
+        let schemas = self.json_schema_defs()?;
+        let methods = self
+            .find_functions()?
+            .filter(|f| !f.name.to_string_lossy().starts_with("__"))
+            .map(|f| {
+                let params = f
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        with_description(
+                            json!({
+                                "name": input.name.to_string_lossy(),
+                                "required": !matches!(input.type_, ScType::Option(_)),
+                                "schema": type_schema(&input.type_),
+                            }),
+                            &input.doc.to_string_lossy(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let result = f
+                    .outputs
+                    .first()
+                    .map_or(json!({ "type": "null" }), type_schema);
+                with_description(
+                    json!({
+                        "name": f.name.to_string_lossy(),
+                        "paramStructure": "by-name",
+                        "params": params,
+                        "result": { "name": "result", "schema": result },
+                    }),
+                    &f.doc.to_string_lossy(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut document = json!({
+            "openrpc": VERSION,
+            "info": { "title": title, "version": version },
+            "methods": methods,
+            "components": { "schemas": schemas },
+        });
+        rebase_refs(&mut document);
+        Ok(document)
+    }
+}
+
+/// Point the `$defs` references of the JSON Schemas to `components.schemas`.
+fn rebase_refs(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        *reference = reference.replace("#/$defs/", "#/components/schemas/");
+                    }
+                    value => rebase_refs(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(rebase_refs),
+        _ => {}
+    }
+}
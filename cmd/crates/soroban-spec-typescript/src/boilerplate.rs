@@ -8,7 +8,7 @@ use std::{
 };
 use stellar_xdr::ScSpecEntry;
 
-use super::generate;
+use super::{generate, generate_shared, generate_tests, shared_type_imports, shared_types};
 
 static PROJECT_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/project_template");
 
@@ -52,6 +52,121 @@ impl Project {
         self.append_index_ts(spec)
     }
 
+    /// Add a vitest suite exercising the contract's functions, run with `npm test`.
+    pub fn add_tests(&self, contract_name: &str, spec: &[ScSpecEntry]) -> std::io::Result<()> {
+        let package_json = self.0.join("package.json");
+        let mut package: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&package_json)?)?;
+        package["scripts"]["test"] = "vitest run".into();
+        package["devDependencies"]["vitest"] = "0.34.6".into();
+        fs::write(
+            &package_json,
+            serde_json::to_string_pretty(&package)? + "\n",
+        )?;
+        fs::create_dir_all(self.0.join("test"))?;
+        fs::write(
+            self.0.join("test/contract.test.ts"),
+            generate_tests(contract_name, spec),
+        )
+    }
+
+    /// Initialize a package with a client for each of several contracts, under
+    /// `src/<contract name>/` and exported as a namespace from `src/index.ts`. The types the
+    /// contracts have in common are generated once, in `src/types.ts`, and imported by each of
+    /// their modules.
+    pub fn init_workspace(
+        &self,
+        package_name: &str,
+        contracts: &[WorkspaceContract],
+        rpc_url: &str,
+        network_passphrase: &str,
+    ) -> std::io::Result<()> {
+        let root: &Path = self.as_ref();
+        let specs = contracts.iter().map(|c| c.spec).collect::<Vec<_>>();
+        let shared = shared_types(&specs);
+        let imports = shared_type_imports(&shared);
+        let template = PROJECT_DIR
+            .get_dir("src")
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
+        let mut index = "export * from './types.js';\n".to_string();
+        for contract in contracts {
+            let dir = root.join("src").join(contract.name);
+            fs::create_dir_all(&dir)?;
+            for file in template.files() {
+                let contents = fill_placeholders(
+                    file.contents_utf8().unwrap_or_default(),
+                    contract.name,
+                    contract.contract_id,
+                    rpc_url,
+                    network_passphrase,
+                );
+                fs::write(
+                    dir.join(file.path().file_name().unwrap_or_default()),
+                    contents,
+                )?;
+            }
+            let spec = contract
+                .spec
+                .iter()
+                .filter(|entry| !shared.contains(entry))
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut code = String::new();
+            if !imports.is_empty() {
+                code.push_str(&format!(
+                    "import {{ {imports} }} from '../types.js';\nexport * from '../types.js';\n\n"
+                ));
+            }
+            code.push_str(&generate(&spec));
+            fs::OpenOptions::new()
+                .append(true)
+                .open(dir.join("index.ts"))?
+                .write_all(code.as_bytes())?;
+            index.push_str(&format!(
+                "export * as {} from './{}/index.js';\n",
+                contract.name.to_lower_camel_case(),
+                contract.name
+            ));
+        }
+        // Only the conversions are shared at the root, each contract has its own constants
+        for file in [
+            "constants.ts",
+            "invoke.ts",
+            "method-options.ts",
+            "server.ts",
+        ] {
+            fs::remove_file(root.join("src").join(file))?;
+        }
+        fs::write(root.join("src/index.ts"), index)?;
+        fs::write(
+            root.join("src/types.ts"),
+            format!("{SHARED_TYPES_PRELUDE}\n{}", generate_shared(&shared)),
+        )?;
+        let tsconfig = root.join("tsconfig.json");
+        fs::write(
+            &tsconfig,
+            fs::read_to_string(&tsconfig)?.replace("\"src/*\"", "\"src/**/*\""),
+        )?;
+        let contract_ids = contracts
+            .iter()
+            .map(|c| format!("{}:{}", c.name, c.contract_id))
+            .collect::<Vec<_>>()
+            .join(",");
+        ["package.json", "README.md"]
+            .into_iter()
+            .try_for_each(|file_name| {
+                let file = &root.join(file_name);
+                let contents = fill_placeholders(
+                    &fs::read_to_string(file)?,
+                    package_name,
+                    &contract_ids,
+                    rpc_url,
+                    network_passphrase,
+                );
+                fs::write(file, contents)
+            })
+    }
+
     fn replace_placeholder_patterns(
         &self,
         contract_name: &str,
@@ -59,20 +174,6 @@ impl Project {
         rpc_url: &str,
         network_passphrase: &str,
     ) -> std::io::Result<()> {
-        let replacement_strings = &[
-            ("INSERT_CONTRACT_NAME_HERE", contract_name),
-            (
-                "INSERT_SCREAMING_SNAKE_CASE_CONTRACT_NAME_HERE",
-                &contract_name.to_shouty_snake_case(),
-            ),
-            (
-                "INSERT_CAMEL_CASE_CONTRACT_NAME_HERE",
-                &contract_name.to_lower_camel_case(),
-            ),
-            ("INSERT_CONTRACT_ID_HERE", contract_id),
-            ("INSERT_NETWORK_PASSPHRASE_HERE", network_passphrase),
-            ("INSERT_RPC_URL_HERE", rpc_url),
-        ];
         let root: &Path = self.as_ref();
         [
             "package.json",
@@ -84,10 +185,13 @@ impl Project {
         .into_iter()
         .try_for_each(|file_name| {
             let file = &root.join(file_name);
-            let mut contents = fs::read_to_string(file).unwrap();
-            for (pattern, replacement) in replacement_strings {
-                contents = contents.replace(pattern, replacement);
-            }
+            let contents = fill_placeholders(
+                &fs::read_to_string(file).unwrap(),
+                contract_name,
+                contract_id,
+                rpc_url,
+                network_passphrase,
+            );
             fs::write(file, contents)
         })
     }
@@ -100,6 +204,62 @@ impl Project {
     }
 }
 
+/// A contract of a package generated with [`Project::init_workspace`].
+pub struct WorkspaceContract<'a> {
+    /// Name of the contract's module, e.g. `token`
+    pub name: &'a str,
+    pub contract_id: &'a str,
+    pub spec: &'a [ScSpecEntry],
+}
+
+/// The imports and helper types the generated types use, which the template's `index.ts` has
+/// for single contract packages.
+const SHARED_TYPES_PRELUDE: &str = r#"import { xdr } from 'soroban-client';
+import { Buffer } from "buffer";
+import { scValStrToJs, scValToJs, addressToScVal, u128ToScVal, i128ToScVal, strToScVal } from './convert.js';
+
+type u32 = number;
+type i32 = number;
+type u64 = bigint;
+type i64 = bigint;
+type u128 = bigint;
+type i128 = bigint;
+type u256 = bigint;
+type i256 = bigint;
+type Address = string;
+type Option<T> = T | undefined;
+type Typepoint = bigint;
+type Duration = bigint;
+"#;
+
+fn fill_placeholders(
+    contents: &str,
+    contract_name: &str,
+    contract_id: &str,
+    rpc_url: &str,
+    network_passphrase: &str,
+) -> String {
+    let replacement_strings = &[
+        ("INSERT_CONTRACT_NAME_HERE", contract_name),
+        (
+            "INSERT_SCREAMING_SNAKE_CASE_CONTRACT_NAME_HERE",
+            &contract_name.to_shouty_snake_case(),
+        ),
+        (
+            "INSERT_CAMEL_CASE_CONTRACT_NAME_HERE",
+            &contract_name.to_lower_camel_case(),
+        ),
+        ("INSERT_CONTRACT_ID_HERE", contract_id),
+        ("INSERT_NETWORK_PASSPHRASE_HERE", network_passphrase),
+        ("INSERT_RPC_URL_HERE", rpc_url),
+    ];
+    let mut contents = contents.to_string();
+    for (pattern, replacement) in replacement_strings {
+        contents = contents.replace(pattern, replacement);
+    }
+    contents
+}
+
 #[cfg(test)]
 mod test {
     use temp_dir::TempDir;
@@ -141,6 +301,47 @@ mod test {
         assert_dirs_equal(temp_dir.path(), &fixture);
     }
 
+    #[test]
+    fn add_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        let p = init(temp_dir.path()).unwrap();
+        let spec = soroban_spec::read::from_wasm(EXAMPLE_WASM).unwrap();
+        p.add_tests("test_custom_types", &spec).unwrap();
+        let package: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("package.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(package["scripts"]["test"], "vitest run");
+        let tests = fs::read_to_string(temp_dir.path().join("test/contract.test.ts")).unwrap();
+        assert!(tests.contains(r#"describe("test_custom_types""#));
+    }
+
+    #[test]
+    fn workspace_shares_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = soroban_spec::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let p: Project = temp_dir.path().to_path_buf().try_into().unwrap();
+        let contract = |name| WorkspaceContract {
+            name,
+            contract_id: "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE",
+            spec: &spec,
+        };
+        p.init_workspace(
+            "custom_types",
+            &[contract("first"), contract("second")],
+            "https://rpc-futurenet.stellar.org:443/soroban/rpc",
+            "Test SDF Future Network ; October 2022",
+        )
+        .unwrap();
+        let types = fs::read_to_string(temp_dir.path().join("src/types.ts")).unwrap();
+        assert!(types.contains("export function TestFromXdr"));
+        let first = fs::read_to_string(temp_dir.path().join("src/first/index.ts")).unwrap();
+        assert!(first.contains("from '../types.js'"));
+        assert!(!first.contains("export interface Test "));
+        let index = fs::read_to_string(temp_dir.path().join("src/index.ts")).unwrap();
+        assert!(index.contains("export * as second from './second/index.js';"));
+    }
+
     #[ignore]
     #[test]
     fn build_package() {
@@ -4,13 +4,13 @@
     clippy::missing_panics_doc
 )]
 
-use std::{fs, io};
+use std::{collections::BTreeMap, fs, io};
 
 use crate::types::{StructField, Type, UnionCase};
 use heck::ToLowerCamelCase;
 use itertools::Itertools;
 use sha2::{Digest, Sha256};
-use stellar_xdr::ScSpecEntry;
+use stellar_xdr::{ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0};
 
 use types::Entry;
 
@@ -73,6 +73,139 @@ pub fn generate(spec: &[ScSpecEntry]) -> String {
     collected.iter().map(entry_to_ts).join("\n")
 }
 
+/// Types defined identically by several of the contracts of a workspace, along with the types
+/// they use, which are generated once in the workspace's `types.ts` rather than in each
+/// contract's module. Error enums stay with their contract, whose functions look them up.
+pub fn shared_types(specs: &[&[ScSpecEntry]]) -> Vec<ScSpecEntry> {
+    let mut definitions = BTreeMap::<String, Vec<&ScSpecEntry>>::new();
+    for entry in specs.iter().flat_map(|spec| spec.iter()) {
+        if let Some(name) = udt_name(entry) {
+            definitions.entry(name).or_default().push(entry);
+        }
+    }
+    let mut shared = definitions
+        .into_iter()
+        .filter(|(name, entries)| {
+            name != "Error" && entries.len() > 1 && entries.iter().all(|e| e == &entries[0])
+        })
+        .map(|(name, entries)| (name, entries[0]))
+        .collect::<BTreeMap<_, _>>();
+    // A type can only move if all the types it uses move with it
+    loop {
+        let unresolved = shared
+            .iter()
+            .filter(|(_, entry)| {
+                let mut used = vec![];
+                udts_of_entry(entry, &mut used);
+                used.iter().any(|name| !shared.contains_key(name))
+            })
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        if unresolved.is_empty() {
+            break;
+        }
+        for name in unresolved {
+            shared.remove(&name);
+        }
+    }
+    shared.into_values().cloned().collect()
+}
+
+/// Generate the shared types of a workspace, exported with their conversion functions.
+pub fn generate_shared(spec: &[ScSpecEntry]) -> String {
+    spec.iter()
+        .map(|entry| entry_to_ts(&Entry::from(entry)).replace("\nfunction ", "\nexport function "))
+        .join("\n")
+}
+
+/// The name of a type other than a function, along with its conversion functions, e.g.
+/// `Point, PointToXdr, PointFromXdr`.
+pub fn shared_type_imports(spec: &[ScSpecEntry]) -> String {
+    spec.iter()
+        .filter_map(udt_name)
+        .map(|name| format!("{name}, {name}ToXdr, {name}FromXdr"))
+        .join(", ")
+}
+
+fn udt_name(entry: &ScSpecEntry) -> Option<String> {
+    match entry {
+        ScSpecEntry::UdtStructV0(s) => Some(s.name.to_string_lossy()),
+        ScSpecEntry::UdtUnionV0(u) => Some(u.name.to_string_lossy()),
+        ScSpecEntry::UdtEnumV0(e) => Some(e.name.to_string_lossy()),
+        ScSpecEntry::FunctionV0(_) | ScSpecEntry::UdtErrorEnumV0(_) => None,
+    }
+}
+
+fn udts_of_entry(entry: &ScSpecEntry, out: &mut Vec<String>) {
+    match entry {
+        ScSpecEntry::UdtStructV0(s) => s.fields.iter().for_each(|f| udts_of_type(&f.type_, out)),
+        ScSpecEntry::UdtUnionV0(u) => {
+            for case in u.cases.iter() {
+                if let ScSpecUdtUnionCaseV0::TupleV0(case) = case {
+                    case.type_.iter().for_each(|t| udts_of_type(t, out));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn udts_of_type(type_: &ScSpecTypeDef, out: &mut Vec<String>) {
+    match type_ {
+        ScSpecTypeDef::Option(o) => udts_of_type(&o.value_type, out),
+        ScSpecTypeDef::Result(r) => {
+            udts_of_type(&r.ok_type, out);
+            udts_of_type(&r.error_type, out);
+        }
+        ScSpecTypeDef::Vec(v) => udts_of_type(&v.element_type, out),
+        ScSpecTypeDef::Set(s) => udts_of_type(&s.element_type, out),
+        ScSpecTypeDef::Map(m) => {
+            udts_of_type(&m.key_type, out);
+            udts_of_type(&m.value_type, out);
+        }
+        ScSpecTypeDef::Tuple(t) => t.value_types.iter().for_each(|t| udts_of_type(t, out)),
+        ScSpecTypeDef::Udt(u) => out.push(u.name.to_string_lossy()),
+        _ => {}
+    }
+}
+
+/// Generate a vitest suite that simulates each function taking no arguments, which are
+/// typically getters, and leaves a todo for each function that needs arguments.
+pub fn generate_tests(contract_name: &str, spec: &[ScSpecEntry]) -> String {
+    let cases = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some(f),
+            _ => None,
+        })
+        .map(|f| {
+            let name = f.name.to_string_lossy();
+            if f.inputs.is_empty() {
+                let js_name = jsify_name(&name);
+                format!(
+                    r#"  it("{name}", async () => {{
+    const result = await contract.{js_name}({{ responseType: 'simulated' }});
+    expect(result).toBeDefined();
+    expect((result as any).error).toBeUndefined();
+  }});"#
+                )
+            } else {
+                format!(r#"  it.todo("{name}");"#)
+            }
+        })
+        .join("\n\n");
+    format!(
+        r#"import {{ describe, expect, it }} from 'vitest';
+import * as contract from '../src/index.js';
+
+// Simulated against the network in src/constants.ts, so nothing is submitted.
+describe("{contract_name}", () => {{
+{cases}
+}});
+"#
+    )
+}
+
 fn doc_to_ts_doc(doc: &str) -> String {
     if doc.is_empty() {
         String::new()
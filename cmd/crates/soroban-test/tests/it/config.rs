@@ -1,8 +1,11 @@
+use assert_cmd::Command;
 use assert_fs::TempDir;
 use soroban_test::{temp_ledger_file, TestEnv};
 use std::{fs, path::Path};
 
-use crate::util::{add_identity, add_test_id, SecretKind, DEFAULT_SEED_PHRASE, HELLO_WORLD};
+use crate::util::{
+    add_identity, add_test_id, SecretKind, DEFAULT_PUB_KEY, DEFAULT_SEED_PHRASE, HELLO_WORLD,
+};
 use soroban_cli::commands::config::network;
 
 const NETWORK_PASSPHRASE: &str = "Local Sandbox Stellar Network ; September 2022";
@@ -185,6 +188,36 @@ fn generate_identity() {
     );
 }
 
+#[test]
+fn bip39_passphrase_is_not_saved() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("config")
+        .env("SOROBAN_BIP39_PASSPHRASE", "hunter2")
+        .args(["identity", "generate", "--seed", "0000000000000000"])
+        .args(["--bip39-passphrase", "test"])
+        .assert()
+        .success();
+    let file_contents =
+        fs::read_to_string(sandbox.dir().join(".soroban/identity/test.toml")).unwrap();
+    assert_eq!(
+        file_contents,
+        format!("seed_phrase = \"{DEFAULT_SEED_PHRASE}\"\nbip39_passphrase = true\n")
+    );
+    let address = |passphrase: &str| {
+        let output = sandbox
+            .new_assert_cmd("config")
+            .env("SOROBAN_BIP39_PASSPHRASE", passphrase)
+            .args(["identity", "address", "test"])
+            .assert()
+            .success();
+        String::from_utf8(output.get_output().stdout.clone()).unwrap()
+    };
+    let with_passphrase = address("hunter2");
+    assert_ne!(with_passphrase.trim(), DEFAULT_PUB_KEY);
+    assert_ne!(with_passphrase, address("hunter3"));
+}
+
 #[test]
 fn seed_phrase() {
     let sandbox = TestEnv::default();
@@ -267,3 +300,70 @@ fn test_hd_path(sandbox: &TestEnv, hd_path: usize) {
     let test_address = sandbox.test_address(hd_path);
     assert_eq!(pub_key, test_address);
 }
+
+#[test]
+fn add_show_and_remove_contract_alias() {
+    let sandbox = TestEnv::default();
+    let id = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "add", "hello", "--id", id])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "show", "hello"])
+        .assert()
+        .success()
+        .stdout(format!("{id}\n"));
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "ls"])
+        .assert()
+        .success()
+        .stdout(format!("hello: {id}\n"));
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "rm", "hello"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "ls"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn doctor_finds_unreadable_identity() {
+    let sandbox = TestEnv::default();
+    let ident_dir = sandbox.dir().join(".soroban/identity");
+    fs::create_dir_all(&ident_dir).unwrap();
+    fs::write(ident_dir.join("broken.toml"), "not a secret").unwrap();
+    sandbox
+        .new_assert_cmd("config")
+        .args(["doctor", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("error: identity broken"));
+}
+
+#[test]
+fn output_format_json() {
+    let sandbox = TestEnv::default();
+    Command::cargo_bin("soroban")
+        .unwrap()
+        .current_dir(sandbox.dir())
+        .args(["--output-format", "json", "config", "identity", "address"])
+        .assert()
+        .success()
+        .stdout(format!("{{\"address\":\"{DEFAULT_PUB_KEY}\"}}\n"));
+    Command::cargo_bin("soroban")
+        .unwrap()
+        .current_dir(sandbox.dir())
+        .args(["--output-format", "quiet", "config", "identity", "address"])
+        .assert()
+        .success()
+        .stdout("");
+}
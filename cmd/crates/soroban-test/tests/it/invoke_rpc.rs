@@ -0,0 +1,398 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
+use soroban_cli::commands::config::{locator::KeyType, secret::Secret};
+use soroban_env_host::xdr::LedgerKey;
+use soroban_test::TestEnv;
+
+use crate::{
+    mock_rpc::{self, MockRpc, PASSPHRASE},
+    util::{DEFAULT_SECRET_KEY, HELLO_WORLD},
+};
+
+fn invoke(sandbox: &TestEnv, rpc: &MockRpc) -> Command {
+    let mut cmd = sandbox.new_assert_cmd("contract");
+    cmd.arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--rpc-url")
+        .arg(&rpc.url)
+        .arg("--network-passphrase")
+        .arg(PASSPHRASE)
+        .arg("--source")
+        .arg(DEFAULT_SECRET_KEY);
+    cmd
+}
+
+fn reserved_sequences(sandbox: &TestEnv) -> Option<String> {
+    std::fs::read_to_string(sandbox.dir().join(".soroban/sequences.toml")).ok()
+}
+
+#[test]
+fn previews_and_failed_simulations_do_not_reserve_a_sequence() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(mock_rpc::account(41)),
+        _ => Err(format!("{method} failed")),
+    });
+    let auth_dir = sandbox.dir().join("auth");
+    for args in [
+        vec!["--send=no"],
+        vec!["--auth-out", auth_dir.to_str().unwrap()],
+        vec![],
+    ] {
+        invoke(&sandbox, &rpc)
+            .args(args)
+            .args(["--", "hello", "--world=world"])
+            .assert()
+            .failure();
+    }
+    assert_eq!(rpc.calls("simulateTransaction").len(), 3);
+    assert_eq!(reserved_sequences(&sandbox), None);
+}
+
+#[test]
+fn sequence_is_reserved_before_signing() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(mock_rpc::account(41)),
+        "simulateTransaction" => Ok(mock_rpc::simulation(100)),
+        _ => Err(format!("{method} failed")),
+    });
+    invoke(&sandbox, &rpc)
+        .args(["--", "hello", "--world=world"])
+        .assert()
+        .failure();
+    assert_eq!(rpc.calls("sendTransaction").len(), 1);
+    assert!(reserved_sequences(&sandbox).unwrap().contains("= 42"));
+}
+
+/// `contract deploy --manifest` of the hello world contract, after the global `flags`.
+fn deploy_manifest(sandbox: &TestEnv, rpc: &MockRpc, flags: &[&str]) -> Command {
+    let manifest = sandbox.dir().join("deploy.toml");
+    std::fs::write(
+        &manifest,
+        format!(
+            "[[contract]]\nname = \"hello\"\nwasm = {:?}\n",
+            HELLO_WORLD.path()
+        ),
+    )
+    .unwrap();
+    let mut cmd = Command::cargo_bin("soroban").unwrap();
+    cmd.current_dir(sandbox.dir())
+        .arg("-q")
+        .args(flags)
+        .arg("contract")
+        .arg("deploy")
+        .arg("--manifest")
+        .arg(manifest)
+        .arg("--rpc-url")
+        .arg(&rpc.url)
+        .arg("--network-passphrase")
+        .arg(PASSPHRASE)
+        .arg("--source")
+        .arg(DEFAULT_SECRET_KEY);
+    cmd
+}
+
+fn uninstalled_network(method: &str) -> Result<serde_json::Value, String> {
+    match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(serde_json::json!({ "entries": null, "latestLedger": "1" })),
+        "simulateTransaction" => Ok(mock_rpc::simulation(100_000)),
+        _ => Err(format!("{method} failed")),
+    }
+}
+
+#[test]
+fn manifest_over_max_total_fee_submits_nothing() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| uninstalled_network(method));
+    deploy_manifest(&sandbox, &rpc, &["--yes"])
+        .arg("--max-total-fee=1000")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("install"))
+        .stderr(predicates::str::contains("--max-total-fee 1000"));
+    assert_eq!(rpc.calls("simulateTransaction").len(), 1);
+    assert!(rpc.calls("sendTransaction").is_empty());
+}
+
+#[test]
+fn manifest_is_not_confirmed_without_a_terminal() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, params| {
+        let is_account = matches!(mock_rpc::requested_key(params), Some(LedgerKey::Account(_)));
+        if method == "getLedgerEntries" && is_account {
+            Ok(mock_rpc::account(41))
+        } else {
+            uninstalled_network(method)
+        }
+    });
+    deploy_manifest(&sandbox, &rpc, &[])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Estimated total fee: 100100 stroops",
+        ))
+        .stderr(predicates::str::contains("not confirmed").not());
+    assert_eq!(rpc.calls("sendTransaction").len(), 1);
+}
+
+#[test]
+fn dry_run_prints_the_transaction_and_succeeds() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(mock_rpc::account(41)),
+        "simulateTransaction" => Ok(mock_rpc::simulation(100)),
+        _ => Err(format!("{method} failed")),
+    });
+    Command::cargo_bin("soroban")
+        .unwrap()
+        .current_dir(sandbox.dir())
+        .args(["-q", "--dry-run", "contract", "invoke", "--id=1", "--wasm"])
+        .arg(HELLO_WORLD.path())
+        .arg("--rpc-url")
+        .arg(&rpc.url)
+        .arg("--network-passphrase")
+        .arg(PASSPHRASE)
+        .arg("--source")
+        .arg(DEFAULT_SECRET_KEY)
+        .args(["--", "hello", "--world=world"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Envelope: "));
+    assert!(rpc.calls("sendTransaction").is_empty());
+    assert_eq!(reserved_sequences(&sandbox), None);
+}
+
+#[test]
+fn instructions_need_a_resource_fee() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| Err(format!("{method} failed")));
+    invoke(&sandbox, &rpc)
+        .args(["--instructions=1000000", "--", "hello", "--world=world"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--resource-fee"));
+    assert!(rpc.calls("simulateTransaction").is_empty());
+}
+
+#[test]
+fn rpc_requests_and_responses_are_logged_at_debug() {
+    let sandbox = TestEnv::default();
+    let rpc = MockRpc::start(|method, _| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(mock_rpc::account(41)),
+        "simulateTransaction" => Ok(mock_rpc::simulation(100)),
+        _ => Err(format!("{method} failed")),
+    });
+    let log = sandbox.dir().join("soroban.log");
+    Command::cargo_bin("soroban")
+        .unwrap()
+        .current_dir(sandbox.dir())
+        .arg("--log-file")
+        .arg(&log)
+        .args(["contract", "invoke", "--id=1", "--send=no", "--wasm"])
+        .arg(HELLO_WORLD.path())
+        .arg("--rpc-url")
+        .arg(&rpc.url)
+        .arg("--network-passphrase")
+        .arg(PASSPHRASE)
+        .arg("--source")
+        .arg(DEFAULT_SECRET_KEY)
+        .args(["--", "hello", "--world=world"])
+        .assert()
+        .success();
+    let log = std::fs::read_to_string(log).unwrap();
+    for method in ["getNetwork", "getLedgerEntries", "simulateTransaction"] {
+        let lines = log
+            .lines()
+            .filter(|line| line.contains("DEBUG") && line.contains(method))
+            .collect::<Vec<_>>();
+        assert!(
+            lines.iter().any(|l| l.contains("params")),
+            "{method}: {log}"
+        );
+        assert!(
+            lines.iter().any(|l| l.contains("response")),
+            "{method}: {log}"
+        );
+    }
+}
+
+/// Save an identity `signer` for the test seed phrase's key, signing with a stub script that
+/// prints the signature saved in `signatures/<hash>`, or records the hash in `requested` and
+/// fails if there is none.
+fn add_stub_signer(sandbox: &TestEnv) {
+    let dir = sandbox.dir().join("signer");
+    std::fs::create_dir_all(dir.join("signatures")).unwrap();
+    let script = dir.join("signer.sh");
+    std::fs::write(
+        &script,
+        format!(
+            concat!(
+                "read hash\n",
+                "cat {dir}/signatures/$hash 2>/dev/null && exit 0\n",
+                "echo $hash > {dir}/requested\n",
+                "exit 1\n",
+            ),
+            dir = dir.display()
+        ),
+    )
+    .unwrap();
+    let public_key = Secret::test_seed_phrase()
+        .unwrap()
+        .public_key(None)
+        .unwrap();
+    KeyType::Identity
+        .write(
+            "signer",
+            &Secret::External {
+                command: format!("sh {}", script.display()),
+                public_key: public_key.to_string(),
+            },
+            &sandbox.dir().join(".soroban"),
+        )
+        .unwrap();
+}
+
+/// Run `cmd` with the stub signer, which fails the first time, then sign the hash it was asked
+/// for and run `cmd` again.
+fn run_with_stub_signer(sandbox: &TestEnv, cmd: impl Fn() -> Command) {
+    let dir = sandbox.dir().join("signer");
+    cmd()
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("exited with"));
+    let requested = std::fs::read_to_string(dir.join("requested")).unwrap();
+    let requested = requested.trim();
+    let hash: [u8; 32] = (0..requested.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&requested[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let signature = Secret::test_seed_phrase()
+        .unwrap()
+        .sign(None, &hash)
+        .unwrap();
+    let signature: String = signature
+        .to_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    std::fs::write(dir.join("signatures").join(requested), signature).unwrap();
+    cmd().assert().failure();
+}
+
+#[test]
+fn external_signers_sign_every_submitted_transaction() {
+    let sandbox = TestEnv::default();
+    add_stub_signer(&sandbox);
+    let rpc = MockRpc::start(|method, _| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => Ok(mock_rpc::account(41)),
+        "simulateTransaction" => Ok(mock_rpc::simulation(100)),
+        _ => Err(format!("{method} failed")),
+    });
+    let wasm = HELLO_WORLD.path();
+    let wasm = wasm.to_str().unwrap();
+    let hash = "0".repeat(64);
+    let commands = [
+        vec!["install", "--force", "--wasm", wasm],
+        vec!["deploy", "--wasm-hash", &hash, "--salt", "1"],
+        vec![
+            "extend",
+            "--wasm",
+            wasm,
+            "--durability",
+            "persistent",
+            "--ledgers",
+            "10",
+        ],
+    ];
+    for (sent, args) in commands.iter().enumerate() {
+        run_with_stub_signer(&sandbox, || {
+            let mut cmd = sandbox.new_assert_cmd("contract");
+            cmd.args(args)
+                .arg("--rpc-url")
+                .arg(&rpc.url)
+                .arg("--network-passphrase")
+                .arg(PASSPHRASE)
+                .arg("--source")
+                .arg("signer");
+            cmd
+        });
+        assert_eq!(rpc.calls("sendTransaction").len(), sent + 1, "{args:?}");
+    }
+}
+
+/// A network with an account and a Stellar asset contract, where simulations succeed until
+/// the `fail_from`th.
+fn asset_contract_network(fail_from: usize) -> MockRpc {
+    let simulations = AtomicUsize::new(0);
+    MockRpc::start(move |method, params| match method {
+        "getNetwork" => Ok(mock_rpc::get_network()),
+        "getLedgerEntries" => match mock_rpc::requested_key(params) {
+            Some(LedgerKey::Account(_)) => Ok(mock_rpc::account(41)),
+            _ => Ok(mock_rpc::stellar_asset_contract()),
+        },
+        "simulateTransaction" if simulations.fetch_add(1, Ordering::SeqCst) + 1 < fail_from => {
+            Ok(mock_rpc::simulation(100))
+        }
+        _ => Err(format!("{method} failed")),
+    })
+}
+
+/// `contract multi-invoke` making `calls` of `set_admin` on the asset contract.
+fn multi_invoke(sandbox: &TestEnv, rpc: &MockRpc, calls: usize) -> Command {
+    let mut cmd = sandbox.new_assert_cmd("contract");
+    cmd.arg("multi-invoke");
+    for _ in 0..calls {
+        cmd.arg("--call").arg(format!(
+            "1 set_admin --new_admin {}",
+            "GBHKQE6YKBKIA5WU5NF3KPBCLV5KVTXYNBQDVIKNI5QZTNZGOHBIDR4S"
+        ));
+    }
+    cmd.arg("--rpc-url")
+        .arg(&rpc.url)
+        .arg("--network-passphrase")
+        .arg(PASSPHRASE)
+        .arg("--source")
+        .arg(DEFAULT_SECRET_KEY);
+    cmd
+}
+
+#[test]
+fn multi_invoke_submits_nothing_if_a_call_fails_simulation() {
+    let sandbox = TestEnv::default();
+    let rpc = asset_contract_network(2);
+    multi_invoke(&sandbox, &rpc, 2)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("call 2"))
+        .stderr(predicates::str::contains("no calls were made"));
+    assert_eq!(rpc.calls("simulateTransaction").len(), 2);
+    assert!(rpc.calls("sendTransaction").is_empty());
+}
+
+#[test]
+fn multi_invoke_over_max_total_fee_submits_nothing() {
+    let sandbox = TestEnv::default();
+    let rpc = asset_contract_network(usize::MAX);
+    multi_invoke(&sandbox, &rpc, 2)
+        .arg("--max-total-fee=300")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Estimated total fee: 400 stroops",
+        ))
+        .stderr(predicates::str::contains("--max-total-fee 300"));
+    assert!(rpc.calls("sendTransaction").is_empty());
+}
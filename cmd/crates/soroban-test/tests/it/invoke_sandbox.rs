@@ -1,3 +1,4 @@
+use assert_cmd::Command;
 use soroban_cli::commands::{
     config::identity,
     contract::{self, fetch},
@@ -72,6 +73,29 @@ fn invoke_hello_world_with_deploy_first() {
         .success();
 }
 
+#[test]
+fn invoke_hello_world_with_alias() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("deploy")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--alias=hello")
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=hello")
+        .arg("--")
+        .arg("hello")
+        .arg("--world=world")
+        .assert()
+        .stdout("[\"Hello\",\"world\"]\n")
+        .success();
+}
+
 #[test]
 fn invoke_hello_world() {
     let sandbox = TestEnv::default();
@@ -89,6 +113,180 @@ fn invoke_hello_world() {
         .success();
 }
 
+#[test]
+fn invoke_with_state_diff() {
+    let sandbox = TestEnv::default();
+    let inc = |expected: &str| {
+        sandbox
+            .new_assert_cmd("contract")
+            .arg("invoke")
+            .arg("--id=1")
+            .arg("--wasm")
+            .arg(HELLO_WORLD.path())
+            .arg("--show-state-diff")
+            .arg("--")
+            .arg("inc")
+            .assert()
+            .stderr(predicates::str::contains(expected.to_string()))
+            .success();
+    };
+    inc("+ CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM persistent \"COUNTER\": 1");
+    inc(
+        "~ CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM persistent \"COUNTER\": 1 -> 2",
+    );
+}
+
+#[test]
+fn extend_contract_instance_and_data() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--")
+        .arg("inc")
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("extend")
+        .arg("--id=1")
+        .arg("--durability=persistent")
+        .arg("--ledgers=10")
+        .assert()
+        .stdout(predicates::str::starts_with("New expiration ledger: "))
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("extend")
+        .arg("--id=1")
+        .arg("--durability=persistent")
+        .arg("--key=COUNTER")
+        .arg("--ledgers=10")
+        .assert()
+        .stdout(predicates::str::starts_with("New expiration ledger: "))
+        .success();
+}
+
+#[test]
+fn invoke_hello_world_with_named_args() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--fn=hello")
+        .arg("--")
+        .arg("world=world")
+        .assert()
+        .stdout("[\"Hello\",\"world\"]\n")
+        .success();
+}
+
+#[test]
+fn invoke_hello_world_with_json_output() {
+    let sandbox = TestEnv::default();
+    let res = Command::cargo_bin("soroban")
+        .unwrap()
+        .current_dir(sandbox.dir())
+        .args([
+            "--output-format",
+            "json",
+            "contract",
+            "invoke",
+            "--id=1",
+            "--wasm",
+        ])
+        .arg(HELLO_WORLD.path())
+        .arg("--")
+        .arg("hello")
+        .arg("--world=world")
+        .assert()
+        .success();
+    let output: serde_json::Value = serde_json::from_slice(&res.get_output().stdout).unwrap();
+    assert_eq!(output["result"], serde_json::json!(["Hello", "world"]));
+    assert!(output["tx_hash"].is_null());
+}
+
+#[test]
+fn multi_invoke_hello_world() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("deploy")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--id=1")
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("multi-invoke")
+        .arg("--call=1 hello --world=one")
+        .arg("--call=1 hello --world=two")
+        .assert()
+        .stdout("[\"Hello\",\"one\"]\n[\"Hello\",\"two\"]\n")
+        .success();
+}
+
+#[test]
+fn invoke_hello_world_with_args_file() {
+    let sandbox = TestEnv::default();
+    let args_file = sandbox.dir().join("args.json");
+    std::fs::write(&args_file, r#"{"world": "world"}"#).unwrap();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--args-file")
+        .arg(&args_file)
+        .arg("--")
+        .arg("hello")
+        .assert()
+        .stdout("[\"Hello\",\"world\"]\n")
+        .success();
+}
+
+#[test]
+fn invoke_list_functions() {
+    TestEnv::default()
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--list")
+        .assert()
+        .stdout(predicates::str::contains(
+            "hello(world: Symbol) -> Vec<Symbol>",
+        ))
+        .success();
+}
+
+#[test]
+fn invoke_hello_world_with_unknown_named_arg() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--fn=hello")
+        .arg("--")
+        .arg("earth=world")
+        .assert()
+        .stderr(predicates::str::contains(
+            "function hello has no argument earth",
+        ));
+}
+
 #[test]
 fn invoke_hello_world_from_file() {
     let sandbox = TestEnv::default();
@@ -325,3 +523,164 @@ async fn fetch() {
     cmd.run().await.unwrap();
     assert!(f.exists());
 }
+
+#[test]
+fn fetch_by_wasm_hash() {
+    let hash = HELLO_WORLD.hash().unwrap();
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("install")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .assert()
+        .success();
+    let out = sandbox.dir().join("fetched.wasm");
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("fetch")
+        .arg(format!("--wasm-hash={hash}"))
+        .arg("--out-file")
+        .arg(&out)
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read(out).unwrap(),
+        std::fs::read(HELLO_WORLD.path()).unwrap()
+    );
+}
+
+#[test]
+fn asset_id_matches_deployed_asset_contract() {
+    let sandbox = TestEnv::default();
+    let id = sandbox
+        .new_assert_cmd("contract")
+        .args(["asset", "id", "--asset", "native"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["asset", "deploy", "--asset", "native"])
+        .assert()
+        .success()
+        .stdout(String::from_utf8(id.get_output().stdout.clone()).unwrap());
+}
+
+#[test]
+fn read_and_invoke_at_past_ledger() {
+    let sandbox = TestEnv::default();
+    for _ in 0..2 {
+        sandbox
+            .new_assert_cmd("contract")
+            .arg("invoke")
+            .arg("--id=1")
+            .arg("--wasm")
+            .arg(HELLO_WORLD.path())
+            .arg("--keep-history")
+            .arg("--")
+            .arg("inc")
+            .assert()
+            .success();
+    }
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("read")
+        .arg("--id=1")
+        .arg("--key=COUNTER")
+        .arg("--at-ledger=1")
+        .assert()
+        .stdout("COUNTER,1\n")
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--at-ledger=1")
+        .arg("--")
+        .arg("inc")
+        .assert()
+        .stdout("2\n")
+        .success();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("read")
+        .arg("--id=1")
+        .arg("--key=COUNTER")
+        .assert()
+        .stdout("COUNTER,2\n")
+        .success();
+}
+
+#[test]
+fn at_ledger_needs_history_and_the_sandbox() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--")
+        .arg("inc")
+        .assert()
+        .success();
+    assert!(!sandbox.dir().join(".soroban/ledger.history").exists());
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("read")
+        .arg("--id=1")
+        .arg("--key=COUNTER")
+        .arg("--at-ledger=1")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--keep-history"));
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--id=1")
+        .arg("--at-ledger=1")
+        .arg("--rpc-url=http://localhost:8000/soroban/rpc")
+        .arg("--")
+        .arg("inc")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+}
+
+#[test]
+fn size_check_fails_over_the_limit() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("size-check")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--max-bytes=1")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("size check failed"));
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("size-check")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .arg("--max-bytes=65536")
+        .assert()
+        .success();
+}
+
+#[test]
+fn bindings_check_fails_on_stale_file() {
+    let sandbox = TestEnv::default();
+    let json = sandbox.dir().join("hello.json");
+    std::fs::write(&json, "[]").unwrap();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["bindings", "json", "--wasm"])
+        .arg(HELLO_WORLD.path())
+        .arg("--check")
+        .arg(&json)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("out of date"));
+}
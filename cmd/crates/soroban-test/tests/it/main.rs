@@ -2,6 +2,8 @@ mod arg_parsing;
 mod config;
 
 mod custom_types;
+mod invoke_rpc;
 mod invoke_sandbox;
+mod mock_rpc;
 mod plugin;
 mod util;
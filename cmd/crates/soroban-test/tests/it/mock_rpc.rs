@@ -0,0 +1,194 @@
+//! A minimal JSON-RPC server standing in for soroban-rpc, for testing how commands use it
+//! without a network.
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde_json::{json, Value};
+use soroban_env_host::xdr::{
+    AccountEntry, AccountEntryExt, AccountId, ContractDataDurability, ContractDataEntry,
+    ContractDataEntryBody, ContractDataEntryData, ContractExecutable, ExtensionPoint, Hash,
+    LedgerEntryData, LedgerFootprint, LedgerKey, PublicKey, ReadXdr, ScAddress, ScContractInstance,
+    ScVal, SequenceNumber, SorobanResources, SorobanTransactionData, String32, Thresholds, Uint256,
+    VecM, WriteXdr,
+};
+
+pub const PASSPHRASE: &str = "Mock Network ; October 2026";
+
+type Handler = dyn Fn(&str, &Value) -> Result<Value, String> + Send + Sync;
+
+pub struct MockRpc {
+    pub url: String,
+    calls: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl MockRpc {
+    /// Serve each request with `handler`, which gets the method and params and returns the
+    /// result, or an error message.
+    pub fn start(
+        handler: impl Fn(&str, &Value) -> Result<Value, String> + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handler: Arc<Handler> = Arc::new(handler);
+        let calls = Arc::new(Mutex::new(vec![]));
+        let served = calls.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                let calls = served.clone();
+                thread::spawn(move || serve(stream, &*handler, &calls));
+            }
+        });
+        Self { url, calls }
+    }
+
+    /// The params of each call made to `method` so far.
+    pub fn calls(&self, method: &str) -> Vec<Value> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(m, _)| m == method)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+}
+
+fn serve(stream: TcpStream, handler: &Handler, calls: &Mutex<Vec<(String, Value)>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut content_length = 0;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+        let request: Value = serde_json::from_slice(&body).unwrap();
+        let method = request["method"].as_str().unwrap_or_default();
+        calls
+            .lock()
+            .unwrap()
+            .push((method.to_string(), request["params"].clone()));
+        let response = match handler(method, &request["params"]) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32603, "message": message },
+            }),
+        };
+        let body = response.to_string();
+        let written = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        if written.is_err() {
+            return;
+        }
+    }
+}
+
+pub fn get_network() -> Value {
+    json!({ "passphrase": PASSPHRASE, "protocolVersion": 20 })
+}
+
+/// A `getLedgerEntries` result holding an account with sequence number `seq_num`.
+pub fn account(seq_num: i64) -> Value {
+    let entry = LedgerEntryData::Account(AccountEntry {
+        account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([0; 32]))),
+        balance: 10_000_000_000,
+        flags: 0,
+        home_domain: String32::default(),
+        inflation_dest: None,
+        num_sub_entries: 0,
+        seq_num: SequenceNumber(seq_num),
+        thresholds: Thresholds([1, 0, 0, 0]),
+        signers: VecM::default(),
+        ext: AccountEntryExt::V0,
+    });
+    ledger_entries(&entry)
+}
+
+/// A `getLedgerEntries` result holding the instance of a Stellar asset contract, whose spec
+/// is built into the CLI.
+pub fn stellar_asset_contract() -> Value {
+    ledger_entries(&LedgerEntryData::ContractData(ContractDataEntry {
+        contract: ScAddress::Contract(Hash([1; 32])),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+        body: ContractDataEntryBody::DataEntry(ContractDataEntryData {
+            flags: 0,
+            val: ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Token,
+                storage: None,
+            }),
+        }),
+        expiration_ledger_seq: 1000,
+    }))
+}
+
+fn ledger_entries(entry: &LedgerEntryData) -> Value {
+    json!({
+        "entries": [{
+            "key": "",
+            "xdr": entry.to_xdr_base64().unwrap(),
+            "lastModifiedLedgerSeq": "1",
+        }],
+        "latestLedger": "1",
+    })
+}
+
+/// The ledger key of a `getLedgerEntries` request for a single key.
+pub fn requested_key(params: &Value) -> Option<LedgerKey> {
+    LedgerKey::from_xdr_base64(params[0][0].as_str()?).ok()
+}
+
+/// A successful `simulateTransaction` result of an invocation returning nothing.
+pub fn simulation(min_resource_fee: u32) -> Value {
+    let transaction_data = SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: VecM::default(),
+                read_write: VecM::default(),
+            },
+            instructions: 0,
+            read_bytes: 0,
+            write_bytes: 0,
+            extended_meta_data_size_bytes: 0,
+        },
+        refundable_fee: i64::from(min_resource_fee),
+    };
+    json!({
+        "transactionData": transaction_data.to_xdr_base64().unwrap(),
+        "events": [],
+        "minResourceFee": min_resource_fee.to_string(),
+        "results": [{ "auth": [], "xdr": ScVal::Void.to_xdr_base64().unwrap() }],
+        "cost": { "cpuInsns": "0", "memBytes": "0" },
+        "latestLedger": "1",
+    })
+}
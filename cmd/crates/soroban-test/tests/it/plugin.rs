@@ -29,6 +29,14 @@ fn soroban_hello() {
     assert_cmd::Command::cargo_bin("soroban")
         .unwrap_or_else(|_| assert_cmd::Command::new("soroban"))
         .arg("--list")
+        .env("PATH", &paths)
+        .assert()
+        .stdout(predicates::str::contains("hello"));
+
+    // Call `soroban plugin ls` with the PATH variable set to include the target/bin directory
+    assert_cmd::Command::cargo_bin("soroban")
+        .unwrap_or_else(|_| assert_cmd::Command::new("soroban"))
+        .args(["plugin", "ls"])
         .env("PATH", paths)
         .assert()
         .stdout(predicates::str::contains("hello"));
@@ -18,6 +18,8 @@ pub fn add_identity(dir: &Path, name: &str, kind: SecretKind, data: &str) {
     let secret = match kind {
         SecretKind::Seed => Secret::SeedPhrase {
             seed_phrase: data.to_string(),
+            bip39_passphrase: false,
+            identity: None,
         },
         SecretKind::Key => Secret::SecretKey {
             secret_key: data.to_string(),
@@ -1,7 +1,16 @@
+use std::{fs::OpenOptions, sync::Mutex};
+
 use clap::{CommandFactory, Parser};
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing::Level;
+use tracing_subscriber::{
+    fmt::{
+        self,
+        writer::{BoxMakeWriter, MakeWriterExt},
+    },
+    EnvFilter,
+};
 
-use soroban_cli::{commands::plugin, Root};
+use soroban_cli::{commands::plugin, output::Output, Root};
 
 #[tokio::main]
 async fn main() {
@@ -30,7 +39,15 @@ async fn main() {
         }
     });
     // Now use root to setup the logger
-    if let Some(level) = root.global_args.log_level() {
+    let stderr_level = root.global_args.log_level();
+    let log_file = root.global_args.log_file.as_ref();
+    // The log file gets DEBUG events at least, whatever stderr gets
+    let level = match (stderr_level, log_file) {
+        (Some(level), Some(_)) => Some(level.max(Level::DEBUG)),
+        (None, Some(_)) => Some(Level::DEBUG),
+        (level, None) => level,
+    };
+    if let Some(level) = level {
         let mut e_filter = EnvFilter::from_default_env()
             .add_directive("hyper=off".parse().unwrap())
             .add_directive(format!("soroban_cli={level}").parse().unwrap());
@@ -47,9 +64,27 @@ async fn main() {
             );
         }
 
+        let stderr = std::io::stderr
+            .with_filter(move |meta| stderr_level.is_some_and(|level| *meta.level() <= level));
+        let writer = match log_file {
+            Some(path) => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .unwrap_or_else(|e| {
+                        eprintln!("error: opening log file {}: {e}", path.display());
+                        std::process::exit(1);
+                    });
+                BoxMakeWriter::new(stderr.and(Mutex::new(file)))
+            }
+            None => BoxMakeWriter::new(stderr),
+        };
+
         let builder = fmt::Subscriber::builder()
             .with_env_filter(e_filter)
-            .with_writer(std::io::stderr);
+            .with_ansi(log_file.is_none())
+            .with_writer(writer);
 
         let subscriber = builder.finish();
         tracing::subscriber::set_global_default(subscriber)
@@ -57,6 +92,11 @@ async fn main() {
     }
 
     if let Err(e) = root.run().await {
-        eprintln!("error: {e}");
+        if root.global_args.output_format == Output::Json {
+            eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
+        } else {
+            eprintln!("error: {e}");
+        }
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use clap::Subcommand;
+use serde_json::{json, Value};
+use soroban_env_host::xdr::{AccountEntry, AccountId, PublicKey, SignerKey, Uint256};
+
+use super::global;
+
+pub mod show;
+
+#[derive(Debug, Subcommand)]
+pub enum Cmd {
+    /// Print an account's sequence number, balance, signers, thresholds and flags
+    Show(show::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Show(#[from] show::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Show(show) => show.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
+
+/// The account's master key.
+pub fn master_key(account: &AccountEntry) -> &[u8; 32] {
+    let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))) = &account.account_id;
+    key
+}
+
+/// The account's signers, the master key first, with their weights.
+pub fn signers(account: &AccountEntry) -> Vec<(String, u32)> {
+    let mut signers = vec![(
+        stellar_strkey::ed25519::PublicKey(*master_key(account)).to_string(),
+        u32::from(account.thresholds.0[0]),
+    )];
+    signers.extend(account.signers.iter().map(|signer| {
+        let key = match &signer.key {
+            SignerKey::Ed25519(Uint256(key)) => {
+                stellar_strkey::ed25519::PublicKey(*key).to_string()
+            }
+            SignerKey::PreAuthTx(Uint256(hash)) => stellar_strkey::PreAuthTx(*hash).to_string(),
+            SignerKey::HashX(Uint256(hash)) => stellar_strkey::HashX(*hash).to_string(),
+            key @ SignerKey::Ed25519SignedPayload(_) => {
+                serde_json::to_string(key).unwrap_or_default()
+            }
+        };
+        (key, signer.weight)
+    }));
+    signers
+}
+
+/// The account's signers and their weights as JSON, the master key first.
+pub fn signers_json(account: &AccountEntry) -> Value {
+    signers(account)
+        .into_iter()
+        .map(|(key, weight)| json!({ "key": key, "weight": weight }))
+        .collect()
+}
+
+/// Write the account's signers, one per line after a `Signers:` line, marking the master key.
+pub fn write_signers(out: &mut String, account: &AccountEntry) {
+    let _ = writeln!(out, "Signers:");
+    for (i, (key, weight)) in signers(account).into_iter().enumerate() {
+        let master = if i == 0 { " (master key)" } else { "" };
+        let _ = writeln!(out, "  {key}{master}: weight {weight}");
+    }
+}
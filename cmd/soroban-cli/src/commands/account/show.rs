@@ -0,0 +1,106 @@
+use std::fmt::Write;
+
+use clap::{command, Parser};
+use serde_json::{json, Value};
+use soroban_env_host::xdr::{AccountEntry, AccountFlags};
+
+use super::{signers_json, write_signers};
+use crate::{
+    commands::{
+        config::{self, locator},
+        global,
+    },
+    format::Format,
+    rpc::{self, Client},
+};
+
+/// Print what is needed to debug `txBAD_SEQ` and `txBAD_AUTH`: the account's sequence number,
+/// and its signers and the thresholds their weights must meet. Also prints its balance and flags.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Account, as a public key or an identity
+    account: String,
+
+    #[command(flatten)]
+    config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Secret(#[from] config::secret::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let public_key = match stellar_strkey::ed25519::PublicKey::from_string(&self.account) {
+            Ok(public_key) => public_key,
+            Err(_) => self.config.account(&self.account)?.public_key(None)?,
+        };
+        let network = self.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
+        let account = client.get_account(&public_key.to_string()).await?;
+        let format = self.config.locator.read_settings()?.format;
+        global_args
+            .formatter()
+            .print(to_text(&account, &format), &to_json(&account))?;
+        Ok(())
+    }
+}
+
+fn flags(account: &AccountEntry) -> Vec<&'static str> {
+    [
+        (AccountFlags::RequiredFlag, "auth_required"),
+        (AccountFlags::RevocableFlag, "auth_revocable"),
+        (AccountFlags::ImmutableFlag, "auth_immutable"),
+        (AccountFlags::ClawbackEnabledFlag, "auth_clawback_enabled"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| account.flags & (*flag as u32) != 0)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+fn to_json(account: &AccountEntry) -> Value {
+    let [_, low, medium, high] = account.thresholds.0;
+    json!({
+        "sequence": account.seq_num.0.to_string(),
+        "balance": account.balance.to_string(),
+        "subentries": account.num_sub_entries,
+        "thresholds": { "low": low, "medium": medium, "high": high },
+        "signers": signers_json(account),
+        "flags": flags(account),
+        "homeDomain": account.home_domain.to_string_lossy(),
+    })
+}
+
+fn to_text(account: &AccountEntry, format: &Format) -> String {
+    let [_, low, medium, high] = account.thresholds.0;
+    let mut out = format!(
+        "Sequence: {} (the next transaction must use {})\n",
+        account.seq_num.0,
+        account.seq_num.0 + 1
+    );
+    let _ = writeln!(out, "Balance: {}", format.amount(account.balance));
+    let _ = writeln!(out, "Subentries: {}", account.num_sub_entries);
+    let _ = writeln!(out, "Thresholds: low {low}, medium {medium}, high {high}");
+    write_signers(&mut out, account);
+    let flags = flags(account);
+    if !flags.is_empty() {
+        let _ = writeln!(out, "Flags: {}", flags.join(", "));
+    }
+    let home_domain = account.home_domain.to_string_lossy();
+    if !home_domain.is_empty() {
+        let _ = writeln!(out, "Home domain: {home_domain}");
+    }
+    out
+}
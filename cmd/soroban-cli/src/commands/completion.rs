@@ -1,8 +1,16 @@
 use clap::{arg, CommandFactory, Parser};
 use clap_complete::{generate, Shell};
-use std::io;
+use sha2::{Digest, Sha256};
+use soroban_env_host::{
+    storage::Storage,
+    xdr::{self, ReadXdr, ScSpecEntry, ScSpecFunctionV0, WriteXdr},
+};
+use std::{fs, io, path::PathBuf, rc::Rc};
 
-use crate::commands::Root;
+use crate::{
+    commands::{config, contract, contract::invoke, Cmd as RootCmd, Root},
+    rpc, utils,
+};
 
 pub const LONG_ABOUT: &str = "\
 Print shell completion code for the specified shell
@@ -14,19 +22,261 @@ To enable autocomplete in the current bash shell, run:
   source <(soroban completion --shell bash)
 
 To enable autocomplete permanently, run:
-  echo \"source <(soroban completion --shell bash)\" >> ~/.bashrc";
+  echo \"source <(soroban completion --shell bash)\" >> ~/.bashrc
+
+Identities, networks and contract aliases are completed from the config, in bash
+and fish. In bash, function names and arguments after the `--` of `contract invoke`
+are also completed from the contract's spec once `--id` or `--wasm` has been given.
+In zsh, use the bash completion:
+  autoload -U bashcompinit && bashcompinit
+  source <(soroban completion --shell bash)";
+
+/// Flags whose values are identities, completed from the config.
+const IDENTITY_FLAGS: &[&str] = &[
+    "--source-account",
+    "--source",
+    "--fee-bump-source",
+    "--identity",
+    "--account",
+];
+/// Flags whose values are networks, completed from the config.
+const NETWORK_FLAGS: &[&str] = &["--network"];
+/// Flags whose values are contracts, completed from the aliases of the network given with
+/// `--network`, or of the sandbox.
+const CONTRACT_FLAGS: &[&str] = &["--id", "--invoke-id", "--contract-instance"];
+
+/// Wraps the generated bash completion so words after `--` in `contract invoke` are
+/// completed from the contract spec, and the values of `FLAGS` from the config.
+const BASH_SPEC_COMPLETION: &str = r#"
+_soroban_complete() {
+    local IFS=$'\n'
+    COMPREPLY=($(compgen -W "$(soroban completion --complete -- "${COMP_WORDS[@]:1:COMP_CWORD-1}" 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+}
+_soroban_with_spec() {
+    local i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        if [[ "${COMP_WORDS[i]}" == "--" ]]; then
+            _soroban_complete
+            return 0
+        fi
+    done
+    case "${COMP_WORDS[COMP_CWORD-1]}" in
+        FLAGS)
+            _soroban_complete
+            return 0
+            ;;
+    esac
+    _soroban "$@"
+}
+complete -F _soroban_with_spec -o bashdefault -o default soroban
+"#;
+
+/// Completes the values of a flag of `config_flags` from the config, in fish.
+const FISH_CONFIG_COMPLETION: &str = r#"complete -c soroban -l FLAG -f -a "(soroban completion --complete -- (commandline -opc)[2..-1] 2>/dev/null)""#;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] config::locator::Error),
+    #[error(transparent)]
+    Network(#[from] config::network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error("parsing contract spec: {0}")]
+    CannotParseContractSpec(soroban_spec::read::FromWasmError),
+}
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     /// The shell type
-    #[arg(long, value_enum)]
-    shell: Shell,
+    #[arg(long, value_enum, required_unless_present = "complete")]
+    shell: Option<Shell>,
+
+    /// Print completion candidates for the command line given after `--`. Used by the shell
+    /// script.
+    #[arg(long, hide = true, conflicts_with = "shell")]
+    complete: bool,
+
+    #[arg(last = true, hide = true, requires = "complete")]
+    words: Vec<String>,
 }
 
 impl Cmd {
-    pub fn run(&self) {
-        let cmd = &mut Root::command();
-        generate(self.shell, cmd, "soroban", &mut io::stdout());
+    pub async fn run(&self) -> Result<(), Error> {
+        if self.complete {
+            // Completion must never get in the way of typing, so failures print nothing.
+            let candidates = if self.words.iter().any(|w| w == "--") {
+                complete_contract_args(&self.words).await
+            } else {
+                complete_config_values(&self.words)
+            };
+            if let Ok(candidates) = candidates {
+                println!("{}", candidates.join("\n"));
+            }
+            return Ok(());
+        }
+        if let Some(shell) = self.shell {
+            let cmd = &mut Root::command();
+            generate(shell, cmd, "soroban", &mut io::stdout());
+            let flags = config_flags();
+            match shell {
+                Shell::Bash => println!(
+                    "{}",
+                    BASH_SPEC_COMPLETION.replace("FLAGS", &flags.join("|"))
+                ),
+                Shell::Fish => {
+                    for flag in flags {
+                        println!(
+                            "{}",
+                            FISH_CONFIG_COMPLETION.replace("FLAG", flag.trim_start_matches("--"))
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
     }
 }
+
+fn config_flags() -> Vec<&'static str> {
+    [IDENTITY_FLAGS, NETWORK_FLAGS, CONTRACT_FLAGS].concat()
+}
+
+/// Complete the value of the flag that is the last of `words` from the config, if it takes
+/// an identity, network or contract. Contract aliases are those of the network given with
+/// `--network`, or of the sandbox.
+fn complete_config_values(words: &[String]) -> Result<Vec<String>, Error> {
+    let Some(flag) = words.last().map(String::as_str) else {
+        return Ok(vec![]);
+    };
+    let locator = config::locator::Args::default();
+    if IDENTITY_FLAGS.contains(&flag) {
+        return Ok(locator.list_identities()?);
+    }
+    if NETWORK_FLAGS.contains(&flag) {
+        return Ok(locator.list_networks()?);
+    }
+    if CONTRACT_FLAGS.contains(&flag) {
+        let network = config::network::Args {
+            network: words
+                .iter()
+                .position(|w| w == "--network")
+                .and_then(|i| words.get(i + 1))
+                .cloned()
+                .or_else(|| std::env::var("SOROBAN_NETWORK").ok()),
+            ..Default::default()
+        };
+        let aliases = locator.list_contract_aliases(&network.alias_key(&locator)?)?;
+        return Ok(aliases.into_iter().map(|(alias, _)| alias).collect());
+    }
+    Ok(vec![])
+}
+
+/// Complete the function name or argument names of an invoke command line, where `words`
+/// are the words typed so far, excluding the binary name and the word being completed.
+async fn complete_contract_args(words: &[String]) -> Result<Vec<String>, Error> {
+    let Some(split) = words.iter().position(|w| w == "--") else {
+        return Ok(vec![]);
+    };
+    let (cmd, slop) = (&words[..split], &words[split + 1..]);
+    let Ok(Root {
+        cmd: RootCmd::Contract(contract::Cmd::Invoke(invoke)),
+        ..
+    }) = Root::try_parse_from(std::iter::once("soroban").chain(cmd.iter().map(String::as_str)))
+    else {
+        return Ok(vec![]);
+    };
+    let spec = soroban_spec_tools::Spec(Some(spec_entries(&invoke).await?));
+    // With `--fn` arguments are `name=value`, otherwise the function comes first as
+    // a subcommand followed by `--name value` flags.
+    let (function, args, format): (_, _, fn(&str) -> String) = match (&invoke.function, slop) {
+        (Some(function), args) => (function, args, |name| format!("{name}=")),
+        (None, [function, args @ ..]) => (function, args, |name| format!("--{name}")),
+        (None, []) => {
+            return Ok(spec
+                .find_functions()?
+                .map(|ScSpecFunctionV0 { name, .. }| name.to_string_lossy())
+                .collect());
+        }
+    };
+    let func = spec.find_function(function)?;
+    Ok(func
+        .inputs
+        .iter()
+        .map(|i| format(&i.name.to_string_lossy()))
+        .filter(|arg| {
+            let given = format!("{}=", arg.trim_end_matches('='));
+            !args.iter().any(|w| w == arg || w.starts_with(&given))
+        })
+        .collect())
+}
+
+async fn spec_entries(cmd: &invoke::Cmd) -> Result<Vec<ScSpecEntry>, Error> {
+    if let Some(spec) = cmd.spec_entries()? {
+        return Ok(spec);
+    }
+    let contract_id =
+        utils::contract_id_from_str(&cmd.config.resolve_contract_id(&cmd.contract_id)?)?;
+    if cmd.config.is_no_network() {
+        let state = cmd.config.get_state()?;
+        let mut storage = Storage::with_recording_footprint(Rc::new(state.clone()));
+        return utils::get_contract_spec_from_storage(
+            &mut storage,
+            &state.sequence_number,
+            contract_id,
+        )
+        .map_err(Error::CannotParseContractSpec);
+    }
+    let network = cmd.config.get_network()?;
+    let cache = cache_path(&cmd.config, &contract_id, &network.network_passphrase)?;
+    if let Ok(cached) = fs::read_to_string(&cache) {
+        if let Ok(spec) = cached
+            .lines()
+            .map(ScSpecEntry::from_xdr_base64)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            return Ok(spec);
+        }
+    }
+    let spec = rpc::Client::new(&network.rpc_url)?
+        .get_remote_contract_spec(&contract_id)
+        .await?;
+    let encoded = spec
+        .iter()
+        .map(WriteXdr::to_xdr_base64)
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(dir) = cache.parent() {
+        // The cache is best effort, completion still works without it.
+        let _ = fs::create_dir_all(dir).and_then(|()| fs::write(&cache, encoded.join("\n")));
+    }
+    Ok(spec)
+}
+
+/// Contract IDs can repeat across networks, so the cache is keyed on both.
+fn cache_path(
+    config: &config::Args,
+    contract_id: &[u8; 32],
+    network_passphrase: &str,
+) -> Result<PathBuf, Error> {
+    let network = hex::encode(&Sha256::digest(network_passphrase)[..4]);
+    Ok(config
+        .config_dir()?
+        .join("cache")
+        .join("spec")
+        .join(format!(
+            "{}-{network}.xdr",
+            stellar_strkey::Contract(*contract_id)
+        )))
+}
@@ -0,0 +1,227 @@
+use std::{fmt::Write, path::Path, time::Duration};
+
+use clap::{command, Parser};
+use serde::Serialize;
+
+use crate::{commands::global, rpc::Client};
+
+use super::{
+    locator::{self, KeyType},
+    network::Network,
+    secret::Secret,
+};
+
+/// How long a network's RPC server has to answer before it is reported unreachable.
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Check the local and global config directories for problems, and suggest how to fix them:
+/// identities and networks that cannot be read, secrets that other users can read, networks
+/// whose RPC server is unreachable or on another network, and `SOROBAN_NETWORK` or
+/// `SOROBAN_ACCOUNT` naming a network or identity that doesn't exist.
+#[derive(Debug, Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Don't contact the networks' RPC servers
+    #[arg(long)]
+    pub offline: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem with the config, and how to fix it.
+#[derive(Debug, Serialize)]
+struct Finding {
+    severity: Severity,
+    subject: String,
+    problem: String,
+    fix: String,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let locations = self.config_locator.local_and_global()?;
+        let mut findings = vec![];
+
+        if let Err(e) = self.config_locator.read_settings() {
+            findings.push(Finding {
+                severity: Severity::Error,
+                subject: "settings".to_string(),
+                problem: e.to_string(),
+                fix: "fix or remove config.toml, or change it with `soroban config set`"
+                    .to_string(),
+            });
+        }
+
+        for (name, location) in KeyType::Identity.list_paths(&locations)? {
+            let path = location.as_ref();
+            let subject = format!("identity {name} ({})", path.display());
+            match KeyType::read_from_path::<Secret>(path).map(|secret| secret.with_identity(&name))
+            {
+                Err(e) => findings.push(Finding {
+                    severity: Severity::Error,
+                    subject,
+                    problem: format!("cannot be read: {e}"),
+                    fix: format!(
+                        "fix the file, or remove it with `soroban config identity rm {name}`"
+                    ),
+                }),
+                Ok(secret) => {
+                    if let Err(e) = secret.public_key(None) {
+                        findings.push(Finding {
+                            severity: Severity::Error,
+                            subject,
+                            problem: format!("has an invalid key: {e}"),
+                            fix: format!("add it again with `soroban config identity add {name}`"),
+                        });
+                    } else if !matches!(secret, Secret::External { .. }) && readable_by_others(path)
+                    {
+                        findings.push(Finding {
+                            severity: Severity::Warning,
+                            subject,
+                            problem: "holds a secret that other users can read".to_string(),
+                            fix: format!("chmod 600 {}", path.display()),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (name, location) in KeyType::Network.list_paths(&locations)? {
+            let path = location.as_ref();
+            let subject = format!("network {name} ({})", path.display());
+            match KeyType::read_from_path::<Network>(path) {
+                Err(e) => findings.push(Finding {
+                    severity: Severity::Error,
+                    subject,
+                    problem: format!("cannot be read: {e}"),
+                    fix: format!(
+                        "fix the file, or remove it with `soroban config network rm {name}`"
+                    ),
+                }),
+                Ok(network) if !self.offline => {
+                    if let Some(finding) = check_rpc(&name, &network, subject).await {
+                        findings.push(finding);
+                    }
+                }
+                Ok(_) => {}
+            }
+        }
+
+        if let Ok(name) = std::env::var("SOROBAN_NETWORK") {
+            if self.config_locator.read_network(&name).is_err() {
+                findings.push(Finding {
+                    severity: Severity::Error,
+                    subject: "SOROBAN_NETWORK".to_string(),
+                    problem: format!("names network {name}, which doesn't exist"),
+                    fix: format!(
+                        "add it with `soroban config network add {name}`, or unset SOROBAN_NETWORK"
+                    ),
+                });
+            }
+        }
+        if let Ok(name) = std::env::var("SOROBAN_ACCOUNT") {
+            let exists =
+                self.config_locator.read_identity(&name).is_ok() || name.parse::<Secret>().is_ok();
+            if !exists {
+                findings.push(Finding {
+                    severity: Severity::Error,
+                    subject: "SOROBAN_ACCOUNT".to_string(),
+                    problem: format!("names identity {name}, which doesn't exist"),
+                    fix: format!(
+                        "add it with `soroban config identity generate {name}`, or unset \
+                         SOROBAN_ACCOUNT"
+                    ),
+                });
+            }
+        }
+
+        let mut text = String::new();
+        for Finding {
+            severity,
+            subject,
+            problem,
+            fix,
+        } in &findings
+        {
+            let severity = match severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            let _ = writeln!(text, "{severity}: {subject} {problem}\n  fix: {fix}");
+        }
+        if findings.is_empty() {
+            text.push_str("No problems found");
+        }
+        global_args.formatter().print(text, &findings)?;
+        Ok(())
+    }
+}
+
+/// Check that the network's RPC server answers, and is on the network of its passphrase.
+async fn check_rpc(name: &str, network: &Network, subject: String) -> Option<Finding> {
+    let unreachable = |problem: String| Finding {
+        severity: Severity::Warning,
+        subject: subject.clone(),
+        problem,
+        fix: format!(
+            "check your connection, or update the RPC URL with `soroban config network add {name} \
+             --rpc-url <URL> --network-passphrase <PASSPHRASE>`"
+        ),
+    };
+    let client = match Client::new(&network.rpc_url) {
+        Ok(client) => client,
+        Err(e) => return Some(unreachable(format!("has an invalid RPC URL: {e}"))),
+    };
+    match tokio::time::timeout(RPC_TIMEOUT, client.get_network()).await {
+        Err(_) => Some(unreachable(format!(
+            "RPC server {} did not answer within {}s",
+            network.rpc_url,
+            RPC_TIMEOUT.as_secs()
+        ))),
+        Ok(Err(e)) => Some(unreachable(format!(
+            "RPC server {} is unreachable: {e}",
+            network.rpc_url
+        ))),
+        Ok(Ok(server)) if server.passphrase != network.network_passphrase => Some(Finding {
+            severity: Severity::Error,
+            subject: subject.clone(),
+            problem: format!(
+                "has passphrase {:?}, but its RPC server is on {:?}",
+                network.network_passphrase, server.passphrase
+            ),
+            fix: format!(
+                "add it again with `soroban config network add {name} --rpc-url {} \
+                 --network-passphrase \"{}\"`",
+                network.rpc_url, server.passphrase
+            ),
+        }),
+        Ok(Ok(_)) => None,
+    }
+}
+
+#[cfg(unix)]
+fn readable_by_others(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o077 != 0)
+}
+
+#[cfg(not(unix))]
+fn readable_by_others(_path: &Path) -> bool {
+    false
+}
@@ -1,4 +1,4 @@
-use crate::commands::config::secret::Secret;
+use crate::commands::{config::secret::Secret, global};
 
 use super::super::{locator, secret};
 use clap::arg;
@@ -13,6 +13,9 @@ pub enum Error {
 
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -30,8 +33,11 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.public_key()?);
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let address = self.public_key()?.to_string();
+        global_args
+            .formatter()
+            .print(&address, &serde_json::json!({ "address": address }))?;
         Ok(())
     }
 
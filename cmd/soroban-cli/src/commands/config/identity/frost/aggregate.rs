@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use clap::arg;
+use soroban_env_host::xdr::{
+    self, DecoratedSignature, Signature, SignatureHint, TransactionEnvelope, WriteXdr,
+};
+
+use super::{
+    scheme::{self, Commitment, SignatureShare},
+    TxArgs, TxError,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Scheme(#[from] scheme::Error),
+    #[error(transparent)]
+    Tx(#[from] TxError),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Group public key printed by `frost trusted-dealer`
+    #[arg(long)]
+    pub group_key: String,
+
+    /// Number of shares required to sign
+    #[arg(long, short = 't')]
+    pub threshold: u16,
+
+    /// Commitment files from every participating signer
+    #[arg(long = "commitment", required = true)]
+    pub commitments: Vec<PathBuf>,
+
+    /// Signature share files from every participating signer
+    #[arg(long = "signature-share", required = true)]
+    pub signature_shares: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub tx: TxArgs,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let commitments = self
+            .commitments
+            .iter()
+            .map(|path| scheme::read_json::<Commitment>(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let shares = self
+            .signature_shares
+            .iter()
+            .map(|path| scheme::read_json::<SignatureShare>(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let signature = scheme::aggregate(
+            &self.group_key,
+            self.threshold,
+            &commitments,
+            &shares,
+            &self.tx.hash()?,
+        )?;
+
+        let key = stellar_strkey::ed25519::PublicKey::from_string(&self.group_key)?;
+        let decorated_signature = DecoratedSignature {
+            hint: SignatureHint(key.0[28..].try_into()?),
+            signature: Signature(signature.try_into()?),
+        };
+        let TransactionEnvelope::Tx(mut envelope) = self.tx.envelope()? else {
+            return Err(TxError::UnsupportedEnvelope.into());
+        };
+        let mut signatures = envelope.signatures.to_vec();
+        signatures.push(decorated_signature);
+        envelope.signatures = signatures.try_into()?;
+        println!("{}", TransactionEnvelope::Tx(envelope).to_xdr_base64()?);
+        Ok(())
+    }
+}
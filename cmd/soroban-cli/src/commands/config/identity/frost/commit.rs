@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::arg;
+
+use super::scheme::{self, KeyShare};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Scheme(#[from] scheme::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Key share file created by `frost trusted-dealer`
+    #[arg(long)]
+    pub share: PathBuf,
+
+    /// Where to write the secret nonces. Keep this file private; it is
+    /// consumed by `frost sign`.
+    #[arg(long)]
+    pub nonces_out: PathBuf,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let share: KeyShare = scheme::read_json(&self.share)?;
+        let (nonces, commitment) = scheme::commit(&share)?;
+        scheme::write_secret_json(&self.nonces_out, &nonces)?;
+        println!("{}", serde_json::to_string_pretty(&commitment)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use clap::Parser;
+use soroban_env_host::xdr::{self, ReadXdr, TransactionEnvelope};
+
+use crate::{
+    commands::config::{locator, network},
+    utils,
+};
+
+pub mod aggregate;
+pub mod commit;
+pub mod scheme;
+pub mod sign;
+pub mod trusted_dealer;
+
+/// Experimental t-of-n threshold signing (FROST) for ed25519 keys.
+///
+/// Shares, nonces, commitments and signature shares are exchanged as JSON
+/// files so each participant can run their round on their own machine. Keys
+/// are split by a trusted dealer; there is no distributed key generation.
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Split a new key into threshold shares on this machine, one file per participant
+    TrustedDealer(trusted_dealer::Cmd),
+    /// Round one: generate nonces and a commitment to share with the other signers
+    Commit(commit::Cmd),
+    /// Round two: produce a signature share for a transaction
+    Sign(sign::Cmd),
+    /// Combine signature shares into a signed transaction envelope
+    Aggregate(aggregate::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TrustedDealer(#[from] trusted_dealer::Error),
+    #[error(transparent)]
+    Commit(#[from] commit::Error),
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+    #[error(transparent)]
+    Aggregate(#[from] aggregate::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match self {
+            Cmd::TrustedDealer(cmd) => cmd.run()?,
+            Cmd::Commit(cmd) => cmd.run()?,
+            Cmd::Sign(cmd) => cmd.run()?,
+            Cmd::Aggregate(cmd) => cmd.run()?,
+        };
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TxError {
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error("only v1 transaction envelopes are supported")]
+    UnsupportedEnvelope,
+}
+
+/// The transaction being signed, shared by the `sign` and `aggregate` rounds
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct TxArgs {
+    /// Base64 encoded `TransactionEnvelope` to sign
+    #[arg(long)]
+    pub xdr: String,
+
+    #[command(flatten)]
+    pub network: network::Args,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl TxArgs {
+    pub fn envelope(&self) -> Result<TransactionEnvelope, TxError> {
+        Ok(TransactionEnvelope::from_xdr_base64(&self.xdr)?)
+    }
+
+    /// The network-specific hash of the transaction, which is what gets signed
+    pub fn hash(&self) -> Result<[u8; 32], TxError> {
+        let TransactionEnvelope::Tx(envelope) = self.envelope()? else {
+            return Err(TxError::UnsupportedEnvelope);
+        };
+        let network = self.network.get(&self.locator)?;
+        Ok(utils::transaction_hash(
+            &envelope.tx,
+            &network.network_passphrase,
+        )?)
+    }
+}
@@ -0,0 +1,559 @@
+//! Minimal FROST(Ed25519, SHA-512) implementation following RFC 9591.
+//!
+//! Keys are split by a trusted dealer (RFC 9591, appendix C) rather than
+//! through distributed key generation. Signatures produced by [`aggregate`]
+//! are plain ed25519 signatures over the message and verify against the
+//! group public key like any other Stellar signature.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::Write,
+    path::Path,
+};
+
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::Identity,
+};
+use ed25519_dalek::Verifier;
+use rand::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+const CONTEXT_STRING: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("threshold must be between 1 and {max_signers}, got {threshold}")]
+    InvalidThreshold { threshold: u16, max_signers: u16 },
+    #[error("invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("expected 32 bytes, got {0}")]
+    InvalidLength(usize),
+    #[error("invalid scalar")]
+    InvalidScalar,
+    #[error("invalid curve point")]
+    InvalidPoint,
+    #[error("missing commitment for signer {0}")]
+    MissingCommitment(u16),
+    #[error("duplicate signer {0}")]
+    DuplicateSigner(u16),
+    #[error("missing signature share for signer {0}")]
+    MissingSignatureShare(u16),
+    #[error("need at least {threshold} signers, got {got}")]
+    NotEnoughSigners { threshold: u16, got: usize },
+    #[error("signature shares do not combine into a valid signature")]
+    InvalidSignature,
+    #[error("reading {path}: {error}")]
+    Read {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+    #[error("writing {path}: {error}")]
+    Write {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+    #[error("parsing {path}: {error}")]
+    Json {
+        path: std::path::PathBuf,
+        error: serde_json::Error,
+    },
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+}
+
+/// A participant's long-lived share of the group signing key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeyShare {
+    pub identifier: u16,
+    pub threshold: u16,
+    pub max_signers: u16,
+    pub group_public_key: String,
+    pub signing_share: String,
+}
+
+/// One-time secret nonces generated in round one. Must never be reused.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Nonces {
+    pub identifier: u16,
+    pub hiding: String,
+    pub binding: String,
+}
+
+/// Public commitments to a participant's [`Nonces`], shared with the other signers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Commitment {
+    pub identifier: u16,
+    pub hiding: String,
+    pub binding: String,
+}
+
+/// A participant's round two output.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignatureShare {
+    pub identifier: u16,
+    pub share: String,
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    let data = fs::read(path).map_err(|error| Error::Read {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    serde_json::from_slice(&data).map_err(|error| Error::Json {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Write a secret (key share or nonces) readable only by the current user.
+pub fn write_secret_json<T: Serialize>(path: &Path, value: &T) -> Result<(), Error> {
+    let data = serde_json::to_vec_pretty(value).map_err(|error| Error::Json {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let write_error = |error| Error::Write {
+        path: path.to_path_buf(),
+        error,
+    };
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // `mode` only applies to new files, so tighten an existing one first.
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(write_error)?;
+        }
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(&data))
+        .map_err(write_error)
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&hasher.finalize());
+    bytes
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(parts))
+}
+
+/// `H1`: binding factors.
+fn h1(m: &[u8]) -> Scalar {
+    hash_to_scalar(&[CONTEXT_STRING, b"rho", m])
+}
+
+/// `H2`: the challenge. Unprefixed so the result is a standard ed25519 signature.
+fn h2(m: &[&[u8]]) -> Scalar {
+    hash_to_scalar(m)
+}
+
+/// `H3`: nonce generation.
+fn h3(random_bytes: &[u8], secret: &[u8]) -> Scalar {
+    hash_to_scalar(&[CONTEXT_STRING, b"nonce", random_bytes, secret])
+}
+
+/// `H4`: message hashing.
+fn h4(m: &[u8]) -> [u8; 64] {
+    hash(&[CONTEXT_STRING, b"msg", m])
+}
+
+/// `H5`: commitment list hashing.
+fn h5(m: &[u8]) -> [u8; 64] {
+    hash(&[CONTEXT_STRING, b"com", m])
+}
+
+/// `nonce_generate` from RFC 9591, section 4.1.
+fn nonce_generate(secret: &Scalar) -> Scalar {
+    let mut random_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut random_bytes);
+    h3(&random_bytes, &secret.to_bytes())
+}
+
+fn to_32(bytes: &[u8]) -> Result<[u8; 32], Error> {
+    bytes
+        .try_into()
+        .map_err(|_| Error::InvalidLength(bytes.len()))
+}
+
+fn decode_scalar(s: &str) -> Result<Scalar, Error> {
+    Scalar::from_canonical_bytes(to_32(&hex::decode(s)?)?).ok_or(Error::InvalidScalar)
+}
+
+/// Decode a commitment point, rejecting the identity and points outside the
+/// prime-order subgroup as RFC 9591 requires.
+fn decode_point(s: &str) -> Result<EdwardsPoint, Error> {
+    CompressedEdwardsY(to_32(&hex::decode(s)?)?)
+        .decompress()
+        .filter(|p| *p != EdwardsPoint::identity() && p.is_torsion_free())
+        .ok_or(Error::InvalidPoint)
+}
+
+fn encode_point(p: &EdwardsPoint) -> String {
+    hex::encode(p.compress().to_bytes())
+}
+
+fn group_key_bytes(group_public_key: &str) -> Result<[u8; 32], Error> {
+    Ok(stellar_strkey::ed25519::PublicKey::from_string(group_public_key)?.0)
+}
+
+/// Trusted dealer key generation: split a freshly generated secret into
+/// `max_signers` shares, any `threshold` of which can sign. The secret itself
+/// is discarded, but whoever runs this has seen every share.
+pub fn trusted_dealer_keygen(threshold: u16, max_signers: u16) -> Result<Vec<KeyShare>, Error> {
+    if threshold == 0 || threshold > max_signers {
+        return Err(Error::InvalidThreshold {
+            threshold,
+            max_signers,
+        });
+    }
+    let coefficients = (0..threshold).map(|_| random_scalar()).collect::<Vec<_>>();
+    let group_public_key = stellar_strkey::ed25519::PublicKey(
+        (&coefficients[0] * &ED25519_BASEPOINT_TABLE)
+            .compress()
+            .to_bytes(),
+    )
+    .to_string();
+    Ok((1..=max_signers)
+        .map(|identifier| {
+            let x = Scalar::from(u64::from(identifier));
+            let share = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, c| acc * x + c);
+            KeyShare {
+                identifier,
+                threshold,
+                max_signers,
+                group_public_key: group_public_key.clone(),
+                signing_share: hex::encode(share.to_bytes()),
+            }
+        })
+        .collect())
+}
+
+/// Round one: generate nonces to keep and commitments to share.
+pub fn commit(share: &KeyShare) -> Result<(Nonces, Commitment), Error> {
+    let signing_share = decode_scalar(&share.signing_share)?;
+    let hiding = nonce_generate(&signing_share);
+    let binding = nonce_generate(&signing_share);
+    let commitment = Commitment {
+        identifier: share.identifier,
+        hiding: encode_point(&(&hiding * &ED25519_BASEPOINT_TABLE)),
+        binding: encode_point(&(&binding * &ED25519_BASEPOINT_TABLE)),
+    };
+    let nonces = Nonces {
+        identifier: share.identifier,
+        hiding: hex::encode(hiding.to_bytes()),
+        binding: hex::encode(binding.to_bytes()),
+    };
+    Ok((nonces, commitment))
+}
+
+struct SigningPackage {
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+    binding_factors: BTreeMap<u16, Scalar>,
+}
+
+impl SigningPackage {
+    fn new(
+        group_public_key: &[u8; 32],
+        commitments: &[Commitment],
+        message: &[u8],
+    ) -> Result<Self, Error> {
+        let mut points = BTreeMap::new();
+        for c in commitments {
+            let pair = (decode_point(&c.hiding)?, decode_point(&c.binding)?);
+            if points.insert(c.identifier, pair).is_some() {
+                return Err(Error::DuplicateSigner(c.identifier));
+            }
+        }
+        let encoded_commitments = points
+            .iter()
+            .flat_map(|(id, (d, e))| {
+                [
+                    Scalar::from(u64::from(*id)).to_bytes().to_vec(),
+                    d.compress().to_bytes().to_vec(),
+                    e.compress().to_bytes().to_vec(),
+                ]
+            })
+            .flatten()
+            .collect::<Vec<u8>>();
+        let rho_input_prefix = [
+            &group_public_key[..],
+            &h4(message),
+            &h5(&encoded_commitments),
+        ]
+        .concat();
+        let binding_factors = points
+            .keys()
+            .map(|id| {
+                let rho_input = [
+                    &rho_input_prefix[..],
+                    &Scalar::from(u64::from(*id)).to_bytes(),
+                ]
+                .concat();
+                (*id, h1(&rho_input))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let group_commitment = points
+            .iter()
+            .fold(EdwardsPoint::identity(), |acc, (id, (d, e))| {
+                acc + d + binding_factors[id] * e
+            });
+        let challenge = h2(&[
+            &group_commitment.compress().to_bytes()[..],
+            group_public_key,
+            message,
+        ]);
+        Ok(Self {
+            group_commitment,
+            challenge,
+            binding_factors,
+        })
+    }
+
+    fn lagrange_coefficient(&self, identifier: u16) -> Scalar {
+        let x_i = Scalar::from(u64::from(identifier));
+        let (num, den) = self
+            .binding_factors
+            .keys()
+            .filter(|id| **id != identifier)
+            .fold((Scalar::one(), Scalar::one()), |(num, den), id| {
+                let x_j = Scalar::from(u64::from(*id));
+                (num * x_j, den * (x_j - x_i))
+            });
+        num * den.invert()
+    }
+}
+
+/// Round two: produce this participant's share of the signature over `message`.
+pub fn sign(
+    share: &KeyShare,
+    nonces: &Nonces,
+    commitments: &[Commitment],
+    message: &[u8],
+) -> Result<SignatureShare, Error> {
+    let group_public_key = group_key_bytes(&share.group_public_key)?;
+    let package = SigningPackage::new(&group_public_key, commitments, message)?;
+    let rho = package
+        .binding_factors
+        .get(&share.identifier)
+        .ok_or(Error::MissingCommitment(share.identifier))?;
+    let z = decode_scalar(&nonces.hiding)?
+        + decode_scalar(&nonces.binding)? * rho
+        + package.lagrange_coefficient(share.identifier)
+            * decode_scalar(&share.signing_share)?
+            * package.challenge;
+    Ok(SignatureShare {
+        identifier: share.identifier,
+        share: hex::encode(z.to_bytes()),
+    })
+}
+
+/// Combine signature shares into an ed25519 signature and verify it against
+/// the group public key.
+pub fn aggregate(
+    group_public_key: &str,
+    threshold: u16,
+    commitments: &[Commitment],
+    shares: &[SignatureShare],
+    message: &[u8],
+) -> Result<[u8; 64], Error> {
+    if shares.len() < usize::from(threshold) {
+        return Err(Error::NotEnoughSigners {
+            threshold,
+            got: shares.len(),
+        });
+    }
+    let key = group_key_bytes(group_public_key)?;
+    let package = SigningPackage::new(&key, commitments, message)?;
+    let mut seen = BTreeSet::new();
+    let mut z = Scalar::zero();
+    for share in shares {
+        if !seen.insert(share.identifier) {
+            return Err(Error::DuplicateSigner(share.identifier));
+        }
+        if !package.binding_factors.contains_key(&share.identifier) {
+            return Err(Error::MissingCommitment(share.identifier));
+        }
+        z += decode_scalar(&share.share)?;
+    }
+    if let Some(id) = package.binding_factors.keys().find(|id| !seen.contains(id)) {
+        return Err(Error::MissingSignatureShare(*id));
+    }
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&package.group_commitment.compress().to_bytes());
+    signature[32..].copy_from_slice(&z.to_bytes());
+
+    let public_key = ed25519_dalek::PublicKey::from_bytes(&key).map_err(|_| Error::InvalidPoint)?;
+    let sig =
+        ed25519_dalek::Signature::try_from(&signature[..]).map_err(|_| Error::InvalidSignature)?;
+    public_key
+        .verify(message, &sig)
+        .map_err(|_| Error::InvalidSignature)?;
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_one(shares: &[KeyShare]) -> (Vec<Nonces>, Vec<Commitment>) {
+        shares.iter().map(|s| commit(s).unwrap()).unzip()
+    }
+
+    fn sign_with(shares: &[KeyShare], message: &[u8]) -> Result<[u8; 64], Error> {
+        let (nonces, commitments) = round_one(shares);
+        let signature_shares = shares
+            .iter()
+            .zip(&nonces)
+            .map(|(share, nonces)| sign(share, nonces, &commitments, message))
+            .collect::<Result<Vec<_>, _>>()?;
+        aggregate(
+            &shares[0].group_public_key,
+            shares[0].threshold,
+            &commitments,
+            &signature_shares,
+            message,
+        )
+    }
+
+    #[test]
+    fn threshold_of_shares_signs() {
+        let shares = trusted_dealer_keygen(2, 3).unwrap();
+        let message = b"hello frost";
+        sign_with(&shares[..2], message).unwrap();
+        sign_with(&shares[1..], message).unwrap();
+        sign_with(&[shares[0].clone(), shares[2].clone()], message).unwrap();
+        sign_with(&shares, message).unwrap();
+    }
+
+    #[test]
+    fn below_threshold_fails() {
+        let shares = trusted_dealer_keygen(3, 3).unwrap();
+        assert!(matches!(
+            sign_with(&shares[..2], b"msg"),
+            Err(Error::NotEnoughSigners { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        assert!(trusted_dealer_keygen(0, 3).is_err());
+        assert!(trusted_dealer_keygen(4, 3).is_err());
+    }
+
+    #[test]
+    fn round_trip_through_files() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let message = b"round trip";
+        let shares = trusted_dealer_keygen(2, 3)
+            .unwrap()
+            .iter()
+            .take(2)
+            .map(|share| {
+                let path = dir.path().join(format!("share-{}.json", share.identifier));
+                write_secret_json(&path, share).unwrap();
+                read_json::<KeyShare>(&path).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let (nonces, commitments) = round_one(&shares);
+        let signature_shares = shares
+            .iter()
+            .zip(&nonces)
+            .map(|(share, nonces)| {
+                let path = dir.path().join(format!("nonces-{}.json", share.identifier));
+                write_secret_json(&path, nonces).unwrap();
+                let nonces = read_json::<Nonces>(&path).unwrap();
+                sign(share, &nonces, &commitments, message).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let signature = aggregate(
+            &shares[0].group_public_key,
+            2,
+            &commitments,
+            &signature_shares,
+            message,
+        )
+        .unwrap();
+
+        let key = group_key_bytes(&shares[0].group_public_key).unwrap();
+        ed25519_dalek::PublicKey::from_bytes(&key)
+            .unwrap()
+            .verify(
+                message,
+                &ed25519_dalek::Signature::try_from(&signature[..]).unwrap(),
+            )
+            .unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.path().join("share-1.json"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn duplicate_signature_share_is_rejected() {
+        let shares = trusted_dealer_keygen(2, 3).unwrap();
+        let message = b"duplicate";
+        let (nonces, commitments) = round_one(&shares[..2]);
+        let first = sign(&shares[0], &nonces[0], &commitments, message).unwrap();
+        let second = sign(&shares[1], &nonces[1], &commitments, message).unwrap();
+        assert!(matches!(
+            aggregate(
+                &shares[0].group_public_key,
+                2,
+                &commitments,
+                &[first.clone(), first.clone(), second],
+                message,
+            ),
+            Err(Error::DuplicateSigner(1))
+        ));
+        assert!(matches!(
+            aggregate(
+                &shares[0].group_public_key,
+                2,
+                &commitments,
+                &[first.clone(), first],
+                message,
+            ),
+            Err(Error::DuplicateSigner(1))
+        ));
+    }
+
+    #[test]
+    fn duplicate_commitment_is_rejected() {
+        let shares = trusted_dealer_keygen(2, 3).unwrap();
+        let (nonces, commitments) = round_one(&shares[..1]);
+        let commitments = [commitments[0].clone(), commitments[0].clone()];
+        assert!(matches!(
+            sign(&shares[0], &nonces[0], &commitments, b"msg"),
+            Err(Error::DuplicateSigner(1))
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::{fs, path::PathBuf};
+
+use clap::arg;
+
+use super::{
+    scheme::{self, Commitment, KeyShare, Nonces},
+    TxArgs, TxError,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Scheme(#[from] scheme::Error),
+    #[error(transparent)]
+    Tx(#[from] TxError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("nonces in {0} belong to signer {1}, not {2}")]
+    NonceMismatch(PathBuf, u16, u16),
+    #[error("removing used nonces {path}: {error}")]
+    RemoveNonces {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Key share file created by `frost trusted-dealer`
+    #[arg(long)]
+    pub share: PathBuf,
+
+    /// Nonces file created by `frost commit`. It is deleted once used.
+    #[arg(long)]
+    pub nonces: PathBuf,
+
+    /// Commitment files from every participating signer, including this one
+    #[arg(long = "commitment", required = true)]
+    pub commitments: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub tx: TxArgs,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let share: KeyShare = scheme::read_json(&self.share)?;
+        let nonces: Nonces = scheme::read_json(&self.nonces)?;
+        if nonces.identifier != share.identifier {
+            return Err(Error::NonceMismatch(
+                self.nonces.clone(),
+                nonces.identifier,
+                share.identifier,
+            ));
+        }
+        let commitments = self
+            .commitments
+            .iter()
+            .map(|path| scheme::read_json::<Commitment>(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let signature_share = scheme::sign(&share, &nonces, &commitments, &self.tx.hash()?)?;
+        // Reusing nonces across two signing sessions leaks the key share.
+        fs::remove_file(&self.nonces).map_err(|error| Error::RemoveNonces {
+            path: self.nonces.clone(),
+            error,
+        })?;
+        println!("{}", serde_json::to_string_pretty(&signature_share)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use clap::arg;
+
+use super::scheme;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Scheme(#[from] scheme::Error),
+    #[error("{0} already exists; refusing to overwrite a key share")]
+    Exists(PathBuf),
+    #[error("{0} is given more than once; every share needs its own destination")]
+    DuplicateOut(PathBuf),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Number of shares required to sign
+    #[arg(long, short = 't')]
+    pub threshold: u16,
+
+    /// Where to write a participant's key share, once per participant. The number of
+    /// shares created is the number of `--out` files. Point each one at a separate
+    /// destination (e.g. removable media) owned by that participant.
+    #[arg(long = "out", required = true)]
+    pub outs: Vec<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut seen = HashSet::new();
+        for path in &self.outs {
+            if !seen.insert(path) {
+                return Err(Error::DuplicateOut(path.clone()));
+            }
+            if path.exists() {
+                return Err(Error::Exists(path.clone()));
+            }
+        }
+        let max_signers = u16::try_from(self.outs.len()).unwrap_or(u16::MAX);
+        let shares = scheme::trusted_dealer_keygen(self.threshold, max_signers)?;
+        eprintln!(
+            "warning: this machine generated every share; a trusted dealer can sign alone \
+             until its copies are destroyed"
+        );
+        for (share, path) in shares.iter().zip(&self.outs) {
+            scheme::write_secret_json(path, share)?;
+            eprintln!("Wrote share {} to {}", share.identifier, path.display());
+        }
+        println!("{}", shares[0].group_public_key);
+        Ok(())
+    }
+}
@@ -42,6 +42,13 @@ pub struct Cmd {
     #[arg(long, short = 'd', conflicts_with = "seed")]
     pub default_seed: bool,
 
+    /// Derive keys with a BIP-39 passphrase (the "25th word"), read from
+    /// SOROBAN_BIP39_PASSPHRASE or prompted for. Use this to match wallets,
+    /// e.g. Freighter or Ledger, that were set up with a passphrase. The
+    /// passphrase is not saved; it is asked for again whenever the keys are used.
+    #[arg(long, conflicts_with = "default_seed")]
+    pub bip39_passphrase: bool,
+
     #[command(flatten)]
     pub network: network::Args,
 }
@@ -51,7 +58,11 @@ impl Cmd {
         let seed_phrase = if self.default_seed {
             Secret::test_seed_phrase()
         } else {
-            Secret::from_seed(self.seed.as_deref())
+            if self.bip39_passphrase {
+                secret::bip39_passphrase(&self.name, true)?;
+            }
+            Secret::from_seed(self.seed.as_deref(), self.bip39_passphrase)
+                .map(|secret| secret.with_identity(&self.name))
         }?;
         let secret = if self.as_secret {
             let secret = seed_phrase.private_key(self.hd_path)?;
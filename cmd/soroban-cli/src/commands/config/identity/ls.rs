@@ -1,10 +1,13 @@
 use super::super::locator;
+use crate::commands::global;
 use clap::command;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -15,8 +18,11 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.config_locator.list_identities()?.join("\n"));
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let identities = self.config_locator.list_identities()?;
+        global_args
+            .formatter()
+            .print(identities.join("\n"), &identities)?;
         Ok(())
     }
 }
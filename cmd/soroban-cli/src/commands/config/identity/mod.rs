@@ -1,12 +1,17 @@
 use clap::Parser;
 
+use crate::commands::global;
+
 pub mod add;
 pub mod address;
+pub mod frost;
 pub mod fund;
 pub mod generate;
 pub mod ls;
 pub mod rm;
+pub mod rotate;
 pub mod show;
+pub mod status;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
@@ -14,6 +19,9 @@ pub enum Cmd {
     Add(add::Cmd),
     /// Given an identity return its address (public key)
     Address(address::Cmd),
+    /// Experimental threshold (t-of-n) signing with FROST key shares
+    #[command(subcommand)]
+    Frost(frost::Cmd),
     /// Fund an identity on a test network
     Fund(fund::Cmd),
     /// Generate a new identity with a seed phrase, currently 12 words
@@ -22,8 +30,13 @@ pub enum Cmd {
     Ls(ls::Cmd),
     /// Remove an identity
     Rm(rm::Cmd),
+    /// Replace an identity with a new key, optionally moving a contract's admin to it first
+    Rotate(rotate::Cmd),
     /// Given an identity return its private key
     Show(show::Cmd),
+    /// Show the state of an identity's account on the network: its sequence number,
+    /// subentries, reserves, and signers
+    Status(status::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -34,6 +47,8 @@ pub enum Error {
     #[error(transparent)]
     Address(#[from] address::Error),
     #[error(transparent)]
+    Frost(#[from] frost::Error),
+    #[error(transparent)]
     Fund(#[from] fund::Error),
 
     #[error(transparent)]
@@ -41,22 +56,30 @@ pub enum Error {
     #[error(transparent)]
     Rm(#[from] rm::Error),
     #[error(transparent)]
+    Rotate(#[from] rotate::Error),
+    #[error(transparent)]
     Ls(#[from] ls::Error),
 
     #[error(transparent)]
     Show(#[from] show::Error),
+
+    #[error(transparent)]
+    Status(#[from] status::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Add(cmd) => cmd.run()?,
-            Cmd::Address(cmd) => cmd.run()?,
+            Cmd::Address(cmd) => cmd.run(global_args)?,
+            Cmd::Frost(cmd) => cmd.run()?,
             Cmd::Fund(cmd) => cmd.run().await?,
             Cmd::Generate(cmd) => cmd.run().await?,
-            Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Ls(cmd) => cmd.run(global_args)?,
             Cmd::Rm(cmd) => cmd.run()?,
-            Cmd::Show(cmd) => cmd.run()?,
+            Cmd::Rotate(cmd) => cmd.run(global_args).await?,
+            Cmd::Show(cmd) => cmd.run(global_args)?,
+            Cmd::Status(cmd) => cmd.run(global_args).await?,
         };
         Ok(())
     }
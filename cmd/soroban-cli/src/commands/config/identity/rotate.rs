@@ -0,0 +1,117 @@
+use clap::{arg, command};
+
+use crate::{
+    commands::{contract::invoke, global},
+    rpc::Outcome,
+};
+
+use super::super::{
+    self as config, locator, network,
+    secret::{self, Secret},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    // Boxed as `invoke::Error` itself wraps `config::Error`, which wraps this type.
+    #[error(transparent)]
+    Invoke(Box<invoke::Error>),
+    #[error("invalid admin function {0:?}, expected the form `set_admin(new_admin)`")]
+    InvalidAdminFn(String),
+    #[error("--contract-id requires a network")]
+    MissingNetwork,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of the identity to rotate
+    pub name: String,
+
+    /// Contract whose admin is the identity being rotated. If provided, the admin is changed
+    /// to the new key before the old one is archived.
+    #[arg(long = "contract-id", requires = "admin_fn")]
+    pub contract_id: Option<String>,
+
+    /// Admin function used to change the admin, with the name of the argument that takes the
+    /// new admin address, e.g. `set_admin(new_admin)`
+    #[arg(long, requires = "contract_id")]
+    pub admin_fn: Option<String>,
+
+    /// If the identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let old = self.locator.read_identity(&self.name)?;
+        let new = Secret::from_seed(None, false)?;
+        let new_address = new.public_key(None)?;
+
+        // Persist the new key before touching the contract so it can't be lost if a later
+        // step fails.
+        let pending = format!("{}-pending", self.name);
+        self.locator.write_identity(&pending, &new)?;
+
+        if let (Some(contract_id), Some(admin_fn)) = (&self.contract_id, &self.admin_fn) {
+            if self.network.is_no_network() {
+                return Err(Error::MissingNetwork);
+            }
+            let (function, arg) = parse_admin_fn(admin_fn)?;
+            let cmd = invoke::Cmd {
+                contract_id: contract_id.clone(),
+                slop: vec![function.into(), format!("--{arg}={new_address}").into()],
+                config: config::Args {
+                    network: self.network.clone(),
+                    source_account: Some(self.name.clone()),
+                    hd_path: self.hd_path,
+                    locator: self.locator.clone(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            match cmd.invoke(global_args).await {
+                Ok(Outcome::Done(_)) => {}
+                // Nothing changed on the network, so the old key stays in use.
+                Ok(Outcome::DryRun) => {
+                    self.locator.remove_identity(&pending)?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.locator.remove_identity(&pending)?;
+                    return Err(Error::Invoke(Box::new(e)));
+                }
+            }
+        }
+
+        let archived = format!(
+            "{}-rotated-{}",
+            self.name,
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        );
+        self.locator.write_identity(&archived, &old)?;
+        self.locator.write_identity(&self.name, &new)?;
+        self.locator.remove_identity(&pending)?;
+        eprintln!("Archived previous key as {archived}");
+        println!("{new_address}");
+        Ok(())
+    }
+}
+
+fn parse_admin_fn(s: &str) -> Result<(&str, &str), Error> {
+    s.strip_suffix(')')
+        .and_then(|s| s.split_once('('))
+        .map(|(function, arg)| (function.trim(), arg.trim()))
+        .filter(|(function, arg)| !function.is_empty() && !arg.is_empty())
+        .ok_or_else(|| Error::InvalidAdminFn(s.to_string()))
+}
@@ -1,4 +1,5 @@
 use super::super::{locator, secret};
+use crate::commands::global;
 use clap::arg;
 
 #[derive(thiserror::Error, Debug)]
@@ -11,6 +12,9 @@ pub enum Error {
 
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -28,8 +32,11 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.private_key()?.to_string());
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let secret_key = self.private_key()?.to_string();
+        global_args
+            .formatter()
+            .print(&secret_key, &serde_json::json!({ "secretKey": secret_key }))?;
         Ok(())
     }
 
@@ -0,0 +1,132 @@
+use clap::command;
+use serde_json::{json, Value};
+use soroban_env_host::xdr::{
+    AccountEntry, AccountEntryExt, AccountEntryExtensionV1, AccountEntryExtensionV1Ext,
+    AccountEntryExtensionV2, SignerKey, Uint256,
+};
+
+use crate::{
+    commands::{
+        account::{master_key, signers_json, write_signers},
+        config::{locator, network},
+        global,
+    },
+    format::Format,
+    rpc,
+};
+
+use super::address;
+
+/// Base reserve of the public networks, in stroops. The RPC server doesn't report it.
+const BASE_RESERVE: i64 = 5_000_000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub network: network::Args,
+    /// Identity whose account to show
+    #[command(flatten)]
+    pub address: address::Cmd,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let public_key = self.address.public_key()?;
+        let network = self.network.get(&self.address.locator)?;
+        let account = rpc::Client::new(&network.rpc_url)?
+            .get_account(&public_key.to_string())
+            .await?;
+        let format = self.address.locator.read_settings()?.format;
+        global_args
+            .formatter()
+            .print(to_text(&account, &format), &to_json(&account))?;
+        if !can_sign(&account, &public_key.0) {
+            eprintln!(
+                "warning: the identity's key is no longer a signer of {public_key}, it cannot sign for the account"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The number of entries the account's reserves are sponsored for, and that it sponsors.
+fn sponsorship(account: &AccountEntry) -> (u32, u32) {
+    match &account.ext {
+        AccountEntryExt::V1(AccountEntryExtensionV1 {
+            ext:
+                AccountEntryExtensionV1Ext::V2(AccountEntryExtensionV2 {
+                    num_sponsored,
+                    num_sponsoring,
+                    ..
+                }),
+            ..
+        }) => (*num_sponsored, *num_sponsoring),
+        _ => (0, 0),
+    }
+}
+
+/// The least balance the account must hold, in stroops.
+fn minimum_balance(account: &AccountEntry) -> i64 {
+    let (num_sponsored, num_sponsoring) = sponsorship(account);
+    let reserves = 2 + i64::from(account.num_sub_entries) + i64::from(num_sponsoring)
+        - i64::from(num_sponsored);
+    reserves * BASE_RESERVE
+}
+
+fn to_json(account: &AccountEntry) -> Value {
+    let (num_sponsored, num_sponsoring) = sponsorship(account);
+    let [_, low, medium, high] = account.thresholds.0;
+    json!({
+        "sequence": account.seq_num.0.to_string(),
+        "balance": account.balance.to_string(),
+        "subentries": account.num_sub_entries,
+        "sponsoring": num_sponsoring,
+        "sponsored": num_sponsored,
+        "minimumBalance": minimum_balance(account).to_string(),
+        "thresholds": { "low": low, "medium": medium, "high": high },
+        "signers": signers_json(account),
+    })
+}
+
+fn to_text(account: &AccountEntry, format: &Format) -> String {
+    let (num_sponsored, num_sponsoring) = sponsorship(account);
+    let [_, low, medium, high] = account.thresholds.0;
+    let mut out = format!(
+        "Sequence number: {}\n\
+         Balance: {}\n\
+         Subentries: {}\n\
+         Sponsoring: {num_sponsoring}, sponsored: {num_sponsored}\n\
+         Minimum balance: {} (assuming a base reserve of {})\n\
+         Thresholds: low {low}, medium {medium}, high {high}\n",
+        account.seq_num.0,
+        format.amount(account.balance),
+        account.num_sub_entries,
+        format.amount(minimum_balance(account)),
+        format.amount(BASE_RESERVE),
+    );
+    write_signers(&mut out, account);
+    out
+}
+
+/// Whether `key` still carries weight on the account, as its master key or as a signer.
+fn can_sign(account: &AccountEntry, key: &[u8; 32]) -> bool {
+    (master_key(account) == key && account.thresholds.0[0] > 0)
+        || account.signers.iter().any(|signer| {
+            signer.weight > 0 && matches!(&signer.key, SignerKey::Ed25519(Uint256(k)) if k == key)
+        })
+}
@@ -1,7 +1,10 @@
 use crate::{commands::HEADING_SANDBOX, utils};
 use clap::arg;
 use soroban_ledger_snapshot::LedgerSnapshot;
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, clap::Args, Clone, Default)]
 #[group(skip)]
@@ -13,6 +16,15 @@ pub struct Args {
         help_heading = HEADING_SANDBOX,
     )]
     pub ledger_file: Option<PathBuf>,
+
+    /// Also keep a copy of the ledger state for each ledger written, next to the ledger file, so
+    /// it can be read back with `--at-ledger`
+    #[arg(
+        long,
+        env = "SOROBAN_LEDGER_HISTORY",
+        help_heading = HEADING_SANDBOX,
+    )]
+    pub keep_history: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -28,6 +40,14 @@ pub enum Error {
         filepath: PathBuf,
         error: soroban_ledger_snapshot::Error,
     },
+
+    #[error("reading snapshot directory {dir}: {error}")]
+    CannotReadHistory { dir: PathBuf, error: std::io::Error },
+
+    #[error(
+        "no snapshot of ledger {ledger} or earlier in {dir}; write ledgers with --keep-history"
+    )]
+    NoSnapshotAtLedger { ledger: u32, dir: PathBuf },
 }
 
 impl Args {
@@ -42,7 +62,59 @@ impl Args {
 
         state
             .write_file(&filepath)
-            .map_err(|e| Error::CannotCommitLedgerFile { filepath, error: e })
+            .map_err(|e| Error::CannotCommitLedgerFile {
+                filepath: filepath.clone(),
+                error: e,
+            })?;
+        if !self.keep_history {
+            return Ok(());
+        }
+        let snapshot = self
+            .history_dir(pwd)
+            .join(format!("{}.json", state.sequence_number));
+        if let Some(dir) = snapshot.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::CannotReadHistory {
+                dir: dir.to_path_buf(),
+                error: e,
+            })?;
+        }
+        state
+            .write_file(&snapshot)
+            .map_err(|e| Error::CannotCommitLedgerFile {
+                filepath: snapshot,
+                error: e,
+            })
+    }
+
+    /// The state as of `ledger`: the latest snapshot written at or before it.
+    pub fn read_at(&self, pwd: &Path, ledger: u32) -> Result<LedgerSnapshot, Error> {
+        let dir = self.history_dir(pwd);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::NoSnapshotAtLedger { ledger, dir })
+            }
+            Err(e) => return Err(Error::CannotReadHistory { dir, error: e }),
+        };
+        let snapshot = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                let sequence = path.file_stem()?.to_str()?.parse::<u32>().ok()?;
+                (sequence <= ledger).then_some((sequence, path))
+            })
+            .max_by_key(|(sequence, _)| *sequence);
+        let Some((_, filepath)) = snapshot else {
+            return Err(Error::NoSnapshotAtLedger { ledger, dir });
+        };
+        LedgerSnapshot::read_file(&filepath)
+            .map_err(|e| Error::CannotReadLedgerFile { filepath, error: e })
+    }
+
+    /// Directory holding a snapshot of each ledger written with `--keep-history`, named by
+    /// sequence number, e.g. `.soroban/ledger.history/12.json`.
+    pub fn history_dir(&self, pwd: &Path) -> PathBuf {
+        self.path(pwd).with_extension("history")
     }
 
     pub fn path(&self, pwd: &Path) -> PathBuf {
@@ -1,14 +1,20 @@
 use clap::arg;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     ffi::OsStr,
     fmt::Display,
     fs, io,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
-use crate::{utils::find_config_dir, Pwd};
+use crate::{
+    format::Format,
+    utils::{self, find_config_dir},
+    Pwd,
+};
 
 use super::{network::Network, secret::Secret};
 
@@ -58,6 +64,10 @@ pub enum Error {
     ConfigMissing(String, String),
     #[error(transparent)]
     String(#[from] std::string::FromUtf8Error),
+    #[error("Invalid alias {0}, only letters, digits, '-' and '_' are allowed")]
+    InvalidAlias(String),
+    #[error(transparent)]
+    TomlSerialization(#[from] toml::ser::Error),
 }
 
 #[derive(Debug, clap::Args, Default, Clone)]
@@ -71,6 +81,19 @@ pub struct Args {
     pub config_dir: Option<PathBuf>,
 }
 
+/// Contract IDs saved under an alias, keyed by network passphrase
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ContractAlias {
+    pub ids: BTreeMap<String, String>,
+}
+
+/// Settings saved with `soroban config set`, in `config.toml` of the config directory
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Settings {
+    pub format: Format,
+}
+
 pub enum Location {
     Local(PathBuf),
     Global(PathBuf),
@@ -176,7 +199,8 @@ impl Args {
             .collect::<Vec<_>>())
     }
     pub fn read_identity(&self, name: &str) -> Result<Secret, Error> {
-        KeyType::Identity.read_with_global(name, &self.local_config()?)
+        let secret: Secret = KeyType::Identity.read_with_global(name, &self.local_config()?)?;
+        Ok(secret.with_identity(name))
     }
 
     pub fn read_network(&self, name: &str) -> Result<Network, Error> {
@@ -198,6 +222,196 @@ impl Args {
     pub fn remove_network(&self, name: &str) -> Result<(), Error> {
         KeyType::Network.remove(name, &self.config_dir()?)
     }
+
+    pub fn save_contract_id(
+        &self,
+        network_passphrase: &str,
+        contract_id: &str,
+        alias: &str,
+    ) -> Result<(), Error> {
+        validate_alias(alias)?;
+        let path = self.config_dir()?;
+        let mut data: ContractAlias = KeyType::ContractIds.read(alias, &path).unwrap_or_default();
+        data.ids
+            .insert(network_passphrase.to_string(), contract_id.to_string());
+        KeyType::ContractIds.write(alias, &data, &path)
+    }
+
+    pub fn get_contract_id(
+        &self,
+        alias: &str,
+        network_passphrase: &str,
+    ) -> Result<Option<String>, Error> {
+        if validate_alias(alias).is_err() {
+            return Ok(None);
+        }
+        Ok(KeyType::ContractIds
+            .read_with_global::<ContractAlias>(alias, &self.local_config()?)
+            .ok()
+            .and_then(|data| data.ids.get(network_passphrase).cloned()))
+    }
+
+    pub fn remove_contract_id(&self, network_passphrase: &str, alias: &str) -> Result<(), Error> {
+        validate_alias(alias)?;
+        let path = self.config_dir()?;
+        let mut data: ContractAlias = KeyType::ContractIds.read(alias, &path)?;
+        data.ids.remove(network_passphrase);
+        if data.ids.is_empty() {
+            KeyType::ContractIds.remove(alias, &path)
+        } else {
+            KeyType::ContractIds.write(alias, &data, &path)
+        }
+    }
+
+    /// Aliases and their contract IDs for the network, local aliases shadowing global ones
+    pub fn list_contract_aliases(
+        &self,
+        network_passphrase: &str,
+    ) -> Result<Vec<(String, String)>, Error> {
+        let mut aliases = BTreeMap::new();
+        for (alias, location) in KeyType::ContractIds
+            .list_paths(&self.local_and_global()?)?
+            .into_iter()
+            .rev()
+        {
+            if let Ok(data) = KeyType::read_from_path::<ContractAlias>(location.as_ref()) {
+                if let Some(contract_id) = data.ids.get(network_passphrase) {
+                    aliases.insert(alias, contract_id.clone());
+                }
+            }
+        }
+        Ok(aliases.into_iter().collect())
+    }
+
+    /// Settings of the local config directory, or of the global one if there are none locally
+    pub fn read_settings(&self) -> Result<Settings, Error> {
+        for dir in [self.local_config()?, global_config_path()?] {
+            let path = dir.join(SETTINGS_FILE);
+            if path.exists() {
+                return Ok(toml::from_slice(&fs::read(path)?)?);
+            }
+        }
+        Ok(Settings::default())
+    }
+
+    pub fn write_settings(&self, settings: &Settings) -> Result<(), Error> {
+        let path = ensure_directory(self.config_dir()?.join(SETTINGS_FILE))?;
+        let data = toml::to_string(settings).map_err(|_| Error::ConfigSerialization)?;
+        Ok(fs::write(path, data)?)
+    }
+
+    /// Return `alias_or_id` unchanged if it is a contract ID, otherwise the ID saved under
+    /// that alias for the network, if any.
+    pub fn resolve_contract_id(
+        &self,
+        alias_or_id: &str,
+        network_passphrase: &str,
+    ) -> Result<String, Error> {
+        if utils::contract_id_from_str(alias_or_id).is_ok() {
+            return Ok(alias_or_id.to_string());
+        }
+        Ok(self
+            .get_contract_id(alias_or_id, network_passphrase)?
+            .unwrap_or_else(|| alias_or_id.to_string()))
+    }
+
+    /// Reserve a sequence number for the account's next transaction on the network: one past
+    /// the greater of `current`, its sequence number on the network, and the last one reserved
+    /// here. Invocations launched back-to-back from the same account then each get their own,
+    /// even before the network has seen the previous ones.
+    pub fn next_sequence(
+        &self,
+        network_passphrase: &str,
+        account: &str,
+        current: i64,
+    ) -> Result<i64, Error> {
+        self.with_sequences(|sequences| {
+            let last = sequences
+                .entry(network_passphrase.to_string())
+                .or_default()
+                .entry(account.to_string())
+                .or_default();
+            *last = current.max(*last) + 1;
+            *last
+        })
+    }
+
+    /// Forget the sequence numbers reserved for the account, e.g. after the network rejected
+    /// one, so the next is based on the network's alone.
+    pub fn reset_sequence(&self, network_passphrase: &str, account: &str) -> Result<(), Error> {
+        self.with_sequences(|sequences| {
+            if let Some(accounts) = sequences.get_mut(network_passphrase) {
+                accounts.remove(account);
+            }
+        })
+    }
+
+    /// Update the sequence number cache while holding a lock file, as other processes may be
+    /// reserving sequence numbers at the same time.
+    fn with_sequences<T>(&self, f: impl FnOnce(&mut Sequences) -> T) -> Result<T, Error> {
+        let path = ensure_directory(self.config_dir()?.join(SEQUENCES_FILE))?;
+        let lock = path.with_extension("lock");
+        acquire_lock(&lock, STALE_LOCK)?;
+        let result = (|| -> Result<T, Error> {
+            let mut sequences: Sequences = if path.exists() {
+                toml::from_slice(&fs::read(&path)?)?
+            } else {
+                Sequences::default()
+            };
+            let result = f(&mut sequences);
+            fs::write(&path, toml::to_string(&sequences)?)?;
+            Ok(result)
+        })();
+        let _ = fs::remove_file(&lock);
+        result
+    }
+}
+
+/// Last sequence number reserved for each account, keyed by network passphrase
+type Sequences = BTreeMap<String, BTreeMap<String, i64>>;
+
+/// How long a lock file can go unmodified before it is assumed to be left over from a process
+/// that died. Locks are only held while reading and writing a small file.
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// Create the lock file, waiting for another process to remove it first. A lock that has not
+/// been modified for `stale_after` is taken over.
+fn acquire_lock(lock: &Path, stale_after: Duration) -> Result<(), Error> {
+    loop {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(lock)
+        {
+            Ok(_) => return Ok(()),
+            Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e.into()),
+            Err(_) => {}
+        }
+        let stale = fs::metadata(lock)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                modified
+                    .elapsed()
+                    .is_ok_and(|elapsed| elapsed >= stale_after)
+            });
+        if stale {
+            let _ = fs::remove_file(lock);
+        } else {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+fn validate_alias(alias: &str) -> Result<(), Error> {
+    if !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidAlias(alias.to_string()))
+    }
 }
 
 fn ensure_directory(dir: PathBuf) -> Result<PathBuf, Error> {
@@ -227,9 +441,13 @@ fn read_dir(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
     Ok(res)
 }
 
+const SETTINGS_FILE: &str = "config.toml";
+const SEQUENCES_FILE: &str = "sequences.toml";
+
 pub enum KeyType {
     Identity,
     Network,
+    ContractIds,
 }
 
 impl Display for KeyType {
@@ -240,6 +458,7 @@ impl Display for KeyType {
             match self {
                 KeyType::Identity => "identity",
                 KeyType::Network => "network",
+                KeyType::ContractIds => "contract-ids",
             }
         )
     }
@@ -339,3 +558,54 @@ impl Pwd for Args {
         self.config_dir = Some(pwd.to_path_buf());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn stale_lock_is_taken_over() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let lock = dir.path().join("sequences.lock");
+        fs::write(&lock, "").unwrap();
+        acquire_lock(&lock, Duration::ZERO).unwrap();
+        assert!(lock.exists());
+    }
+
+    #[test]
+    fn fresh_lock_is_waited_for() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let lock = dir.path().join("sequences.lock");
+        fs::write(&lock, "").unwrap();
+        let holder = {
+            let lock = lock.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(200));
+                fs::remove_file(lock).unwrap();
+            })
+        };
+        let start = Instant::now();
+        acquire_lock(&lock, Duration::from_secs(60)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        holder.join().unwrap();
+        assert!(lock.exists());
+    }
+
+    #[test]
+    fn sequences_are_reserved_once() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let locator = Args {
+            global: false,
+            config_dir: Some(dir.path().to_path_buf()),
+        };
+        let account = "GDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCR4W4";
+        assert_eq!(locator.next_sequence("test", account, 41).unwrap(), 42);
+        assert_eq!(locator.next_sequence("test", account, 41).unwrap(), 43);
+        assert_eq!(locator.next_sequence("other", account, 41).unwrap(), 42);
+        locator.reset_sequence("test", account).unwrap();
+        assert_eq!(locator.next_sequence("test", account, 41).unwrap(), 42);
+        assert!(!dir.path().join(".soroban/sequences.lock").exists());
+    }
+}
@@ -2,18 +2,28 @@ use std::path::PathBuf;
 
 use clap::{arg, command, Parser};
 use serde::{Deserialize, Serialize};
+use soroban_env_host::xdr::{
+    self, DecoratedSignature, FeeBumpTransaction, FeeBumpTransactionEnvelope,
+    FeeBumpTransactionExt, FeeBumpTransactionInnerTx, MuxedAccount, Signature, SignatureHint,
+    Transaction, TransactionEnvelope, TransactionV1Envelope, Uint256,
+};
 use soroban_ledger_snapshot::LedgerSnapshot;
 
-use crate::Pwd;
+use crate::{
+    commands::{global, HEADING_RPC},
+    utils, Pwd,
+};
 
 use self::{network::Network, secret::Secret};
 
+pub mod doctor;
 pub mod events_file;
 pub mod identity;
 pub mod ledger_file;
 pub mod locator;
 pub mod network;
 pub mod secret;
+pub mod set;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
@@ -24,6 +34,13 @@ pub enum Cmd {
     /// Configure different networks
     #[command(subcommand)]
     Network(network::Cmd),
+
+    /// Change a setting, e.g. how output is formatted with `format.timestamps`,
+    /// `format.thousands-separator` and `format.amounts`
+    Set(set::Cmd),
+
+    /// Check the config for problems, and suggest fixes
+    Doctor(doctor::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -34,6 +51,12 @@ pub enum Error {
     #[error(transparent)]
     Network(#[from] network::Error),
 
+    #[error(transparent)]
+    Set(#[from] set::Error),
+
+    #[error(transparent)]
+    Doctor(#[from] doctor::Error),
+
     #[error(transparent)]
     Ledger(#[from] ledger_file::Error),
 
@@ -42,13 +65,18 @@ pub enum Error {
 
     #[error(transparent)]
     Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
-            Cmd::Identity(identity) => identity.run().await?,
-            Cmd::Network(network) => network.run()?,
+            Cmd::Identity(identity) => identity.run(global_args).await?,
+            Cmd::Network(network) => network.run(global_args).await?,
+            Cmd::Set(set) => set.run()?,
+            Cmd::Doctor(doctor) => doctor.run(global_args).await?,
         }
         Ok(())
     }
@@ -71,19 +99,75 @@ pub struct Args {
     /// If using a seed phrase, which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
     pub hd_path: Option<usize>,
 
+    #[arg(long, help_heading = HEADING_RPC)]
+    /// Account that pays the transaction fee instead of the source account, by wrapping the signed transaction in a fee bump transaction. Can be an identity, a secret key, or a seed phrase, whose first key (`m/44'/148'/0'`) is used regardless of `--hd-path`
+    pub fee_bump_source: Option<String>,
+
     #[command(flatten)]
     pub locator: locator::Args,
 }
 
 impl Args {
     pub fn key_pair(&self) -> Result<ed25519_dalek::Keypair, Error> {
-        let key = if let Some(source_account) = &self.source_account {
-            self.account(source_account)?
+        Ok(self.source_secret()?.key_pair(self.hd_path)?)
+    }
+
+    pub fn source_secret(&self) -> Result<Secret, Error> {
+        if let Some(source_account) = &self.source_account {
+            self.account(source_account)
         } else {
-            secret::Secret::test_seed_phrase()?
-        };
+            Ok(secret::Secret::test_seed_phrase()?)
+        }
+    }
+
+    pub fn public_key(&self) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
+        Ok(self.source_secret()?.public_key(self.hd_path)?)
+    }
 
-        Ok(key.key_pair(self.hd_path)?)
+    /// Sign the transaction with the source account, which may be an external signer.
+    pub fn sign(
+        &self,
+        tx: &Transaction,
+        network_passphrase: &str,
+    ) -> Result<TransactionEnvelope, Error> {
+        let tx_hash = utils::transaction_hash(tx, network_passphrase)?;
+        let decorated_signature =
+            decorated_signature(&self.source_secret()?, self.hd_path, &tx_hash)?;
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: vec![decorated_signature].try_into()?,
+        }))
+    }
+
+    /// Sign the transaction with the source account, then wrap it in a fee bump transaction
+    /// signed by `--fee-bump-source` if one was given. `inclusion_fee` is the part of the
+    /// transaction's fee bid for inclusion (`--fee`), the rest being its resource fee.
+    pub fn sign_for_submission(
+        &self,
+        tx: &Transaction,
+        inclusion_fee: u32,
+        network_passphrase: &str,
+    ) -> Result<TransactionEnvelope, Error> {
+        let TransactionEnvelope::Tx(inner) = self.sign(tx, network_passphrase)? else {
+            unreachable!("sign returns a v1 envelope")
+        };
+        let Some(fee_bump_source) = &self.fee_bump_source else {
+            return Ok(TransactionEnvelope::Tx(inner));
+        };
+        // `--hd-path` selects the source's key, so the fee payer uses its first key
+        let fee_payer = self.account(fee_bump_source)?;
+        let fee_bump = FeeBumpTransaction {
+            fee_source: MuxedAccount::Ed25519(Uint256(fee_payer.public_key(None)?.0)),
+            fee: fee_bump_fee(tx, inclusion_fee),
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        };
+        let hash = utils::fee_bump_transaction_hash(&fee_bump, network_passphrase)?;
+        let decorated_signature = decorated_signature(&fee_payer, None, &hash)?;
+        Ok(TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+            tx: fee_bump,
+            signatures: vec![decorated_signature].try_into()?,
+        }))
     }
 
     pub fn account(&self, account_str: &str) -> Result<Secret, Error> {
@@ -102,10 +186,32 @@ impl Args {
         self.network.is_no_network()
     }
 
+    /// Resolve a contract alias saved for the current network, passing contract IDs through.
+    pub fn resolve_contract_id(&self, alias_or_id: &str) -> Result<String, Error> {
+        Ok(self
+            .locator
+            .resolve_contract_id(alias_or_id, &self.network.alias_key(&self.locator)?)?)
+    }
+
+    pub fn save_contract_alias(&self, alias: &str, contract_id: &str) -> Result<(), Error> {
+        Ok(self.locator.save_contract_id(
+            &self.network.alias_key(&self.locator)?,
+            contract_id,
+            alias,
+        )?)
+    }
+
     pub fn get_state(&self) -> Result<LedgerSnapshot, Error> {
         Ok(self.ledger_file.read(&self.locator.config_dir()?)?)
     }
 
+    /// The sandbox state as of a past ledger, from the snapshots kept alongside the ledger file.
+    pub fn get_state_at(&self, ledger: u32) -> Result<LedgerSnapshot, Error> {
+        Ok(self
+            .ledger_file
+            .read_at(&self.locator.config_dir()?, ledger)?)
+    }
+
     pub fn set_state(&self, state: &mut LedgerSnapshot) -> Result<(), Error> {
         Ok(self.ledger_file.write(state, &self.locator.config_dir()?)?)
     }
@@ -115,6 +221,34 @@ impl Args {
     }
 }
 
+/// The fee a fee bump of `tx` offers: the inner transaction's inclusion fee rate for each of
+/// its operations and for the fee bump itself, plus the inner resource fee, which is not bid
+/// again. Only the fee actually incurred is charged.
+fn fee_bump_fee(tx: &Transaction, inclusion_fee: u32) -> i64 {
+    let ops = i64::try_from(tx.operations.len())
+        .unwrap_or(i64::MAX)
+        .max(1);
+    let inclusion_fee = i64::from(inclusion_fee.min(tx.fee));
+    let resource_fee = i64::from(tx.fee) - inclusion_fee;
+    // Round the rate up so the fee bump never bids less than the inner transaction.
+    let rate = (inclusion_fee + ops - 1) / ops;
+    (ops + 1) * rate + resource_fee
+}
+
+/// Sign `hash` with the secret, with the hint of its public key.
+pub fn decorated_signature(
+    secret: &Secret,
+    hd_path: Option<usize>,
+    hash: &[u8; 32],
+) -> Result<DecoratedSignature, Error> {
+    let signature = secret.sign(hd_path, hash)?;
+    let public_key = secret.public_key(hd_path)?;
+    Ok(DecoratedSignature {
+        hint: SignatureHint(public_key.0[28..].try_into()?),
+        signature: Signature(signature.to_bytes().try_into()?),
+    })
+}
+
 impl Pwd for Args {
     fn set_pwd(&mut self, pwd: &std::path::Path) {
         self.locator.set_pwd(pwd);
@@ -123,3 +257,86 @@ impl Pwd for Args {
 
 #[derive(Default, Serialize, Deserialize)]
 pub struct Config {}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::Verifier;
+    use soroban_env_host::xdr::{
+        BumpSequenceOp, Memo, Operation, OperationBody, Preconditions, SequenceNumber,
+        TransactionExt,
+    };
+
+    use super::*;
+
+    const SOURCE: &str = "SC36BWNUOCZAO7DMEJNNKFV6BOTPJP7IG5PSHLUOLT6DZFRU3D3XGIXW";
+    const FEE_PAYER: &str =
+        "coral light army gather adapt blossom school alcohol coral light army giggle";
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn tx(ops: usize, fee: u32) -> Transaction {
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::BumpSequence(BumpSequenceOp {
+                bump_to: SequenceNumber(0),
+            }),
+        };
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![op; ops].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    fn verify(key: &ed25519_dalek::Keypair, hash: &[u8; 32], signature: &DecoratedSignature) {
+        let signature = ed25519_dalek::Signature::try_from(signature.signature.0.as_slice());
+        key.verify(hash, &signature.unwrap()).unwrap();
+    }
+
+    #[test]
+    fn fee_bump_fee_and_signatures() {
+        let config = Args {
+            source_account: Some(SOURCE.to_string()),
+            fee_bump_source: Some(FEE_PAYER.to_string()),
+            // Selects the source's key only, the fee payer signs with its first key
+            hd_path: Some(1),
+            ..Args::default()
+        };
+        // One operation bidding 100 for inclusion, with a resource fee of 5000
+        let TransactionEnvelope::TxFeeBump(envelope) = config
+            .sign_for_submission(&tx(1, 5100), 100, PASSPHRASE)
+            .unwrap()
+        else {
+            panic!("expected a fee bump envelope");
+        };
+        assert_eq!(envelope.tx.fee, 2 * 100 + 5000);
+
+        let fee_payer = FEE_PAYER.parse::<Secret>().unwrap().key_pair(None).unwrap();
+        assert_eq!(
+            envelope.tx.fee_source,
+            MuxedAccount::Ed25519(Uint256(fee_payer.public.to_bytes()))
+        );
+        let [signature] = envelope.signatures.as_slice() else {
+            panic!("expected one fee bump signature");
+        };
+        let hash = utils::fee_bump_transaction_hash(&envelope.tx, PASSPHRASE).unwrap();
+        verify(&fee_payer, &hash, signature);
+
+        let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+        let [signature] = inner.signatures.as_slice() else {
+            panic!("expected one inner signature");
+        };
+        let source = SOURCE.parse::<Secret>().unwrap().key_pair(None).unwrap();
+        let hash = utils::transaction_hash(&inner.tx, PASSPHRASE).unwrap();
+        verify(&source, &hash, signature);
+    }
+
+    #[test]
+    fn fee_bump_fee_covers_every_operation() {
+        assert_eq!(fee_bump_fee(&tx(3, 300), 300), 400);
+        assert_eq!(fee_bump_fee(&tx(2, 1101), 101), 153 + 1000);
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Write;
+
+use clap::{command, Parser};
+use serde_json::{json, Map, Value};
+use soroban_env_host::xdr::{ConfigSettingEntry, ConfigSettingId};
+
+use super::locator;
+use crate::{
+    commands::global,
+    rpc::{self, Client, FeeDistribution},
+};
+
+/// Config settings that hold resource prices.
+const PRICE_SETTINGS: &[ConfigSettingId] = &[
+    ConfigSettingId::ContractComputeV0,
+    ConfigSettingId::ContractLedgerCostV0,
+    ConfigSettingId::ContractHistoricalDataV0,
+    ConfigSettingId::ContractMetaDataV0,
+    ConfigSettingId::ContractBandwidthV0,
+];
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] Box<super::Error>),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Print the network's passphrase, protocol version and latest ledger, the inclusion fees bid
+/// recently, and the prices of Soroban resources.
+#[derive(Debug, Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    network: super::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network = self.network.get(&self.locator).map_err(Box::new)?;
+        let client = Client::new(&network.rpc_url)?;
+        let info = client.get_network().await?;
+        let latest = client.get_latest_ledger().await?;
+        // getFeeStats is newer than the rest, so older servers still get the other info.
+        let fee_stats = match client.get_fee_stats().await {
+            Ok(fee_stats) => Some(fee_stats),
+            Err(e) => {
+                eprintln!("warning: the server did not report fee stats: {e}");
+                None
+            }
+        };
+        let prices = resource_prices(&client.get_config_settings(PRICE_SETTINGS).await?)?;
+
+        let mut text = format!(
+            "Passphrase: {}\nProtocol version: {}\nLatest ledger: {}\n",
+            info.passphrase, latest.protocol_version, latest.sequence
+        );
+        if let Some(url) = &info.friendbot_url {
+            let _ = writeln!(text, "Friendbot: {url}");
+        }
+        if let Some(fee_stats) = &fee_stats {
+            write_fees(&mut text, "Inclusion fees", &fee_stats.inclusion_fee);
+            write_fees(
+                &mut text,
+                "Soroban inclusion fees",
+                &fee_stats.soroban_inclusion_fee,
+            );
+        }
+        let _ = writeln!(text, "Resource prices, in stroops:");
+        for (name, price) in &prices {
+            let _ = writeln!(text, "  {name}: {price}");
+        }
+        let json = json!({
+            "passphrase": info.passphrase,
+            "protocolVersion": latest.protocol_version,
+            "latestLedger": latest.sequence,
+            "friendbotUrl": info.friendbot_url,
+            "feeStats": fee_stats,
+            "resourcePrices": prices,
+        });
+        global_args.formatter().print(text, &json)?;
+        Ok(())
+    }
+}
+
+fn write_fees(out: &mut String, title: &str, fees: &FeeDistribution) {
+    let _ = writeln!(
+        out,
+        "{title}, in stroops, over the last {} ledgers ({} transactions):",
+        fees.ledger_count, fees.transaction_count
+    );
+    let _ = writeln!(
+        out,
+        "  min {} | p10 {} | p50 {} | p90 {} | p95 {} | p99 {} | max {} | mode {}",
+        fees.min, fees.p10, fees.p50, fees.p90, fees.p95, fees.p99, fees.max, fees.mode
+    );
+}
+
+/// The fields of the settings that are prices, i.e. that are named after fees. Settings are read
+/// through their JSON so that every price is included, whichever the protocol has.
+fn resource_prices(settings: &[ConfigSettingEntry]) -> Result<Map<String, Value>, Error> {
+    let mut prices = Map::new();
+    for setting in settings {
+        let Value::Object(setting) = serde_json::to_value(setting)? else {
+            continue;
+        };
+        for fields in setting.into_values() {
+            let Value::Object(fields) = fields else {
+                continue;
+            };
+            prices.extend(fields.into_iter().filter(|(name, _)| name.contains("fee")));
+        }
+    }
+    Ok(prices)
+}
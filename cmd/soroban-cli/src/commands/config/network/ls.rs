@@ -1,12 +1,14 @@
 use clap::command;
 
-use super::locator;
-use crate::commands::config::locator::Location;
+use super::{locator, Network};
+use crate::commands::{config::locator::Location, global};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -20,9 +22,27 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        let res = if self.long { self.ls_l() } else { self.ls() }?.join("\n");
-        println!("{res}");
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let formatter = global_args.formatter();
+        if !self.long {
+            let names = self.ls()?;
+            formatter.print(names.join("\n"), &names)?;
+            return Ok(());
+        }
+        let networks = self.networks_long()?;
+        let json = networks
+            .iter()
+            .map(|(name, network, location)| {
+                serde_json::json!({
+                    "name": name,
+                    "global": matches!(location, Location::Global(_)),
+                    "path": location.as_ref(),
+                    "rpcUrl": network.rpc_url,
+                    "networkPassphrase": network.network_passphrase,
+                })
+            })
+            .collect::<Vec<_>>();
+        formatter.print(self.ls_l()?.join("\n"), &json)?;
         Ok(())
     }
 
@@ -31,13 +51,20 @@ impl Cmd {
     }
 
     pub fn ls_l(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .networks_long()?
+            .iter()
+            .map(|(name, network, location)| format!("{location}\nName: {name}\n{network:#?}\n"))
+            .collect())
+    }
+
+    fn networks_long(&self) -> Result<Vec<(String, Network, Location)>, Error> {
         Ok(self
             .config_locator
             .list_networks_long()?
-            .iter()
-            .filter_map(|(name, network, location)| {
-                (!self.config_locator.global || matches!(location, Location::Global(_)))
-                    .then(|| Some(format!("{location}\nName: {name}\n{network:#?}\n")))?
+            .into_iter()
+            .filter(|(_, _, location)| {
+                !self.config_locator.global || matches!(location, Location::Global(_))
             })
             .collect())
     }
@@ -6,13 +6,14 @@ use serde_json::Value;
 use stellar_strkey::ed25519::PublicKey;
 
 use crate::{
-    commands::HEADING_RPC,
+    commands::{global, HEADING_RPC},
     rpc::{self, Client},
 };
 
 use super::locator;
 
 pub mod add;
+pub mod info;
 pub mod ls;
 pub mod rm;
 
@@ -24,6 +25,8 @@ pub enum Cmd {
     Rm(rm::Cmd),
     /// List networks
     Ls(ls::Cmd),
+    /// Print the network's protocol version, latest ledger, recent fees, and resource prices
+    Info(info::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -37,6 +40,9 @@ pub enum Error {
     #[error(transparent)]
     Ls(#[from] ls::Error),
 
+    #[error(transparent)]
+    Info(#[from] info::Error),
+
     #[error(transparent)]
     Config(#[from] locator::Error),
 
@@ -57,11 +63,12 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Add(cmd) => cmd.run()?,
             Cmd::Rm(new) => new.run()?,
-            Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Ls(cmd) => cmd.run(global_args)?,
+            Cmd::Info(cmd) => cmd.run(global_args).await?,
         };
         Ok(())
     }
@@ -116,8 +123,20 @@ impl Args {
     pub fn is_no_network(&self) -> bool {
         self.network.is_none() && self.network_passphrase.is_none() && self.rpc_url.is_none()
     }
+
+    /// Network passphrase that contract aliases are saved under, with sandbox aliases kept
+    /// separately.
+    pub fn alias_key(&self, locator: &locator::Args) -> Result<String, Error> {
+        if self.is_no_network() {
+            Ok(SANDBOX_ALIAS_KEY.to_string())
+        } else {
+            Ok(self.get(locator)?.network_passphrase)
+        }
+    }
 }
 
+pub const SANDBOX_ALIAS_KEY: &str = "sandbox";
+
 #[derive(Debug, clap::Args, Serialize, Deserialize, Clone)]
 #[group(skip)]
 pub struct Network {
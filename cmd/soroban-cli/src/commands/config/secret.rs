@@ -1,6 +1,13 @@
 use clap::arg;
+use ed25519_dalek::{Signer, Verifier};
 use serde::{Deserialize, Serialize};
-use std::{io::Write, str::FromStr};
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
 use stellar_strkey::ed25519::{PrivateKey, PublicKey};
 
 use crate::utils;
@@ -21,6 +28,14 @@ pub enum Error {
     Ed25519(#[from] ed25519_dalek::SignatureError),
     #[error("Invalid address {0}")]
     InvalidAddress(String),
+    #[error("passphrases do not match")]
+    PassphraseMismatch,
+    #[error("identity uses an external signer and has no private key")]
+    ExternalSigner,
+    #[error("external signer {0}")]
+    ExternalCommand(String),
+    #[error("external signer returned an invalid signature")]
+    InvalidExternalSignature,
 }
 
 #[derive(Debug, clap::Args, Clone)]
@@ -33,11 +48,29 @@ pub struct Args {
     /// Add using 12 word seed phrase to generate secret_key
     #[arg(long, conflicts_with = "secret_key")]
     pub seed_phrase: bool,
+    /// Add an identity that signs by running this command, e.g. a cloud KMS or HSM client.
+    /// The command receives the hex encoded transaction hash on stdin and must print a hex
+    /// encoded ed25519 signature to stdout.
+    #[arg(
+        long,
+        requires = "public_key",
+        conflicts_with_all = ["secret_key", "seed_phrase"],
+    )]
+    pub external_command: Option<String>,
+    /// Public key (G...) of the external signer
+    #[arg(long, requires = "external_command")]
+    pub public_key: Option<String>,
 }
 
 impl Args {
     pub fn read_secret(&self) -> Result<Secret, Error> {
-        if let Ok(secret_key) = std::env::var("SOROBAN_SECRET_KEY") {
+        if let (Some(command), Some(public_key)) = (&self.external_command, &self.public_key) {
+            PublicKey::from_string(public_key)?;
+            Ok(Secret::External {
+                command: command.clone(),
+                public_key: public_key.clone(),
+            })
+        } else if let Ok(secret_key) = std::env::var("SOROBAN_SECRET_KEY") {
             Ok(Secret::SecretKey { secret_key })
         } else if self.secret_key {
             println!("Type a secret key: ");
@@ -60,6 +93,8 @@ impl Args {
                     .map(ToString::to_string)
                     .collect::<Vec<_>>()
                     .join(" "),
+                bip39_passphrase: false,
+                identity: None,
             })
         } else {
             Err(Error::PasswordRead {})
@@ -70,8 +105,24 @@ impl Args {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Secret {
-    SecretKey { secret_key: String },
-    SeedPhrase { seed_phrase: String },
+    SecretKey {
+        secret_key: String,
+    },
+    SeedPhrase {
+        seed_phrase: String,
+        /// Whether a BIP-39 passphrase (the "25th word") is mixed into the seed. The passphrase
+        /// itself is never saved, see [`bip39_passphrase`]
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        bip39_passphrase: bool,
+        /// Name of the identity read, which the BIP-39 passphrase is asked for and cached by
+        #[serde(skip)]
+        identity: Option<String>,
+    },
+    External {
+        /// Command that signs a transaction hash, see `Args::external_command`
+        command: String,
+        public_key: String,
+    },
 }
 
 impl FromStr for Secret {
@@ -85,6 +136,8 @@ impl FromStr for Secret {
         } else if sep5::SeedPhrase::from_str(s).is_ok() {
             Ok(Secret::SeedPhrase {
                 seed_phrase: s.to_string(),
+                bip39_passphrase: false,
+                identity: None,
             })
         } else {
             Err(Error::InvalidAddress(s.to_string()))
@@ -96,13 +149,31 @@ impl Secret {
     pub fn private_key(&self, index: Option<usize>) -> Result<PrivateKey, Error> {
         Ok(match self {
             Secret::SecretKey { secret_key } => PrivateKey::from_string(secret_key)?,
-            Secret::SeedPhrase { seed_phrase } => sep5::SeedPhrase::from_str(seed_phrase)?
-                .from_path_index(index.unwrap_or_default(), None)?
-                .private(),
+            Secret::SeedPhrase {
+                seed_phrase,
+                bip39_passphrase: with_passphrase,
+                identity,
+            } => {
+                let passphrase = if *with_passphrase {
+                    Some(bip39_passphrase(
+                        identity.as_deref().unwrap_or_default(),
+                        false,
+                    )?)
+                } else {
+                    None
+                };
+                sep5::SeedPhrase::from_str(seed_phrase)?
+                    .from_path_index(index.unwrap_or_default(), passphrase.as_deref())?
+                    .private()
+            }
+            Secret::External { .. } => return Err(Error::ExternalSigner),
         })
     }
 
     pub fn public_key(&self, index: Option<usize>) -> Result<PublicKey, Error> {
+        if let Secret::External { public_key, .. } = self {
+            return Ok(PublicKey::from_string(public_key)?);
+        }
         let key = self.key_pair(index)?;
         Ok(stellar_strkey::ed25519::PublicKey::from_payload(
             key.public.as_bytes(),
@@ -113,7 +184,22 @@ impl Secret {
         Ok(utils::into_key_pair(&self.private_key(index)?)?)
     }
 
-    pub fn from_seed(seed: Option<&str>) -> Result<Self, Error> {
+    /// Sign a transaction hash, shelling out to the signer's command for external identities.
+    pub fn sign(
+        &self,
+        index: Option<usize>,
+        hash: &[u8; 32],
+    ) -> Result<ed25519_dalek::Signature, Error> {
+        match self {
+            Secret::External {
+                command,
+                public_key,
+            } => sign_with_command(command, public_key, hash),
+            _ => Ok(self.key_pair(index)?.sign(hash)),
+        }
+    }
+
+    pub fn from_seed(seed: Option<&str>, bip39_passphrase: bool) -> Result<Self, Error> {
         let seed_phrase = if let Some(seed) = seed.map(str::as_bytes) {
             sep5::SeedPhrase::from_entropy(seed)
         } else {
@@ -121,15 +207,187 @@ impl Secret {
         }?
         .seed_phrase
         .into_phrase();
-        Ok(Secret::SeedPhrase { seed_phrase })
+        Ok(Secret::SeedPhrase {
+            seed_phrase,
+            bip39_passphrase,
+            identity: None,
+        })
+    }
+
+    /// Mark a seed phrase as the identity `name`, so its BIP-39 passphrase is that identity's.
+    #[must_use]
+    pub fn with_identity(mut self, name: &str) -> Self {
+        if let Secret::SeedPhrase { identity, .. } = &mut self {
+            *identity = Some(name.to_string());
+        }
+        self
     }
 
     pub fn test_seed_phrase() -> Result<Self, Error> {
-        Self::from_seed(Some("0000000000000000"))
+        Self::from_seed(Some("0000000000000000"), false)
+    }
+}
+
+/// BIP-39 passphrases by identity name, as each identity may have its own.
+static BIP39_PASSPHRASES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+/// The BIP-39 passphrase of a seed phrase `identity` that has one, from
+/// SOROBAN_BIP39_PASSPHRASE or else prompted for, once per identity and process. With
+/// `confirm`, the prompt asks twice so a typo doesn't silently derive a different set of keys.
+pub fn bip39_passphrase(identity: &str, confirm: bool) -> Result<String, Error> {
+    let passphrases = BIP39_PASSPHRASES.get_or_init(Mutex::default);
+    if let Some(passphrase) = passphrases.lock().unwrap().get(identity) {
+        return Ok(passphrase.clone());
+    }
+    let passphrase = match std::env::var("SOROBAN_BIP39_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) if confirm => read_bip39_passphrase(identity)?,
+        Err(_) => {
+            eprintln!("Type the BIP-39 passphrase of {identity}: ");
+            read_password()?
+        }
+    };
+    passphrases
+        .lock()
+        .unwrap()
+        .insert(identity.to_string(), passphrase.clone());
+    Ok(passphrase)
+}
+
+fn read_bip39_passphrase(identity: &str) -> Result<String, Error> {
+    eprintln!("Type a BIP-39 passphrase for {identity}: ");
+    let passphrase = read_password()?;
+    eprintln!("Confirm the BIP-39 passphrase: ");
+    if passphrase != read_password()? {
+        return Err(Error::PassphraseMismatch);
+    }
+    Ok(passphrase)
+}
+
+fn sign_with_command(
+    command: &str,
+    public_key: &str,
+    hash: &[u8; 32],
+) -> Result<ed25519_dalek::Signature, Error> {
+    let args = shlex::split(command)
+        .filter(|args| !args.is_empty())
+        .ok_or_else(|| Error::ExternalCommand(format!("cannot parse command {command:?}")))?;
+    let mut child = Command::new(&args[0])
+        .args(&args[1..])
+        .env("SOROBAN_SIGNER_PUBLIC_KEY", public_key)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::ExternalCommand(format!("{}: {e}", args[0])))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "{}", hex::encode(hash))
+            .map_err(|e| Error::ExternalCommand(format!("{}: {e}", args[0])))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| Error::ExternalCommand(format!("{}: {e}", args[0])))?;
+    if !output.status.success() {
+        return Err(Error::ExternalCommand(format!(
+            "{} exited with {}",
+            args[0], output.status
+        )));
     }
+    let bytes = hex::decode(String::from_utf8_lossy(&output.stdout).trim())
+        .map_err(|_| Error::InvalidExternalSignature)?;
+    let signature = ed25519_dalek::Signature::try_from(&bytes[..])
+        .map_err(|_| Error::InvalidExternalSignature)?;
+    ed25519_dalek::PublicKey::from_bytes(&PublicKey::from_string(public_key)?.0)?
+        .verify(hash, &signature)
+        .map_err(|_| Error::InvalidExternalSignature)?;
+    Ok(signature)
 }
 
 fn read_password() -> Result<String, Error> {
     std::io::stdout().flush().map_err(|_| Error::PasswordRead)?;
     rpassword::read_password().map_err(|_| Error::PasswordRead)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    const HASH: [u8; 32] = [7; 32];
+
+    /// An external identity for the test seed phrase's key, signing with a shell script that
+    /// runs `body` after reading the hash into `$hash`.
+    fn external(dir: &assert_fs::TempDir, body: &str) -> Secret {
+        let script = dir.path().join("signer.sh");
+        fs::write(&script, format!("read hash\n{body}\n")).unwrap();
+        Secret::External {
+            command: format!("sh {}", script.display()),
+            public_key: Secret::test_seed_phrase()
+                .unwrap()
+                .public_key(None)
+                .unwrap()
+                .to_string(),
+        }
+    }
+
+    fn signature_hex(hash: &[u8; 32]) -> String {
+        let key = Secret::test_seed_phrase().unwrap().key_pair(None).unwrap();
+        hex::encode(key.sign(hash).to_bytes())
+    }
+
+    #[test]
+    fn external_signature_is_checked_and_returned() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let signer = external(
+            &dir,
+            &format!(
+                "[ \"$hash\" = {} ] || exit 1\necho {}",
+                hex::encode(HASH),
+                signature_hex(&HASH)
+            ),
+        );
+        let signature = signer.sign(None, &HASH).unwrap();
+        assert_eq!(hex::encode(signature.to_bytes()), signature_hex(&HASH));
+    }
+
+    #[test]
+    fn external_signature_of_another_hash_is_rejected() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let signer = external(&dir, &format!("echo {}", signature_hex(&[8; 32])));
+        assert!(matches!(
+            signer.sign(None, &HASH),
+            Err(Error::InvalidExternalSignature)
+        ));
+    }
+
+    #[test]
+    fn bip39_passphrases_are_kept_per_identity() {
+        BIP39_PASSPHRASES
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap()
+            .extend([
+                ("alice".to_string(), "first".to_string()),
+                ("bob".to_string(), "second".to_string()),
+            ]);
+        let key = |name: &str| {
+            Secret::from_seed(Some("0000000000000000"), true)
+                .unwrap()
+                .with_identity(name)
+                .public_key(None)
+                .unwrap()
+        };
+        assert_ne!(key("alice"), key("bob"));
+        assert_eq!(key("alice"), key("alice"));
+    }
+
+    #[test]
+    fn external_signer_failure_is_reported() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let signer = external(&dir, "echo 'key not found' >&2\nexit 3");
+        let Err(Error::ExternalCommand(message)) = signer.sign(None, &HASH) else {
+            panic!("expected the signer to fail");
+        };
+        assert!(message == "sh exited with exit status: 3", "{message}");
+    }
+}
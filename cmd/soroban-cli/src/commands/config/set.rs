@@ -0,0 +1,102 @@
+use clap::{command, Parser, ValueEnum};
+
+use crate::format::{Amounts, Timestamps};
+
+use super::locator;
+
+/// Settings that can be changed, as `<section>.<setting>`
+const KEYS: &[&str] = &[
+    "format.timestamps",
+    "format.thousands-separator",
+    "format.amounts",
+];
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error("unknown setting {0}, expected one of: {}", KEYS.join(", "))]
+    UnknownKey(String),
+    #[error("invalid value {value:?} for {key}, expected one of: {expected}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: String,
+    },
+}
+
+#[derive(Debug, Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Setting to change: format.timestamps (utc, local, unix), format.thousands-separator
+    /// (true, false) or format.amounts (xlm, stroops)
+    pub key: String,
+    /// New value of the setting
+    pub value: String,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut settings = self.config_locator.read_settings()?;
+        let format = &mut settings.format;
+        match self.key.as_str() {
+            "format.timestamps" => format.timestamps = self.parse::<Timestamps>()?,
+            "format.thousands-separator" => format.thousands_separator = self.parse_bool()?,
+            "format.amounts" => format.amounts = self.parse::<Amounts>()?,
+            key => return Err(Error::UnknownKey(key.to_string())),
+        }
+        Ok(self.config_locator.write_settings(&settings)?)
+    }
+
+    fn parse<T: ValueEnum>(&self) -> Result<T, Error> {
+        T::from_str(&self.value, true).map_err(|_| self.invalid_value(&possible_values::<T>()))
+    }
+
+    fn parse_bool(&self) -> Result<bool, Error> {
+        self.value
+            .parse()
+            .map_err(|_| self.invalid_value("true, false"))
+    }
+
+    fn invalid_value(&self, expected: &str) -> Error {
+        Error::InvalidValue {
+            key: self.key.clone(),
+            value: self.value.clone(),
+            expected: expected.to_string(),
+        }
+    }
+}
+
+fn possible_values<T: ValueEnum>() -> String {
+    T::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value().map(|v| v.get_name().to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamps() {
+        let cmd = Cmd {
+            key: "format.timestamps".to_string(),
+            value: "Unix".to_string(),
+            config_locator: locator::Args::default(),
+        };
+        assert_eq!(cmd.parse::<Timestamps>().unwrap(), Timestamps::Unix);
+        let cmd = Cmd {
+            value: "nope".to_string(),
+            ..cmd
+        };
+        assert!(matches!(
+            cmd.parse::<Timestamps>(),
+            Err(Error::InvalidValue { expected, .. }) if expected == "utc, local, unix"
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use clap::{arg, command, Parser};
+use soroban_env_host::{
+    storage::Storage,
+    xdr::{ScSpecEntry, ScSpecFunctionV0},
+};
+
+use crate::{
+    commands::{config, contract::invoke},
+    rpc, utils,
+};
+
+/// Getters contracts commonly expose their admin or owner through. The Stellar Asset Contract
+/// has `admin`.
+const ADMIN_FUNCTIONS: &[&str] = &["admin", "get_admin", "owner", "get_owner"];
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias to look up the admin of
+    #[arg(long = "id")]
+    pub contract_id: String,
+
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error("parsing contract spec: {0}")]
+    CannotParseContractSpec(soroban_spec::read::FromWasmError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("contract has none of the admin getters {}", ADMIN_FUNCTIONS.join(", "))]
+    NoAdminFunction,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        for (function, admin) in self.admins().await? {
+            println!("{function}: {admin}");
+        }
+        Ok(())
+    }
+
+    /// Simulate each admin getter the contract has, returning the getter and its result.
+    pub async fn admins(&self) -> Result<Vec<(String, String)>, Error> {
+        let contract_id = self.config.resolve_contract_id(&self.contract_id)?;
+        let getters = self
+            .spec_entries(&utils::contract_id_from_str(&contract_id)?)
+            .await?
+            .into_iter()
+            .filter_map(|entry| match entry {
+                ScSpecEntry::FunctionV0(ScSpecFunctionV0 { name, inputs, .. })
+                    if inputs.is_empty()
+                        && ADMIN_FUNCTIONS.contains(&name.to_string_lossy().as_str()) =>
+                {
+                    Some(name.to_string_lossy())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if getters.is_empty() {
+            return Err(Error::NoAdminFunction);
+        }
+        let mut admins = vec![];
+        for function in getters {
+            let output = invoke::Cmd {
+                contract_id: contract_id.clone(),
+                slop: vec![function.clone().into()],
+                config: self.config.clone(),
+                ..Default::default()
+            }
+            .simulate()
+            .await?;
+            let result = serde_json::from_str::<serde_json::Value>(&output)?["result"].clone();
+            let admin = match result {
+                serde_json::Value::String(s) => s,
+                value => value.to_string(),
+            };
+            admins.push((function, admin));
+        }
+        Ok(admins)
+    }
+
+    async fn spec_entries(&self, contract_id: &[u8; 32]) -> Result<Vec<ScSpecEntry>, Error> {
+        if self.config.is_no_network() {
+            let state = self.config.get_state()?;
+            let mut storage = Storage::with_recording_footprint(Rc::new(state.clone()));
+            return utils::get_contract_spec_from_storage(
+                &mut storage,
+                &state.sequence_number,
+                *contract_id,
+            )
+            .map_err(Error::CannotParseContractSpec);
+        }
+        let network = self.config.get_network()?;
+        Ok(rpc::Client::new(&network.rpc_url)?
+            .get_remote_contract_spec(contract_id)
+            .await?)
+    }
+}
@@ -0,0 +1,48 @@
+pub mod add;
+pub mod ls;
+pub mod rm;
+pub mod show;
+
+use crate::commands::global;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Save a contract ID under an alias for the network
+    Add(add::Cmd),
+
+    /// List the contract aliases saved for the network
+    Ls(ls::Cmd),
+
+    /// Remove a contract alias for the network
+    Rm(rm::Cmd),
+
+    /// Print the contract ID saved under an alias for the network
+    Show(show::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Add(#[from] add::Error),
+
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+
+    #[error(transparent)]
+    Rm(#[from] rm::Error),
+
+    #[error(transparent)]
+    Show(#[from] show::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Add(add) => add.run()?,
+            Cmd::Ls(ls) => ls.run(global_args)?,
+            Cmd::Rm(rm) => rm.run()?,
+            Cmd::Show(show) => show.run(global_args)?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::config::{locator, network},
+    utils,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Alias to save the contract ID under
+    pub alias: String,
+
+    /// Contract ID the alias refers to
+    #[arg(long = "id")]
+    pub contract_id: String,
+
+    /// Replace the contract ID if the alias is already in use on the network
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error("cannot parse contract ID {0}: {1}")]
+    CannotParseContractId(String, stellar_strkey::DecodeError),
+    #[error("alias {alias} already refers to {contract_id}, use --overwrite to replace it")]
+    AlreadyExists { alias: String, contract_id: String },
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        utils::contract_id_from_str(&self.contract_id)
+            .map_err(|e| Error::CannotParseContractId(self.contract_id.clone(), e))?;
+        let network_passphrase = self.network.alias_key(&self.locator)?;
+        if let Some(contract_id) = self
+            .locator
+            .get_contract_id(&self.alias, &network_passphrase)?
+        {
+            if !self.overwrite && contract_id != self.contract_id {
+                return Err(Error::AlreadyExists {
+                    alias: self.alias.clone(),
+                    contract_id,
+                });
+            }
+        }
+        self.locator
+            .save_contract_id(&network_passphrase, &self.contract_id, &self.alias)?;
+        Ok(())
+    }
+}
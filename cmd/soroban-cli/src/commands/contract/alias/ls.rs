@@ -0,0 +1,46 @@
+use clap::{command, Parser};
+
+use crate::commands::{
+    config::{locator, network},
+    global,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network_passphrase = self.network.alias_key(&self.locator)?;
+        let aliases = self.locator.list_contract_aliases(&network_passphrase)?;
+        let text = aliases
+            .iter()
+            .map(|(alias, contract_id)| format!("{alias}: {contract_id}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let json = aliases
+            .iter()
+            .map(|(alias, contract_id)| {
+                serde_json::json!({ "alias": alias, "contractId": contract_id })
+            })
+            .collect::<Vec<_>>();
+        global_args.formatter().print(text, &json)?;
+        Ok(())
+    }
+}
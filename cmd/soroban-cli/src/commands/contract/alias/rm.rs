@@ -0,0 +1,33 @@
+use clap::{command, Parser};
+
+use crate::commands::config::{locator, network};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Alias to remove
+    pub alias: String,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let network_passphrase = self.network.alias_key(&self.locator)?;
+        Ok(self
+            .locator
+            .remove_contract_id(&network_passphrase, &self.alias)?)
+    }
+}
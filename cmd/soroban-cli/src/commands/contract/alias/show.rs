@@ -0,0 +1,46 @@
+use clap::{command, Parser};
+
+use crate::commands::{
+    config::{locator, network},
+    global,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Alias to look up
+    pub alias: String,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error("no contract found for alias {0} on this network")]
+    NotFound(String),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network_passphrase = self.network.alias_key(&self.locator)?;
+        let contract_id = self
+            .locator
+            .get_contract_id(&self.alias, &network_passphrase)?
+            .ok_or_else(|| Error::NotFound(self.alias.clone()))?;
+        global_args.formatter().print(
+            &contract_id,
+            &serde_json::json!({ "contractId": contract_id }),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::commands::{
+    global,
+    lab::token::{id, wrap},
+};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Deploy the Stellar Asset Contract for a classic asset, printing its contract ID
+    Deploy(wrap::Cmd),
+
+    /// Compute the contract ID of an asset's Stellar Asset Contract without contacting the
+    /// network
+    Id(id::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Deploy(#[from] wrap::Error),
+
+    #[error(transparent)]
+    Id(#[from] id::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Deploy(deploy) => deploy.run(global_args).await?,
+            Cmd::Id(id) => id.run(global_args)?,
+        }
+        Ok(())
+    }
+}
@@ -1,27 +1,79 @@
+pub mod check;
+pub mod convert;
+pub mod custom;
+pub mod dart;
 pub mod json;
+pub mod json_schema;
+pub mod kotlin;
+pub mod open_rpc;
+pub mod package;
+pub mod python;
 pub mod rust;
+pub mod swift;
 pub mod typescript;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
+    /// Generate a package from a directory of templates
+    Custom(custom::Cmd),
+
+    /// Generate a Dart package using the Stellar Flutter SDK
+    Dart(dart::Cmd),
+
     /// Generate Json Bindings
     Json(json::Cmd),
 
-    /// Generate Rust bindings
+    /// Generate JSON Schemas of the contract's types and function arguments
+    JsonSchema(json_schema::Cmd),
+
+    /// Generate a Kotlin package using the Java Stellar SDK
+    Kotlin(kotlin::Cmd),
+
+    /// Generate an OpenRPC document with a method per contract function
+    OpenRpc(open_rpc::Cmd),
+
+    /// Generate a Python package using the Stellar Python SDK
+    Python(python::Cmd),
+
+    /// Generate Rust bindings, or a crate with `--output-dir`
     Rust(rust::Cmd),
 
+    /// Generate a Swift package using the Stellar iOS/macOS SDK
+    Swift(swift::Cmd),
+
     /// Generate a TypeScript / JavaScript package
     Typescript(typescript::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Custom(#[from] custom::Error),
+
+    #[error(transparent)]
+    Dart(#[from] dart::Error),
+
     #[error(transparent)]
     Json(#[from] json::Error),
 
+    #[error(transparent)]
+    JsonSchema(#[from] json_schema::Error),
+
+    #[error(transparent)]
+    Kotlin(#[from] kotlin::Error),
+
+    #[error(transparent)]
+    OpenRpc(#[from] open_rpc::Error),
+
+    #[error(transparent)]
+    Python(#[from] python::Error),
+
     #[error(transparent)]
     Rust(#[from] rust::Error),
 
+    #[error(transparent)]
+    Swift(#[from] swift::Error),
+
     #[error(transparent)]
     Typescript(#[from] typescript::Error),
 }
@@ -29,8 +81,15 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         match &self {
+            Cmd::Custom(custom) => custom.run().await?,
+            Cmd::Dart(dart) => dart.run().await?,
             Cmd::Json(json) => json.run()?,
+            Cmd::JsonSchema(json_schema) => json_schema.run()?,
+            Cmd::Kotlin(kotlin) => kotlin.run().await?,
+            Cmd::OpenRpc(open_rpc) => open_rpc.run()?,
+            Cmd::Python(python) => python.run().await?,
             Cmd::Rust(rust) => rust.run()?,
+            Cmd::Swift(swift) => swift.run().await?,
             Cmd::Typescript(ts) => ts.run().await?,
         }
         Ok(())
@@ -0,0 +1,111 @@
+//! `--check` of the bindings commands, which generate into a temporary directory and compare
+//! the result with the bindings already in `--output-dir`, e.g. to catch stale bindings in CI.
+//! The commands that print a single document compare it with the file given to `--check`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("--output-dir {0:?} does not exist, there are no bindings to check")]
+    NoBindings(PathBuf),
+    #[error("bindings in {output_dir:?} are out of date with the contract, regenerate them with --overwrite:\n{files}")]
+    Stale { output_dir: PathBuf, files: String },
+    #[error("reading {path:?}, there are no bindings to check: {error}")]
+    CannotReadFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("{0:?} is out of date with the contract, regenerate it by running the command without --check")]
+    StaleFile(PathBuf),
+}
+
+/// Fails if the file at `path` doesn't hold `generated`. Trailing whitespace is ignored, as
+/// the file is usually the printed document redirected into it.
+pub fn compare_file(path: &Path, generated: &str) -> Result<(), Error> {
+    let contents = fs::read_to_string(path).map_err(|error| Error::CannotReadFile {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    if contents.trim_end() == generated.trim_end() {
+        Ok(())
+    } else {
+        Err(Error::StaleFile(path.to_path_buf()))
+    }
+}
+
+/// A temporary directory to generate bindings in, removed when dropped.
+pub struct Generated {
+    root: PathBuf,
+    output_dir: PathBuf,
+}
+
+impl Generated {
+    /// The generated directory has the same name as `output_dir`, which names the packages.
+    pub fn new(output_dir: &Path) -> Result<Self, Error> {
+        let name = output_dir
+            .canonicalize()
+            .map_err(|_| Error::NoBindings(output_dir.to_path_buf()))?
+            .file_name()
+            .unwrap_or_default()
+            .to_os_string();
+        let root = std::env::temp_dir().join(format!("soroban-bindings-{}", std::process::id()));
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            output_dir: root.join(name),
+            root,
+        })
+    }
+
+    pub fn output_dir(&self) -> PathBuf {
+        self.output_dir.clone()
+    }
+
+    /// Fails listing the generated files that are missing from, or differ in, `output_dir`.
+    /// Files only in `output_dir`, such as build outputs, are ignored.
+    pub fn compare(&self, output_dir: &Path) -> Result<(), Error> {
+        let mut stale = vec![];
+        for file in files(&self.output_dir)? {
+            let relative = file.strip_prefix(&self.output_dir).unwrap_or(&file);
+            let committed = output_dir.join(relative);
+            let status = match fs::read(&committed) {
+                Ok(contents) if contents == fs::read(&file)? => continue,
+                Ok(_) => "changed",
+                Err(_) => "missing",
+            };
+            stale.push(format!("  {status}: {}", relative.display()));
+        }
+        if stale.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Stale {
+                output_dir: output_dir.to_path_buf(),
+                files: stale.join("\n"),
+            })
+        }
+    }
+}
+
+impl Drop for Generated {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// The files under `dir`, recursively, sorted.
+pub fn files(dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
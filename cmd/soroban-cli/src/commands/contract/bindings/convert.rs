@@ -0,0 +1,161 @@
+//! Expressions converting values of a spec type to and from `SCVal`s, for the generators whose
+//! runtime has a helper per type: `toU32(x)` and `fromU32(x)`, `toOption(x, f)`, `toVec(x, f)`,
+//! `toMap(x, kf, vf)`, `toTuple(xs)` and `fromTuple(x, f)`, with the contract's own types
+//! converted by `x.toScVal()` and `Name.fromScVal(x)`.
+use soroban_env_host::xdr::ScSpecTypeDef;
+
+/// How a language writes the parts of the expressions that are not helper calls.
+pub struct Syntax {
+    /// A closure of one parameter, e.g. `(v) => body` in Dart
+    pub closure: fn(param: &str, body: &str) -> String,
+    /// A list literal
+    pub list: fn(elements: &[String]) -> String,
+    /// The `i`th element, from 0, of a list
+    pub index: fn(list: &str, i: usize) -> String,
+    /// The `i`th element, from 0, of a tuple
+    pub tuple_get: fn(tuple: &str, i: usize) -> String,
+    /// A tuple literal
+    pub tuple_new: fn(elements: &[String]) -> String,
+    /// The absent value, e.g. `null`
+    pub null: &'static str,
+}
+
+/// The name of the helpers converting `type_`, e.g. `U32` for `toU32` and `fromU32`, for the
+/// types without type parameters.
+fn helper(type_: &ScSpecTypeDef) -> Option<&'static str> {
+    Some(match type_ {
+        ScSpecTypeDef::Bool => "Bool",
+        ScSpecTypeDef::U32 => "U32",
+        ScSpecTypeDef::I32 => "I32",
+        ScSpecTypeDef::U64 => "U64",
+        ScSpecTypeDef::I64 => "I64",
+        ScSpecTypeDef::U128 => "U128",
+        ScSpecTypeDef::I128 => "I128",
+        ScSpecTypeDef::U256 => "U256",
+        ScSpecTypeDef::I256 => "I256",
+        ScSpecTypeDef::Timepoint => "Timepoint",
+        ScSpecTypeDef::Duration => "Duration",
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "Bytes",
+        ScSpecTypeDef::String => "Str",
+        ScSpecTypeDef::Symbol => "Symbol",
+        ScSpecTypeDef::Address => "Address",
+        _ => return None,
+    })
+}
+
+/// An expression converting `val`, of `type_`, to an `SCVal`. `depth` keeps the names of
+/// nested closure parameters apart.
+pub fn to_scval(syntax: &Syntax, type_: &ScSpecTypeDef, val: &str, depth: usize) -> String {
+    if let Some(helper) = helper(type_) {
+        return format!("to{helper}({val})");
+    }
+    let (v, k) = (format!("v{depth}"), format!("k{depth}"));
+    let closure = |param: &str, type_: &ScSpecTypeDef| {
+        (syntax.closure)(param, &to_scval(syntax, type_, param, depth + 1))
+    };
+    match type_ {
+        ScSpecTypeDef::Void => "toVoid()".to_string(),
+        ScSpecTypeDef::Option(option) => {
+            format!("toOption({val}, {})", closure(&v, &option.value_type))
+        }
+        ScSpecTypeDef::Result(result) => to_scval(syntax, &result.ok_type, val, depth),
+        ScSpecTypeDef::Vec(vec) => format!("toVec({val}, {})", closure(&v, &vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("toVec({val}, {})", closure(&v, &set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "toMap({val}, {}, {})",
+            closure(&k, &map.key_type),
+            closure(&v, &map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "toTuple({})",
+            (syntax.list)(
+                &tuple
+                    .value_types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, type_)| to_scval(syntax, type_, &(syntax.tuple_get)(val, i), depth))
+                    .collect::<Vec<_>>()
+            )
+        ),
+        ScSpecTypeDef::Udt(_) => format!("{val}.toScVal()"),
+        _ => val.to_string(),
+    }
+}
+
+/// An expression converting the `SCVal` `val` to `type_`.
+pub fn from_scval(syntax: &Syntax, type_: &ScSpecTypeDef, val: &str, depth: usize) -> String {
+    if let Some(helper) = helper(type_) {
+        return format!("from{helper}({val})");
+    }
+    let (v, k) = (format!("v{depth}"), format!("k{depth}"));
+    let closure = |param: &str, type_: &ScSpecTypeDef| {
+        (syntax.closure)(param, &from_scval(syntax, type_, param, depth + 1))
+    };
+    match type_ {
+        ScSpecTypeDef::Void => syntax.null.to_string(),
+        ScSpecTypeDef::Option(option) => {
+            format!("fromOption({val}, {})", closure(&v, &option.value_type))
+        }
+        ScSpecTypeDef::Result(result) => from_scval(syntax, &result.ok_type, val, depth),
+        ScSpecTypeDef::Vec(vec) => format!("fromVec({val}, {})", closure(&v, &vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("fromVec({val}, {})", closure(&v, &set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "fromMap({val}, {}, {})",
+            closure(&k, &map.key_type),
+            closure(&v, &map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => {
+            let elements = tuple
+                .value_types
+                .iter()
+                .enumerate()
+                .map(|(i, type_)| from_scval(syntax, type_, &(syntax.index)(&v, i), depth + 1))
+                .collect::<Vec<_>>();
+            format!(
+                "fromTuple({val}, {})",
+                (syntax.closure)(&v, &(syntax.tuple_new)(&elements))
+            )
+        }
+        ScSpecTypeDef::Udt(udt) => format!("{}.fromScVal({val})", udt.name.to_string_lossy()),
+        _ => val.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScSpecTypeOption, ScSpecTypeTuple, ScSpecTypeVec};
+
+    const SYNTAX: Syntax = Syntax {
+        closure: |param, body| format!("({param}) => {body}"),
+        list: |elements| format!("[{}]", elements.join(", ")),
+        index: |list, i| format!("{list}[{i}]"),
+        tuple_get: |tuple, i| format!("{tuple}.${}", i + 1),
+        tuple_new: |elements| format!("({},)", elements.join(", ")),
+        null: "null",
+    };
+
+    #[test]
+    fn nested_conversions() {
+        let type_ = ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+                value_types: vec![
+                    ScSpecTypeDef::Symbol,
+                    ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                        value_type: Box::new(ScSpecTypeDef::U32),
+                    })),
+                ]
+                .try_into()
+                .unwrap(),
+            }))),
+        }));
+        assert_eq!(
+            to_scval(&SYNTAX, &type_, "x", 0),
+            "toVec(x, (v0) => toTuple([toSymbol(v0.$1), toOption(v0.$2, (v1) => toU32(v1))]))"
+        );
+        assert_eq!(
+            from_scval(&SYNTAX, &type_, "x", 0),
+            "fromVec(x, (v0) => fromTuple(v0, (v1) => (fromSymbol(v1[0]), fromOption(v1[1], (v2) => fromU32(v2)),)))"
+        );
+    }
+}
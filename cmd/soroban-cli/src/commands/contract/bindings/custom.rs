@@ -0,0 +1,97 @@
+use std::{fs, path::PathBuf};
+
+use clap::{command, Parser};
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use minijinja::{context, Environment};
+use soroban_env_host::xdr::ScSpecEntry;
+use soroban_spec_json::types::Entry;
+
+use super::{
+    check,
+    package::{self, Files, Package},
+};
+
+/// Render each file of `--template-dir`, and its path, as a minijinja template to the same path
+/// under `--output-dir`, e.g. `src/{{ name }}.rs`.
+///
+/// Templates get the package's `name`, `contract_id`, `rpc_url` and `network_passphrase`, the
+/// contract's `entries` as in the output of `bindings json`, and its `functions`, the function
+/// entries without the ones reserved for the host. `snake_case`, `lower_camel_case`,
+/// `upper_camel_case`, `kebab_case` and `shouty_snake_case` filters rename identifiers.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Directory of the templates
+    #[arg(long)]
+    template_dir: PathBuf,
+
+    #[command(flatten)]
+    package: package::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Package(#[from] package::Error),
+    #[error("reading templates in {0:?}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("rendering template {0:?}: {1}")]
+    Render(PathBuf, minijinja::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let templates = check::files(&self.template_dir)
+            .map_err(|e| Error::Read(self.template_dir.clone(), e))?
+            .into_iter()
+            .map(|path| {
+                let source = fs::read_to_string(&path).map_err(|e| Error::Read(path.clone(), e))?;
+                let path = path
+                    .strip_prefix(&self.template_dir)
+                    .unwrap_or(&path)
+                    .to_path_buf();
+                Ok((path, source))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.package
+            .try_run(|package| render(&templates, package))
+            .await
+    }
+}
+
+fn render(templates: &[(PathBuf, String)], package: &Package) -> Result<Files, Error> {
+    let mut env = Environment::new();
+    // Block tags on their own lines don't leave blank lines in the output
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("snake_case", |s: String| s.to_snake_case());
+    env.add_filter("lower_camel_case", |s: String| s.to_lower_camel_case());
+    env.add_filter("upper_camel_case", |s: String| s.to_upper_camel_case());
+    env.add_filter("kebab_case", |s: String| s.to_kebab_case());
+    env.add_filter("shouty_snake_case", |s: String| s.to_shouty_snake_case());
+    let entries = package.spec.iter().map(Entry::from).collect::<Vec<_>>();
+    let functions = package
+        .functions()
+        .map(|function| Entry::from(&ScSpecEntry::FunctionV0(function.clone())))
+        .collect::<Vec<_>>();
+    let ctx = context! {
+        name => package.name,
+        contract_id => package.contract_id,
+        rpc_url => package.rpc_url,
+        network_passphrase => package.network_passphrase,
+        entries => entries,
+        functions => functions,
+    };
+    templates
+        .iter()
+        .map(|(path, source)| {
+            let render = |name: &str, source: &str| {
+                env.render_named_str(name, source, &ctx)
+                    .map_err(|e| Error::Render(path.clone(), e))
+            };
+            let name = path.to_string_lossy();
+            Ok((render(&name, &name)?, render(&name, source)?))
+        })
+        .collect()
+}
@@ -0,0 +1,443 @@
+use clap::{command, Parser};
+use heck::ToLowerCamelCase;
+use soroban_env_host::xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use super::{
+    convert::{self, Syntax},
+    package::{self, Files, Package},
+};
+
+const CLIENT: &str = include_str!("dart/contract_client.dart");
+
+const DART: Syntax = Syntax {
+    closure: |param, body| format!("({param}) => {body}"),
+    list: |elements| format!("[{}]", elements.join(", ")),
+    index: |list, i| format!("{list}[{i}]"),
+    tuple_get: |tuple, i| format!("{tuple}.${}", i + 1),
+    tuple_new: |elements| format!("({},)", elements.join(", ")),
+    null: "null",
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    package: package::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Package(#[from] package::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.package.run(files).await?;
+        Ok(())
+    }
+}
+
+fn files(package: &Package) -> Files {
+    let Package {
+        name, contract_id, ..
+    } = package;
+    vec![
+        (
+            "pubspec.yaml".to_string(),
+            format!(
+                r#"name: {name}
+description: Client for contract {contract_id}. Generated by `soroban contract bindings dart`.
+version: 0.0.0
+publish_to: none
+
+environment:
+  sdk: ">=3.0.0 <4.0.0"
+
+dependencies:
+  stellar_flutter_sdk: ^1.6.0
+"#
+            ),
+        ),
+        (
+            "lib/src/contract_client.dart".to_string(),
+            CLIENT.to_string(),
+        ),
+        (format!("lib/{name}.dart"), library(package)),
+    ]
+}
+
+/// The package's library: a class per type of the spec, converting to and from `XdrSCVal`s,
+/// and a `Client` with a method per function.
+fn library(package: &Package) -> String {
+    let Package {
+        contract_id,
+        rpc_url,
+        network_passphrase,
+        spec,
+        ..
+    } = package;
+    let mut out = format!(
+        r#"/// Client for contract {contract_id}. Generated by `soroban contract bindings dart`.
+library;
+
+import 'dart:typed_data';
+
+import 'package:stellar_flutter_sdk/stellar_flutter_sdk.dart';
+
+import 'src/contract_client.dart';
+
+export 'src/contract_client.dart' show ContractClient, InvocationError;
+
+const defaultContractId = '{contract_id}';
+const defaultRpcUrl = '{rpc_url}';
+const defaultNetworkPassphrase = '{network_passphrase}';
+"#
+    );
+    for entry in spec {
+        out.push_str(&match entry {
+            ScSpecEntry::UdtStructV0(udt) => struct_class(udt),
+            ScSpecEntry::UdtUnionV0(udt) => union_class(udt),
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::FunctionV0(_) => continue,
+        });
+    }
+    out.push_str(
+        r#"
+class Client extends ContractClient {
+  Client(KeyPair source,
+      {String contractId = defaultContractId,
+      String rpcUrl = defaultRpcUrl,
+      String networkPassphrase = defaultNetworkPassphrase,
+      int timeout = 30})
+      : super(contractId, rpcUrl, networkPassphrase, source, timeout: timeout);
+"#,
+    );
+    for function in package.functions() {
+        out.push_str(&method(function));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn struct_class(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let fields = fields
+        .iter()
+        .map(|field| (field.name.to_string_lossy(), &field.type_))
+        .collect::<Vec<_>>();
+    // Tuple structs have fields named by position, and are stored as a vec
+    let is_tuple = fields
+        .iter()
+        .all(|(name, _)| name.chars().all(|c| c.is_ascii_digit()));
+    let attr = |field: &str| {
+        if is_tuple {
+            format!("f{field}")
+        } else {
+            identifier(&field.to_lower_camel_case())
+        }
+    };
+    let mut out = format!("\n{}class {name} {{\n", comment(&doc.to_string_lossy(), 0));
+    for (field, type_) in &fields {
+        out.push_str(&format!("  final {} {};\n", dart_type(type_), attr(field)));
+    }
+    out.push_str(&format!(
+        "\n  const {name}({{{}}});\n",
+        fields
+            .iter()
+            .map(|(field, _)| format!("required this.{}", attr(field)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    let (to, from) = if is_tuple {
+        (
+            format!(
+                "XdrSCVal.forVec([{}])",
+                fields
+                    .iter()
+                    .map(|(field, type_)| to_scval(type_, &attr(field)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "final vals = val.vec!;\n    return {name}({});",
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (field, type_))| format!(
+                        "{}: {}",
+                        attr(field),
+                        from_scval(type_, &format!("vals[{i}]"))
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    } else {
+        (
+            format!(
+                "toStruct({{{}}})",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!("'{field}': {}", to_scval(type_, &attr(field))))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "final vals = fromStruct(val);\n    return {name}({});",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!(
+                        "{}: {}",
+                        attr(field),
+                        from_scval(type_, &format!("vals['{field}']!"))
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    };
+    out.push_str(&format!(
+        r#"
+  XdrSCVal toScVal() => {to};
+
+  static {name} fromScVal(XdrSCVal val) {{
+    {from}
+  }}
+}}
+"#
+    ));
+    out
+}
+
+fn union_class(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let mut to = String::new();
+    let mut from = String::new();
+    for case in cases.iter() {
+        let (case, types) = match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => (case.name.to_string_lossy(), vec![]),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                (case.name.to_string_lossy(), case.type_.to_vec())
+            }
+        };
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| {
+                format!(
+                    ", {}",
+                    to_scval(type_, &format!("(values[{i}] as {})", dart_type(type_)))
+                )
+            })
+            .collect::<String>();
+        to.push_str(&format!(
+            "      '{case}' => XdrSCVal.forVec([toSymbol('{case}'){values}]),\n"
+        ));
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| from_scval(type_, &format!("vals[{}]", i + 1)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        from.push_str(&format!("      '{case}' => {name}(tag, [{values}]),\n"));
+    }
+    format!(
+        r#"
+{}class {name} {{
+  final String tag;
+  final List<Object?> values;
+
+  const {name}(this.tag, [this.values = const []]);
+
+  XdrSCVal toScVal() => switch (tag) {{
+{to}      _ => throw ArgumentError('unknown case $tag'),
+    }};
+
+  static {name} fromScVal(XdrSCVal val) {{
+    final vals = val.vec!;
+    final tag = fromSymbol(vals[0]);
+    return switch (tag) {{
+{from}      _ => throw ArgumentError('unknown case $tag'),
+    }};
+  }}
+}}
+"#,
+        comment(&doc.to_string_lossy(), 0)
+    )
+}
+
+fn int_enum(doc: &str, name: &str, cases: impl Iterator<Item = (String, u32)>) -> String {
+    let cases = cases
+        .map(|(case, value)| format!("  {}({value})", identifier(&case.to_lower_camel_case())))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!(
+        r#"
+{}enum {name} {{
+{cases};
+
+  final int value;
+
+  const {name}(this.value);
+
+  XdrSCVal toScVal() => toU32(value);
+
+  static {name} fromScVal(XdrSCVal val) {{
+    final value = fromU32(val);
+    return {name}.values.firstWhere((c) => c.value == value);
+  }}
+}}
+"#,
+        comment(doc, 0)
+    )
+}
+
+fn method(
+    ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    }: &ScSpecFunctionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let params = inputs
+        .iter()
+        .map(|input| {
+            format!(
+                "{} {}, ",
+                dart_type(&input.type_),
+                identifier(&input.name.to_string_lossy().to_lower_camel_case())
+            )
+        })
+        .collect::<String>();
+    let args = inputs
+        .iter()
+        .map(|input| {
+            to_scval(
+                &input.type_,
+                &identifier(&input.name.to_string_lossy().to_lower_camel_case()),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (output, result) = match outputs.first() {
+        Some(output) => (dart_type(output), from_scval(output, "result")),
+        None => ("void".to_string(), String::new()),
+    };
+    let invoke = format!("invoke('{name}', [{args}], simulateOnly: simulateOnly)");
+    let body = if result.is_empty() {
+        format!("await {invoke};")
+    } else {
+        format!("final result = await {invoke};\n    return {result};")
+    };
+    format!(
+        r#"
+{}  Future<{output}> {}({params}{{bool simulateOnly = false}}) async {{
+    {body}
+  }}
+"#,
+        comment(&doc.to_string_lossy(), 2),
+        identifier(&name.to_lower_camel_case()),
+    )
+}
+
+fn dart_type(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Void => "void".to_string(),
+        ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::Timepoint
+        | ScSpecTypeDef::Duration
+        | ScSpecTypeDef::Error => "int".to_string(),
+        ScSpecTypeDef::U128 | ScSpecTypeDef::I128 | ScSpecTypeDef::U256 | ScSpecTypeDef::I256 => {
+            "BigInt".to_string()
+        }
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "Uint8List".to_string(),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => {
+            "String".to_string()
+        }
+        ScSpecTypeDef::Option(option) => format!("{}?", dart_type(&option.value_type)),
+        ScSpecTypeDef::Result(result) => dart_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("List<{}>", dart_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("List<{}>", dart_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "Map<{}, {}>",
+            dart_type(&map.key_type),
+            dart_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "({},)",
+            tuple
+                .value_types
+                .iter()
+                .map(dart_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string_lossy(),
+        ScSpecTypeDef::Val => "XdrSCVal".to_string(),
+    }
+}
+
+fn to_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::to_scval(&DART, type_, val, 0)
+}
+
+fn from_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::from_scval(&DART, type_, val, 0)
+}
+
+fn comment(doc: &str, indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    doc.lines()
+        .map(|line| format!("{indent}/// {line}\n"))
+        .collect()
+}
+
+/// Names that are Dart reserved words, such as `default`, get a trailing `_`.
+fn identifier(name: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+        "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+        "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void",
+        "while", "with",
+    ];
+    if RESERVED.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,187 @@
+// Invoke the contract with the Stellar Flutter SDK. Generated by `soroban contract bindings dart`.
+import 'dart:typed_data';
+
+import 'package:stellar_flutter_sdk/stellar_flutter_sdk.dart';
+
+class InvocationError implements Exception {
+  final String message;
+
+  InvocationError(this.message);
+
+  @override
+  String toString() => 'InvocationError: $message';
+}
+
+class ContractClient {
+  final String contractId;
+  final SorobanServer server;
+  final Network network;
+
+  /// Signs and pays for the transactions. A keypair without a secret seed, from
+  /// `KeyPair.fromAccountId`, can only simulate them.
+  final KeyPair source;
+
+  /// Seconds to wait for a transaction to be included in a ledger.
+  final int timeout;
+
+  ContractClient(this.contractId, String rpcUrl, String networkPassphrase,
+      this.source, {this.timeout = 30})
+      : server = SorobanServer(rpcUrl),
+        network = Network(networkPassphrase);
+
+  Future<XdrSCVal> invoke(String function, List<XdrSCVal> args,
+      {bool simulateOnly = false}) async {
+    final account = await server.getAccount(source.accountId);
+    if (account == null) {
+      throw InvocationError('account ${source.accountId} not found');
+    }
+    final operation = InvokeHostFuncOpBuilder(
+            InvokeContractHostFunction(contractId, function, arguments: args))
+        .build();
+    final tx = TransactionBuilder(account).addOperation(operation).build();
+    final simulation =
+        await server.simulateTransaction(SimulateTransactionRequest(tx));
+    if (simulation.resultError != null) {
+      throw InvocationError(simulation.resultError!);
+    }
+    if (simulateOnly || !source.canSign()) {
+      return simulation.results!.first.resultValue!;
+    }
+
+    tx.sorobanTransactionData = simulation.transactionData;
+    tx.addResourceFee(simulation.minResourceFee!);
+    tx.setSorobanAuth(simulation.sorobanAuth);
+    tx.sign(source, network);
+    final sent = await server.sendTransaction(tx);
+    if (sent.status == SendTransactionResponse.STATUS_ERROR) {
+      throw InvocationError(sent.errorResultXdr ?? 'transaction rejected');
+    }
+    for (var i = 0; i < timeout; i++) {
+      final response = await server.getTransaction(sent.hash!);
+      if (response.status == GetTransactionResponse.STATUS_SUCCESS) {
+        return response.getResultValue()!;
+      }
+      if (response.status == GetTransactionResponse.STATUS_FAILED) {
+        throw InvocationError(response.resultXdr ?? 'transaction failed');
+      }
+      await Future.delayed(const Duration(seconds: 1));
+    }
+    throw InvocationError('transaction ${sent.hash} not included in a ledger');
+  }
+}
+
+XdrSCVal toBool(bool v) => XdrSCVal.forBool(v);
+bool fromBool(XdrSCVal val) => val.b!;
+
+XdrSCVal toVoid() => XdrSCVal.forVoid();
+
+XdrSCVal toU32(int v) => XdrSCVal.forU32(v);
+int fromU32(XdrSCVal val) => val.u32!.uint32;
+
+XdrSCVal toI32(int v) => XdrSCVal.forI32(v);
+int fromI32(XdrSCVal val) => val.i32!.int32;
+
+XdrSCVal toU64(int v) => XdrSCVal.forU64(v);
+int fromU64(XdrSCVal val) => val.u64!.uint64;
+
+XdrSCVal toI64(int v) => XdrSCVal.forI64(v);
+int fromI64(XdrSCVal val) => val.i64!.int64;
+
+XdrSCVal toTimepoint(int v) => XdrSCVal.forTimepoint(v);
+int fromTimepoint(XdrSCVal val) => val.timepoint!.uint64;
+
+XdrSCVal toDuration(int v) => XdrSCVal.forDuration(v);
+int fromDuration(XdrSCVal val) => val.duration!.uint64;
+
+final _mask64 = (BigInt.one << 64) - BigInt.one;
+
+XdrSCVal toU128(BigInt v) =>
+    XdrSCVal.forU128Parts((v >> 64).toInt(), (v & _mask64).toInt());
+BigInt fromU128(XdrSCVal val) =>
+    (BigInt.from(val.u128!.hi.uint64).toUnsigned(64) << 64) |
+    BigInt.from(val.u128!.lo.uint64).toUnsigned(64);
+
+XdrSCVal toI128(BigInt v) =>
+    XdrSCVal.forI128Parts((v >> 64).toInt(), (v & _mask64).toInt());
+BigInt fromI128(XdrSCVal val) =>
+    (BigInt.from(val.i128!.hi.int64) << 64) |
+    BigInt.from(val.i128!.lo.uint64).toUnsigned(64);
+
+List<int> _words(BigInt v) =>
+    [192, 128, 64, 0].map((shift) => ((v >> shift) & _mask64).toInt()).toList();
+BigInt _fromWords(BigInt hiHi, int hiLo, int loHi, int loLo) => [hiLo, loHi, loLo]
+    .fold(hiHi, (acc, word) => (acc << 64) | BigInt.from(word).toUnsigned(64));
+
+XdrSCVal toU256(BigInt v) {
+  final w = _words(v);
+  return XdrSCVal.forU256Parts(w[0], w[1], w[2], w[3]);
+}
+
+BigInt fromU256(XdrSCVal val) => _fromWords(
+    BigInt.from(val.u256!.hiHi.uint64).toUnsigned(64),
+    val.u256!.hiLo.uint64,
+    val.u256!.loHi.uint64,
+    val.u256!.loLo.uint64);
+
+XdrSCVal toI256(BigInt v) {
+  final w = _words(v);
+  return XdrSCVal.forI256Parts(w[0], w[1], w[2], w[3]);
+}
+
+BigInt fromI256(XdrSCVal val) => _fromWords(
+    BigInt.from(val.i256!.hiHi.int64),
+    val.i256!.hiLo.uint64,
+    val.i256!.loHi.uint64,
+    val.i256!.loLo.uint64);
+
+XdrSCVal toBytes(Uint8List v) => XdrSCVal.forBytes(v);
+Uint8List fromBytes(XdrSCVal val) => val.bytes!.dataValue;
+
+XdrSCVal toStr(String v) => XdrSCVal.forString(v);
+String fromStr(XdrSCVal val) => val.str!;
+
+XdrSCVal toSymbol(String v) => XdrSCVal.forSymbol(v);
+String fromSymbol(XdrSCVal val) => val.sym!;
+
+XdrSCVal toAddress(String v) => (v.startsWith('C')
+        ? Address.forContractId(StrKey.decodeContractIdHex(v))
+        : Address.forAccountId(v))
+    .toXdrSCVal();
+String fromAddress(XdrSCVal val) {
+  final address = Address.fromXdrSCVal(val);
+  return address.accountId ??
+      StrKey.encodeContractIdHex(address.contractId!);
+}
+
+XdrSCVal toOption<T>(T? v, XdrSCVal Function(T) f) =>
+    v == null ? XdrSCVal.forVoid() : f(v);
+T? fromOption<T>(XdrSCVal val, T Function(XdrSCVal) f) =>
+    val.discriminant == XdrSCValType.SCV_VOID ? null : f(val);
+
+XdrSCVal toVec<T>(List<T> v, XdrSCVal Function(T) f) =>
+    XdrSCVal.forVec(v.map(f).toList());
+List<T> fromVec<T>(XdrSCVal val, T Function(XdrSCVal) f) =>
+    val.vec!.map(f).toList();
+
+XdrSCVal toMap<K, V>(
+        Map<K, V> v, XdrSCVal Function(K) kf, XdrSCVal Function(V) vf) =>
+    XdrSCVal.forMap(
+        v.entries.map((e) => XdrSCMapEntry(kf(e.key), vf(e.value))).toList());
+Map<K, V> fromMap<K, V>(
+        XdrSCVal val, K Function(XdrSCVal) kf, V Function(XdrSCVal) vf) =>
+    {for (final e in val.map!) kf(e.key): vf(e.val)};
+
+/// Tuples are vecs of their elements.
+XdrSCVal toTuple(List<XdrSCVal> vals) => XdrSCVal.forVec(vals);
+T fromTuple<T>(XdrSCVal val, T Function(List<XdrSCVal>) f) => f(val.vec!);
+
+/// Structs are maps keyed by field name, sorted as the host expects.
+XdrSCVal toStruct(Map<String, XdrSCVal> fields) {
+  final names = fields.keys.toList()..sort();
+  return XdrSCVal.forMap(names
+      .map((name) => XdrSCMapEntry(XdrSCVal.forSymbol(name), fields[name]!))
+      .toList());
+}
+
+Map<String, XdrSCVal> fromStruct(XdrSCVal val) =>
+    {for (final e in val.map!) e.key.sym!: e.val};
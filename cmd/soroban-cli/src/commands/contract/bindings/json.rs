@@ -1,21 +1,30 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::PathBuf};
 
 use clap::{command, Parser};
 use soroban_spec_json;
 
 use crate::wasm;
 
+use super::check;
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     wasm: wasm::Args,
+
+    /// Instead of printing the JSON, check that this file holds it, e.g. to catch stale
+    /// bindings in CI
+    #[arg(long, value_name = "FILE")]
+    check: Option<PathBuf>,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("generate json from file: {0}")]
     GenerateJsonFromFile(soroban_spec_json::GenerateFromFileError),
+    #[error(transparent)]
+    Check(#[from] check::Error),
 }
 
 impl Cmd {
@@ -23,6 +32,9 @@ impl Cmd {
         let wasm_path_str = self.wasm.wasm.to_string_lossy();
         let json = soroban_spec_json::generate_from_file(&wasm_path_str, None)
             .map_err(Error::GenerateJsonFromFile)?;
+        if let Some(path) = &self.check {
+            return Ok(check::compare_file(path, &json)?);
+        }
         println!("{json}");
         Ok(())
     }
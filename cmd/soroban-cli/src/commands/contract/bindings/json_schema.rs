@@ -0,0 +1,49 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use clap::{command, Parser};
+use soroban_spec_tools::Spec;
+
+use crate::wasm;
+
+use super::check;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    wasm: wasm::Args,
+
+    /// Only output the schema of this function's arguments, as an object keyed by argument name
+    #[arg(long = "fn")]
+    function: Option<String>,
+
+    /// Instead of printing the schema, check that this file holds it, e.g. to catch stale
+    /// bindings in CI
+    #[arg(long, value_name = "FILE")]
+    check: Option<PathBuf>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Check(#[from] check::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let spec = Spec(Some(self.wasm.parse()?.spec));
+        let schema = spec.json_schema(self.function.as_deref())?;
+        let schema = serde_json::to_string_pretty(&schema)?;
+        if let Some(path) = &self.check {
+            return Ok(check::compare_file(path, &schema)?);
+        }
+        println!("{schema}");
+        Ok(())
+    }
+}
@@ -0,0 +1,480 @@
+use clap::{command, Parser};
+use heck::ToLowerCamelCase;
+use soroban_env_host::xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use super::{
+    convert::{self, Syntax},
+    package::{self, Files, Package},
+};
+
+const CLIENT: &str = include_str!("kotlin/ContractClient.kt");
+
+const KOTLIN: Syntax = Syntax {
+    closure: |param, body| format!("{{ {param} -> {body} }}"),
+    list: |elements| format!("listOf({})", elements.join(", ")),
+    index: |list, i| format!("{list}[{i}]"),
+    tuple_get: |tuple, i| format!("{tuple}.component{}()", i + 1),
+    tuple_new: |elements| format!("{}({})", tuple_class(elements.len()), elements.join(", ")),
+    null: "Unit",
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    package: package::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Package(#[from] package::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.package.run(files).await?;
+        Ok(())
+    }
+}
+
+fn files(package: &Package) -> Files {
+    let name = &package.name;
+    let sources = format!("src/main/kotlin/{name}");
+    vec![
+        (
+            "settings.gradle.kts".to_string(),
+            format!("rootProject.name = \"{name}\"\n"),
+        ),
+        (
+            "build.gradle.kts".to_string(),
+            r#"plugins {
+    kotlin("jvm") version "1.9.22"
+    `java-library`
+}
+
+version = "0.0.0"
+
+repositories {
+    mavenCentral()
+}
+
+dependencies {
+    api("network.lightsail:stellar-sdk:0.44.0")
+    api("org.jetbrains.kotlinx:kotlinx-coroutines-core:1.8.0")
+}
+"#
+            .to_string(),
+        ),
+        (
+            format!("{sources}/ContractClient.kt"),
+            CLIENT.replacen("\n\n", &format!("\n\npackage {name}\n\n"), 1),
+        ),
+        (format!("{sources}/Client.kt"), library(package)),
+    ]
+}
+
+/// The package's types, data classes converting to and from `SCVal`s, and a `Client` with a
+/// suspending method per function.
+fn library(package: &Package) -> String {
+    let Package {
+        name,
+        contract_id,
+        rpc_url,
+        network_passphrase,
+        spec,
+    } = package;
+    let mut out = format!(
+        r#"// Client for contract {contract_id}. Generated by `soroban contract bindings kotlin`.
+
+package {name}
+
+import java.math.BigInteger
+import org.stellar.sdk.KeyPair
+import org.stellar.sdk.xdr.SCVal
+
+const val DEFAULT_CONTRACT_ID = "{contract_id}"
+const val DEFAULT_RPC_URL = "{rpc_url}"
+const val DEFAULT_NETWORK_PASSPHRASE = "{network_passphrase}"
+"#
+    );
+    for entry in spec {
+        out.push_str(&match entry {
+            ScSpecEntry::UdtStructV0(udt) => data_class(udt),
+            ScSpecEntry::UdtUnionV0(udt) => sealed_class(udt),
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc, name, cases, ..
+            }) => enum_class(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                doc, name, cases, ..
+            }) => enum_class(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::FunctionV0(_) => continue,
+        });
+    }
+    out.push_str(
+        r#"
+class Client(
+    source: KeyPair,
+    contractId: String = DEFAULT_CONTRACT_ID,
+    rpcUrl: String = DEFAULT_RPC_URL,
+    networkPassphrase: String = DEFAULT_NETWORK_PASSPHRASE,
+    timeout: Int = 30,
+) : ContractClient(contractId, rpcUrl, networkPassphrase, source, timeout) {
+"#,
+    );
+    for function in package.functions() {
+        out.push_str(&method(function));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn data_class(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let fields = fields
+        .iter()
+        .map(|field| (field.name.to_string_lossy(), &field.type_))
+        .collect::<Vec<_>>();
+    // Tuple structs have fields named by position, and are stored as a vec
+    let is_tuple = fields
+        .iter()
+        .all(|(name, _)| name.chars().all(|c| c.is_ascii_digit()));
+    let attr = |field: &str| {
+        if is_tuple {
+            format!("f{field}")
+        } else {
+            identifier(&field.to_lower_camel_case())
+        }
+    };
+    let properties = fields
+        .iter()
+        .map(|(field, type_)| format!("    val {}: {},\n", attr(field), kotlin_type(type_)))
+        .collect::<String>();
+    let (to, from) = if is_tuple {
+        (
+            format!(
+                "toTuple(listOf({}))",
+                fields
+                    .iter()
+                    .map(|(field, type_)| to_scval(type_, &attr(field)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "fromTuple(scVal, {{ vals -> {name}({}) }})",
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, type_))| from_scval(type_, &format!("vals[{i}]")))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    } else {
+        (
+            format!(
+                "toStruct(mapOf({}))",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!(
+                        "\"{field}\" to {}",
+                        to_scval(type_, &attr(field))
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "fromStruct(scVal).let {{ vals -> {name}({}) }}",
+                fields
+                    .iter()
+                    .map(|(field, type_)| from_scval(type_, &format!("field(vals, \"{field}\")")))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    };
+    format!(
+        r#"
+{}data class {name}(
+{properties}) {{
+    fun toScVal(): SCVal = {to}
+
+    companion object {{
+        fun fromScVal(scVal: SCVal): {name} = {from}
+    }}
+}}
+"#,
+        comment(&doc.to_string_lossy(), 0)
+    )
+}
+
+/// Unions are sealed classes with a subclass per case, holding the case's values.
+fn sealed_class(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let mut subclasses = String::new();
+    let mut from = String::new();
+    for case in cases.iter() {
+        let (case, types) = match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => (case.name.to_string_lossy(), vec![]),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                (case.name.to_string_lossy(), case.type_.to_vec())
+            }
+        };
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| format!(", {}", to_scval(type_, &format!("v{i}"))))
+            .collect::<String>();
+        let to = format!(
+            "override fun toScVal(): SCVal = toTuple(listOf(toSymbol(\"{case}\"){values}))"
+        );
+        if types.is_empty() {
+            subclasses.push_str(&format!(
+                "    object {case} : {name}() {{\n        {to}\n    }}\n"
+            ));
+            from.push_str(&format!("                \"{case}\" -> {case}\n"));
+            continue;
+        }
+        let properties = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| format!("val v{i}: {}", kotlin_type(type_)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        subclasses.push_str(&format!(
+            "    data class {case}({properties}) : {name}() {{\n        {to}\n    }}\n"
+        ));
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| from_scval(type_, &format!("vals[{}]", i + 1)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        from.push_str(&format!("                \"{case}\" -> {case}({values})\n"));
+    }
+    format!(
+        r#"
+{}sealed class {name} {{
+    abstract fun toScVal(): SCVal
+
+{subclasses}
+    companion object {{
+        fun fromScVal(scVal: SCVal): {name} = fromTuple(scVal, {{ vals ->
+            when (val tag = fromSymbol(vals[0])) {{
+{from}                else -> throw InvocationError("unknown case $tag")
+            }}
+        }})
+    }}
+}}
+"#,
+        comment(&doc.to_string_lossy(), 0)
+    )
+}
+
+fn enum_class(doc: &str, name: &str, cases: impl Iterator<Item = (String, u32)>) -> String {
+    let cases = cases
+        .map(|(case, value)| format!("    {}({value})", identifier(&case)))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!(
+        r#"
+{}enum class {name}(val value: Long) {{
+{cases};
+
+    fun toScVal(): SCVal = toU32(value)
+
+    companion object {{
+        fun fromScVal(scVal: SCVal): {name} = fromU32(scVal).let {{ value ->
+            values().firstOrNull {{ it.value == value }}
+                ?: throw InvocationError("unknown case $value")
+        }}
+    }}
+}}
+"#,
+        comment(doc, 0)
+    )
+}
+
+fn method(
+    ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    }: &ScSpecFunctionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let params = inputs
+        .iter()
+        .map(|input| {
+            format!(
+                "{}: {}, ",
+                identifier(&input.name.to_string_lossy().to_lower_camel_case()),
+                kotlin_type(&input.type_)
+            )
+        })
+        .collect::<String>();
+    let args = inputs
+        .iter()
+        .map(|input| {
+            to_scval(
+                &input.type_,
+                &identifier(&input.name.to_string_lossy().to_lower_camel_case()),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let invoke = format!("invoke(\"{name}\", listOf({args}), simulateOnly)");
+    let (output, body) = match outputs.first() {
+        Some(output) => (
+            format!(": {}", kotlin_type(output)),
+            format!(
+                "val result = {invoke}\n        return {}",
+                from_scval(output, "result")
+            ),
+        ),
+        None => (String::new(), invoke),
+    };
+    format!(
+        r#"
+{}    suspend fun {}({params}simulateOnly: Boolean = false){output} {{
+        {body}
+    }}
+"#,
+        comment(&doc.to_string_lossy(), 4),
+        identifier(&name.to_lower_camel_case()),
+    )
+}
+
+fn kotlin_type(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Bool => "Boolean".to_string(),
+        ScSpecTypeDef::Void => "Unit".to_string(),
+        // The SDK takes unsigned 32 bit integers as `Long`s
+        ScSpecTypeDef::U32 | ScSpecTypeDef::I64 => "Long".to_string(),
+        ScSpecTypeDef::I32 => "Int".to_string(),
+        ScSpecTypeDef::U64
+        | ScSpecTypeDef::Timepoint
+        | ScSpecTypeDef::Duration
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128
+        | ScSpecTypeDef::U256
+        | ScSpecTypeDef::I256 => "BigInteger".to_string(),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "ByteArray".to_string(),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => {
+            "String".to_string()
+        }
+        ScSpecTypeDef::Option(option) => format!("{}?", kotlin_type(&option.value_type)),
+        ScSpecTypeDef::Result(result) => kotlin_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("List<{}>", kotlin_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("List<{}>", kotlin_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "Map<{}, {}>",
+            kotlin_type(&map.key_type),
+            kotlin_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "{}<{}>",
+            tuple_class(tuple.value_types.len()),
+            tuple
+                .value_types
+                .iter()
+                .map(kotlin_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string_lossy(),
+        ScSpecTypeDef::Val | ScSpecTypeDef::Error => "SCVal".to_string(),
+    }
+}
+
+/// Pairs and triples are the standard library's, and the runtime has the other sizes.
+fn tuple_class(len: usize) -> String {
+    match len {
+        2 => "Pair".to_string(),
+        3 => "Triple".to_string(),
+        len => format!("Tuple{len}"),
+    }
+}
+
+fn to_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::to_scval(&KOTLIN, type_, val, 0)
+}
+
+fn from_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::from_scval(&KOTLIN, type_, val, 0)
+}
+
+fn comment(doc: &str, indent: usize) -> String {
+    if doc.is_empty() {
+        return String::new();
+    }
+    let indent = " ".repeat(indent);
+    let lines = doc
+        .lines()
+        .map(|line| format!("{indent} * {line}\n"))
+        .collect::<String>();
+    format!("{indent}/**\n{lines}{indent} */\n")
+}
+
+/// Names that are Kotlin hard keywords, such as `in`, are quoted with backticks.
+fn identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as",
+        "break",
+        "class",
+        "continue",
+        "do",
+        "else",
+        "false",
+        "for",
+        "fun",
+        "if",
+        "in",
+        "interface",
+        "is",
+        "null",
+        "object",
+        "package",
+        "return",
+        "super",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typealias",
+        "typeof",
+        "val",
+        "var",
+        "when",
+        "while",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,152 @@
+// Invoke the contract with the Java Stellar SDK. Generated by `soroban contract bindings kotlin`.
+
+import java.math.BigInteger
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.withContext
+import org.stellar.sdk.InvokeHostFunctionOperation
+import org.stellar.sdk.KeyPair
+import org.stellar.sdk.Network
+import org.stellar.sdk.SorobanServer
+import org.stellar.sdk.Transaction
+import org.stellar.sdk.TransactionBuilder
+import org.stellar.sdk.responses.sorobanrpc.GetTransactionResponse
+import org.stellar.sdk.responses.sorobanrpc.SendTransactionResponse
+import org.stellar.sdk.scval.Scv
+import org.stellar.sdk.xdr.SCVal
+import org.stellar.sdk.xdr.SCValType
+
+class InvocationError(message: String) : Exception(message)
+
+open class ContractClient(
+    val contractId: String,
+    rpcUrl: String,
+    networkPassphrase: String,
+    /**
+     * Signs and pays for the transactions. A keypair without a secret seed, from
+     * `KeyPair.fromAccountId`, can only simulate them.
+     */
+    val source: KeyPair,
+    /** Seconds to wait for a transaction to be included in a ledger. */
+    val timeout: Int = 30,
+) {
+    val server = SorobanServer(rpcUrl)
+    val network = Network(networkPassphrase)
+
+    suspend fun invoke(function: String, args: List<SCVal>, simulateOnly: Boolean = false): SCVal =
+        withContext(Dispatchers.IO) {
+            val account = server.getAccount(source.accountId)
+            val operation = InvokeHostFunctionOperation
+                .invokeContractFunctionOperationBuilder(contractId, function, args)
+                .build()
+            val tx = TransactionBuilder(account, network)
+                .addOperation(operation)
+                .setBaseFee(Transaction.MIN_BASE_FEE.toLong())
+                .setTimeout(timeout.toLong())
+                .build()
+            val simulation = server.simulateTransaction(tx)
+            simulation.error?.let { throw InvocationError(it) }
+            if (simulateOnly || !source.canSign()) {
+                val result = simulation.results?.firstOrNull()
+                    ?: throw InvocationError("simulation of $function returned no value")
+                return@withContext SCVal.fromXdrBase64(result.xdr)
+            }
+
+            val prepared = server.prepareTransaction(tx, simulation)
+            prepared.sign(source)
+            val sent = server.sendTransaction(prepared)
+            if (sent.status == SendTransactionResponse.SendTransactionStatus.ERROR) {
+                throw InvocationError(sent.errorResultXdr ?: "transaction rejected")
+            }
+            repeat(timeout) {
+                val response = server.getTransaction(sent.hash)
+                when (response.status) {
+                    GetTransactionResponse.GetTransactionStatus.SUCCESS ->
+                        return@withContext response.resultValue ?: Scv.toVoid()
+                    GetTransactionResponse.GetTransactionStatus.FAILED ->
+                        throw InvocationError(response.resultXdr ?: "transaction failed")
+                    else -> delay(1000)
+                }
+            }
+            throw InvocationError("transaction ${sent.hash} not included in a ledger")
+        }
+}
+
+fun toBool(v: Boolean): SCVal = Scv.toBoolean(v)
+fun fromBool(v: SCVal): Boolean = Scv.fromBoolean(v)
+
+fun toVoid(): SCVal = Scv.toVoid()
+
+fun toU32(v: Long): SCVal = Scv.toUint32(v)
+fun fromU32(v: SCVal): Long = Scv.fromUint32(v)
+
+fun toI32(v: Int): SCVal = Scv.toInt32(v)
+fun fromI32(v: SCVal): Int = Scv.fromInt32(v)
+
+fun toU64(v: BigInteger): SCVal = Scv.toUint64(v)
+fun fromU64(v: SCVal): BigInteger = Scv.fromUint64(v)
+
+fun toI64(v: Long): SCVal = Scv.toInt64(v)
+fun fromI64(v: SCVal): Long = Scv.fromInt64(v)
+
+fun toTimepoint(v: BigInteger): SCVal = Scv.toTimePoint(v)
+fun fromTimepoint(v: SCVal): BigInteger = Scv.fromTimePoint(v)
+
+fun toDuration(v: BigInteger): SCVal = Scv.toDuration(v)
+fun fromDuration(v: SCVal): BigInteger = Scv.fromDuration(v)
+
+fun toU128(v: BigInteger): SCVal = Scv.toUint128(v)
+fun fromU128(v: SCVal): BigInteger = Scv.fromUint128(v)
+
+fun toI128(v: BigInteger): SCVal = Scv.toInt128(v)
+fun fromI128(v: SCVal): BigInteger = Scv.fromInt128(v)
+
+fun toU256(v: BigInteger): SCVal = Scv.toUint256(v)
+fun fromU256(v: SCVal): BigInteger = Scv.fromUint256(v)
+
+fun toI256(v: BigInteger): SCVal = Scv.toInt256(v)
+fun fromI256(v: SCVal): BigInteger = Scv.fromInt256(v)
+
+fun toBytes(v: ByteArray): SCVal = Scv.toBytes(v)
+fun fromBytes(v: SCVal): ByteArray = Scv.fromBytes(v)
+
+fun toStr(v: String): SCVal = Scv.toString(v)
+fun fromStr(v: SCVal): String = String(Scv.fromString(v))
+
+fun toSymbol(v: String): SCVal = Scv.toSymbol(v)
+fun fromSymbol(v: SCVal): String = Scv.fromSymbol(v)
+
+fun toAddress(v: String): SCVal = Scv.toAddress(v)
+fun fromAddress(v: SCVal): String = Scv.fromAddress(v).toString()
+
+fun <T> toOption(v: T?, f: (T) -> SCVal): SCVal = if (v == null) Scv.toVoid() else f(v)
+fun <T> fromOption(v: SCVal, f: (SCVal) -> T): T? =
+    if (v.discriminant == SCValType.SCV_VOID) null else f(v)
+
+fun <T> toVec(v: List<T>, f: (T) -> SCVal): SCVal = Scv.toVec(v.map(f))
+fun <T> fromVec(v: SCVal, f: (SCVal) -> T): List<T> = Scv.fromVec(v).map(f)
+
+fun <K, V> toMap(v: Map<K, V>, kf: (K) -> SCVal, vf: (V) -> SCVal): SCVal =
+    Scv.toMap(LinkedHashMap(v.entries.associate { kf(it.key) to vf(it.value) }))
+fun <K, V> fromMap(v: SCVal, kf: (SCVal) -> K, vf: (SCVal) -> V): Map<K, V> =
+    Scv.fromMap(v).entries.associate { kf(it.key) to vf(it.value) }
+
+/** Tuples of other sizes than the `Pair` and `Triple` of the standard library. */
+data class Tuple1<A>(val first: A)
+data class Tuple4<A, B, C, D>(val first: A, val second: B, val third: C, val fourth: D)
+data class Tuple5<A, B, C, D, E>(val first: A, val second: B, val third: C, val fourth: D, val fifth: E)
+data class Tuple6<A, B, C, D, E, F>(
+    val first: A, val second: B, val third: C, val fourth: D, val fifth: E, val sixth: F,
+)
+
+/** Tuples are vecs of their elements. */
+fun toTuple(vals: List<SCVal>): SCVal = Scv.toVec(vals)
+fun <T> fromTuple(v: SCVal, f: (List<SCVal>) -> T): T = f(Scv.fromVec(v).toList())
+
+/** Structs are maps keyed by field name, sorted as the host expects. */
+fun toStruct(fields: Map<String, SCVal>): SCVal =
+    Scv.toMap(LinkedHashMap(fields.toSortedMap().entries.associate { Scv.toSymbol(it.key) to it.value }))
+fun fromStruct(v: SCVal): Map<String, SCVal> =
+    Scv.fromMap(v).entries.associate { Scv.fromSymbol(it.key) to it.value }
+fun field(fields: Map<String, SCVal>, name: String): SCVal =
+    fields[name] ?: throw InvocationError("missing field $name")
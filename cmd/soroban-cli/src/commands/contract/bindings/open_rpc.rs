@@ -0,0 +1,61 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use clap::{command, Parser};
+use soroban_spec_tools::Spec;
+
+use crate::wasm;
+
+use super::check;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    wasm: wasm::Args,
+
+    /// Title of the API, defaults to the name of the wasm file
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Version of the API
+    #[arg(long, default_value = "0.0.0")]
+    api_version: String,
+
+    /// Instead of printing the document, check that this file holds it, e.g. to catch stale
+    /// bindings in CI
+    #[arg(long, value_name = "FILE")]
+    check: Option<PathBuf>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Check(#[from] check::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let spec = Spec(Some(self.wasm.parse()?.spec));
+        let title = self.title.clone().unwrap_or_else(|| {
+            self.wasm
+                .wasm
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        });
+        let document = spec.open_rpc(&title, &self.api_version)?;
+        let document = serde_json::to_string_pretty(&document)?;
+        if let Some(path) = &self.check {
+            return Ok(check::compare_file(path, &document)?);
+        }
+        println!("{document}");
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+//! Shared by the generators of packages calling a deployed contract: where the spec comes from,
+//! the network settings baked into the package, and writing, or `--check`ing, its files.
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{command, Parser};
+use heck::ToSnakeCase;
+use soroban_env_host::xdr::{ScSpecEntry, ScSpecFunctionV0};
+
+use super::check::{self, Generated};
+use crate::wasm;
+use crate::{
+    commands::{
+        config::{
+            ledger_file, locator,
+            network::{self, Network},
+        },
+        contract::{self, fetch},
+    },
+    utils::contract_spec::{self, ContractSpec},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Path to optional wasm binary
+    #[arg(long)]
+    pub wasm: Option<std::path::PathBuf>,
+
+    /// Where to place generated package
+    #[arg(long)]
+    pub output_dir: PathBuf,
+
+    /// Whether to overwrite output directory if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+
+    /// Instead of writing the bindings, check that those in the output directory are up to
+    /// date with the contract, and fail listing the files that are not
+    #[arg(long, conflicts_with = "overwrite")]
+    pub check: bool,
+
+    /// The contract ID/address on the network, or an alias saved for it
+    #[arg(long, alias = "id")]
+    pub contract_id: String,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("--output-dir cannot be a file: {0:?}")]
+    IsFile(PathBuf),
+
+    #[error("--output-dir already exists and you did not specify --overwrite: {0:?}")]
+    OutputDirExists(PathBuf),
+
+    #[error("--output-dir filepath not representable as utf-8: {0:?}")]
+    NotUtf8(OsString),
+
+    #[error(transparent)]
+    Network(#[from] network::Error),
+
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Fetch(#[from] fetch::Error),
+    #[error(transparent)]
+    Spec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Check(#[from] check::Error),
+}
+
+/// What a generator needs to know about the package.
+pub struct Package {
+    /// The name of the output directory, in snake case
+    pub name: String,
+    pub contract_id: String,
+    pub rpc_url: String,
+    pub network_passphrase: String,
+    pub spec: Vec<ScSpecEntry>,
+}
+
+impl Package {
+    /// The functions of the contract, without the ones reserved for the host, such as
+    /// `__constructor`.
+    pub fn functions(&self) -> impl Iterator<Item = &ScSpecFunctionV0> {
+        self.spec.iter().filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(function)
+                if !function.name.to_string_lossy().starts_with("__") =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+    }
+}
+
+/// The files of a package, by path relative to the output directory.
+pub type Files = Vec<(String, String)>;
+
+impl Args {
+    /// Write the files `generate` makes of the package to `--output-dir`, or with `--check`
+    /// compare them with the ones there.
+    pub async fn run(&self, generate: fn(&Package) -> Files) -> Result<(), Error> {
+        self.try_run(|package| Ok(generate(package))).await
+    }
+
+    /// Like [`Args::run`], for generators that can fail.
+    pub async fn try_run<E: From<Error>>(
+        &self,
+        generate: impl FnOnce(&Package) -> Result<Files, E>,
+    ) -> Result<(), E> {
+        let Network {
+            rpc_url,
+            network_passphrase,
+            ..
+        } = self
+            .network
+            .get(&self.locator)
+            .ok()
+            .unwrap_or_else(Network::futurenet);
+        let contract_id = self
+            .locator
+            .resolve_contract_id(&self.contract_id, &network_passphrase)
+            .map_err(Error::from)?;
+        let spec = self.spec(&contract_id).await?;
+        let generated = if self.check {
+            Some(Generated::new(&self.output_dir).map_err(Error::from)?)
+        } else {
+            self.create_output_dir()?;
+            None
+        };
+        let package = Package {
+            name: package_name(&self.output_dir)?,
+            contract_id,
+            rpc_url,
+            network_passphrase,
+            spec,
+        };
+        let files = generate(&package)?;
+        self.write(generated, files)?;
+        Ok(())
+    }
+
+    fn write(&self, generated: Option<Generated>, files: Files) -> Result<(), Error> {
+        let output_dir = generated
+            .as_ref()
+            .map_or_else(|| self.output_dir.clone(), Generated::output_dir);
+        for (path, contents) in files {
+            let path = output_dir.join(path);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, contents)?;
+        }
+        if let Some(generated) = generated {
+            generated.compare(&self.output_dir)?;
+        } else {
+            println!("Generated package in {}", self.output_dir.display());
+        }
+        Ok(())
+    }
+
+    async fn spec(&self, contract_id: &str) -> Result<Vec<ScSpecEntry>, Error> {
+        Ok(if let Some(wasm) = &self.wasm {
+            let wasm: wasm::Args = wasm.into();
+            wasm.parse()?.spec
+        } else {
+            let fetch = contract::fetch::Cmd {
+                contract_id: Some(contract_id.to_string()),
+                wasm_hash: None,
+                out_file: None,
+                locator: self.locator.clone(),
+                network: self.network.clone(),
+                ledger_file: ledger_file::Args::default(),
+            };
+            let bytes = fetch.get_bytes().await?;
+            ContractSpec::new(&bytes)?.spec
+        })
+    }
+
+    fn create_output_dir(&self) -> Result<(), Error> {
+        if self.output_dir.is_file() {
+            return Err(Error::IsFile(self.output_dir.clone()));
+        }
+        if self.output_dir.exists() {
+            if self.overwrite {
+                fs::remove_dir_all(&self.output_dir)?;
+            } else {
+                return Err(Error::OutputDirExists(self.output_dir.clone()));
+            }
+        }
+        fs::create_dir_all(&self.output_dir)?;
+        Ok(())
+    }
+}
+
+fn package_name(output_dir: &Path) -> Result<String, Error> {
+    let absolute_path = output_dir.canonicalize()?;
+    let file_name = absolute_path.file_name().unwrap_or_default();
+    Ok(file_name
+        .to_str()
+        .ok_or_else(|| Error::NotUtf8(file_name.to_os_string()))?
+        .to_snake_case())
+}
@@ -0,0 +1,518 @@
+use clap::{command, Parser};
+use soroban_env_host::xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use super::package::{self, Files, Package};
+
+const CLIENT: &str = include_str!("python/_client.py");
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    package: package::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Package(#[from] package::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.package.run(files).await?;
+        Ok(())
+    }
+}
+
+fn files(package: &Package) -> Files {
+    let name = &package.name;
+    vec![
+        (
+            "pyproject.toml".to_string(),
+            format!(
+                r#"[project]
+name = "{name}"
+version = "0.0.0"
+requires-python = ">=3.8"
+dependencies = ["stellar-sdk>=9.0.0"]
+
+[build-system]
+requires = ["setuptools>=61"]
+build-backend = "setuptools.build_meta"
+"#
+            ),
+        ),
+        (format!("{name}/_client.py"), CLIENT.to_string()),
+        (format!("{name}/__init__.py"), generate(package)),
+    ]
+}
+
+/// The package's `__init__.py`: a class per type of the spec, converting to and from `SCVal`s,
+/// and a `Client` with a method per function.
+fn generate(package: &Package) -> String {
+    let Package {
+        contract_id,
+        rpc_url,
+        network_passphrase,
+        spec,
+        ..
+    } = package;
+    let mut out = format!(
+        r#""""Client for contract {contract_id}. Generated by `soroban contract bindings python`."""
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Any, Dict, List, Optional, Tuple
+
+from stellar_sdk import Keypair, scval, xdr
+
+from ._client import ContractClient, InvocationError, _optional
+
+CONTRACT_ID = "{contract_id}"
+RPC_URL = "{rpc_url}"
+NETWORK_PASSPHRASE = "{network_passphrase}"
+"#
+    );
+    for entry in spec {
+        out.push_str(&match entry {
+            ScSpecEntry::UdtStructV0(udt) => struct_class(udt),
+            ScSpecEntry::UdtUnionV0(udt) => union_class(udt),
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::FunctionV0(_) => continue,
+        });
+    }
+    out.push_str(&format!(
+        r#"
+
+class Client(ContractClient):
+    def __init__(
+        self,
+        source: Keypair,
+        contract_id: str = CONTRACT_ID,
+        rpc_url: str = RPC_URL,
+        network_passphrase: str = NETWORK_PASSPHRASE,
+        **kwargs: Any,
+    ):
+        super().__init__(contract_id, rpc_url, network_passphrase, source, **kwargs)
+"#
+    ));
+    for function in package.functions() {
+        out.push_str(&method(function));
+    }
+    out
+}
+
+fn struct_class(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let fields = fields
+        .iter()
+        .map(|field| (field.name.to_string_lossy(), &field.type_))
+        .collect::<Vec<_>>();
+    // Tuple structs have fields named by position, and are stored as a vec
+    let is_tuple = fields
+        .iter()
+        .all(|(name, _)| name.chars().all(|c| c.is_ascii_digit()));
+    let attr = |field: &str| {
+        if is_tuple {
+            format!("f{field}")
+        } else {
+            identifier(field)
+        }
+    };
+    let mut out = format!(
+        "\n\n@dataclass\nclass {name}:\n{}",
+        docstring(&doc.to_string_lossy(), 4)
+    );
+    for (field, type_) in &fields {
+        out.push_str(&format!("    {}: {}\n", attr(field), py_type(type_)));
+    }
+    let (to, from) = if is_tuple {
+        (
+            format!(
+                "scval.to_vec([{}])",
+                fields
+                    .iter()
+                    .map(|(field, type_)| to_scval(type_, &format!("self.{}", attr(field)), 0))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "(lambda vals: cls({}))(scval.from_vec(val))",
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, type_))| from_scval(type_, &format!("vals[{i}]"), 0))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    } else {
+        (
+            format!(
+                "scval.to_struct({{{}}})",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!(
+                        "\"{field}\": {}",
+                        to_scval(type_, &format!("self.{}", attr(field)), 0)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "(lambda vals: cls({}))(scval.from_struct(val))",
+                fields
+                    .iter()
+                    .map(|(field, type_)| from_scval(type_, &format!("vals[\"{field}\"]"), 0))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    };
+    out.push_str(&format!(
+        r#"
+    def to_scval(self) -> xdr.SCVal:
+        return {to}
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> "{name}":
+        return {from}
+"#
+    ));
+    out
+}
+
+fn union_class(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let mut to = String::new();
+    let mut from = String::new();
+    for case in cases.iter() {
+        let (case, types) = match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => (case.name.to_string_lossy(), vec![]),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                (case.name.to_string_lossy(), case.type_.to_vec())
+            }
+        };
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| format!(", {}", to_scval(type_, &format!("self.values[{i}]"), 0)))
+            .collect::<String>();
+        to.push_str(&format!(
+            "        if self.tag == \"{case}\":\n            return scval.to_vec([scval.to_symbol(\"{case}\"){values}])\n"
+        ));
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| from_scval(type_, &format!("vals[{}]", i + 1), 0))
+            .collect::<Vec<_>>()
+            .join(", ");
+        from.push_str(&format!(
+            "        if tag == \"{case}\":\n            return cls(tag, [{values}])\n"
+        ));
+    }
+    format!(
+        r#"
+
+@dataclass
+class {name}:
+{}    tag: str
+    values: List[Any]
+
+    def to_scval(self) -> xdr.SCVal:
+{to}        raise ValueError(f"unknown case {{self.tag}}")
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> "{name}":
+        vals = scval.from_vec(val)
+        tag = scval.from_symbol(vals[0])
+{from}        raise ValueError(f"unknown case {{tag}}")
+"#,
+        docstring(&doc.to_string_lossy(), 4)
+    )
+}
+
+fn int_enum(doc: &str, name: &str, cases: impl Iterator<Item = (String, u32)>) -> String {
+    let mut out = format!("\n\nclass {name}(IntEnum):\n{}", docstring(doc, 4));
+    for (case, value) in cases {
+        out.push_str(&format!("    {} = {value}\n", identifier(&case)));
+    }
+    out.push_str(&format!(
+        r#"
+    def to_scval(self) -> xdr.SCVal:
+        return scval.to_uint32(self.value)
+
+    @classmethod
+    def from_scval(cls, val: xdr.SCVal) -> "{name}":
+        return cls(scval.from_uint32(val))
+"#
+    ));
+    out
+}
+
+fn method(
+    ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    }: &ScSpecFunctionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let params = inputs
+        .iter()
+        .map(|input| {
+            format!(
+                ", {}: {}",
+                identifier(&input.name.to_string_lossy()),
+                py_type(&input.type_)
+            )
+        })
+        .collect::<String>();
+    let args = inputs
+        .iter()
+        .map(|input| to_scval(&input.type_, &identifier(&input.name.to_string_lossy()), 0))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (output, result) = match outputs.first() {
+        Some(output) => (py_type(output), from_scval(output, "result", 0)),
+        None => ("None".to_string(), "None".to_string()),
+    };
+    format!(
+        r#"
+    def {}(self{params}, *, simulate_only: bool = False) -> {output}:
+{}        result = self._invoke("{name}", [{args}], simulate_only)
+        return {result}
+"#,
+        identifier(&name),
+        docstring(&doc.to_string_lossy(), 8)
+    )
+}
+
+fn py_type(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Void => "None".to_string(),
+        ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128
+        | ScSpecTypeDef::U256
+        | ScSpecTypeDef::I256
+        | ScSpecTypeDef::Timepoint
+        | ScSpecTypeDef::Duration
+        | ScSpecTypeDef::Error => "int".to_string(),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "bytes".to_string(),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => "str".to_string(),
+        ScSpecTypeDef::Option(option) => format!("Optional[{}]", py_type(&option.value_type)),
+        ScSpecTypeDef::Result(result) => py_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("List[{}]", py_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("List[{}]", py_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "Dict[{}, {}]",
+            py_type(&map.key_type),
+            py_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "Tuple[{}]",
+            tuple
+                .value_types
+                .iter()
+                .map(py_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(udt) => format!("\"{}\"", udt.name.to_string_lossy()),
+        ScSpecTypeDef::Val => "xdr.SCVal".to_string(),
+    }
+}
+
+/// Python expression converting `val`, of `type_`, to an `SCVal`. `depth` keeps the names of
+/// nested comprehensions apart.
+fn to_scval(type_: &ScSpecTypeDef, val: &str, depth: usize) -> String {
+    let (v, k) = (format!("v{depth}"), format!("k{depth}"));
+    match type_ {
+        ScSpecTypeDef::Bool => format!("scval.to_bool({val})"),
+        ScSpecTypeDef::Void => "scval.to_void()".to_string(),
+        ScSpecTypeDef::U32 => format!("scval.to_uint32({val})"),
+        ScSpecTypeDef::I32 => format!("scval.to_int32({val})"),
+        ScSpecTypeDef::U64 => format!("scval.to_uint64({val})"),
+        ScSpecTypeDef::I64 => format!("scval.to_int64({val})"),
+        ScSpecTypeDef::U128 => format!("scval.to_uint128({val})"),
+        ScSpecTypeDef::I128 => format!("scval.to_int128({val})"),
+        ScSpecTypeDef::U256 => format!("scval.to_uint256({val})"),
+        ScSpecTypeDef::I256 => format!("scval.to_int256({val})"),
+        ScSpecTypeDef::Timepoint => format!("scval.to_timepoint({val})"),
+        ScSpecTypeDef::Duration => format!("scval.to_duration({val})"),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => format!("scval.to_bytes({val})"),
+        ScSpecTypeDef::String => format!("scval.to_string({val})"),
+        ScSpecTypeDef::Symbol => format!("scval.to_symbol({val})"),
+        ScSpecTypeDef::Address => format!("scval.to_address({val})"),
+        ScSpecTypeDef::Option(option) => format!(
+            "(scval.to_void() if {val} is None else {})",
+            to_scval(&option.value_type, val, depth)
+        ),
+        ScSpecTypeDef::Result(result) => to_scval(&result.ok_type, val, depth),
+        ScSpecTypeDef::Vec(vec) => format!(
+            "scval.to_vec([{} for {v} in {val}])",
+            to_scval(&vec.element_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Set(set) => format!(
+            "scval.to_vec([{} for {v} in {val}])",
+            to_scval(&set.element_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Map(map) => format!(
+            "scval.to_map({{{}: {} for {k}, {v} in {val}.items()}})",
+            to_scval(&map.key_type, &k, depth + 1),
+            to_scval(&map.value_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "scval.to_vec([{}])",
+            tuple
+                .value_types
+                .iter()
+                .enumerate()
+                .map(|(i, type_)| to_scval(type_, &format!("{val}[{i}]"), depth))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(_) => format!("{val}.to_scval()"),
+        ScSpecTypeDef::Val | ScSpecTypeDef::Error => val.to_string(),
+    }
+}
+
+/// Python expression converting the `SCVal` `val` to `type_`.
+fn from_scval(type_: &ScSpecTypeDef, val: &str, depth: usize) -> String {
+    let (v, k) = (format!("v{depth}"), format!("k{depth}"));
+    match type_ {
+        ScSpecTypeDef::Bool => format!("scval.from_bool({val})"),
+        ScSpecTypeDef::Void => "None".to_string(),
+        ScSpecTypeDef::U32 => format!("scval.from_uint32({val})"),
+        ScSpecTypeDef::I32 => format!("scval.from_int32({val})"),
+        ScSpecTypeDef::U64 => format!("scval.from_uint64({val})"),
+        ScSpecTypeDef::I64 => format!("scval.from_int64({val})"),
+        ScSpecTypeDef::U128 => format!("scval.from_uint128({val})"),
+        ScSpecTypeDef::I128 => format!("scval.from_int128({val})"),
+        ScSpecTypeDef::U256 => format!("scval.from_uint256({val})"),
+        ScSpecTypeDef::I256 => format!("scval.from_int256({val})"),
+        ScSpecTypeDef::Timepoint => format!("scval.from_timepoint({val})"),
+        ScSpecTypeDef::Duration => format!("scval.from_duration({val})"),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => format!("scval.from_bytes({val})"),
+        ScSpecTypeDef::String => format!("scval.from_string({val}).decode()"),
+        ScSpecTypeDef::Symbol => format!("scval.from_symbol({val})"),
+        ScSpecTypeDef::Address => format!("scval.from_address({val}).address"),
+        ScSpecTypeDef::Option(option) => format!(
+            "(lambda {v}: None if {v} is None else {})(_optional({val}))",
+            from_scval(&option.value_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Result(result) => from_scval(&result.ok_type, val, depth),
+        ScSpecTypeDef::Vec(vec) => format!(
+            "[{} for {v} in scval.from_vec({val})]",
+            from_scval(&vec.element_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Set(set) => format!(
+            "[{} for {v} in scval.from_vec({val})]",
+            from_scval(&set.element_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Map(map) => format!(
+            "{{{}: {} for {k}, {v} in scval.from_map({val}).items()}}",
+            from_scval(&map.key_type, &k, depth + 1),
+            from_scval(&map.value_type, &v, depth + 1)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "(lambda {v}: ({},))(scval.from_vec({val}))",
+            tuple
+                .value_types
+                .iter()
+                .enumerate()
+                .map(|(i, type_)| from_scval(type_, &format!("{v}[{i}]"), depth + 1))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(udt) => format!("{}.from_scval({val})", udt.name.to_string_lossy()),
+        ScSpecTypeDef::Val | ScSpecTypeDef::Error => val.to_string(),
+    }
+}
+
+fn docstring(doc: &str, indent: usize) -> String {
+    if doc.is_empty() {
+        return String::new();
+    }
+    let indent = " ".repeat(indent);
+    let doc = doc
+        .replace("\"\"\"", "\\\"\\\"\\\"")
+        .replace('\n', &format!("\n{indent}"));
+    format!("{indent}\"\"\"{doc}\"\"\"\n")
+}
+
+/// Names that are Python keywords, such as a token's `from` argument, get a trailing `_`.
+fn identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "try", "while", "with", "yield",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeVec};
+
+    #[test]
+    fn nested_conversions() {
+        let type_ = ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+            key_type: Box::new(ScSpecTypeDef::Symbol),
+            value_type: Box::new(ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                    value_type: Box::new(ScSpecTypeDef::U32),
+                }))),
+            }))),
+        }));
+        assert_eq!(py_type(&type_), "Dict[str, List[Optional[int]]]");
+        assert_eq!(
+            to_scval(&type_, "x", 0),
+            "scval.to_map({scval.to_symbol(k0): scval.to_vec([(scval.to_void() if v1 is None else scval.to_uint32(v1)) for v1 in v0]) for k0, v0 in x.items()})"
+        );
+        assert_eq!(
+            from_scval(&type_, "x", 0),
+            "{scval.from_symbol(k0): [(lambda v2: None if v2 is None else scval.from_uint32(v2))(_optional(v1)) for v1 in scval.from_vec(v0)] for k0, v0 in scval.from_map(x).items()}"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+"""Invoke the contract with the Stellar Python SDK. Generated by `soroban contract bindings python`."""
+import time
+from typing import List, Optional
+
+from stellar_sdk import Keypair, SorobanServer, TransactionBuilder, xdr
+from stellar_sdk.soroban_rpc import GetTransactionStatus, SendTransactionStatus
+
+
+class InvocationError(Exception):
+    pass
+
+
+class ContractClient:
+    def __init__(
+        self,
+        contract_id: str,
+        rpc_url: str,
+        network_passphrase: str,
+        source: Keypair,
+        base_fee: int = 100,
+        timeout: int = 30,
+    ):
+        """`source` signs and pays for the transactions. A keypair without a secret key, from
+        `Keypair.from_public_key`, can only simulate them."""
+        self.contract_id = contract_id
+        self.server = SorobanServer(rpc_url)
+        self.network_passphrase = network_passphrase
+        self.source = source
+        self.base_fee = base_fee
+        self.timeout = timeout
+
+    def _invoke(
+        self, function: str, args: List[xdr.SCVal], simulate_only: bool
+    ) -> xdr.SCVal:
+        account = self.server.load_account(self.source.public_key)
+        tx = (
+            TransactionBuilder(account, self.network_passphrase, base_fee=self.base_fee)
+            .set_timeout(self.timeout)
+            .append_invoke_contract_function_op(self.contract_id, function, args)
+            .build()
+        )
+        if simulate_only or not self.source.can_sign():
+            simulation = self.server.simulate_transaction(tx)
+            if simulation.error:
+                raise InvocationError(simulation.error)
+            return xdr.SCVal.from_xdr(simulation.results[0].xdr)
+
+        tx = self.server.prepare_transaction(tx)
+        tx.sign(self.source)
+        sent = self.server.send_transaction(tx)
+        if sent.status == SendTransactionStatus.ERROR:
+            raise InvocationError(sent.error_result_xdr)
+        deadline = time.monotonic() + self.timeout
+        while True:
+            response = self.server.get_transaction(sent.hash)
+            if response.status != GetTransactionStatus.NOT_FOUND:
+                break
+            if time.monotonic() > deadline:
+                raise InvocationError(f"transaction {sent.hash} not included in a ledger")
+            time.sleep(1)
+        if response.status != GetTransactionStatus.SUCCESS:
+            raise InvocationError(response.result_xdr)
+        meta = xdr.TransactionMeta.from_xdr(response.result_meta_xdr)
+        return meta.v3.soroban_meta.return_value
+
+
+def _optional(val: xdr.SCVal) -> Optional[xdr.SCVal]:
+    return None if val.type == xdr.SCValType.SCV_VOID else val
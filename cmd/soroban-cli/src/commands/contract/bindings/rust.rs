@@ -1,15 +1,42 @@
-use std::fmt::Debug;
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use clap::{command, Parser};
-use soroban_spec_rust::{self, ToFormattedString};
+use heck::ToKebabCase;
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{ScSpecEntry, ScSpecFunctionV0};
+use soroban_spec_rust::{self, types::generate_type_ident, ToFormattedString};
 
+use super::check::{self, Generated};
 use crate::wasm;
 
+/// The soroban-sdk generated crates build against, the one this CLI is built with.
+const SOROBAN_SDK_DEPENDENCY: &str = r#"{ git = "https://github.com/stellar/rs-soroban-sdk", rev = "b1cec10b79cee3c1ecf63079b1088ce97b32c68a", features = ["testutils"] }"#;
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     wasm: wasm::Args,
+
+    /// Write a crate to this directory instead of printing the code. It has the contract's
+    /// types and a `Client` for tests with soroban-sdk, and with its `rpc` feature an
+    /// `RpcClient` that calls the contract on a network
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+
+    /// Whether to overwrite output directory if it already exists
+    #[arg(long, requires = "output_dir")]
+    overwrite: bool,
+
+    /// Instead of writing the crate, check that the one in the output directory is up to date
+    /// with the contract, and fail listing the files that are not
+    #[arg(long, requires = "output_dir", conflicts_with = "overwrite")]
+    check: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -18,10 +45,35 @@ pub enum Error {
     GenerateRustFromFile(soroban_spec_rust::GenerateFromFileError),
     #[error("format rust error: {0}")]
     FormatRust(String),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error("reading contract spec: {0}")]
+    Spec(#[from] soroban_spec::read::FromWasmError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("--output-dir cannot be a file: {0:?}")]
+    IsFile(PathBuf),
+    #[error("--output-dir already exists and you did not specify --overwrite: {0:?}")]
+    OutputDirExists(PathBuf),
+    #[error("--output-dir filepath not representable as utf-8: {0:?}")]
+    NotUtf8(OsString),
+    #[error(transparent)]
+    Check(#[from] check::Error),
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
+        if let Some(output_dir) = &self.output_dir {
+            if self.check {
+                let generated = Generated::new(output_dir)?;
+                self.write_crate(&generated.output_dir())?;
+                generated.compare(output_dir)?;
+                return Ok(());
+            }
+            self.write_crate(output_dir)?;
+            println!("Generated crate in {}", output_dir.display());
+            return Ok(());
+        }
         let wasm_path_str = self.wasm.wasm.to_string_lossy();
         let code = soroban_spec_rust::generate_from_file(&wasm_path_str, None)
             .map_err(Error::GenerateRustFromFile)?;
@@ -36,4 +88,215 @@ impl Cmd {
             }
         }
     }
+
+    fn write_crate(&self, output_dir: &Path) -> Result<(), Error> {
+        if output_dir.is_file() {
+            return Err(Error::IsFile(output_dir.to_path_buf()));
+        }
+        if output_dir.exists() {
+            if self.overwrite {
+                fs::remove_dir_all(output_dir)?;
+            } else {
+                return Err(Error::OutputDirExists(output_dir.to_path_buf()));
+            }
+        }
+        fs::create_dir_all(output_dir.join("src"))?;
+        let name = output_dir.canonicalize()?;
+        let name = name.file_name().unwrap_or_default();
+        let name = name
+            .to_str()
+            .ok_or_else(|| Error::NotUtf8(name.to_os_string()))?
+            .to_kebab_case();
+
+        let wasm = self.wasm.read()?;
+        let spec = soroban_spec::read::from_wasm(&wasm)?;
+        let sha256 = format!("{:x}", Sha256::digest(&wasm));
+        // The path is relative to the crate's manifest, where the wasm is copied
+        let code = soroban_spec_rust::generate(&spec, "contract.wasm", &sha256)
+            .to_formatted_string()
+            .map_err(|e| Error::FormatRust(e.to_string()))?;
+        fs::write(output_dir.join("contract.wasm"), &wasm)?;
+        fs::write(
+            output_dir.join("Cargo.toml"),
+            format!(
+                r#"[package]
+name = "{name}"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+soroban-sdk = {SOROBAN_SDK_DEPENDENCY}
+soroban-cli = {{ version = "={}", optional = true }}
+serde_json = {{ version = "1", optional = true }}
+
+[features]
+rpc = ["dep:soroban-cli", "dep:serde_json"]
+"#,
+                env!("CARGO_PKG_VERSION")
+            ),
+        )?;
+        fs::write(
+            output_dir.join("src/lib.rs"),
+            format!("#![no_std]\n\n{code}\n#[cfg(feature = \"rpc\")]\npub mod rpc;\n"),
+        )?;
+        fs::write(output_dir.join("src/rpc.rs"), rpc_client(&spec))?;
+        Ok(())
+    }
+}
+
+/// Source of the `rpc` module: an `RpcClient` with a method per contract function, taking and
+/// returning the same soroban-sdk types as the test `Client`, that invokes the contract with
+/// `soroban contract invoke`.
+fn rpc_client(spec: &[ScSpecEntry]) -> String {
+    let mut methods = String::new();
+    for ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    } in spec.iter().filter_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(function) => Some(function),
+        _ => None,
+    }) {
+        let name = name.to_string_lossy();
+        if name.starts_with("__") {
+            continue;
+        }
+        let params = inputs
+            .iter()
+            .map(|input| {
+                let ty = generate_type_ident(&input.type_)
+                    .to_string()
+                    .replace(' ', "");
+                format!(", {}: &{ty}", input.name.to_string_lossy())
+            })
+            .collect::<String>();
+        let args = inputs
+            .iter()
+            .map(|input| {
+                let arg = input.name.to_string_lossy();
+                format!("(\"{arg}\", self.arg({arg})?), ")
+            })
+            .collect::<String>();
+        let output = outputs.first().map_or("()".to_string(), |output| {
+            generate_type_ident(output).to_string().replace(' ', "")
+        });
+        for line in doc.to_string_lossy().lines() {
+            methods.push_str(&format!("    /// {line}\n"));
+        }
+        methods.push_str(&format!(
+            "    pub async fn {name}(&self{params}) -> Result<{output}, Error> {{
+        self.invoke(\"{name}\", vec![{args}]).await
+    }}
+
+"
+        ));
+    }
+    format!(
+        r#"//! Call the contract on a network through the soroban CLI, with the same soroban-sdk types
+//! as the test `Client`.
+extern crate std;
+
+use std::{{format, string::String, vec, vec::Vec}};
+
+use soroban_cli::{{
+    commands::{{config, contract::invoke, global}},
+    rpc::Outcome,
+    soroban_spec_tools::{{self, Spec}},
+    utils::contract_spec::ContractSpec,
+    xdr::{{self, ReadXdr as _, WriteXdr as _}},
+}};
+use soroban_sdk::{{
+    xdr::{{ReadXdr, ScVal, WriteXdr}},
+    Env, IntoVal, TryFromVal, Val,
+}};
+
+#[derive(Debug)]
+pub enum Error {{
+    Invoke(invoke::Error),
+    Convert(String),
+}}
+
+pub struct RpcClient {{
+    /// Contract ID or alias
+    pub contract_id: String,
+    /// Network, source account and config directory, as given to `soroban contract invoke`
+    pub config: config::Args,
+    /// Whether to submit the transaction or only simulate it, e.g. for read-only functions
+    pub send: invoke::SendMode,
+    env: Env,
+}}
+
+impl RpcClient {{
+    pub fn new(contract_id: &str, config: config::Args) -> Self {{
+        Self {{
+            contract_id: contract_id.into(),
+            config,
+            send: invoke::SendMode::default(),
+            env: Env::default(),
+        }}
+    }}
+
+{methods}    fn arg<T: IntoVal<Env, Val>>(&self, arg: &T) -> Result<xdr::ScVal, Error> {{
+        let val: Val = arg.into_val(&self.env);
+        let val = ScVal::try_from_val(&self.env, &val).map_err(|e| convert(&e))?;
+        let val = val.to_xdr_base64().map_err(|e| convert(&e))?;
+        xdr::ScVal::from_xdr_base64(val).map_err(|e| convert(&e))
+    }}
+
+    async fn invoke<T: TryFromVal<Env, Val>>(
+        &self,
+        function: &str,
+        args: Vec<(&str, xdr::ScVal)>,
+    ) -> Result<T, Error> {{
+        let mut slop = vec![function.into()];
+        for (name, val) in args {{
+            let json = soroban_spec_tools::to_json(&val).map_err(|e| convert(&e))?;
+            slop.push(format!("--{{name}}").into());
+            slop.push(json.to_string().into());
+        }}
+        let result = invoke::Cmd {{
+            contract_id: self.contract_id.clone(),
+            send: self.send,
+            slop,
+            config: self.config.clone(),
+            ..Default::default()
+        }}
+        .invoke(&global::Args::default())
+        .await
+        .map_err(Error::Invoke)?;
+        let Outcome::Done(result) = result else {{
+            unreachable!("the default global args don't ask for a dry run")
+        }};
+        // Simulations print the return value in an object with their events, fees and footprint
+        let result = if self.send == invoke::SendMode::No {{
+            let simulation: serde_json::Value =
+                serde_json::from_str(&result).map_err(|e| convert(&e))?;
+            simulation["result"].to_string()
+        }} else {{
+            result
+        }};
+        let spec = Spec(Some(
+            ContractSpec::new(crate::WASM).map_err(|e| convert(&e))?.spec,
+        ));
+        let function = spec.find_function(function).map_err(|e| convert(&e))?;
+        let val = match function.outputs.first() {{
+            Some(output) => spec
+                .from_string(&result, output)
+                .map_err(|e| convert(&e))?,
+            None => xdr::ScVal::Void,
+        }};
+        let val = val.to_xdr_base64().map_err(|e| convert(&e))?;
+        let val = ScVal::from_xdr_base64(val).map_err(|e| convert(&e))?;
+        let val = Val::try_from_val(&self.env, &val).map_err(|e| convert(&e))?;
+        T::try_from_val(&self.env, &val).map_err(|e| convert(&e))
+    }}
+}}
+
+fn convert(error: &impl core::fmt::Debug) -> Error {{
+    Error::Convert(format!("{{error:?}}"))
+}}
+"#
+    )
 }
@@ -0,0 +1,512 @@
+use clap::{command, Parser};
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use soroban_env_host::xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use super::{
+    convert::{self, Syntax},
+    package::{self, Files, Package},
+};
+
+const CLIENT: &str = include_str!("swift/ContractClient.swift");
+
+const SWIFT: Syntax = Syntax {
+    closure: |param, body| format!("{{ {param} in try {body} }}"),
+    list: |elements| format!("[{}]", elements.join(", ")),
+    index: |list, i| format!("{list}[{i}]"),
+    tuple_get: |tuple, i| format!("{tuple}.{i}"),
+    tuple_new: |elements| format!("({})", elements.join(", ")),
+    null: "()",
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    package: package::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Package(#[from] package::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        self.package.run(files).await?;
+        Ok(())
+    }
+}
+
+fn files(package: &Package) -> Files {
+    let module = package.name.to_upper_camel_case();
+    vec![
+        (
+            "Package.swift".to_string(),
+            format!(
+                r#"// swift-tools-version:5.7
+import PackageDescription
+
+let package = Package(
+    name: "{module}",
+    platforms: [.macOS(.v12), .iOS(.v15)],
+    products: [.library(name: "{module}", targets: ["{module}"])],
+    dependencies: [
+        .package(url: "https://github.com/Soneso/stellar-ios-mac-sdk.git", from: "2.5.0"),
+    ],
+    targets: [
+        .target(
+            name: "{module}",
+            dependencies: [.product(name: "stellarsdk", package: "stellar-ios-mac-sdk")]
+        ),
+    ]
+)
+"#
+            ),
+        ),
+        (
+            format!("Sources/{module}/ContractClient.swift"),
+            CLIENT.to_string(),
+        ),
+        (format!("Sources/{module}/{module}.swift"), library(package)),
+    ]
+}
+
+/// The module's types, `Codable` and converting to and from `SCValXDR`s, and a `Client` with an
+/// async method per function.
+fn library(package: &Package) -> String {
+    let Package {
+        contract_id,
+        rpc_url,
+        network_passphrase,
+        spec,
+        ..
+    } = package;
+    let mut out = format!(
+        r#"// Client for contract {contract_id}. Generated by `soroban contract bindings swift`.
+import Foundation
+import stellarsdk
+
+public let defaultContractId = "{contract_id}"
+public let defaultRpcUrl = "{rpc_url}"
+public let defaultNetworkPassphrase = "{network_passphrase}"
+"#
+    );
+    for entry in spec {
+        out.push_str(&match entry {
+            ScSpecEntry::UdtStructV0(udt) => struct_type(udt),
+            ScSpecEntry::UdtUnionV0(udt) => union_enum(udt),
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                doc, name, cases, ..
+            }) => int_enum(
+                &doc.to_string_lossy(),
+                &name.to_string_lossy(),
+                cases
+                    .iter()
+                    .map(|case| (case.name.to_string_lossy(), case.value)),
+            ),
+            ScSpecEntry::FunctionV0(_) => continue,
+        });
+    }
+    out.push_str(
+        r#"
+public class Client: ContractClient {
+    public init(source: KeyPair, contractId: String = defaultContractId,
+                rpcUrl: String = defaultRpcUrl,
+                networkPassphrase: String = defaultNetworkPassphrase, timeout: Int = 30) {
+        super.init(contractId: contractId, rpcUrl: rpcUrl,
+                   networkPassphrase: networkPassphrase, source: source, timeout: timeout)
+    }
+"#,
+    );
+    for function in package.functions() {
+        out.push_str(&method(function));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn struct_type(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let fields = fields
+        .iter()
+        .map(|field| (field.name.to_string_lossy(), &field.type_))
+        .collect::<Vec<_>>();
+    // Tuple structs have fields named by position, and are stored as a vec
+    let is_tuple = fields
+        .iter()
+        .all(|(name, _)| name.chars().all(|c| c.is_ascii_digit()));
+    let attr = |field: &str| {
+        if is_tuple {
+            format!("f{field}")
+        } else {
+            identifier(&field.to_lower_camel_case())
+        }
+    };
+    let mut out = format!(
+        "\n{}public struct {name}: Codable {{\n",
+        comment(&doc.to_string_lossy(), 0)
+    );
+    for (field, type_) in &fields {
+        out.push_str(&format!(
+            "    public var {}: {}\n",
+            attr(field),
+            swift_type(type_)
+        ));
+    }
+    out.push_str(&format!(
+        "\n    public init({}) {{\n{}    }}\n",
+        fields
+            .iter()
+            .map(|(field, type_)| format!("{}: {}", attr(field), swift_type(type_)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        fields
+            .iter()
+            .map(|(field, _)| format!("        self.{0} = {0}\n", attr(field)))
+            .collect::<String>()
+    ));
+    let (to, from) = if is_tuple {
+        (
+            format!(
+                "try toTuple([{}])",
+                fields
+                    .iter()
+                    .map(|(field, type_)| to_scval(type_, &attr(field)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "try fromTuple(val, {{ vals in {name}({}) }})",
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (field, type_))| format!(
+                        "{}: try {}",
+                        attr(field),
+                        from_scval(type_, &format!("vals[{i}]"))
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    } else {
+        (
+            format!(
+                "try toStruct([{}])",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!("\"{field}\": {}", to_scval(type_, &attr(field))))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "let vals = try fromStruct(val)\n        return {name}({})",
+                fields
+                    .iter()
+                    .map(|(field, type_)| format!(
+                        "{}: try {}",
+                        attr(field),
+                        from_scval(type_, &format!("field(vals, \"{field}\")"))
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    };
+    out.push_str(&format!(
+        r#"
+    public func toScVal() throws -> SCValXDR {{
+        {to}
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        {from}
+    }}
+}}
+"#
+    ));
+    out
+}
+
+/// Unions are enums with the values of each case as associated values.
+fn union_enum(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let mut decl = String::new();
+    let mut to = String::new();
+    let mut from = String::new();
+    for case in cases.iter() {
+        let (tag, types) = match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => (case.name.to_string_lossy(), vec![]),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                (case.name.to_string_lossy(), case.type_.to_vec())
+            }
+        };
+        let case = identifier(&tag.to_lower_camel_case());
+        if types.is_empty() {
+            decl.push_str(&format!("    case {case}\n"));
+            to.push_str(&format!(
+                "        case .{case}: return try toTuple([toSymbol(\"{tag}\")])\n"
+            ));
+            from.push_str(&format!("            case \"{tag}\": return .{case}\n"));
+            continue;
+        }
+        let bindings = (0..types.len())
+            .map(|i| format!("let v{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let values = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| format!(", {}", to_scval(type_, &format!("v{i}"))))
+            .collect::<String>();
+        let args = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_)| format!("try {}", from_scval(type_, &format!("vals[{}]", i + 1))))
+            .collect::<Vec<_>>()
+            .join(", ");
+        decl.push_str(&format!(
+            "    case {case}({})\n",
+            types.iter().map(swift_type).collect::<Vec<_>>().join(", ")
+        ));
+        to.push_str(&format!(
+            "        case .{case}({bindings}): return try toTuple([toSymbol(\"{tag}\"){values}])\n"
+        ));
+        from.push_str(&format!(
+            "            case \"{tag}\": return .{case}({args})\n"
+        ));
+    }
+    format!(
+        r#"
+{}public enum {name}: Codable {{
+{decl}
+    public func toScVal() throws -> SCValXDR {{
+        switch self {{
+{to}        }}
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        try fromTuple(val, {{ vals in
+            switch try fromSymbol(vals[0]) {{
+{from}            case let tag: throw InvocationError("unknown case \(tag)")
+            }}
+        }})
+    }}
+}}
+"#,
+        comment(&doc.to_string_lossy(), 0)
+    )
+}
+
+fn int_enum(doc: &str, name: &str, cases: impl Iterator<Item = (String, u32)>) -> String {
+    let cases = cases
+        .map(|(case, value)| {
+            format!(
+                "    case {} = {value}\n",
+                identifier(&case.to_lower_camel_case())
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"
+{}public enum {name}: UInt32, Codable {{
+{cases}
+    public func toScVal() throws -> SCValXDR {{
+        try toU32(rawValue)
+    }}
+
+    public static func fromScVal(_ val: SCValXDR) throws -> {name} {{
+        let value = try fromU32(val)
+        guard let case_ = {name}(rawValue: value) else {{
+            throw InvocationError("unknown case \(value)")
+        }}
+        return case_
+    }}
+}}
+"#,
+        comment(doc, 0)
+    )
+}
+
+fn method(
+    ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    }: &ScSpecFunctionV0,
+) -> String {
+    let name = name.to_string_lossy();
+    let params = inputs
+        .iter()
+        .map(|input| {
+            format!(
+                "{}: {}, ",
+                identifier(&input.name.to_string_lossy().to_lower_camel_case()),
+                swift_type(&input.type_)
+            )
+        })
+        .collect::<String>();
+    let args = inputs
+        .iter()
+        .map(|input| {
+            to_scval(
+                &input.type_,
+                &identifier(&input.name.to_string_lossy().to_lower_camel_case()),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let invoke = format!("try await invoke(\"{name}\", [{args}], simulateOnly: simulateOnly)");
+    let (output, body) = match outputs.first() {
+        Some(output) => (
+            format!(" -> {}", swift_type(output)),
+            format!(
+                "let result = {invoke}\n        return try {}",
+                from_scval(output, "result")
+            ),
+        ),
+        None => (String::new(), format!("_ = {invoke}")),
+    };
+    format!(
+        r#"
+{}    public func {}({params}simulateOnly: Bool = false) async throws{output} {{
+        {body}
+    }}
+"#,
+        comment(&doc.to_string_lossy(), 4),
+        identifier(&name.to_lower_camel_case()),
+    )
+}
+
+fn swift_type(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Bool => "Bool".to_string(),
+        ScSpecTypeDef::Void => "Void".to_string(),
+        ScSpecTypeDef::U32 => "UInt32".to_string(),
+        ScSpecTypeDef::I32 => "Int32".to_string(),
+        ScSpecTypeDef::U64 | ScSpecTypeDef::Timepoint | ScSpecTypeDef::Duration => {
+            "UInt64".to_string()
+        }
+        ScSpecTypeDef::I64 => "Int64".to_string(),
+        ScSpecTypeDef::U128 => "UInt128PartsXDR".to_string(),
+        ScSpecTypeDef::I128 => "Int128PartsXDR".to_string(),
+        ScSpecTypeDef::U256 => "UInt256PartsXDR".to_string(),
+        ScSpecTypeDef::I256 => "Int256PartsXDR".to_string(),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "Data".to_string(),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => {
+            "String".to_string()
+        }
+        ScSpecTypeDef::Option(option) => format!("{}?", swift_type(&option.value_type)),
+        ScSpecTypeDef::Result(result) => swift_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("[{}]", swift_type(&vec.element_type)),
+        ScSpecTypeDef::Set(set) => format!("[{}]", swift_type(&set.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "[{}: {}]",
+            swift_type(&map.key_type),
+            swift_type(&map.value_type)
+        ),
+        ScSpecTypeDef::Tuple(tuple) => format!(
+            "({})",
+            tuple
+                .value_types
+                .iter()
+                .map(swift_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string_lossy(),
+        ScSpecTypeDef::Val | ScSpecTypeDef::Error => "SCValXDR".to_string(),
+    }
+}
+
+fn to_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::to_scval(&SWIFT, type_, val, 0)
+}
+
+fn from_scval(type_: &ScSpecTypeDef, val: &str) -> String {
+    convert::from_scval(&SWIFT, type_, val, 0)
+}
+
+fn comment(doc: &str, indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    doc.lines()
+        .map(|line| format!("{indent}/// {line}\n"))
+        .collect()
+}
+
+/// Names that are Swift keywords, such as `default`, are quoted with backticks.
+fn identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "default",
+        "defer",
+        "do",
+        "else",
+        "enum",
+        "extension",
+        "false",
+        "for",
+        "func",
+        "guard",
+        "if",
+        "import",
+        "in",
+        "init",
+        "inout",
+        "internal",
+        "is",
+        "let",
+        "nil",
+        "operator",
+        "private",
+        "protocol",
+        "public",
+        "repeat",
+        "return",
+        "self",
+        "static",
+        "struct",
+        "subscript",
+        "super",
+        "switch",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "var",
+        "where",
+        "while",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_string()
+    }
+}
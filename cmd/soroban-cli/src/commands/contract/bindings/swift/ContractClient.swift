@@ -0,0 +1,240 @@
+// Invoke the contract with the Stellar iOS/macOS SDK. Generated by `soroban contract bindings swift`.
+import Foundation
+import stellarsdk
+
+public struct InvocationError: Error, CustomStringConvertible {
+    public let message: String
+
+    public init(_ message: String) {
+        self.message = message
+    }
+
+    public var description: String { "InvocationError: \(message)" }
+}
+
+open class ContractClient {
+    public let contractId: String
+    public let server: SorobanServer
+    public let network: Network
+
+    /// Signs and pays for the transactions. A keypair without a secret seed, from
+    /// `KeyPair(accountId:)`, can only simulate them.
+    public let source: KeyPair
+
+    /// Seconds to wait for a transaction to be included in a ledger.
+    public let timeout: Int
+
+    public init(contractId: String, rpcUrl: String, networkPassphrase: String, source: KeyPair,
+                timeout: Int = 30) {
+        self.contractId = contractId
+        self.server = SorobanServer(endpoint: rpcUrl)
+        self.network = Network.custom(passphrase: networkPassphrase)
+        self.source = source
+        self.timeout = timeout
+    }
+
+    public func invoke(_ function: String, _ args: [SCValXDR], simulateOnly: Bool = false)
+        async throws -> SCValXDR
+    {
+        guard case .success(let account) = await server.getAccount(accountId: source.accountId)
+        else {
+            throw InvocationError("account \(source.accountId) not found")
+        }
+        let operation = try InvokeHostFunctionOperation.forInvokingContract(
+            contractId: contractId, functionName: function, functionArguments: args)
+        let tx = try Transaction(sourceAccount: account, operations: [operation], memo: Memo.none)
+        guard
+            case .success(let simulation) = await server.simulateTransaction(
+                simulateTxRequest: SimulateTransactionRequest(transaction: tx))
+        else {
+            throw InvocationError("simulation of \(function) failed")
+        }
+        if let error = simulation.error {
+            throw InvocationError(error)
+        }
+        if simulateOnly || source.privateKey == nil {
+            guard let value = simulation.results?.first?.value else {
+                throw InvocationError("simulation of \(function) returned no value")
+            }
+            return value
+        }
+
+        tx.setSorobanTransactionData(data: simulation.transactionData!)
+        tx.addResourceFee(resourceFee: simulation.minResourceFee!)
+        tx.setSorobanAuth(auth: simulation.sorobanAuth)
+        try tx.sign(keyPair: source, network: network)
+        guard case .success(let sent) = await server.sendTransaction(transaction: tx) else {
+            throw InvocationError("transaction rejected")
+        }
+        if sent.status == SendTransactionResponse.STATUS_ERROR {
+            throw InvocationError(sent.errorResultXdr ?? "transaction rejected")
+        }
+        for _ in 0..<timeout {
+            if case .success(let response) = await server.getTransaction(
+                transactionHash: sent.transactionId)
+            {
+                if response.status == GetTransactionResponse.STATUS_SUCCESS {
+                    return response.resultValue ?? .void
+                }
+                if response.status == GetTransactionResponse.STATUS_FAILED {
+                    throw InvocationError(response.resultXdr ?? "transaction failed")
+                }
+            }
+            try await Task.sleep(nanoseconds: 1_000_000_000)
+        }
+        throw InvocationError("transaction \(sent.transactionId) not included in a ledger")
+    }
+}
+
+private func unexpected(_ val: SCValXDR, _ type: String) -> InvocationError {
+    InvocationError("expected \(type), got \(val)")
+}
+
+public func toBool(_ v: Bool) throws -> SCValXDR { .bool(v) }
+public func fromBool(_ val: SCValXDR) throws -> Bool {
+    guard case .bool(let v) = val else { throw unexpected(val, "bool") }
+    return v
+}
+
+public func toVoid() throws -> SCValXDR { .void }
+
+public func toU32(_ v: UInt32) throws -> SCValXDR { .u32(v) }
+public func fromU32(_ val: SCValXDR) throws -> UInt32 {
+    guard case .u32(let v) = val else { throw unexpected(val, "u32") }
+    return v
+}
+
+public func toI32(_ v: Int32) throws -> SCValXDR { .i32(v) }
+public func fromI32(_ val: SCValXDR) throws -> Int32 {
+    guard case .i32(let v) = val else { throw unexpected(val, "i32") }
+    return v
+}
+
+public func toU64(_ v: UInt64) throws -> SCValXDR { .u64(v) }
+public func fromU64(_ val: SCValXDR) throws -> UInt64 {
+    guard case .u64(let v) = val else { throw unexpected(val, "u64") }
+    return v
+}
+
+public func toI64(_ v: Int64) throws -> SCValXDR { .i64(v) }
+public func fromI64(_ val: SCValXDR) throws -> Int64 {
+    guard case .i64(let v) = val else { throw unexpected(val, "i64") }
+    return v
+}
+
+public func toTimepoint(_ v: UInt64) throws -> SCValXDR { .timepoint(v) }
+public func fromTimepoint(_ val: SCValXDR) throws -> UInt64 {
+    guard case .timepoint(let v) = val else { throw unexpected(val, "timepoint") }
+    return v
+}
+
+public func toDuration(_ v: UInt64) throws -> SCValXDR { .duration(v) }
+public func fromDuration(_ val: SCValXDR) throws -> UInt64 {
+    guard case .duration(let v) = val else { throw unexpected(val, "duration") }
+    return v
+}
+
+public func toU128(_ v: UInt128PartsXDR) throws -> SCValXDR { .u128(v) }
+public func fromU128(_ val: SCValXDR) throws -> UInt128PartsXDR {
+    guard case .u128(let v) = val else { throw unexpected(val, "u128") }
+    return v
+}
+
+public func toI128(_ v: Int128PartsXDR) throws -> SCValXDR { .i128(v) }
+public func fromI128(_ val: SCValXDR) throws -> Int128PartsXDR {
+    guard case .i128(let v) = val else { throw unexpected(val, "i128") }
+    return v
+}
+
+public func toU256(_ v: UInt256PartsXDR) throws -> SCValXDR { .u256(v) }
+public func fromU256(_ val: SCValXDR) throws -> UInt256PartsXDR {
+    guard case .u256(let v) = val else { throw unexpected(val, "u256") }
+    return v
+}
+
+public func toI256(_ v: Int256PartsXDR) throws -> SCValXDR { .i256(v) }
+public func fromI256(_ val: SCValXDR) throws -> Int256PartsXDR {
+    guard case .i256(let v) = val else { throw unexpected(val, "i256") }
+    return v
+}
+
+public func toBytes(_ v: Data) throws -> SCValXDR { .bytes(v) }
+public func fromBytes(_ val: SCValXDR) throws -> Data {
+    guard case .bytes(let v) = val else { throw unexpected(val, "bytes") }
+    return v
+}
+
+public func toStr(_ v: String) throws -> SCValXDR { .string(v) }
+public func fromStr(_ val: SCValXDR) throws -> String {
+    guard case .string(let v) = val else { throw unexpected(val, "string") }
+    return v
+}
+
+public func toSymbol(_ v: String) throws -> SCValXDR { .symbol(v) }
+public func fromSymbol(_ val: SCValXDR) throws -> String {
+    guard case .symbol(let v) = val else { throw unexpected(val, "symbol") }
+    return v
+}
+
+public func toAddress(_ v: String) throws -> SCValXDR {
+    .address(v.hasPrefix("C") ? try SCAddressXDR(contractId: v) : try SCAddressXDR(accountId: v))
+}
+public func fromAddress(_ val: SCValXDR) throws -> String {
+    guard case .address(let address) = val else { throw unexpected(val, "address") }
+    if let accountId = address.accountId {
+        return accountId
+    }
+    return try address.contractId!.encodeContractIdHex()
+}
+
+public func toOption<T>(_ v: T?, _ f: (T) throws -> SCValXDR) throws -> SCValXDR {
+    try v.map(f) ?? .void
+}
+public func fromOption<T>(_ val: SCValXDR, _ f: (SCValXDR) throws -> T) throws -> T? {
+    if case .void = val { return nil }
+    return try f(val)
+}
+
+public func toVec<T>(_ v: [T], _ f: (T) throws -> SCValXDR) throws -> SCValXDR {
+    .vec(try v.map(f))
+}
+public func fromVec<T>(_ val: SCValXDR, _ f: (SCValXDR) throws -> T) throws -> [T] {
+    guard case .vec(let vals?) = val else { throw unexpected(val, "vec") }
+    return try vals.map(f)
+}
+
+public func toMap<K, V>(
+    _ v: [K: V], _ kf: (K) throws -> SCValXDR, _ vf: (V) throws -> SCValXDR
+) throws -> SCValXDR {
+    .map(try v.map { try SCMapEntryXDR(key: kf($0.key), val: vf($0.value)) })
+}
+public func fromMap<K: Hashable, V>(
+    _ val: SCValXDR, _ kf: (SCValXDR) throws -> K, _ vf: (SCValXDR) throws -> V
+) throws -> [K: V] {
+    guard case .map(let entries?) = val else { throw unexpected(val, "map") }
+    return Dictionary(uniqueKeysWithValues: try entries.map { (try kf($0.key), try vf($0.val)) })
+}
+
+/// Tuples are vecs of their elements.
+public func toTuple(_ vals: [SCValXDR]) throws -> SCValXDR { .vec(vals) }
+public func fromTuple<T>(_ val: SCValXDR, _ f: ([SCValXDR]) throws -> T) throws -> T {
+    guard case .vec(let vals?) = val else { throw unexpected(val, "tuple") }
+    return try f(vals)
+}
+
+/// Structs are maps keyed by field name, sorted as the host expects.
+public func toStruct(_ fields: [String: SCValXDR]) throws -> SCValXDR {
+    .map(fields.keys.sorted().map { SCMapEntryXDR(key: .symbol($0), val: fields[$0]!) })
+}
+public func fromStruct(_ val: SCValXDR) throws -> [String: SCValXDR] {
+    guard case .map(let entries?) = val else { throw unexpected(val, "struct") }
+    var fields: [String: SCValXDR] = [:]
+    for entry in entries {
+        fields[try fromSymbol(entry.key)] = entry.val
+    }
+    return fields
+}
+public func field(_ fields: [String: SCValXDR], _ name: String) throws -> SCValXDR {
+    guard let val = fields[name] else { throw InvocationError("missing field \(name)") }
+    return val
+}
@@ -1,8 +1,18 @@
-use std::{ffi::OsString, fmt::Debug, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 
 use clap::{command, Parser};
-use soroban_spec_typescript::{self as typescript, boilerplate::Project};
+use soroban_env_host::xdr::ScSpecEntry;
+use soroban_spec_typescript::{
+    self as typescript,
+    boilerplate::{Project, WorkspaceContract},
+};
 
+use super::check::{self, Generated};
 use crate::wasm;
 use crate::{
     commands::{
@@ -30,9 +40,33 @@ pub struct Cmd {
     #[arg(long)]
     overwrite: bool,
 
-    /// The contract ID/address on the network
-    #[arg(long, alias = "id")]
-    contract_id: String,
+    /// Instead of writing the bindings, check that those in the output directory are up to
+    /// date with the contract, and fail listing the files that are not
+    #[arg(long, conflicts_with = "overwrite")]
+    check: bool,
+
+    /// The contract ID/address on the network, or an alias saved for it
+    #[arg(long, alias = "id", required_unless_present = "manifest")]
+    contract_id: Option<String>,
+
+    /// Generate one package with a client for each contract listed in this TOML file, under
+    /// `[contracts.<name>]` with an `id` and optionally a `wasm`, sharing the types they have in
+    /// common
+    #[arg(long, conflicts_with_all = ["wasm", "contract_id", "tests"])]
+    manifest: Option<PathBuf>,
+
+    /// Also emit a vitest suite, run with `npm test`, that simulates each function taking no
+    /// arguments against the network
+    #[arg(long)]
+    tests: bool,
+
+    /// Only write the files, without running `npm install`, nor `npm run build`
+    #[arg(long)]
+    no_install: bool,
+
+    /// Run `npm install` but not `npm run build`
+    #[arg(long)]
+    no_build: bool,
 
     #[command(flatten)]
     locator: locator::Args,
@@ -66,16 +100,120 @@ pub enum Error {
     Fetch(#[from] fetch::Error),
     #[error(transparent)]
     Spec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error("reading manifest {0:?}: {1}")]
+    Manifest(PathBuf, toml::de::Error),
+    #[error(transparent)]
+    Check(#[from] check::Error),
+}
+
+/// Contracts to generate a workspace package for with `--manifest`, e.g.
+///
+/// ```toml
+/// [contracts.token]
+/// # A contract ID, or an alias saved for it on the network
+/// id = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+/// # Optional, the spec is otherwise fetched from the network. Relative to the manifest.
+/// wasm = "target/wasm32-unknown-unknown/release/token.wasm"
+/// ```
+#[derive(serde::Deserialize)]
+struct Manifest {
+    contracts: BTreeMap<String, ManifestContract>,
+}
+
+#[derive(serde::Deserialize)]
+struct ManifestContract {
+    id: String,
+    wasm: Option<PathBuf>,
 }
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
-        let spec = if let Some(wasm) = &self.wasm {
-            let wasm: wasm::Args = wasm.into();
-            wasm.parse().unwrap().spec
+        if !self.check {
+            return self.generate().await;
+        }
+        let generated = Generated::new(&self.output_dir)?;
+        Self {
+            output_dir: generated.output_dir(),
+            overwrite: true,
+            check: false,
+            no_install: true,
+            ..self.clone()
+        }
+        .generate()
+        .await?;
+        generated.compare(&self.output_dir)?;
+        Ok(())
+    }
+
+    async fn generate(&self) -> Result<(), Error> {
+        if let Some(manifest) = &self.manifest {
+            return self.run_workspace(manifest).await;
+        }
+        let contract_id =
+            self.resolve_contract_id(self.contract_id.as_deref().unwrap_or_default())?;
+        let spec = self.spec(self.wasm.as_deref(), &contract_id).await?;
+        self.create_output_dir()?;
+        let p: Project = self.output_dir.clone().try_into()?;
+        let (rpc_url, network_passphrase) = self.network();
+        let contract_name = self.package_name()?;
+        p.init(
+            &contract_name,
+            &contract_id,
+            &rpc_url,
+            &network_passphrase,
+            &spec,
+        )?;
+        if self.tests {
+            p.add_tests(&contract_name, &spec)?;
+        }
+        self.install()
+    }
+
+    async fn run_workspace(&self, manifest: &Path) -> Result<(), Error> {
+        let Manifest { contracts } = toml::from_str(&std::fs::read_to_string(manifest)?)
+            .map_err(|e| Error::Manifest(manifest.to_path_buf(), e))?;
+        let base = manifest.parent().unwrap_or(Path::new(""));
+        let mut specs = Vec::with_capacity(contracts.len());
+        for (name, contract) in &contracts {
+            let wasm = contract.wasm.as_ref().map(|wasm| base.join(wasm));
+            let contract_id = self.resolve_contract_id(&contract.id)?;
+            let spec = self.spec(wasm.as_deref(), &contract_id).await?;
+            specs.push((name, contract_id, spec));
+        }
+        self.create_output_dir()?;
+        let p: Project = self.output_dir.clone().try_into()?;
+        let (rpc_url, network_passphrase) = self.network();
+        let workspace = specs
+            .iter()
+            .map(|(name, contract_id, spec)| WorkspaceContract {
+                name: name.as_str(),
+                contract_id,
+                spec,
+            })
+            .collect::<Vec<_>>();
+        p.init_workspace(
+            &self.package_name()?,
+            &workspace,
+            &rpc_url,
+            &network_passphrase,
+        )?;
+        self.install()
+    }
+
+    async fn spec(
+        &self,
+        wasm: Option<&Path>,
+        contract_id: &str,
+    ) -> Result<Vec<ScSpecEntry>, Error> {
+        Ok(if let Some(wasm) = wasm {
+            let wasm: wasm::Args = (&wasm.to_path_buf()).into();
+            wasm.parse()?.spec
         } else {
             let fetch = contract::fetch::Cmd {
-                contract_id: self.contract_id.clone(),
+                contract_id: Some(contract_id.to_string()),
+                wasm_hash: None,
                 out_file: None,
                 locator: self.locator.clone(),
                 network: self.network.clone(),
@@ -83,7 +221,10 @@ impl Cmd {
             };
             let bytes = fetch.get_bytes().await?;
             ContractSpec::new(&bytes)?.spec
-        };
+        })
+    }
+
+    fn create_output_dir(&self) -> Result<(), Error> {
         if self.output_dir.is_file() {
             return Err(Error::IsFile(self.output_dir.clone()));
         }
@@ -95,7 +236,18 @@ impl Cmd {
             }
         }
         std::fs::create_dir_all(&self.output_dir)?;
-        let p: Project = self.output_dir.clone().try_into()?;
+        Ok(())
+    }
+
+    /// Resolve a contract alias saved for the network the package calls.
+    fn resolve_contract_id(&self, alias_or_id: &str) -> Result<String, Error> {
+        let (_, network_passphrase) = self.network();
+        Ok(self
+            .locator
+            .resolve_contract_id(alias_or_id, &network_passphrase)?)
+    }
+
+    fn network(&self) -> (String, String) {
         let Network {
             rpc_url,
             network_passphrase,
@@ -105,23 +257,30 @@ impl Cmd {
             .get(&self.locator)
             .ok()
             .unwrap_or_else(Network::futurenet);
+        (rpc_url, network_passphrase)
+    }
+
+    fn package_name(&self) -> Result<String, Error> {
         let absolute_path = self.output_dir.canonicalize()?;
-        let file_name = absolute_path.file_name().unwrap();
-        let contract_name = &file_name
+        let file_name = absolute_path.file_name().unwrap_or_default();
+        Ok(file_name
             .to_str()
-            .ok_or_else(|| Error::NotUtf8(file_name.to_os_string()))?;
-        p.init(
-            contract_name,
-            &self.contract_id,
-            &rpc_url,
-            &network_passphrase,
-            &spec,
-        )?;
+            .ok_or_else(|| Error::NotUtf8(file_name.to_os_string()))?
+            .to_string())
+    }
+
+    fn install(&self) -> Result<(), Error> {
+        if self.no_install {
+            return Ok(());
+        }
         std::process::Command::new("npm")
             .arg("install")
             .current_dir(&self.output_dir)
             .spawn()?
             .wait()?;
+        if self.no_build {
+            return Ok(());
+        }
         std::process::Command::new("npm")
             .arg("run")
             .arg("build")
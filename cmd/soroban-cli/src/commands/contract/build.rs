@@ -6,7 +6,7 @@ use std::{
     ffi::OsStr,
     fmt::Debug,
     fs, io,
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
 };
 
@@ -143,12 +143,8 @@ impl Cmd {
                 if let Some(out_dir) = &self.out_dir {
                     fs::create_dir_all(out_dir).map_err(Error::CreatingOutDir)?;
 
-                    let file = format!("{}.wasm", p.name.replace('-', "_"));
-                    let target_file_path = Path::new(target_dir)
-                        .join("wasm32-unknown-unknown")
-                        .join(&self.profile)
-                        .join(&file);
-                    let out_file_path = Path::new(out_dir).join(&file);
+                    let target_file_path = self.target_file(target_dir.as_std_path(), &p);
+                    let out_file_path = Path::new(out_dir).join(wasm_file_name(&p));
                     fs::copy(target_file_path, out_file_path).map_err(Error::CopyingWasmFile)?;
                 }
             }
@@ -157,6 +153,24 @@ impl Cmd {
         Ok(())
     }
 
+    /// The paths in the cargo target directory of the wasm files that the packages build to.
+    pub fn wasm_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let metadata = self.metadata()?;
+        let target_dir = metadata.target_directory.as_std_path();
+        Ok(self
+            .packages(&metadata)
+            .iter()
+            .map(|p| self.target_file(target_dir, p))
+            .collect())
+    }
+
+    fn target_file(&self, target_dir: &Path, p: &Package) -> PathBuf {
+        target_dir
+            .join("wasm32-unknown-unknown")
+            .join(&self.profile)
+            .join(wasm_file_name(p))
+    }
+
     fn features(&self) -> Option<Vec<String>> {
         self.features
             .as_ref()
@@ -192,3 +206,7 @@ impl Cmd {
         cmd.exec()
     }
 }
+
+fn wasm_file_name(p: &Package) -> String {
+    format!("{}.wasm", p.name.replace('-', "_"))
+}
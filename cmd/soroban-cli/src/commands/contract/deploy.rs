@@ -1,3 +1,5 @@
+pub mod manifest;
+
 use std::array::TryFromSliceError;
 use std::fmt::Debug;
 use std::num::ParseIntError;
@@ -17,8 +19,12 @@ use soroban_env_host::{
 };
 
 use crate::{
-    commands::{config, contract::install, HEADING_RPC, HEADING_SANDBOX},
-    rpc::{self, Client},
+    commands::{
+        config,
+        contract::{install, invoke},
+        global, history, HEADING_RPC, HEADING_SANDBOX,
+    },
+    rpc::{self, Client, Outcome},
     utils, wasm,
 };
 
@@ -26,7 +32,7 @@ use crate::{
 #[command(group(
     clap::ArgGroup::new("wasm_src")
         .required(true)
-        .args(&["wasm", "wasm_hash"]),
+        .args(&["wasm", "wasm_hash", "manifest"]),
 ))]
 #[group(skip)]
 pub struct Cmd {
@@ -38,6 +44,16 @@ pub struct Cmd {
     #[arg(long = "wasm-hash", conflicts_with = "wasm", group = "wasm_src")]
     wasm_hash: Option<String>,
 
+    /// TOML manifest listing several contracts to install, deploy and initialize in
+    /// dependency order. Prints the name and ID of each deployed contract.
+    #[arg(long, group = "wasm_src", conflicts_with_all = ["contract_id", "salt", "alias"])]
+    manifest: Option<std::path::PathBuf>,
+
+    /// Refuse to deploy a manifest whose transactions are estimated to cost more than this in
+    /// total, in stroops. Estimates come from simulating each transaction before submitting it
+    #[arg(long, requires = "manifest", help_heading = HEADING_RPC)]
+    max_total_fee: Option<u64>,
+
     /// Contract ID to deploy to
     #[arg(
         long = "id",
@@ -52,6 +68,10 @@ pub struct Cmd {
         help_heading = HEADING_RPC,
     )]
     salt: Option<String>,
+    /// Save the deployed contract's ID under this alias for the network, so it can be passed
+    /// to `--id` in place of the ID
+    #[arg(long)]
+    alias: Option<String>,
     #[command(flatten)]
     config: config::Args,
     #[command(flatten)]
@@ -92,24 +112,81 @@ pub enum Error {
     Config(#[from] config::Error),
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
+    #[error("reading manifest {path}: {error}")]
+    CannotReadManifest {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+    #[error("parsing manifest {path}: {error}")]
+    CannotParseManifest {
+        path: std::path::PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("contract {0} appears more than once in the manifest")]
+    DuplicateManifestName(String),
+    #[error("contract {name} depends on {dependency}, which is not in the manifest")]
+    UnknownManifestDependency { name: String, dependency: String },
+    #[error("contracts in the manifest depend on each other: {0}")]
+    ManifestDependencyCycle(String),
+    #[error("initializing {name}: {error}")]
+    Init {
+        name: String,
+        error: Box<invoke::Error>,
+    },
+    #[error("estimated total fee of {total} stroops is over --max-total-fee {max}")]
+    MaxTotalFeeExceeded { total: u64, max: u64 },
+    #[error("deployment not confirmed, confirm it or pass --yes")]
+    NotConfirmed,
+    #[error("reading confirmation: {0}")]
+    Confirmation(std::io::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
-        let res_str = self.run_and_get_contract_id().await?;
-        println!("{res_str}");
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let formatter = global_args.formatter();
+        if let Some(path) = &self.manifest {
+            let Outcome::Done(contract_ids) = manifest::deploy(self, global_args, path).await?
+            else {
+                return Ok(());
+            };
+            for (name, contract_id) in contract_ids {
+                formatter.print(
+                    format!("{name}: {contract_id}"),
+                    &serde_json::json!({ "name": name, "contractId": contract_id }),
+                )?;
+            }
+            return Ok(());
+        }
+        let Outcome::Done(contract_id) = self.run_and_get_contract_id(global_args).await? else {
+            return Ok(());
+        };
+        formatter.print(
+            &contract_id,
+            &serde_json::json!({ "contractId": contract_id }),
+        )?;
         Ok(())
     }
 
-    pub async fn run_and_get_contract_id(&self) -> Result<String, Error> {
+    pub async fn run_and_get_contract_id(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<Outcome<String>, Error> {
         let wasm_hash = if let Some(wasm) = &self.wasm {
-            let hash = install::Cmd {
+            let Outcome::Done(hash) = install::Cmd {
                 wasm: wasm::Args { wasm: wasm.clone() },
                 config: self.config.clone(),
                 fee: self.fee.clone(),
+                force: false,
             }
-            .run_and_get_hash()
-            .await?;
+            .run_and_get_hash(global_args)
+            .await?
+            else {
+                return Ok(Outcome::DryRun);
+            };
             hex::encode(hash)
         } else {
             self.wasm_hash
@@ -125,11 +202,15 @@ impl Cmd {
             }
         })?);
 
-        if self.config.is_no_network() {
-            self.run_in_sandbox(hash)
+        let outcome = if self.config.is_no_network() {
+            Outcome::Done(self.run_in_sandbox(hash)?)
         } else {
-            self.run_against_rpc_server(hash).await
+            self.run_against_rpc_server(global_args, hash).await?
+        };
+        if let (Some(alias), Outcome::Done(contract_id)) = (&self.alias, &outcome) {
+            self.config.save_contract_alias(alias, contract_id)?;
         }
+        Ok(outcome)
     }
 
     #[allow(clippy::needless_pass_by_value)]
@@ -155,13 +236,14 @@ impl Cmd {
         Ok(stellar_strkey::Contract(contract_id).to_string())
     }
 
-    async fn run_against_rpc_server(&self, wasm_hash: Hash) -> Result<String, Error> {
+    async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+        wasm_hash: Hash,
+    ) -> Result<Outcome<String>, Error> {
         let network = self.config.get_network()?;
         let salt: [u8; 32] = match &self.salt {
-            Some(h) => soroban_spec_tools::utils::padded_hex_from_str(h, 32)
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?
-                .try_into()
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?,
+            Some(h) => parse_salt(h)?,
             None => rand::thread_rng().gen::<[u8; 32]>(),
         };
 
@@ -169,13 +251,12 @@ impl Cmd {
         client
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
-        let key = self.config.key_pair()?;
+        let key = self.config.public_key()?;
 
         // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
-
-        let account_details = client.get_account(&public_strkey).await?;
+        let account_details = client.get_account(&key.to_string()).await?;
         let sequence: i64 = account_details.seq_num.into();
+        let wasm_hash_bytes = wasm_hash.0;
         let (tx, contract_id) = build_create_contract_tx(
             wasm_hash,
             sequence + 1,
@@ -184,10 +265,30 @@ impl Cmd {
             salt,
             &key,
         )?;
-        client
-            .prepare_and_send_transaction(&tx, &key, &network.network_passphrase, None)
-            .await?;
-        Ok(stellar_strkey::Contract(contract_id.0).to_string())
+        let tx = self
+            .fee
+            .apply_to_assembled_tx(client.prepare_transaction(&tx, None).await?);
+        let Outcome::Done(_) = client
+            .send_transaction(
+                &self
+                    .config
+                    .sign_for_submission(&tx, self.fee.fee, &network.network_passphrase)?,
+                &global_args.submission(&network.network_passphrase),
+            )
+            .await?
+        else {
+            return Ok(Outcome::DryRun);
+        };
+        history::record(
+            &self.config,
+            &network,
+            &tx,
+            &[contract_id.0],
+            Some(&wasm_hash_bytes),
+        );
+        Ok(Outcome::Done(
+            stellar_strkey::Contract(contract_id.0).to_string(),
+        ))
     }
 }
 
@@ -197,11 +298,9 @@ fn build_create_contract_tx(
     fee: u32,
     network_passphrase: &str,
     salt: [u8; 32],
-    key: &ed25519_dalek::Keypair,
+    key: &stellar_strkey::ed25519::PublicKey,
 ) -> Result<(Transaction, Hash), Error> {
-    let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(
-        key.public.to_bytes().into(),
-    ));
+    let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(key.0.into()));
 
     let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
         address: ScAddress::Account(source_account),
@@ -220,7 +319,7 @@ fn build_create_contract_tx(
         }),
     };
     let tx = Transaction {
-        source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+        source_account: MuxedAccount::Ed25519(Uint256(key.0)),
         fee,
         seq_num: SequenceNumber(sequence),
         cond: Preconditions::None,
@@ -232,7 +331,19 @@ fn build_create_contract_tx(
     Ok((tx, Hash(contract_id.into())))
 }
 
-fn get_contract_id(
+/// Parse a hex salt of up to 32 bytes, left padded with zeros.
+pub fn parse_salt(salt: &str) -> Result<[u8; 32], Error> {
+    soroban_spec_tools::utils::padded_hex_from_str(salt, 32)
+        .map_err(|_| Error::CannotParseSalt {
+            salt: salt.to_string(),
+        })?
+        .try_into()
+        .map_err(|_| Error::CannotParseSalt {
+            salt: salt.to_string(),
+        })
+}
+
+pub fn get_contract_id(
     contract_id_preimage: ContractIdPreimage,
     network_passphrase: &str,
 ) -> Result<Hash, Error> {
@@ -265,8 +376,10 @@ mod tests {
             1,
             "Public Global Stellar Network ; September 2015",
             [0u8; 32],
-            &utils::parse_secret_key("SBFGFF27Y64ZUGFAIG5AMJGQODZZKV2YQKAVUUN4HNE24XZXD2OEUVUP")
-                .unwrap(),
+            &stellar_strkey::ed25519::PublicKey::from_string(
+                "GBHKQE6YKBKIA5WU5NF3KPBCLV5KVTXYNBQDVIKNI5QZTNZGOHBIDR4S",
+            )
+            .unwrap(),
         );
 
         assert!(result.is_ok());
@@ -0,0 +1,524 @@
+//! Deploy several contracts described in a TOML manifest, e.g.
+//!
+//! ```toml
+//! [[contract]]
+//! name = "token"
+//! wasm = "target/wasm32-unknown-unknown/release/token.wasm"
+//! init = { fn = "initialize", args = { admin = "alice", decimal = "7" } }
+//!
+//! [[contract]]
+//! name = "pool"
+//! wasm = "target/wasm32-unknown-unknown/release/pool.wasm"
+//! salt = "01"
+//! init = { fn = "initialize", args = { token = "${token}" } }
+//! ```
+//!
+//! `${name}` in an init argument is replaced with the ID of the contract deployed under
+//! that name, which also orders it before the contract referring to it. `depends_on` adds
+//! ordering without a reference. Each deployed ID is saved as an alias under its name.
+//!
+//! Against a network, every transaction that can be simulated before anything is submitted
+//! is, and the estimated total fee is shown, asking to go ahead when run from a terminal. The
+//! rest, which need earlier transactions to have landed, are simulated right before they are
+//! submitted. All of them count towards `--max-total-fee`.
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+use rand::Rng;
+use serde::Deserialize;
+use soroban_env_host::xdr::{Hash, LedgerKey, Transaction};
+
+use super::{build_create_contract_tx, parse_salt, Cmd, Error};
+use crate::{
+    commands::{
+        contract::{install, invoke},
+        global,
+    },
+    rpc::{Client, Outcome},
+    utils, wasm,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(rename = "contract", default)]
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    pub name: String,
+    /// Path to the WASM file, relative to the manifest
+    pub wasm: PathBuf,
+    pub salt: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub init: Option<Init>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Init {
+    #[serde(rename = "fn")]
+    pub function: String,
+    /// Arguments by name, with values as they would be passed on the command line
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+}
+
+impl Contract {
+    /// Names of the contracts this one has to be deployed after.
+    fn dependencies(&self) -> Vec<String> {
+        let mut deps = self.depends_on.clone();
+        for value in self.init.iter().flat_map(|init| init.args.values()) {
+            deps.extend(references(value).map(ToString::to_string));
+        }
+        deps
+    }
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let data = fs::read_to_string(path).map_err(|error| Error::CannotReadManifest {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        toml::from_str(&data).map_err(|error| Error::CannotParseManifest {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// The contracts ordered so each comes after the contracts it depends on, keeping the
+    /// manifest order otherwise.
+    pub fn ordered(&self) -> Result<Vec<&Contract>, Error> {
+        let mut names = HashMap::new();
+        for (i, contract) in self.contracts.iter().enumerate() {
+            if names.insert(contract.name.as_str(), i).is_some() {
+                return Err(Error::DuplicateManifestName(contract.name.clone()));
+            }
+        }
+        let deps = self
+            .contracts
+            .iter()
+            .map(|contract| {
+                contract
+                    .dependencies()
+                    .iter()
+                    .map(|dep| {
+                        names.get(dep.as_str()).copied().ok_or_else(|| {
+                            Error::UnknownManifestDependency {
+                                name: contract.name.clone(),
+                                dependency: dep.clone(),
+                            }
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut done = vec![false; self.contracts.len()];
+        let mut ordered = Vec::with_capacity(self.contracts.len());
+        while ordered.len() < self.contracts.len() {
+            let next = (0..self.contracts.len())
+                .find(|&i| !done[i] && deps[i].iter().all(|&d| done[d]))
+                .ok_or_else(|| {
+                    Error::ManifestDependencyCycle(
+                        (0..self.contracts.len())
+                            .filter(|&i| !done[i])
+                            .map(|i| self.contracts[i].name.clone())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    )
+                })?;
+            done[next] = true;
+            ordered.push(&self.contracts[next]);
+        }
+        Ok(ordered)
+    }
+}
+
+/// Install and deploy every contract in the manifest, returning the name and ID of each in
+/// deployment order. Contracts sharing a WASM file install it once. A dry run stops at the
+/// first transaction, as the others depend on it.
+pub async fn deploy(
+    cmd: &Cmd,
+    global_args: &global::Args,
+    path: &Path,
+) -> Result<Outcome<Vec<(String, String)>>, Error> {
+    let manifest = Manifest::read(path)?;
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+    let ordered = manifest.ordered()?;
+    // Chosen once, so the deployments simulated up front are the ones submitted
+    let salts = ordered
+        .iter()
+        .map(|contract| {
+            let salt = match &contract.salt {
+                Some(salt) => parse_salt(salt)?,
+                None => rand::thread_rng().gen::<[u8; 32]>(),
+            };
+            Ok((contract.name.clone(), salt))
+        })
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+    let mut preflight = if cmd.config.is_no_network() {
+        None
+    } else {
+        Some(Preflight::run(cmd, global_args, base, &ordered, &salts).await?)
+    };
+    let mut installed: HashMap<PathBuf, Hash> = HashMap::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut deployed = vec![];
+    for contract in ordered {
+        let wasm = base.join(&contract.wasm);
+        let hash = if let Some(hash) = installed.get(&wasm) {
+            hash.clone()
+        } else {
+            let Outcome::Done(hash) = install::Cmd {
+                wasm: wasm::Args { wasm: wasm.clone() },
+                config: cmd.config.clone(),
+                fee: cmd.fee.clone(),
+                force: false,
+            }
+            .run_and_get_hash(global_args)
+            .await?
+            else {
+                return Ok(Outcome::DryRun);
+            };
+            installed.insert(wasm, hash.clone());
+            hash
+        };
+        let salt = salts[&contract.name];
+        if let Some(preflight) = &mut preflight {
+            preflight.before_deploy(contract, &hash, salt).await?;
+        }
+        let Outcome::Done(contract_id) = Cmd {
+            wasm: None,
+            wasm_hash: Some(hex::encode(hash)),
+            manifest: None,
+            max_total_fee: None,
+            contract_id: None,
+            salt: Some(hex::encode(salt)),
+            alias: Some(contract.name.clone()),
+            config: cmd.config.clone(),
+            fee: cmd.fee.clone(),
+        }
+        .run_and_get_contract_id(global_args)
+        .await?
+        else {
+            return Ok(Outcome::DryRun);
+        };
+        if let Some(init) = &contract.init {
+            if let Some(preflight) = &mut preflight {
+                preflight
+                    .before_init(contract, init, &contract_id, &ids)
+                    .await?;
+            }
+            let initialized = init_cmd(cmd, init, &contract_id, &ids)
+                .invoke(global_args)
+                .await
+                .map_err(|error| Error::Init {
+                    name: contract.name.clone(),
+                    error: Box::new(error),
+                })?;
+            if initialized == Outcome::DryRun {
+                return Ok(Outcome::DryRun);
+            }
+        }
+        ids.insert(contract.name.clone(), contract_id.clone());
+        deployed.push((contract.name.clone(), contract_id));
+    }
+    Ok(Outcome::Done(deployed))
+}
+
+fn init_cmd(
+    cmd: &Cmd,
+    init: &Init,
+    contract_id: &str,
+    ids: &HashMap<String, String>,
+) -> invoke::Cmd {
+    invoke::Cmd {
+        contract_id: contract_id.to_string(),
+        function: Some(init.function.clone()),
+        slop: init
+            .args
+            .iter()
+            .map(|(name, value)| format!("{name}={}", substitute(value, ids)).into())
+            .collect(),
+        config: cmd.config.clone(),
+        fee: cmd.fee.clone(),
+        ..Default::default()
+    }
+}
+
+/// Fees of the manifest's transactions, estimated by simulating them.
+struct Preflight<'a> {
+    cmd: &'a Cmd,
+    client: Client,
+    network_passphrase: String,
+    /// Estimated fees of the transactions simulated so far, in stroops
+    total: u64,
+    /// Contracts whose deployment was simulated up front
+    deploys: HashSet<String>,
+}
+
+impl<'a> Preflight<'a> {
+    /// Simulate every transaction that can be before anything is submitted, deploying with
+    /// `salts` by contract name, print the fees and ask to go ahead if there's a terminal to
+    /// ask on. Without one, `--max-total-fee` is what bounds the cost.
+    async fn run(
+        cmd: &'a Cmd,
+        global_args: &global::Args,
+        base: &Path,
+        ordered: &[&Contract],
+        salts: &HashMap<String, [u8; 32]>,
+    ) -> Result<Self, Error> {
+        let network = cmd.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
+        client
+            .verify_network_passphrase(Some(&network.network_passphrase))
+            .await?;
+        let mut preflight = Self {
+            cmd,
+            client,
+            network_passphrase: network.network_passphrase,
+            total: 0,
+            deploys: HashSet::new(),
+        };
+        let mut on_network = HashMap::new();
+        let mut later = 0;
+        eprintln!("Simulating the manifest's transactions:");
+        for contract in ordered {
+            let wasm = base.join(&contract.wasm);
+            if !on_network.contains_key(&wasm) {
+                let installed = preflight.is_installed(&wasm).await?;
+                if installed {
+                    eprintln!("  install {}: already installed", wasm.display());
+                } else {
+                    let fee = preflight.install_fee(&wasm).await?;
+                    preflight.add(&format!("install {}", wasm.display()), fee)?;
+                }
+                on_network.insert(wasm.clone(), installed);
+            }
+            if on_network[&wasm] {
+                let hash = utils::contract_hash(&wasm::Args { wasm }.read()?)?;
+                let fee = preflight.deploy_fee(&hash, salts[&contract.name]).await?;
+                preflight.add(&format!("deploy {}", contract.name), fee)?;
+                preflight.deploys.insert(contract.name.clone());
+            } else {
+                eprintln!(
+                    "  deploy {}: simulated once its WASM is installed",
+                    contract.name
+                );
+                later += 1;
+            }
+            if contract.init.is_some() {
+                eprintln!(
+                    "  initialize {}: simulated once it is deployed",
+                    contract.name
+                );
+                later += 1;
+            }
+        }
+        eprintln!(
+            "Estimated total fee: {} stroops, plus {later} transactions simulated as they are \
+             reached",
+            preflight.total
+        );
+        if !global_args.yes
+            && !global_args.dry_run
+            && std::io::stdin().is_terminal()
+            && !utils::confirm("Deploy the manifest?").map_err(Error::Confirmation)?
+        {
+            return Err(Error::NotConfirmed);
+        }
+        Ok(preflight)
+    }
+
+    /// Simulate deploying `contract` if that wasn't possible up front.
+    async fn before_deploy(
+        &mut self,
+        contract: &Contract,
+        hash: &Hash,
+        salt: [u8; 32],
+    ) -> Result<(), Error> {
+        if self.deploys.contains(&contract.name) {
+            return Ok(());
+        }
+        let fee = self.deploy_fee(hash, salt).await?;
+        self.add(&format!("deploy {}", contract.name), fee)
+    }
+
+    async fn before_init(
+        &mut self,
+        contract: &Contract,
+        init: &Init,
+        contract_id: &str,
+        ids: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let simulation = init_cmd(self.cmd, init, contract_id, ids)
+            .simulate()
+            .await
+            .map_err(|error| Error::Init {
+                name: contract.name.clone(),
+                error: Box::new(error),
+            })?;
+        let fee = serde_json::from_str::<serde_json::Value>(&simulation)?["fee"]
+            .as_u64()
+            .unwrap_or_default();
+        self.add(&format!("initialize {}", contract.name), fee)
+    }
+
+    /// Count a simulated fee towards the total, which must stay within `--max-total-fee`.
+    fn add(&mut self, step: &str, fee: u64) -> Result<(), Error> {
+        self.total += fee;
+        eprintln!("  {step}: {fee} stroops");
+        match self.cmd.max_total_fee {
+            Some(max) if self.total > max => Err(Error::MaxTotalFeeExceeded {
+                total: self.total,
+                max,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    async fn is_installed(&self, wasm: &Path) -> Result<bool, Error> {
+        let key: LedgerKey = wasm::Args {
+            wasm: wasm.to_path_buf(),
+        }
+        .try_into()?;
+        let entries = self.client.get_ledger_entries(vec![key]).await?.entries;
+        Ok(entries.is_some_and(|entries| !entries.is_empty()))
+    }
+
+    async fn install_fee(&self, wasm: &Path) -> Result<u64, Error> {
+        let key = self.cmd.config.public_key()?;
+        let contract = wasm::Args {
+            wasm: wasm.to_path_buf(),
+        }
+        .read()?;
+        let (tx, _) = install::build_install_contract_code_tx(contract, 0, self.cmd.fee.fee, &key)?;
+        self.simulated_fee(&tx).await
+    }
+
+    async fn deploy_fee(&self, hash: &Hash, salt: [u8; 32]) -> Result<u64, Error> {
+        let key = self.cmd.config.public_key()?;
+        let (tx, _) = build_create_contract_tx(
+            hash.clone(),
+            0,
+            self.cmd.fee.fee,
+            &self.network_passphrase,
+            salt,
+            &key,
+        )?;
+        self.simulated_fee(&tx).await
+    }
+
+    /// The fee `tx` will pay once simulated and assembled. The sequence number doesn't matter
+    /// to simulation.
+    async fn simulated_fee(&self, tx: &Transaction) -> Result<u64, Error> {
+        let assembled = self.client.prepare_transaction(tx, None).await?;
+        let fee = crate::fee::Args {
+            print_cost: false,
+            ..self.cmd.fee.clone()
+        };
+        Ok(fee.apply_to_assembled_tx(assembled).fee.into())
+    }
+}
+
+/// Names referenced as `${name}` in `value`.
+fn references(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split("${")
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
+
+fn substitute(value: &str, ids: &HashMap<String, String>) -> String {
+    ids.iter().fold(value.to_string(), |value, (name, id)| {
+        value.replace(&format!("${{{name}}}"), id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(toml: &str) -> Manifest {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn names(manifest: &Manifest) -> Vec<&str> {
+        manifest
+            .ordered()
+            .unwrap()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn orders_by_references_and_depends_on() {
+        let m = manifest(
+            r#"
+            [[contract]]
+            name = "pool"
+            wasm = "pool.wasm"
+            init = { fn = "initialize", args = { token = "${token}" } }
+
+            [[contract]]
+            name = "router"
+            wasm = "router.wasm"
+            depends_on = ["pool"]
+
+            [[contract]]
+            name = "token"
+            wasm = "token.wasm"
+            "#,
+        );
+        assert_eq!(names(&m), ["token", "pool", "router"]);
+    }
+
+    #[test]
+    fn rejects_cycles_and_unknown_names() {
+        let m = manifest(
+            r#"
+            [[contract]]
+            name = "a"
+            wasm = "a.wasm"
+            depends_on = ["b"]
+
+            [[contract]]
+            name = "b"
+            wasm = "b.wasm"
+            depends_on = ["a"]
+            "#,
+        );
+        assert!(matches!(
+            m.ordered(),
+            Err(Error::ManifestDependencyCycle(_))
+        ));
+        let m = manifest(
+            r#"
+            [[contract]]
+            name = "a"
+            wasm = "a.wasm"
+            depends_on = ["c"]
+            "#,
+        );
+        assert!(matches!(
+            m.ordered(),
+            Err(Error::UnknownManifestDependency { .. })
+        ));
+    }
+
+    #[test]
+    fn substitutes_ids() {
+        let ids = HashMap::from([("token".to_string(), "CABC".to_string())]);
+        assert_eq!(substitute("${token}", &ids), "CABC");
+        assert_eq!(references("${a} and ${b}").collect::<Vec<_>>(), ["a", "b"]);
+    }
+}
@@ -0,0 +1,438 @@
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use clap::{command, Parser, ValueEnum};
+use soroban_env_host::xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+use crate::wasm;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    wasm: wasm::Args,
+
+    /// Where to write the documentation, as `index.md` or `index.html`
+    #[arg(long)]
+    output_dir: PathBuf,
+
+    /// Whether to overwrite output directory if it already exists
+    #[arg(long)]
+    overwrite: bool,
+
+    /// Format of the documentation
+    #[arg(long, value_enum, default_value = "markdown")]
+    format: Format,
+
+    /// Title of the documentation, defaults to the name of the wasm file
+    #[arg(long)]
+    title: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// A Markdown page
+    Markdown,
+    /// A standalone HTML page
+    Html,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("--output-dir cannot be a file: {0:?}")]
+    IsFile(PathBuf),
+    #[error("--output-dir already exists and you did not specify --overwrite: {0:?}")]
+    OutputDirExists(PathBuf),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let spec = self.wasm.parse()?.spec;
+        if self.output_dir.is_file() {
+            return Err(Error::IsFile(self.output_dir.clone()));
+        }
+        if self.output_dir.exists() {
+            if self.overwrite {
+                fs::remove_dir_all(&self.output_dir)?;
+            } else {
+                return Err(Error::OutputDirExists(self.output_dir.clone()));
+            }
+        }
+        fs::create_dir_all(&self.output_dir)?;
+        let title = self.title.clone().unwrap_or_else(|| {
+            self.wasm
+                .wasm
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        });
+        let sections = sections(&spec);
+        let path = match self.format {
+            Format::Markdown => {
+                let path = self.output_dir.join("index.md");
+                fs::write(&path, markdown(&title, &sections))?;
+                path
+            }
+            Format::Html => {
+                let path = self.output_dir.join("index.html");
+                fs::write(&path, html(&title, &sections))?;
+                path
+            }
+        };
+        println!("Wrote {}", path.display());
+        Ok(())
+    }
+}
+
+/// A function or type of the contract.
+struct Item {
+    kind: &'static str,
+    name: String,
+    doc: String,
+    /// For functions the return type
+    signature: Option<Vec<Part>>,
+    /// Arguments, fields or cases, with their type or value, and doc
+    members: Vec<(String, Vec<Part>, String)>,
+}
+
+/// Pieces of a type, so that user defined types can be linked to.
+enum Part {
+    Text(String),
+    Type(String),
+}
+
+fn sections(spec: &[ScSpecEntry]) -> Vec<(&'static str, Vec<Item>)> {
+    let mut functions = vec![];
+    let mut types = vec![];
+    let mut errors = vec![];
+    for entry in spec {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                if !function.name.to_string_lossy().starts_with("__") {
+                    functions.push(function_item(function));
+                }
+            }
+            ScSpecEntry::UdtStructV0(udt) => types.push(struct_item(udt)),
+            ScSpecEntry::UdtUnionV0(udt) => types.push(union_item(udt)),
+            ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                doc, name, cases, ..
+            }) => types.push(Item {
+                kind: "enum",
+                name: name.to_string_lossy(),
+                doc: doc.to_string_lossy(),
+                signature: None,
+                members: cases
+                    .iter()
+                    .map(|case| {
+                        (
+                            case.name.to_string_lossy(),
+                            vec![Part::Text(case.value.to_string())],
+                            case.doc.to_string_lossy(),
+                        )
+                    })
+                    .collect(),
+            }),
+            ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+                doc, name, cases, ..
+            }) => errors.push(Item {
+                kind: "error",
+                name: name.to_string_lossy(),
+                doc: doc.to_string_lossy(),
+                signature: None,
+                members: cases
+                    .iter()
+                    .map(|case| {
+                        (
+                            case.name.to_string_lossy(),
+                            vec![Part::Text(case.value.to_string())],
+                            case.doc.to_string_lossy(),
+                        )
+                    })
+                    .collect(),
+            }),
+        }
+    }
+    vec![
+        ("Functions", functions),
+        ("Types", types),
+        ("Errors", errors),
+    ]
+    .into_iter()
+    .filter(|(_, items)| !items.is_empty())
+    .collect()
+}
+
+fn function_item(
+    ScSpecFunctionV0 {
+        doc,
+        name,
+        inputs,
+        outputs,
+    }: &ScSpecFunctionV0,
+) -> Item {
+    Item {
+        kind: "fn",
+        name: name.to_string_lossy(),
+        doc: doc.to_string_lossy(),
+        signature: Some(
+            outputs
+                .first()
+                .map_or_else(|| vec![Part::Text("()".to_string())], type_parts),
+        ),
+        members: inputs
+            .iter()
+            .map(|input| {
+                (
+                    input.name.to_string_lossy(),
+                    type_parts(&input.type_),
+                    input.doc.to_string_lossy(),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn struct_item(
+    ScSpecUdtStructV0 {
+        doc, name, fields, ..
+    }: &ScSpecUdtStructV0,
+) -> Item {
+    Item {
+        kind: "struct",
+        name: name.to_string_lossy(),
+        doc: doc.to_string_lossy(),
+        signature: None,
+        members: fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.to_string_lossy(),
+                    type_parts(&field.type_),
+                    field.doc.to_string_lossy(),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn union_item(
+    ScSpecUdtUnionV0 {
+        doc, name, cases, ..
+    }: &ScSpecUdtUnionV0,
+) -> Item {
+    Item {
+        kind: "union",
+        name: name.to_string_lossy(),
+        doc: doc.to_string_lossy(),
+        signature: None,
+        members: cases
+            .iter()
+            .map(|case| match case {
+                ScSpecUdtUnionCaseV0::VoidV0(case) => (
+                    case.name.to_string_lossy(),
+                    vec![],
+                    case.doc.to_string_lossy(),
+                ),
+                ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                    let mut parts = vec![Part::Text("(".to_string())];
+                    for (i, type_) in case.type_.iter().enumerate() {
+                        if i > 0 {
+                            parts.push(Part::Text(", ".to_string()));
+                        }
+                        parts.extend(type_parts(type_));
+                    }
+                    parts.push(Part::Text(")".to_string()));
+                    (
+                        case.name.to_string_lossy(),
+                        parts,
+                        case.doc.to_string_lossy(),
+                    )
+                }
+            })
+            .collect(),
+    }
+}
+
+/// A type as it is written in a contract, e.g. `Option<Vec<Address>>`.
+fn type_parts(type_: &ScSpecTypeDef) -> Vec<Part> {
+    let text = |s: &str| Part::Text(s.to_string());
+    let generic = |name: &str, args: &[&ScSpecTypeDef]| {
+        let mut parts = vec![Part::Text(format!("{name}<"))];
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                parts.push(text(", "));
+            }
+            parts.extend(type_parts(arg));
+        }
+        parts.push(text(">"));
+        parts
+    };
+    match type_ {
+        ScSpecTypeDef::Option(option) => generic("Option", &[&option.value_type]),
+        ScSpecTypeDef::Result(result) => generic("Result", &[&result.ok_type, &result.error_type]),
+        ScSpecTypeDef::Vec(vec) => generic("Vec", &[&vec.element_type]),
+        ScSpecTypeDef::Set(set) => generic("Set", &[&set.element_type]),
+        ScSpecTypeDef::Map(map) => generic("Map", &[&map.key_type, &map.value_type]),
+        ScSpecTypeDef::Tuple(tuple) => {
+            let mut parts = vec![text("(")];
+            for (i, type_) in tuple.value_types.iter().enumerate() {
+                if i > 0 {
+                    parts.push(text(", "));
+                }
+                parts.extend(type_parts(type_));
+            }
+            parts.push(text(")"));
+            parts
+        }
+        ScSpecTypeDef::BytesN(bytes) => vec![Part::Text(format!("BytesN<{}>", bytes.n))],
+        ScSpecTypeDef::Udt(udt) => vec![Part::Type(udt.name.to_string_lossy())],
+        ScSpecTypeDef::Val => vec![text("Val")],
+        ScSpecTypeDef::Bool => vec![text("bool")],
+        ScSpecTypeDef::Void => vec![text("()")],
+        ScSpecTypeDef::Error => vec![text("Error")],
+        ScSpecTypeDef::U32 => vec![text("u32")],
+        ScSpecTypeDef::I32 => vec![text("i32")],
+        ScSpecTypeDef::U64 => vec![text("u64")],
+        ScSpecTypeDef::I64 => vec![text("i64")],
+        ScSpecTypeDef::Timepoint => vec![text("Timepoint")],
+        ScSpecTypeDef::Duration => vec![text("Duration")],
+        ScSpecTypeDef::U128 => vec![text("u128")],
+        ScSpecTypeDef::I128 => vec![text("i128")],
+        ScSpecTypeDef::U256 => vec![text("U256")],
+        ScSpecTypeDef::I256 => vec![text("I256")],
+        ScSpecTypeDef::Bytes => vec![text("Bytes")],
+        ScSpecTypeDef::String => vec![text("String")],
+        ScSpecTypeDef::Symbol => vec![text("Symbol")],
+        ScSpecTypeDef::Address => vec![text("Address")],
+    }
+}
+
+/// A type as it is written in a contract, as text.
+pub fn type_name(type_: &ScSpecTypeDef) -> String {
+    type_parts(type_)
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(s) | Part::Type(s) => s,
+        })
+        .collect()
+}
+
+fn markdown(title: &str, sections: &[(&str, Vec<Item>)]) -> String {
+    let type_ = |parts: &[Part]| {
+        parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => format!("`{text}`"),
+                Part::Type(name) => format!("[`{name}`](#{})", name.to_lowercase()),
+            })
+            .collect::<String>()
+    };
+    let mut out = format!("# {title}\n");
+    for (heading, items) in sections {
+        out.push_str(&format!("\n## {heading}\n"));
+        for item in items {
+            out.push_str(&format!("\n### {}\n\n", item.name));
+            match &item.signature {
+                Some(returns) => out.push_str(&format!("`{}` → {}\n", item.kind, type_(returns))),
+                None => out.push_str(&format!("`{}`\n", item.kind)),
+            }
+            if !item.doc.is_empty() {
+                out.push_str(&format!("\n{}\n", item.doc));
+            }
+            if !item.members.is_empty() {
+                out.push_str("\n| Name | Type | Description |\n| --- | --- | --- |\n");
+                for (name, parts, doc) in &item.members {
+                    out.push_str(&format!(
+                        "| `{name}` | {} | {} |\n",
+                        type_(parts),
+                        doc.replace('\n', " ").replace('|', "\\|")
+                    ));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn html(title: &str, sections: &[(&str, Vec<Item>)]) -> String {
+    let type_ = |parts: &[Part]| {
+        let code = parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => escape(text),
+                Part::Type(name) => format!(
+                    "<a href=\"#{}\">{}</a>",
+                    escape(&name.to_lowercase()),
+                    escape(name)
+                ),
+            })
+            .collect::<String>();
+        format!("<code>{code}</code>")
+    };
+    let mut body = format!("<h1>{}</h1>\n", escape(title));
+    for (heading, items) in sections {
+        body.push_str(&format!("<h2>{heading}</h2>\n"));
+        for item in items {
+            body.push_str(&format!(
+                "<section id=\"{}\">\n<h3><small>{}</small> {}",
+                escape(&item.name.to_lowercase()),
+                item.kind,
+                escape(&item.name)
+            ));
+            if let Some(returns) = &item.signature {
+                body.push_str(&format!(" → {}", type_(returns)));
+            }
+            body.push_str("</h3>\n");
+            for paragraph in item.doc.split("\n\n").filter(|p| !p.is_empty()) {
+                body.push_str(&format!("<p>{}</p>\n", escape(paragraph)));
+            }
+            if !item.members.is_empty() {
+                body.push_str("<table>\n<tr><th>Name</th><th>Type</th><th>Description</th></tr>\n");
+                for (name, parts, doc) in &item.members {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                        escape(name),
+                        type_(parts),
+                        escape(doc)
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+            body.push_str("</section>\n");
+        }
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }}
+section {{ border-top: 1px solid #ddd; }}
+small {{ color: #888; font-weight: normal; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border: 1px solid #ddd; padding: 0.3em 0.6em; text-align: left; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        escape(title)
+    )
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -18,7 +18,8 @@ use stellar_strkey::DecodeError;
 use crate::{
     commands::config,
     commands::contract::Durability,
-    rpc::{self, Client},
+    commands::{global, history},
+    rpc::{self, Client, Outcome},
     utils, wasm, Pwd,
 };
 
@@ -26,20 +27,20 @@ use crate::{
 #[group(skip)]
 pub struct Cmd {
     /// Contract ID to which owns the data entries.
-    /// If no keys provided the Contract's instance will be bumped
+    /// If no keys provided the Contract's instance will be extended
     #[arg(
         long = "id",
         required_unless_present = "wasm",
         required_unless_present = "wasm_hash"
     )]
     contract_id: Option<String>,
-    /// Storage key (symbols only)
-    #[arg(long = "key", conflicts_with = "key_xdr")]
-    key: Option<String>,
-    /// Storage key (base64-encoded XDR)
-    #[arg(long = "key-xdr", conflicts_with = "key")]
-    key_xdr: Option<String>,
-    /// Path to Wasm file of contract code to bump
+    /// Storage key (symbols only), can be repeated to extend several entries at once
+    #[arg(long = "key")]
+    key: Vec<String>,
+    /// Storage key (base64-encoded XDR), can be repeated
+    #[arg(long = "key-xdr")]
+    key_xdr: Vec<String>,
+    /// Path to Wasm file of contract code to extend
     #[arg(
         long,
         conflicts_with = "contract_id",
@@ -48,7 +49,7 @@ pub struct Cmd {
         conflicts_with = "wasm_hash"
     )]
     wasm: Option<PathBuf>,
-    /// Path to Wasm file of contract code to bump
+    /// Hash of contract code to extend
     #[arg(
         long,
         conflicts_with = "contract_id",
@@ -61,9 +62,9 @@ pub struct Cmd {
     #[arg(long, value_enum, required = true)]
     durability: Durability,
 
-    /// Number of ledgers to extend the entries
-    #[arg(long, required = true)]
-    ledgers_to_expire: u32,
+    /// Number of ledgers to extend the entries by
+    #[arg(long, alias = "ledgers-to-expire")]
+    ledgers: u32,
 
     #[command(flatten)]
     config: config::Args,
@@ -111,37 +112,51 @@ pub enum Error {
     Rpc(#[from] rpc::Error),
     #[error(transparent)]
     Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
-    pub async fn run(&self) -> Result<(), Error> {
-        let expiration_ledger_seq = if self.config.is_no_network() {
-            self.run_in_sandbox()?
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let outcome = if self.config.is_no_network() {
+            Outcome::Done(self.run_in_sandbox()?)
         } else {
-            self.run_against_rpc_server().await?
+            self.run_against_rpc_server(global_args).await?
+        };
+        let Outcome::Done(expiration_ledger_seqs) = outcome else {
+            return Ok(());
         };
 
-        println!("New expiration ledger: {expiration_ledger_seq}");
+        let formatter = global_args.formatter();
+        for expiration_ledger_seq in expiration_ledger_seqs {
+            formatter.print(
+                format!("New expiration ledger: {expiration_ledger_seq}"),
+                &serde_json::json!({ "expirationLedger": expiration_ledger_seq }),
+            )?;
+        }
 
         Ok(())
     }
 
-    async fn run_against_rpc_server(&self) -> Result<u32, Error> {
+    async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<Outcome<Vec<u32>>, Error> {
         let network = self.config.get_network()?;
         tracing::trace!(?network);
-        let needle = self.parse_key()?;
+        let needles = self.parse_keys()?;
+        let (contract_ids, wasm_hash) = owners(&needles);
         let network = &self.config.get_network()?;
         let client = Client::new(&network.rpc_url)?;
-        let key = self.config.key_pair()?;
+        let key = self.config.public_key()?;
 
         // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
-        let account_details = client.get_account(&public_strkey).await?;
+        let account_details = client.get_account(&key.to_string()).await?;
         let sequence: i64 = account_details.seq_num.into();
 
         let tx = Transaction {
-            source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+            source_account: MuxedAccount::Ed25519(Uint256(key.0)),
             fee: self.fee.fee,
             seq_num: SequenceNumber(sequence + 1),
             cond: Preconditions::None,
@@ -150,7 +165,7 @@ impl Cmd {
                 source_account: None,
                 body: OperationBody::BumpFootprintExpiration(BumpFootprintExpirationOp {
                     ext: ExtensionPoint::V0,
-                    ledgers_to_expire: self.ledgers_to_expire,
+                    ledgers_to_expire: self.ledgers,
                 }),
             }]
             .try_into()?,
@@ -158,7 +173,7 @@ impl Cmd {
                 ext: ExtensionPoint::V0,
                 resources: SorobanResources {
                     footprint: LedgerFootprint {
-                        read_only: vec![needle].try_into()?,
+                        read_only: needles.try_into()?,
                         read_write: vec![].try_into()?,
                     },
                     instructions: 0,
@@ -170,9 +185,27 @@ impl Cmd {
             }),
         };
 
-        let (result, meta, events) = client
-            .prepare_and_send_transaction(&tx, &key, &network.network_passphrase, None)
-            .await?;
+        let tx = self
+            .fee
+            .apply_to_assembled_tx(client.prepare_transaction(&tx, None).await?);
+        let Outcome::Done((result, meta, events)) = client
+            .send_transaction(
+                &self
+                    .config
+                    .sign_for_submission(&tx, self.fee.fee, &network.network_passphrase)?,
+                &global_args.submission(&network.network_passphrase),
+            )
+            .await?
+        else {
+            return Ok(Outcome::DryRun);
+        };
+        history::record(
+            &self.config,
+            network,
+            &tx,
+            &contract_ids,
+            wasm_hash.as_ref(),
+        );
 
         tracing::trace!(?result);
         tracing::trace!(?meta);
@@ -180,25 +213,18 @@ impl Cmd {
             tracing::info!("Events:\n {events:#?}");
         }
 
-        // The transaction from core will succeed regardless of whether it actually found & bumped
-        // the entry, so we have to inspect the result meta to tell if it worked or not.
+        // The transaction from core will succeed regardless of whether it actually found &
+        // extended the entries, so we have to inspect the result meta to tell if it worked or not.
         let TransactionMeta::V3(TransactionMetaV3 { operations, .. }) = meta else {
             return Err(Error::LedgerEntryNotFound);
         };
-
-        // Simply check if there is exactly one entry here. We only support bumping a single
-        // entry via this command (which we should fix separately, but).
-        if operations.len() == 0 {
+        let Some(operation) = operations.first() else {
             return Err(Error::LedgerEntryNotFound);
-        }
-
-        if operations[0].changes.len() != 2 {
-            return Err(Error::LedgerEntryNotFound);
-        }
-
-        match (&operations[0].changes[0], &operations[0].changes[1]) {
-            (
-                LedgerEntryChange::State(_),
+        };
+        let expiration_ledger_seqs = operation
+            .changes
+            .iter()
+            .filter_map(|change| match change {
                 LedgerEntryChange::Updated(LedgerEntry {
                     data:
                         LedgerEntryData::ContractData(ContractDataEntry {
@@ -210,21 +236,25 @@ impl Cmd {
                             ..
                         }),
                     ..
-                }),
-            ) => Ok(*expiration_ledger_seq),
-            _ => Err(Error::LedgerEntryNotFound),
+                }) => Some(*expiration_ledger_seq),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if expiration_ledger_seqs.is_empty() {
+            return Err(Error::LedgerEntryNotFound);
         }
+        Ok(Outcome::Done(expiration_ledger_seqs))
     }
 
-    fn run_in_sandbox(&self) -> Result<u32, Error> {
-        let needle = self.parse_key()?;
+    fn run_in_sandbox(&self) -> Result<Vec<u32>, Error> {
+        let needles = self.parse_keys()?;
 
         // Initialize storage and host
         // TODO: allow option to separate input and output file
         let mut state = self.config.get_state()?;
 
         // Update all matching entries
-        let mut expiration_ledger_seq = None;
+        let mut expiration_ledger_seqs = vec![];
         state.ledger_entries = state
             .ledger_entries
             .iter()
@@ -233,9 +263,9 @@ impl Cmd {
                 let new_v = v.as_ref().clone();
                 (
                     Box::new(new_k.clone()),
-                    Box::new(if needle == new_k {
-                        let (new_v, new_expiration) = bump_entry(&new_v, self.ledgers_to_expire);
-                        expiration_ledger_seq = Some(new_expiration);
+                    Box::new(if needles.contains(&new_k) {
+                        let (new_v, new_expiration) = extend_entry(&new_v, self.ledgers);
+                        expiration_ledger_seqs.push(new_expiration);
                         new_v
                     } else {
                         new_v
@@ -246,51 +276,67 @@ impl Cmd {
 
         self.config.set_state(&mut state)?;
 
-        let Some(new_expiration_ledger_seq) = expiration_ledger_seq else {
+        if expiration_ledger_seqs.is_empty() {
             return Err(Error::LedgerEntryNotFound);
-        };
-
-        Ok(new_expiration_ledger_seq)
+        }
+        Ok(expiration_ledger_seqs)
     }
 
-    fn parse_key(&self) -> Result<LedgerKey, Error> {
-        let key = if let Some(key) = &self.key {
-            soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol).map_err(|e| {
-                Error::CannotParseKey {
-                    key: key.clone(),
-                    error: e,
-                }
-            })?
-        } else if let Some(key) = &self.key_xdr {
-            ScVal::from_xdr_base64(key).map_err(|e| Error::CannotParseXdrKey {
-                key: key.clone(),
-                error: e,
-            })?
-        } else if let Some(wasm) = &self.wasm {
-            return Ok(crate::wasm::Args { wasm: wasm.clone() }.try_into()?);
+    fn parse_keys(&self) -> Result<Vec<LedgerKey>, Error> {
+        if let Some(wasm) = &self.wasm {
+            return Ok(vec![crate::wasm::Args { wasm: wasm.clone() }.try_into()?]);
         } else if let Some(wasm_hash) = &self.wasm_hash {
-            return Ok(LedgerKey::ContractCode(LedgerKeyContractCode {
+            return Ok(vec![LedgerKey::ContractCode(LedgerKeyContractCode {
                 hash: Hash(
                     utils::contract_id_from_str(wasm_hash)
                         .map_err(|e| Error::CannotParseContractId(wasm_hash.clone(), e))?,
                 ),
                 body_type: ContractEntryBodyType::DataEntry,
-            }));
-        } else {
-            ScVal::LedgerKeyContractInstance
-        };
-        let contract_id = contract_id(self.contract_id.as_ref().unwrap())?;
+            })]);
+        }
+        let mut keys = vec![];
+        for key in &self.key {
+            keys.push(
+                soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol).map_err(
+                    |e| Error::CannotParseKey {
+                        key: key.clone(),
+                        error: e,
+                    },
+                )?,
+            );
+        }
+        for key in &self.key_xdr {
+            keys.push(
+                ScVal::from_xdr_base64(key).map_err(|e| Error::CannotParseXdrKey {
+                    key: key.clone(),
+                    error: e,
+                })?,
+            );
+        }
+        if keys.is_empty() {
+            keys.push(ScVal::LedgerKeyContractInstance);
+        }
+        let contract_id = contract_id(
+            &self
+                .config
+                .resolve_contract_id(self.contract_id.as_ref().unwrap())?,
+        )?;
 
-        Ok(LedgerKey::ContractData(LedgerKeyContractData {
-            contract: ScAddress::Contract(Hash(contract_id)),
-            durability: self.durability.into(),
-            body_type: ContractEntryBodyType::DataEntry,
-            key,
-        }))
+        Ok(keys
+            .into_iter()
+            .map(|key| {
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: ScAddress::Contract(Hash(contract_id)),
+                    durability: self.durability.into(),
+                    body_type: ContractEntryBodyType::DataEntry,
+                    key,
+                })
+            })
+            .collect())
     }
 }
 
-fn bump_entry(v: &LedgerEntry, ledgers_to_expire: u32) -> (LedgerEntry, u32) {
+fn extend_entry(v: &LedgerEntry, ledgers_to_expire: u32) -> (LedgerEntry, u32) {
     let mut new_v = v.clone();
     let mut new_expiration_ledger_seq = 0;
     if let LedgerEntryData::ContractData(ref mut data) = new_v.data {
@@ -306,3 +352,22 @@ fn bump_entry(v: &LedgerEntry, ledgers_to_expire: u32) -> (LedgerEntry, u32) {
 fn contract_id(s: &str) -> Result<[u8; 32], Error> {
     utils::contract_id_from_str(s).map_err(|e| Error::CannotParseContractId(s.to_string(), e))
 }
+
+/// The contracts and the Wasm that the entries of `keys` belong to, for the history.
+fn owners(keys: &[LedgerKey]) -> (Vec<[u8; 32]>, Option<[u8; 32]>) {
+    let mut contract_ids = vec![];
+    let mut wasm_hash = None;
+    for key in keys {
+        match key {
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: ScAddress::Contract(Hash(id)),
+                ..
+            }) if !contract_ids.contains(id) => contract_ids.push(*id),
+            LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: Hash(hash), ..
+            }) => wasm_hash = Some(*hash),
+            _ => {}
+        }
+    }
+    (contract_ids, wasm_hash)
+}
@@ -34,8 +34,16 @@ use crate::{
 #[group(skip)]
 pub struct Cmd {
     /// Contract ID to fetch
-    #[arg(long = "id", env = "SOROBAN_CONTRACT_ID")]
-    pub contract_id: String,
+    #[arg(
+        long = "id",
+        env = "SOROBAN_CONTRACT_ID",
+        required_unless_present = "wasm_hash",
+        conflicts_with = "wasm_hash"
+    )]
+    pub contract_id: Option<String>,
+    /// Hash of the installed contract code to fetch, in hex, instead of a contract's code
+    #[arg(long)]
+    pub wasm_hash: Option<String>,
     /// Where to write output otherwise stdout is used
     #[arg(long, short = 'o')]
     pub out_file: Option<std::path::PathBuf>,
@@ -84,6 +92,8 @@ pub enum Error {
     CannotWriteContractFile(PathBuf, io::Error),
     #[error("cannot parse contract ID {0}: {1}")]
     CannotParseContractId(String, DecodeError),
+    #[error("cannot parse wasm hash {0}, expected 32 bytes of hex")]
+    CannotParseWasmHash(String),
     #[error("network details not provided")]
     NetworkNotProvided,
     #[error(transparent)]
@@ -136,11 +146,14 @@ impl Cmd {
     pub async fn run_against_rpc_server(&self) -> Result<Vec<u8>, Error> {
         let network = self.network()?;
         tracing::trace!(?network);
-        let contract_id = self.contract_id()?;
         let client = Client::new(&network.rpc_url)?;
         client
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
+        if let Some(hash) = self.wasm_hash()? {
+            return Ok(client.get_remote_wasm_from_hash(hash).await?);
+        }
+        let contract_id = self.contract_id()?;
         // async closures are not yet stable
         Ok(client.get_remote_wasm(&contract_id).await?)
     }
@@ -150,16 +163,36 @@ impl Cmd {
     }
 
     pub fn run_in_sandbox(&self) -> Result<Vec<u8>, Error> {
-        let contract_id = self.contract_id()?;
         // Initialize storage and host
         let snap = Rc::new(self.get_state()?);
         let mut storage = Storage::with_recording_footprint(snap);
+        if let Some(hash) = self.wasm_hash()? {
+            return Ok(get_contract_code_from_storage(&mut storage, hash)?);
+        }
+        let contract_id = self.contract_id()?;
         Ok(get_contract_wasm_from_storage(&mut storage, contract_id)?)
     }
 
     fn contract_id(&self) -> Result<[u8; 32], Error> {
-        utils::contract_id_from_str(&self.contract_id)
-            .map_err(|e| Error::CannotParseContractId(self.contract_id.clone(), e))
+        let contract_id = self.locator.resolve_contract_id(
+            self.contract_id.as_deref().unwrap_or_default(),
+            &self.network.alias_key(&self.locator)?,
+        )?;
+        utils::contract_id_from_str(&contract_id)
+            .map_err(|e| Error::CannotParseContractId(contract_id.clone(), e))
+    }
+
+    fn wasm_hash(&self) -> Result<Option<xdr::Hash>, Error> {
+        self.wasm_hash
+            .as_ref()
+            .map(|hash| {
+                hex::decode(hash)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .map(xdr::Hash)
+                    .ok_or_else(|| Error::CannotParseWasmHash(hash.clone()))
+            })
+            .transpose()
     }
 }
 
@@ -188,28 +221,7 @@ pub fn get_contract_wasm_from_storage(
                 ..
             } => match executable {
                 ContractExecutable::Wasm(hash) => {
-                    if let Ok(rc) = storage.get(
-                        &LedgerKey::ContractCode(LedgerKeyContractCode {
-                            hash: hash.clone(),
-                            body_type: ContractEntryBodyType::DataEntry,
-                        })
-                        .into(),
-                        &Budget::default(),
-                    ) {
-                        match rc.as_ref() {
-                            xdr::LedgerEntry {
-                                data:
-                                    LedgerEntryData::ContractCode(ContractCodeEntry {
-                                        body: ContractCodeEntryBody::DataEntry(code),
-                                        ..
-                                    }),
-                                ..
-                            } => Ok(code.to_vec()),
-                            _ => Err(FromWasmError::NotFound),
-                        }
-                    } else {
-                        Err(FromWasmError::NotFound)
-                    }
+                    get_contract_code_from_storage(storage, hash.clone())
                 }
                 ContractExecutable::Token => todo!(),
             },
@@ -218,3 +230,27 @@ pub fn get_contract_wasm_from_storage(
         _ => Err(FromWasmError::NotFound),
     }
 }
+
+pub fn get_contract_code_from_storage(
+    storage: &mut Storage,
+    hash: xdr::Hash,
+) -> Result<Vec<u8>, FromWasmError> {
+    let key = LedgerKey::ContractCode(LedgerKeyContractCode {
+        hash,
+        body_type: ContractEntryBodyType::DataEntry,
+    });
+    match storage.get(&key.into(), &Budget::default()) {
+        Ok(rc) => match rc.as_ref() {
+            xdr::LedgerEntry {
+                data:
+                    LedgerEntryData::ContractCode(ContractCodeEntry {
+                        body: ContractCodeEntryBody::DataEntry(code),
+                        ..
+                    }),
+                ..
+            } => Ok(code.to_vec()),
+            _ => Err(FromWasmError::NotFound),
+        },
+        _ => Err(FromWasmError::NotFound),
+    }
+}
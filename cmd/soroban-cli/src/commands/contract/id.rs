@@ -0,0 +1,65 @@
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    AccountId, ContractIdPreimage, ContractIdPreimageFromAddress, PublicKey, ScAddress, Uint256,
+};
+
+use crate::commands::{config, contract::deploy, global};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Salt the contract will be deployed with, as passed to `contract deploy --salt`
+    #[arg(long)]
+    salt: String,
+
+    #[command(flatten)]
+    config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Deploy(#[from] deploy::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let contract_id = self.contract_id()?;
+        global_args.formatter().print(
+            contract_id,
+            &serde_json::json!({
+                "contractId": contract_id.to_string(),
+                "hex": hex::encode(contract_id.0),
+            }),
+        )?;
+        Ok(())
+    }
+
+    pub fn contract_id(&self) -> Result<stellar_strkey::Contract, Error> {
+        let salt = deploy::parse_salt(&self.salt)?;
+        // The deployer may be given as a plain account ID, no secret needed.
+        let source = match self
+            .config
+            .source_account
+            .as_deref()
+            .map(stellar_strkey::ed25519::PublicKey::from_string)
+        {
+            Some(Ok(public_key)) => public_key,
+            _ => self.config.public_key()?,
+        };
+        let network = self.config.get_network()?;
+        // The WASM isn't part of the preimage, so the ID is known before it is installed.
+        let preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+                source.0,
+            )))),
+            salt: Uint256(salt),
+        });
+        let contract_id = deploy::get_contract_id(preimage, &network.network_passphrase)?;
+        Ok(stellar_strkey::Contract(contract_id.0))
+    }
+}
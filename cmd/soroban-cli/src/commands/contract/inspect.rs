@@ -1,22 +1,45 @@
 use clap::{command, Parser};
-use soroban_env_host::xdr;
+use regex::Regex;
+use soroban_env_host::xdr::{self, ScSpecEntry, WriteXdr};
 use std::{fmt::Debug, path::PathBuf};
 use tracing::debug;
 
 use super::SpecOutput;
-use crate::{commands::config::locator, wasm};
+use crate::{
+    commands::config::{locator, network},
+    encoding, rpc,
+    utils::{contract_imports::ContractImports, contract_spec::ContractSpec},
+    wasm,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     wasm: wasm::Args,
-    /// Output just XDR in base64
+    /// Format to output the spec in
     #[arg(long, default_value = "docs")]
     output: SpecOutput,
 
+    /// Only output the spec entries, functions and types, with names matching this glob, e.g.
+    /// `get_*`. `*` matches any characters and `?` a single one
+    #[arg(long, conflicts_with = "function")]
+    filter: Option<String>,
+
+    /// Only output the spec entry of this function
+    #[arg(long = "fn")]
+    function: Option<String>,
+
+    #[command(flatten)]
+    encoding: encoding::Args,
+
     #[clap(flatten)]
     locator: locator::Args,
+
+    /// With a network, warn if the contract's environment interface version is not supported by
+    /// the network's protocol
+    #[command(flatten)]
+    network: network::Args,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -29,21 +52,138 @@ pub enum Error {
     Xdr(#[from] xdr::Error),
     #[error(transparent)]
     Spec(#[from] crate::utils::contract_spec::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error("function {0:?} not found in {1:?}")]
+    FunctionNotFound(String, PathBuf),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Parser(#[from] wasmparser::BinaryReaderError),
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        let wasm = self.wasm.parse()?;
+    pub async fn run(&self) -> Result<(), Error> {
+        let mut wasm = self.wasm.parse()?;
+        if !self.network.is_no_network() {
+            self.check_interface_version(&wasm).await?;
+        }
+        if let Some(function) = &self.function {
+            wasm.spec.retain(|entry| {
+                matches!(entry, ScSpecEntry::FunctionV0(_)) && entry_name(entry) == *function
+            });
+            if wasm.spec.is_empty() {
+                return Err(Error::FunctionNotFound(
+                    function.clone(),
+                    self.wasm.wasm.clone(),
+                ));
+            }
+        } else if let Some(filter) = &self.filter {
+            let filter = glob(filter);
+            wasm.spec
+                .retain(|entry| filter.is_match(&entry_name(entry)));
+        }
         debug!("File: {}", self.wasm.wasm.to_string_lossy());
         let output = match self.output {
-            SpecOutput::XdrBase64 => wasm
-                .spec_base64
-                .clone()
-                .ok_or_else(|| Error::MissingSpec(self.wasm.wasm.clone()))?,
-            SpecOutput::XdrBase64Array => wasm.spec_as_json_array()?,
+            SpecOutput::XdrBase64 => {
+                if wasm.spec_base64.is_none() {
+                    return Err(Error::MissingSpec(self.wasm.wasm.clone()));
+                }
+                let bytes = wasm
+                    .spec
+                    .iter()
+                    .map(WriteXdr::to_xdr)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .concat();
+                return Ok(self.encoding.print(&bytes)?);
+            }
+            SpecOutput::XdrBase64Array => {
+                let entries = wasm
+                    .spec
+                    .iter()
+                    .map(|e| Ok(format!("\"{}\"", self.encoding.encode(&e.to_xdr()?))))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                format!("[{}]", entries.join(",\n"))
+            }
             SpecOutput::Docs => wasm.to_string(),
+            SpecOutput::Json => soroban_spec_json::generate(&wasm.spec),
+            SpecOutput::Imports => {
+                let mut imports = ContractImports::new(&self.wasm.read()?)?;
+                if self.function.is_some() || self.filter.is_some() {
+                    imports.calls.retain(|name, _| {
+                        wasm.spec.iter().any(|entry| {
+                            matches!(entry, ScSpecEntry::FunctionV0(_))
+                                && entry_name(entry) == *name
+                        })
+                    });
+                }
+                imports.to_string()
+            }
         };
         println!("{output}");
         Ok(())
     }
+
+    async fn check_interface_version(&self, wasm: &ContractSpec) -> Result<(), Error> {
+        let Some((protocol, pre_release)) = wasm.interface_version() else {
+            return Ok(());
+        };
+        let network = self.network.get(&self.locator)?;
+        let network_protocol = rpc::Client::new(&network.rpc_url)?
+            .get_network()
+            .await?
+            .protocol_version;
+        if protocol > network_protocol {
+            eprintln!(
+                "warning: the contract was built for protocol {protocol}, the network is on protocol {network_protocol}, it cannot be deployed"
+            );
+        } else if pre_release != 0 && protocol < network_protocol {
+            eprintln!(
+                "warning: the contract was built against a pre-release of protocol {protocol}, the network is on protocol {network_protocol}, it cannot be deployed"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A regex matching the whole of the names matched by the glob `pattern`.
+fn glob(pattern: &str) -> Regex {
+    let re = pattern
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{re}$")).expect("escaped glob should be a valid regex")
+}
+
+fn entry_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtStructV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtUnionV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtEnumV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtErrorEnumV0(x) => x.name.to_string_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob;
+
+    #[test]
+    fn glob_matches_whole_names() {
+        let filter = glob("get_*");
+        assert!(filter.is_match("get_balance"));
+        assert!(!filter.is_match("try_get_balance"));
+        assert!(glob("de?").is_match("dec"));
+        assert!(glob("a.b").is_match("a.b"));
+        assert!(!glob("a.b").is_match("axb"));
+    }
 }
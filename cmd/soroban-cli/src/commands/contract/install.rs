@@ -2,16 +2,19 @@ use std::array::TryFromSliceError;
 use std::fmt::Debug;
 use std::num::ParseIntError;
 
-use clap::{command, Parser};
+use clap::{arg, command, Parser};
 use soroban_env_host::xdr::{
-    Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp, Memo, MuxedAccount, Operation,
-    OperationBody, Preconditions, SequenceNumber, Transaction, TransactionExt, TransactionResult,
-    TransactionResultResult, Uint256, VecM,
+    Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp, LedgerKey, Memo, MuxedAccount,
+    Operation, OperationBody, Preconditions, SequenceNumber, Transaction, TransactionExt,
+    TransactionResult, TransactionResultResult, Uint256, VecM,
 };
 
 use super::restore;
-use crate::rpc::{self, Client};
-use crate::{commands::config, utils, wasm};
+use crate::rpc::{self, Client, Outcome};
+use crate::{
+    commands::{config, global, history},
+    utils, wasm,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -22,6 +25,9 @@ pub struct Cmd {
     pub fee: crate::fee::Args,
     #[command(flatten)]
     pub wasm: wasm::Args,
+    /// Upload the WASM even if the network already has contract code with the same hash
+    #[arg(long)]
+    pub force: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -44,21 +50,31 @@ pub enum Error {
     UnexpectedSimulateTransactionResultSize { length: usize },
     #[error(transparent)]
     Restore(#[from] restore::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
-        let res_str = hex::encode(self.run_and_get_hash().await?);
-        println!("{res_str}");
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let Outcome::Done(hash) = self.run_and_get_hash(global_args).await? else {
+            return Ok(());
+        };
+        let wasm_hash = hex::encode(hash);
+        global_args
+            .formatter()
+            .print(&wasm_hash, &serde_json::json!({ "wasmHash": wasm_hash }))?;
         Ok(())
     }
 
-    pub async fn run_and_get_hash(&self) -> Result<Hash, Error> {
+    pub async fn run_and_get_hash(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<Outcome<Hash>, Error> {
         let contract = self.wasm.read()?;
         if self.config.is_no_network() {
-            self.run_in_sandbox(contract)
+            Ok(Outcome::Done(self.run_in_sandbox(contract)?))
         } else {
-            self.run_against_rpc_server(contract).await
+            self.run_against_rpc_server(global_args, contract).await
         }
     }
 
@@ -75,38 +91,54 @@ impl Cmd {
         Ok(wasm_hash)
     }
 
-    async fn run_against_rpc_server(&self, contract: Vec<u8>) -> Result<Hash, Error> {
+    async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+        contract: Vec<u8>,
+    ) -> Result<Outcome<Hash>, Error> {
         let network = self.config.get_network()?;
         let client = Client::new(&network.rpc_url)?;
         client
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
-        let key = self.config.key_pair()?;
+        if !self.force {
+            let code_key: LedgerKey = self.wasm.clone().try_into()?;
+            let existing = client.get_ledger_entries(vec![code_key]).await?.entries;
+            if existing.is_some_and(|entries| !entries.is_empty()) {
+                eprintln!("Contract code is already installed, skipping upload. Use --force to upload it anyway.");
+                return Ok(Outcome::Done(utils::contract_hash(&contract)?));
+            }
+        }
+        let key = self.config.public_key()?;
 
         // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
-        let account_details = client.get_account(&public_strkey).await?;
+        let account_details = client.get_account(&key.to_string()).await?;
         let sequence: i64 = account_details.seq_num.into();
 
         let (tx_without_preflight, hash) =
             build_install_contract_code_tx(contract.clone(), sequence + 1, self.fee.fee, &key)?;
 
-        // Currently internal errors are not returned if the contract code is expired
-        if let (
-            TransactionResult {
-                result: TransactionResultResult::TxInternalError,
-                ..
-            },
-            _,
-            _,
-        ) = client
-            .prepare_and_send_transaction(
-                &tx_without_preflight,
-                &key,
-                &network.network_passphrase,
-                None,
+        let tx = self.fee.apply_to_assembled_tx(
+            client
+                .prepare_transaction(&tx_without_preflight, None)
+                .await?,
+        );
+        let Outcome::Done((result, _, _)) = client
+            .send_transaction(
+                &self
+                    .config
+                    .sign_for_submission(&tx, self.fee.fee, &network.network_passphrase)?,
+                &global_args.submission(&network.network_passphrase),
             )
             .await?
+        else {
+            return Ok(Outcome::DryRun);
+        };
+        // Currently internal errors are not returned if the contract code is expired
+        if let TransactionResult {
+            result: TransactionResultResult::TxInternalError,
+            ..
+        } = result
         {
             // Now just need to restore it and don't have to install again
             restore::Cmd {
@@ -118,11 +150,12 @@ impl Cmd {
                 config: self.config.clone(),
                 fee: self.fee.clone(),
             }
-            .run_against_rpc_server()
+            .run_against_rpc_server(global_args)
             .await?;
         }
+        history::record(&self.config, &network, &tx, &[], Some(&hash.0));
 
-        Ok(hash)
+        Ok(Outcome::Done(hash))
     }
 }
 
@@ -130,12 +163,12 @@ pub(crate) fn build_install_contract_code_tx(
     source_code: Vec<u8>,
     sequence: i64,
     fee: u32,
-    key: &ed25519_dalek::Keypair,
+    key: &stellar_strkey::ed25519::PublicKey,
 ) -> Result<(Transaction, Hash), XdrError> {
     let hash = utils::contract_hash(&source_code)?;
 
     let op = Operation {
-        source_account: Some(MuxedAccount::Ed25519(Uint256(key.public.to_bytes()))),
+        source_account: Some(MuxedAccount::Ed25519(Uint256(key.0))),
         body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
             host_function: HostFunction::UploadContractWasm(source_code.try_into()?),
             auth: VecM::default(),
@@ -143,7 +176,7 @@ pub(crate) fn build_install_contract_code_tx(
     };
 
     let tx = Transaction {
-        source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+        source_account: MuxedAccount::Ed25519(Uint256(key.0)),
         fee,
         seq_num: SequenceNumber(sequence),
         cond: Preconditions::None,
@@ -165,8 +198,10 @@ mod tests {
             b"foo".to_vec(),
             300,
             1,
-            &utils::parse_secret_key("SBFGFF27Y64ZUGFAIG5AMJGQODZZKV2YQKAVUUN4HNE24XZXD2OEUVUP")
-                .unwrap(),
+            &stellar_strkey::ed25519::PublicKey::from_string(
+                "GBHKQE6YKBKIA5WU5NF3KPBCLV5KVTXYNBQDVIKNI5QZTNZGOHBIDR4S",
+            )
+            .unwrap(),
         );
 
         assert!(result.is_ok());
@@ -4,20 +4,32 @@ use std::ffi::OsString;
 use std::num::ParseIntError;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::{fmt::Debug, fs, io, rc::Rc};
+use std::{
+    fmt::Debug,
+    fs, io,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use clap::{arg, command, value_parser, Parser};
 use heck::ToKebabCase;
+use sha2::{Digest, Sha256};
 use soroban_env_host::{
     budget::Budget,
     events::HostEvent,
     storage::Storage,
     xdr::{
-        self, AccountId, Error as XdrError, Hash, HostFunction, InvokeContractArgs,
-        InvokeHostFunctionOp, LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyAccount, Memo,
-        MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScAddress, ScSpecEntry,
+        self, AccountId, ConfigSettingEntry, ConfigSettingId, ContractCodeEntry,
+        ContractDataDurability, ContractDataEntry, ContractDataEntryBody, ContractDataEntryData,
+        ContractEntryBodyType, ContractEvent, ContractEventType, ContractExecutable,
+        DiagnosticEvent, Error as XdrError, Hash, HostFunction, InvokeContractArgs,
+        InvokeHostFunctionOp, LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyAccount,
+        LedgerKeyContractCode, LedgerKeyContractData, Memo, MuxedAccount, Operation, OperationBody,
+        Preconditions, PublicKey, ReadXdr, ScAddress, ScContractInstance, ScSpecEntry,
         ScSpecFunctionV0, ScSpecTypeDef, ScVal, ScVec, SequenceNumber, SorobanAddressCredentials,
-        SorobanAuthorizationEntry, SorobanCredentials, Transaction, TransactionExt, Uint256, VecM,
+        SorobanAuthorizationEntry, SorobanCredentials, SorobanResources, SorobanTransactionData,
+        Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, VecM,
+        WriteXdr,
     },
     DiagnosticLevel, Host, HostError,
 };
@@ -25,14 +37,18 @@ use soroban_env_host::{
 use soroban_spec::read::FromWasmError;
 use stellar_strkey::DecodeError;
 
-use super::super::{
-    config::{self, events_file, locator},
-    events,
+use super::{
+    super::{
+        config::{self, events_file, locator},
+        events, global, history,
+    },
+    restore,
 };
 use crate::{
-    commands::HEADING_SANDBOX,
-    rpc::{self, Client},
-    utils::{self, contract_spec, create_ledger_footprint, default_account_ledger_entry},
+    commands::{HEADING_RPC, HEADING_SANDBOX},
+    output::Output,
+    rpc::{self, Client, Outcome},
+    utils::{self, contract_spec, create_ledger_footprint, default_account_ledger_entry, parsing},
     Pwd,
 };
 use soroban_spec_tools::Spec;
@@ -41,7 +57,7 @@ use soroban_spec_tools::Spec;
 #[allow(clippy::struct_excessive_bools)]
 #[group(skip)]
 pub struct Cmd {
-    /// Contract ID to invoke
+    /// Contract ID or alias to invoke
     #[arg(long = "id", env = "SOROBAN_CONTRACT_ID")]
     pub contract_id: String,
     /// WASM file of the contract to invoke (if using sandbox will deploy this file)
@@ -58,6 +74,72 @@ pub struct Cmd {
           help_heading = HEADING_SANDBOX)]
     pub unlimited_budget: bool,
 
+    /// Instead of submitting, write each authorization entry the invocation requires to its
+    /// own file in this directory so other signers can sign them
+    #[arg(long, alias = "write-auth", conflicts_with = "auth_in", help_heading = HEADING_RPC)]
+    pub auth_out: Option<PathBuf>,
+    /// Number of ledgers the entries written by `--auth-out` remain valid for
+    #[arg(long, default_value = "720", help_heading = HEADING_RPC)]
+    pub auth_expiration_ledgers: u32,
+    /// Directory of signed authorization entries, as written by `--auth-out` and signed with
+    /// `tx sign-auth`, to include in the transaction
+    #[arg(long, alias = "auth", help_heading = HEADING_RPC)]
+    pub auth_in: Option<PathBuf>,
+    /// Whether to submit the transaction. With `no` the invocation is only simulated, and its
+    /// return value, events, fees, and footprint are printed as JSON, at no cost
+    #[arg(long, value_enum, default_value_t, help_heading = HEADING_RPC)]
+    pub send: SendMode,
+    /// Print to stderr which addresses must authorize which invocations, and with which
+    /// arguments, before signing. Combine with `--send=no` to only preview them
+    #[arg(long)]
+    pub explain_auth: bool,
+    /// Print to stderr the contract data entries the invocation added, removed or changed
+    #[arg(long)]
+    pub show_state_diff: bool,
+    /// If simulation fails because the contract's instance or code is archived, restore them
+    /// with a RestoreFootprint transaction, then invoke again
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub auto_restore: bool,
+    /// Simulate the invocation against the local sandbox state as it was at this ledger,
+    /// without changing it. Past states are only kept for ledgers written with
+    /// `--keep-history`; the RPC only serves the latest state
+    #[arg(long, conflicts_with_all = ["wasm", "network", "rpc_url"])]
+    pub at_ledger: Option<u32>,
+    /// Invoke against the ledger entries of this snapshot file, as written by `snapshot
+    /// create`, instead of the network or the ledger file, without changing it
+    #[arg(long, conflicts_with = "at_ledger")]
+    pub snapshot: Option<PathBuf>,
+    /// Print to stderr each change in the submitted transaction's status, the ledger it was
+    /// included in, and its decoded result meta
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub watch: bool,
+    /// Seconds to wait for the submitted transaction to be included in a ledger [default: 10]
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub timeout: Option<u64>,
+    /// Sequence number of the transaction, instead of reserving the account's next one
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub sequence: Option<i64>,
+    /// Print to stderr the resources the simulated invocation uses, such as instructions and
+    /// bytes read and written, against the network's per-transaction limits
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub profile: bool,
+
+    /// Function to invoke. When given, arguments after `--` are passed as `name=value`, e.g.
+    /// `--fn transfer -- to=G... amount=100`
+    #[arg(long = "fn")]
+    pub function: Option<String>,
+
+    /// JSON or TOML (by `.toml` extension) file of an object mapping argument names to their
+    /// values, as an alternative to passing long arguments after `--`. Arguments given after
+    /// `--` take precedence
+    #[arg(long)]
+    pub args_file: Option<PathBuf>,
+
+    /// Instead of invoking, list the contract's functions with their argument types and docs,
+    /// fetching the spec from the network if no `--wasm` is given
+    #[arg(long, conflicts_with_all = ["function", "args_file", "CONTRACT_FN_AND_ARGS"])]
+    pub list: bool,
+
     /// Function name as subcommand, then arguments for that function as `--arg-name value`
     #[arg(last = true, id = "CONTRACT_FN_AND_ARGS")]
     pub slop: Vec<OsString>,
@@ -149,12 +231,66 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error("Contract Error\n{0}: {1}")]
     ContractInvoke(String, String),
+    #[error("{message}{events}")]
+    InvocationFailed { message: String, events: String },
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
     ContractSpec(#[from] contract_spec::Error),
     #[error("")]
     MissingFileArg(PathBuf),
+    #[error("writing auth entry {0:?}: {1}")]
+    CannotWriteAuthEntry(PathBuf, io::Error),
+    #[error("reading auth entries from {0:?}: {1}")]
+    CannotReadAuthEntries(PathBuf, io::Error),
+    #[error("no auth entries found in {0:?}")]
+    NoAuthEntries(PathBuf),
+    #[error("expected argument of the form name=value, got {0:?}")]
+    InvalidNamedArg(String),
+    #[error("function {function} has no argument {arg}, expected one of: {expected}")]
+    UnknownNamedArg {
+        function: String,
+        arg: String,
+        expected: String,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("reading args file {0:?}: {1}")]
+    CannotReadArgsFile(PathBuf, io::Error),
+    #[error("parsing args file {0:?}: {1}")]
+    CannotParseArgsFile(PathBuf, String),
+    #[error("args file {0:?} must hold an object of argument names to values")]
+    ArgsFileNotObject(PathBuf),
+    #[error(
+        "{error}\nthe contract's {archived} archived, restore with `--auto-restore` or \
+         `soroban contract restore --id {contract_id}`"
+    )]
+    ArchivedEntries {
+        error: rpc::Error,
+        archived: String,
+        contract_id: String,
+    },
+    #[error(transparent)]
+    Restore(#[from] restore::Error),
+    #[error("reading snapshot {0:?}: {1}")]
+    CannotReadSnapshot(PathBuf, soroban_ledger_snapshot::Error),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, clap::ValueEnum)]
+pub enum SendMode {
+    /// Simulate, then sign and submit the transaction
+    #[default]
+    Yes,
+    /// Only simulate the transaction
+    No,
+}
+
+/// The outcome of invoking a function in an in-process host.
+pub struct HostInvocation {
+    /// The return value, or with JSON output an object with it and the invocation's events
+    pub output: String,
+    pub events: Vec<HostEvent>,
+    pub budget: Budget,
 }
 
 impl From<Infallible> for Error {
@@ -168,6 +304,7 @@ impl Cmd {
         &self,
         contract_id: [u8; 32],
         spec_entries: &[ScSpecEntry],
+        network_id: &Hash,
     ) -> Result<(String, Spec, InvokeContractArgs), Error> {
         let spec = Spec(Some(spec_entries.to_vec()));
         let mut cmd = clap::Command::new(self.contract_id.clone())
@@ -179,7 +316,26 @@ impl Cmd {
             cmd = cmd.subcommand(build_custom_cmd(&name.to_string_lossy(), &spec)?);
         }
         cmd.build();
-        let mut matches_ = cmd.get_matches_from(&self.slop);
+        let mut slop = if let Some(function) = &self.function {
+            named_args_to_slop(function, &self.slop, &spec)?
+        } else {
+            self.slop.clone()
+        };
+        if let Some(path) = &self.args_file {
+            let given = slop.clone();
+            for (name, value) in read_args_file(path)? {
+                let flag = format!("--{name}");
+                let prefix = format!("{flag}=");
+                if !given.iter().any(|arg| {
+                    let arg = arg.to_string_lossy();
+                    arg == flag || arg.starts_with(&prefix)
+                }) {
+                    slop.push(flag.into());
+                    slop.push(value.into());
+                }
+            }
+        }
+        let mut matches_ = cmd.get_matches_from(&slop);
         let (function, matches_) = &matches_.remove_subcommand().unwrap();
 
         let func = spec.find_function(function)?;
@@ -199,6 +355,13 @@ impl Cmd {
                         };
                         if let Ok(address) = cmd.public_key() {
                             s = address.to_string();
+                        } else if let Ok(asset) = parsing::parse_asset(&s) {
+                            // Classic assets, e.g. `USDC:G...`, become their asset contract
+                            let hash = utils::contract_id_hash_from_asset_and_network_id(
+                                &asset,
+                                network_id.clone(),
+                            )?;
+                            s = stellar_strkey::Contract(hash.0).to_string();
                         }
                     }
                     spec.from_string(&s, &i.type_)
@@ -254,21 +417,45 @@ impl Cmd {
         Ok((function.clone(), spec, invoke_args))
     }
 
-    pub async fn run(&self) -> Result<(), Error> {
-        let res = self.invoke().await?;
-        println!("{res}");
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if let Outcome::Done(res) = self.invoke(global_args).await? {
+            print_result(global_args, &res)?;
+        }
         Ok(())
     }
 
-    pub async fn invoke(&self) -> Result<String, Error> {
-        if self.config.is_no_network() {
-            self.run_in_sandbox()
+    pub async fn invoke(&self, global_args: &global::Args) -> Result<Outcome<String>, Error> {
+        if self.list {
+            return Ok(Outcome::Done(self.list_functions().await?));
+        }
+        if self.config.is_no_network() || self.at_ledger.is_some() || self.snapshot.is_some() {
+            Ok(Outcome::Done(
+                self.run_in_sandbox(global_args.output_format)?,
+            ))
         } else {
-            self.run_against_rpc_server().await
+            self.run_against_rpc_server(global_args).await
         }
     }
 
-    pub async fn run_against_rpc_server(&self) -> Result<String, Error> {
+    /// Invoke as with `--send=no`, which only simulates on a network, returning what that
+    /// prints.
+    pub async fn simulate(&self) -> Result<String, Error> {
+        let cmd = Cmd {
+            send: SendMode::No,
+            ..self.clone()
+        };
+        // Simulations submit nothing, so there is no dry run to tell apart.
+        match cmd.invoke(&global::Args::default()).await? {
+            Outcome::Done(output) => Ok(output),
+            Outcome::DryRun => unreachable!("simulations are never submitted"),
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    pub async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<Outcome<String>, Error> {
         let network = self.config.get_network()?;
         tracing::trace!(?network);
         let contract_id = self.contract_id()?;
@@ -276,12 +463,14 @@ impl Cmd {
         client
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
-        let key = self.config.key_pair()?;
+        let public_key = self.config.public_key()?;
 
-        // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
-        let account_details = client.get_account(&public_strkey).await?;
-        let sequence: i64 = account_details.seq_num.into();
+        // A sequence number is only reserved right before signing, so previews and failed
+        // simulations don't use one up.
+        let sequence = match self.sequence {
+            Some(sequence) => sequence,
+            None => account_sequence(&client, &public_key).await? + 1,
+        };
 
         // Get the contract
         let spec_entries = if let Some(spec) = self.spec_entries()? {
@@ -291,18 +480,168 @@ impl Cmd {
         };
 
         // Get the ledger footprint
-        let (function, spec, host_function_params) =
-            self.build_host_function_parameters(contract_id, &spec_entries)?;
-        let tx = build_invoke_contract_tx(
+        let (function, spec, host_function_params) = self.build_host_function_parameters(
+            contract_id,
+            &spec_entries,
+            &Hash(Sha256::digest(network.network_passphrase.as_bytes()).into()),
+        )?;
+        let mut tx = build_invoke_contract_tx(
             host_function_params.clone(),
-            sequence + 1,
+            sequence,
             self.fee.fee,
-            &key,
+            &public_key,
         )?;
 
-        let (result, meta, events) = client
-            .prepare_and_send_transaction(&tx, &key, &network.network_passphrase, Some(log_events))
-            .await?;
+        if let Some(dir) = &self.auth_out {
+            let tx = client.prepare_transaction(&tx, Some(log_events)).await?;
+            let expiration_ledger =
+                client.get_latest_ledger().await?.sequence + self.auth_expiration_ledgers;
+            return write_auth_entries(dir, &tx, expiration_ledger).map(Outcome::Done);
+        }
+        if let Some(dir) = &self.auth_in {
+            let auth = read_auth_entries(dir)?;
+            let mut op = tx.operations[0].clone();
+            if let OperationBody::InvokeHostFunction(ref mut body) = op.body {
+                body.auth = auth;
+            }
+            tx.operations = vec![op].try_into()?;
+        }
+        if self.send == SendMode::No {
+            let simulation = client
+                .simulate_transaction(&TransactionEnvelope::Tx(TransactionV1Envelope {
+                    tx,
+                    signatures: VecM::default(),
+                }))
+                .await
+                .map_err(|e| name_contract_errors(e, &spec))?;
+            if self.profile {
+                let data = SorobanTransactionData::from_xdr_base64(&simulation.transaction_data)?;
+                self.print_profile(&client, &data.resources).await?;
+            }
+            if self.explain_auth {
+                let auth = simulation
+                    .results
+                    .iter()
+                    .flat_map(|r| &r.auth)
+                    .map(SorobanAuthorizationEntry::from_xdr_base64)
+                    .collect::<Result<Vec<_>, _>>()?;
+                eprint!("{}", crate::log::explain_auth(&auth));
+            }
+            return simulation_to_json(&simulation, &spec, &function, self.fee.fee)
+                .map(Outcome::Done);
+        }
+
+        let simulate_start = Instant::now();
+        let assembled = match client.prepare_transaction(&tx, Some(log_events)).await {
+            Err(error @ rpc::Error::TransactionSimulationFailed { .. }) => {
+                let archived = archived_entries(&client, contract_id).await?;
+                if archived.is_empty() {
+                    return Err(name_contract_errors(error, &spec));
+                }
+                if !self.auto_restore {
+                    return Err(Error::ArchivedEntries {
+                        error,
+                        archived: describe_archived(&archived),
+                        contract_id: stellar_strkey::Contract(contract_id).to_string(),
+                    });
+                }
+                eprintln!("Restoring the contract's {}", describe_archived(&archived));
+                if self.restore_cmd().restore(global_args, archived).await? == Outcome::DryRun {
+                    return Ok(Outcome::DryRun);
+                }
+                // The restore used the sequence number the invocation was built with.
+                if self.sequence.is_some() {
+                    tx.seq_num = SequenceNumber(tx.seq_num.0 + 1);
+                }
+                client
+                    .prepare_transaction(&tx, Some(log_events))
+                    .await
+                    .map_err(|e| name_contract_errors(e, &spec))?
+            }
+            res => res.map_err(|e| name_contract_errors(e, &spec))?,
+        };
+        let mut tx = self.fee.apply_to_assembled_tx(assembled);
+        let simulate_time = simulate_start.elapsed();
+        if let (true, TransactionExt::V1(data)) = (self.profile, &tx.ext) {
+            self.print_profile(&client, &data.resources).await?;
+        }
+        if self.explain_auth {
+            eprint!("{}", crate::log::explain_auth(&auth_entries(&tx)));
+        }
+        let state_keys = if self.show_state_diff {
+            written_contract_data(&tx)
+        } else {
+            vec![]
+        };
+        let state_before = read_entries(&client, &state_keys).await?;
+        let send_start = Instant::now();
+        let timeout = self
+            .timeout
+            .map_or(rpc::DEFAULT_SUBMISSION_TIMEOUT, Duration::from_secs);
+        let submission = global_args.submission(&network.network_passphrase);
+        // A dry run submits nothing, so it keeps the sequence number the invocation was built
+        // with rather than reserve one.
+        if self.sequence.is_none() && !submission.dry_run {
+            tx.seq_num = SequenceNumber(
+                self.next_sequence(&client, &network.network_passphrase, &public_key)
+                    .await?,
+            );
+        }
+        let mut retried = false;
+        let (result, meta, events, send_metrics) = loop {
+            let sent = client
+                .send_transaction_with_metrics(
+                    &self.config.sign_for_submission(
+                        &tx,
+                        self.fee.fee,
+                        &network.network_passphrase,
+                    )?,
+                    &submission,
+                    timeout,
+                    |status, ledger, elapsed| {
+                        if self.watch {
+                            let ledger = ledger
+                                .map(|l| format!(" in ledger {l}"))
+                                .unwrap_or_default();
+                            eprintln!("[{:>3}s] {status}{ledger}", elapsed.as_secs());
+                        }
+                    },
+                )
+                .await;
+            match sent {
+                // Another process used the reserved sequence number, or one reserved earlier
+                // was never submitted; start over from the network's.
+                Err(rpc::Error::TransactionBadSequence) if self.sequence.is_none() && !retried => {
+                    eprintln!(
+                        "Sequence number {} was rejected, retrying with the account's next one",
+                        tx.seq_num.0
+                    );
+                    let account = public_key.to_string();
+                    self.config
+                        .locator
+                        .reset_sequence(&network.network_passphrase, &account)?;
+                    tx.seq_num = SequenceNumber(
+                        self.next_sequence(&client, &network.network_passphrase, &public_key)
+                            .await?,
+                    );
+                    retried = true;
+                }
+                sent => match sent.map_err(|e| name_contract_errors(e, &spec))? {
+                    Outcome::Done(sent) => break sent,
+                    Outcome::DryRun => return Ok(Outcome::DryRun),
+                },
+            }
+        };
+        let send_time = send_start.elapsed();
+        let tx_hash = utils::transaction_hash(&tx, &network.network_passphrase)?;
+        history::record(&self.config, &network, &tx, &[contract_id], None);
+        if self.watch {
+            eprintln!("Result meta:\n{}", serde_json::to_string_pretty(&meta)?);
+        }
+        if self.show_state_diff {
+            let state_after = read_entries(&client, &state_keys).await?;
+            print_state_diff(&state_before, &state_after);
+        }
 
         tracing::debug!(?result);
         if !events.is_empty() {
@@ -310,28 +649,83 @@ impl Cmd {
         }
 
         let xdr::TransactionMeta::V3(xdr::TransactionMetaV3 {
-            soroban_meta: Some(xdr::SorobanTransactionMeta { return_value, .. }),
+            soroban_meta:
+                Some(xdr::SorobanTransactionMeta {
+                    return_value,
+                    events: contract_events,
+                    ..
+                }),
             ..
         }) = meta
         else {
             return Err(Error::MissingOperationResult);
         };
 
-        output_to_string(&spec, &return_value, &function)
+        if global_args.output_format == Output::Json {
+            return invocation_to_json(
+                &spec,
+                &return_value,
+                &function,
+                Some(hex::encode(tx_hash)),
+                Some(result.fee_charged),
+                &contract_events,
+                &events,
+                Some(serde_json::json!({
+                    "simulate_ms": simulate_time.as_millis(),
+                    "send_ms": send_time.as_millis(),
+                    "ledger": send_metrics.ledger,
+                    "polls": send_metrics.polls,
+                    "estimated_fee": tx.fee,
+                    "fee_charged": result.fee_charged,
+                })),
+            )
+            .map(Outcome::Done);
+        }
+        output_to_string(&spec, &return_value, &function).map(Outcome::Done)
     }
 
-    pub fn run_in_sandbox(&self) -> Result<String, Error> {
-        let contract_id = self.contract_id()?;
+    pub fn run_in_sandbox(&self, output: Output) -> Result<String, Error> {
         // Initialize storage and host
         // TODO: allow option to separate input and output file
-        let mut state = self.config.get_state()?;
+        let mut state = match (self.at_ledger, &self.snapshot) {
+            (Some(ledger), _) => self.config.get_state_at(ledger)?,
+            (None, Some(snapshot)) => soroban_ledger_snapshot::LedgerSnapshot::read_file(snapshot)
+                .map_err(|e| Error::CannotReadSnapshot(snapshot.clone(), e))?,
+            (None, None) => self.config.get_state()?,
+        };
+        let invocation = self.invoke_in_host(&mut state, output)?;
+        // Invocations at a past ledger or against a snapshot are read-only
+        if self.at_ledger.is_none() && self.snapshot.is_none() {
+            self.config.set_state(&mut state)?;
+            if !invocation.events.is_empty() {
+                self.events_file.commit(
+                    &invocation.events,
+                    &state,
+                    &self.config.locator.config_dir()?,
+                )?;
+            }
+        }
+        Ok(invocation.output)
+    }
+
+    /// Invoke the function in an in-process host against `state`, deploying `--wasm` to it
+    /// first if given, and update `state` with the invocation's changes.
+    #[allow(clippy::too_many_lines)]
+    pub fn invoke_in_host(
+        &self,
+        state: &mut soroban_ledger_snapshot::LedgerSnapshot,
+        output: Output,
+    ) -> Result<HostInvocation, Error> {
+        let contract_id = self.contract_id()?;
 
         // If a file is specified, deploy the contract to storage
-        self.deploy_contract_in_sandbox(&mut state, &contract_id)?;
+        self.deploy_contract_in_sandbox(state, &contract_id)?;
+
+        let state_before = contract_data_entries(state);
 
         // Create source account, adding it to the ledger if not already present.
         let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
-            self.config.key_pair()?.public.to_bytes(),
+            self.config.public_key()?.0,
         )));
         let source_account_ledger_key = LedgerKey::Account(LedgerKeyAccount {
             account_id: source_account.clone(),
@@ -368,8 +762,11 @@ impl Cmd {
         ledger_info.timestamp += 5;
         h.set_ledger_info(ledger_info.clone())?;
 
-        let (function, spec, host_function_params) =
-            self.build_host_function_parameters(contract_id, &spec_entries)?;
+        let (function, spec, host_function_params) = self.build_host_function_parameters(
+            contract_id,
+            &spec_entries,
+            &Hash(ledger_info.network_id),
+        )?;
         h.set_diagnostic_level(DiagnosticLevel::Debug)?;
         let resv = h
             .invoke_function(HostFunction::InvokeContract(host_function_params))
@@ -403,6 +800,9 @@ impl Cmd {
                 root_invocation: payload.invocation,
             })
             .collect();
+        if self.explain_auth {
+            eprint!("{}", crate::log::explain_auth(&contract_auth));
+        }
         let (storage, budget, events, expiration_ledger_bumps) = h.try_finish().map_err(|h| h.1)?;
         let footprint = &create_ledger_footprint(&storage.footprint);
         log_events(
@@ -414,12 +814,42 @@ impl Cmd {
 
         utils::bump_ledger_entry_expirations(&mut state.ledger_entries, &expiration_ledger_bumps);
 
-        self.config.set_state(&mut state)?;
-        if !events.0.is_empty() {
-            self.events_file
-                .commit(&events.0, &state, &self.config.locator.config_dir()?)?;
+        if self.show_state_diff {
+            print_state_diff(&state_before, &contract_data_entries(state));
         }
-        Ok(res_str)
+        let output = if output == Output::Json {
+            let contract_events = events
+                .0
+                .iter()
+                .filter(|e| !e.failed_call && e.event.type_ == ContractEventType::Contract)
+                .map(|e| e.event.clone())
+                .collect::<Vec<_>>();
+            let diagnostic_events = events
+                .0
+                .iter()
+                .map(|e| DiagnosticEvent {
+                    in_successful_contract_call: !e.failed_call,
+                    event: e.event.clone(),
+                })
+                .collect::<Vec<_>>();
+            invocation_to_json(
+                &spec,
+                &resv,
+                &function,
+                None,
+                None,
+                &contract_events,
+                &diagnostic_events,
+                None,
+            )?
+        } else {
+            res_str
+        };
+        Ok(HostInvocation {
+            output,
+            events: events.0,
+            budget,
+        })
     }
 
     pub fn deploy_contract_in_sandbox(
@@ -463,9 +893,267 @@ impl Cmd {
 }
 
 impl Cmd {
+    pub async fn list_functions(&self) -> Result<String, Error> {
+        let spec_entries = if let Some(spec) = self.spec_entries()? {
+            spec
+        } else if self.config.is_no_network() {
+            let state = self.config.get_state()?;
+            let mut storage = Storage::with_recording_footprint(Rc::new(state.clone()));
+            utils::get_contract_spec_from_storage(
+                &mut storage,
+                &state.sequence_number,
+                self.contract_id()?,
+            )
+            .map_err(Error::CannotParseContractSpec)?
+        } else {
+            let network = self.config.get_network()?;
+            Client::new(&network.rpc_url)?
+                .get_remote_contract_spec(&self.contract_id()?)
+                .await?
+        };
+        format_functions(&Spec(Some(spec_entries)))
+    }
+
+    async fn print_profile(
+        &self,
+        client: &Client,
+        resources: &SorobanResources,
+    ) -> Result<(), Error> {
+        let (mut instructions, mut read_entries, mut read_bytes) = (None, None, None);
+        let (mut write_entries, mut write_bytes, mut meta_data) = (None, None, None);
+        for setting in client
+            .get_config_settings(&[
+                ConfigSettingId::ContractComputeV0,
+                ConfigSettingId::ContractLedgerCostV0,
+                ConfigSettingId::ContractMetaDataV0,
+            ])
+            .await?
+        {
+            match setting {
+                ConfigSettingEntry::ContractComputeV0(compute) => {
+                    instructions = u64::try_from(compute.tx_max_instructions).ok();
+                }
+                ConfigSettingEntry::ContractLedgerCostV0(cost) => {
+                    read_entries = Some(cost.tx_max_read_ledger_entries.into());
+                    read_bytes = Some(cost.tx_max_read_bytes.into());
+                    write_entries = Some(cost.tx_max_write_ledger_entries.into());
+                    write_bytes = Some(cost.tx_max_write_bytes.into());
+                }
+                ConfigSettingEntry::ContractMetaDataV0(meta) => {
+                    meta_data = Some(meta.tx_max_extended_meta_data_size_bytes.into());
+                }
+                _ => {}
+            }
+        }
+        let footprint = &resources.footprint;
+        let read = footprint.read_only.len() + footprint.read_write.len();
+        let format = self.config.locator.read_settings()?.format;
+        eprint!(
+            "{}",
+            crate::log::profile(
+                &[
+                    ("instructions", resources.instructions.into(), instructions),
+                    ("read entries", read as u64, read_entries),
+                    ("read bytes", resources.read_bytes.into(), read_bytes),
+                    (
+                        "write entries",
+                        footprint.read_write.len() as u64,
+                        write_entries
+                    ),
+                    ("write bytes", resources.write_bytes.into(), write_bytes),
+                    (
+                        "events and return value",
+                        resources.extended_meta_data_size_bytes.into(),
+                        meta_data
+                    ),
+                ],
+                &format,
+            )
+        );
+        Ok(())
+    }
+
+    /// Reserve the account's next sequence number, see [`locator::Args::next_sequence`].
+    async fn next_sequence(
+        &self,
+        client: &Client,
+        network_passphrase: &str,
+        public_key: &stellar_strkey::ed25519::PublicKey,
+    ) -> Result<i64, Error> {
+        let current = account_sequence(client, public_key).await?;
+        Ok(self.config.locator.next_sequence(
+            network_passphrase,
+            &public_key.to_string(),
+            current,
+        )?)
+    }
+
+    fn restore_cmd(&self) -> restore::Cmd {
+        restore::Cmd {
+            contract_id: None,
+            key: vec![],
+            key_xdr: vec![],
+            wasm: None,
+            wasm_hash: None,
+            config: self.config.clone(),
+            fee: self.fee.clone(),
+        }
+    }
+
     fn contract_id(&self) -> Result<[u8; 32], Error> {
-        utils::contract_id_from_str(&self.contract_id)
-            .map_err(|e| Error::CannotParseContractId(self.contract_id.clone(), e))
+        let contract_id = self.config.resolve_contract_id(&self.contract_id)?;
+        utils::contract_id_from_str(&contract_id)
+            .map_err(|e| Error::CannotParseContractId(contract_id.clone(), e))
+    }
+}
+
+/// The account's sequence number on the network.
+async fn account_sequence(
+    client: &Client,
+    public_key: &stellar_strkey::ed25519::PublicKey,
+) -> Result<i64, Error> {
+    Ok(client
+        .get_account(&public_key.to_string())
+        .await?
+        .seq_num
+        .into())
+}
+
+/// Keys of the contract's instance and code entries if they have expired, in which case
+/// they need restoring before the contract can be invoked.
+async fn archived_entries(client: &Client, contract_id: [u8; 32]) -> Result<Vec<LedgerKey>, Error> {
+    let instance_key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::Contract(Hash(contract_id)),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+        body_type: ContractEntryBodyType::DataEntry,
+    });
+    let mut archived = vec![];
+    let Some((data, latest_ledger)) = read_entry(client, &instance_key).await? else {
+        return Ok(archived);
+    };
+    let LedgerEntryData::ContractData(ContractDataEntry {
+        body,
+        expiration_ledger_seq,
+        ..
+    }) = data
+    else {
+        return Ok(archived);
+    };
+    if expiration_ledger_seq < latest_ledger {
+        archived.push(instance_key);
+    }
+    if let ContractDataEntryBody::DataEntry(ContractDataEntryData {
+        val:
+            ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(hash),
+                ..
+            }),
+        ..
+    }) = body
+    {
+        let code_key = LedgerKey::ContractCode(LedgerKeyContractCode {
+            hash,
+            body_type: ContractEntryBodyType::DataEntry,
+        });
+        if let Some((
+            LedgerEntryData::ContractCode(ContractCodeEntry {
+                expiration_ledger_seq,
+                ..
+            }),
+            latest_ledger,
+        )) = read_entry(client, &code_key).await?
+        {
+            if expiration_ledger_seq < latest_ledger {
+                archived.push(code_key);
+            }
+        }
+    }
+    Ok(archived)
+}
+
+/// The entry of `key`, if it exists, and the latest ledger it was read at.
+async fn read_entry(
+    client: &Client,
+    key: &LedgerKey,
+) -> Result<Option<(LedgerEntryData, u32)>, Error> {
+    let response = client.get_ledger_entries(vec![key.clone()]).await?;
+    let latest_ledger = response.latest_ledger.parse().unwrap_or(u32::MAX);
+    response
+        .entries
+        .unwrap_or_default()
+        .first()
+        .map(|entry| Ok((LedgerEntryData::from_xdr_base64(&entry.xdr)?, latest_ledger)))
+        .transpose()
+}
+
+fn describe_archived(keys: &[LedgerKey]) -> String {
+    match keys {
+        [LedgerKey::ContractData(_), LedgerKey::ContractCode(_)] => "instance and code are",
+        [LedgerKey::ContractCode(_)] => "code is",
+        _ => "instance is",
+    }
+    .to_string()
+}
+
+/// Keys of the contract data the transaction may write, from its footprint.
+fn written_contract_data(tx: &Transaction) -> Vec<LedgerKey> {
+    match &tx.ext {
+        TransactionExt::V1(data) => data
+            .resources
+            .footprint
+            .read_write
+            .iter()
+            .filter(|key| matches!(key, LedgerKey::ContractData(_)))
+            .cloned()
+            .collect(),
+        TransactionExt::V0 => vec![],
+    }
+}
+
+async fn read_entries(
+    client: &Client,
+    keys: &[LedgerKey],
+) -> Result<Vec<(LedgerKey, LedgerEntryData)>, Error> {
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+    client
+        .get_ledger_entries(keys.to_vec())
+        .await?
+        .entries
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| {
+            Ok((
+                LedgerKey::from_xdr_base64(&entry.key)?,
+                LedgerEntryData::from_xdr_base64(&entry.xdr)?,
+            ))
+        })
+        .collect()
+}
+
+fn contract_data_entries(
+    state: &soroban_ledger_snapshot::LedgerSnapshot,
+) -> Vec<(LedgerKey, LedgerEntryData)> {
+    state
+        .ledger_entries
+        .iter()
+        .filter(|(key, _)| matches!(**key, LedgerKey::ContractData(_)))
+        .map(|(key, entry)| ((**key).clone(), entry.data.clone()))
+        .collect()
+}
+
+fn print_state_diff(
+    before: &[(LedgerKey, LedgerEntryData)],
+    after: &[(LedgerKey, LedgerEntryData)],
+) {
+    let changes = crate::log::state_diff(before, after);
+    if changes.is_empty() {
+        eprintln!("No contract data changed");
+    }
+    for change in changes {
+        eprintln!("{change}");
     }
 }
 
@@ -483,6 +1171,14 @@ fn log_events(
     }
 }
 
+/// Print a result of [`Cmd::invoke`] with the global `--output-format`. With JSON output the
+/// result is already JSON, and is printed on one line like other commands' results.
+pub fn print_result(global_args: &global::Args, result: &str) -> Result<(), serde_json::Error> {
+    let json = serde_json::from_str(result)
+        .unwrap_or_else(|_| serde_json::Value::String(result.to_string()));
+    global_args.formatter().print(result, &json)
+}
+
 pub fn output_to_string(spec: &Spec, res: &ScVal, function: &str) -> Result<String, Error> {
     let mut res_str = String::new();
     if let Some(output) = spec.find_function(function)?.outputs.get(0) {
@@ -497,11 +1193,196 @@ pub fn output_to_string(spec: &Spec, res: &ScVal, function: &str) -> Result<Stri
     Ok(res_str)
 }
 
+/// One line per function with its argument and return types, followed by its docs.
+fn format_functions(spec: &Spec) -> Result<String, Error> {
+    let mut lines = vec![];
+    for func in spec.find_functions()? {
+        let inputs = func
+            .inputs
+            .iter()
+            .map(|i| {
+                format!(
+                    "{}: {}",
+                    i.name.to_string_lossy(),
+                    spec.arg_value_name(&i.type_, 0).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let output = func
+            .outputs
+            .get(0)
+            .and_then(|o| spec.arg_value_name(o, 0))
+            .map(|o| format!(" -> {o}"))
+            .unwrap_or_default();
+        lines.push(format!("{}({inputs}){output}", func.name.to_string_lossy()));
+        for doc in func.doc.to_string_lossy().lines() {
+            lines.push(format!("    {doc}"));
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+fn result_to_json(spec: &Spec, res: &ScVal, function: &str) -> Result<serde_json::Value, Error> {
+    match spec.find_function(function)?.outputs.get(0) {
+        Some(output) => spec
+            .xdr_to_json(res, output)
+            .map_err(|error| Error::CannotPrintResult {
+                result: res.clone(),
+                error,
+            }),
+        None => Ok(serde_json::Value::Null),
+    }
+}
+
+/// Summarize a sent invocation. The hash and fee are only known when sent to a network.
+#[allow(clippy::too_many_arguments)]
+fn invocation_to_json(
+    spec: &Spec,
+    res: &ScVal,
+    function: &str,
+    tx_hash: Option<String>,
+    fee_charged: Option<i64>,
+    events: &[ContractEvent],
+    diagnostic_events: &[DiagnosticEvent],
+    metrics: Option<serde_json::Value>,
+) -> Result<String, Error> {
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "result": result_to_json(spec, res, function)?,
+        "tx_hash": tx_hash,
+        "fee_charged": fee_charged,
+        "events": events,
+        "diagnostic_events": diagnostic_events,
+        "metrics": metrics,
+    }))?)
+}
+
+/// Summarize a simulation of the invocation, decoding the return value with the spec.
+fn simulation_to_json(
+    simulation: &rpc::SimulateTransactionResponse,
+    spec: &Spec,
+    function: &str,
+    fee: u32,
+) -> Result<String, Error> {
+    let result = match simulation.results.first() {
+        Some(result) => result_to_json(spec, &ScVal::from_xdr_base64(&result.xdr)?, function)?,
+        None => serde_json::Value::Null,
+    };
+    let events = simulation
+        .events
+        .iter()
+        .map(DiagnosticEvent::from_xdr_base64)
+        .collect::<Result<Vec<_>, _>>()?;
+    let transaction_data = SorobanTransactionData::from_xdr_base64(&simulation.transaction_data)?;
+    // Same adjustment as `rpc::assemble` makes before sending.
+    let total_fee = fee.max(crate::fee::Args::default().fee + simulation.min_resource_fee);
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "result": result,
+        "events": events,
+        "fee": total_fee,
+        "min_resource_fee": simulation.min_resource_fee,
+        "cost": simulation.cost,
+        "resources": transaction_data.resources,
+        "latest_ledger": simulation.latest_ledger,
+    }))?)
+}
+
+fn auth_entries(tx: &Transaction) -> Vec<SorobanAuthorizationEntry> {
+    match tx.operations.first().map(|op| &op.body) {
+        Some(OperationBody::InvokeHostFunction(body)) => body.auth.to_vec(),
+        _ => vec![],
+    }
+}
+
+fn auth_entry_file_name(index: usize, entry: &SorobanAuthorizationEntry) -> String {
+    let signer = match &entry.credentials {
+        SorobanCredentials::SourceAccount => "source-account".to_string(),
+        SorobanCredentials::Address(SorobanAddressCredentials { address, .. }) => match address {
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(k)))) => {
+                stellar_strkey::ed25519::PublicKey(*k).to_string()
+            }
+            ScAddress::Contract(Hash(h)) => stellar_strkey::Contract(*h).to_string(),
+        },
+    };
+    format!("{index:02}-{signer}.xdr")
+}
+
+/// Write each auth entry of the prepared transaction to `dir` as base64 XDR, setting the
+/// signature expiration of entries that need a signature. Returns a summary of the files.
+fn write_auth_entries(
+    dir: &Path,
+    tx: &Transaction,
+    expiration_ledger: u32,
+) -> Result<String, Error> {
+    fs::create_dir_all(dir).map_err(|e| Error::CannotWriteAuthEntry(dir.to_path_buf(), e))?;
+    let OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }) =
+        &tx.operations[0].body
+    else {
+        return Err(Error::MissingOperationResult);
+    };
+    let mut summary = Vec::with_capacity(auth.len());
+    for (index, entry) in auth.iter().enumerate() {
+        let mut entry = entry.clone();
+        let note = if let SorobanCredentials::Address(credentials) = &mut entry.credentials {
+            credentials.signature_expiration_ledger = expiration_ledger;
+            format!("needs signature, expires at ledger {expiration_ledger}")
+        } else {
+            "signed by the source account".to_string()
+        };
+        let path = dir.join(auth_entry_file_name(index, &entry));
+        fs::write(&path, entry.to_xdr_base64()?)
+            .map_err(|e| Error::CannotWriteAuthEntry(path.clone(), e))?;
+        summary.push(format!("{}: {note}", path.display()));
+    }
+    Ok(summary.join("\n"))
+}
+
+/// Read the auth entries written by `--auth-out`, in file name order.
+fn read_auth_entries(dir: &Path) -> Result<VecM<SorobanAuthorizationEntry>, Error> {
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| Error::CannotReadAuthEntries(dir.to_path_buf(), e))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Error::CannotReadAuthEntries(dir.to_path_buf(), e))?;
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "xdr"));
+    paths.sort();
+    if paths.is_empty() {
+        return Err(Error::NoAuthEntries(dir.to_path_buf()));
+    }
+    let entries = paths
+        .iter()
+        .map(|path| {
+            let data = fs::read_to_string(path)
+                .map_err(|e| Error::CannotReadAuthEntries(path.clone(), e))?;
+            Ok(SorobanAuthorizationEntry::from_xdr_base64(data.trim())?)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(entries.try_into()?)
+}
+
+/// Render the diagnostic events of a failed simulation or transaction with the contract's error
+/// codes named after its spec.
+fn name_contract_errors(error: rpc::Error, spec: &Spec) -> Error {
+    let (message, events) = match error {
+        rpc::Error::TransactionSimulationFailed { error, events } => {
+            (format!("transaction simulation failed: {error}"), events)
+        }
+        rpc::Error::TransactionFailed { result, events } => {
+            (format!("transaction failed: {result}"), events)
+        }
+        error => return error.into(),
+    };
+    Error::InvocationFailed {
+        message,
+        events: crate::log::diagnostic_events(&events, Some(spec)),
+    }
+}
+
 fn build_invoke_contract_tx(
     parameters: InvokeContractArgs,
     sequence: i64,
     fee: u32,
-    key: &ed25519_dalek::Keypair,
+    source: &stellar_strkey::ed25519::PublicKey,
 ) -> Result<Transaction, Error> {
     let op = Operation {
         source_account: None,
@@ -511,7 +1392,7 @@ fn build_invoke_contract_tx(
         }),
     };
     Ok(Transaction {
-        source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+        source_account: MuxedAccount::Ed25519(Uint256(source.0)),
         fee,
         seq_num: SequenceNumber(sequence),
         cond: Preconditions::None,
@@ -589,6 +1470,68 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
     Ok(cmd)
 }
 
+/// Convert `name=value` arguments into the `function --name value` form expected by the
+/// generated function command, checking each name against the function's inputs.
+fn named_args_to_slop(
+    function: &str,
+    args: &[OsString],
+    spec: &Spec,
+) -> Result<Vec<OsString>, Error> {
+    let func = spec
+        .find_function(function)
+        .map_err(|_| Error::FunctionNotFoundInContractSpec(function.to_string()))?;
+    let inputs = func
+        .inputs
+        .iter()
+        .map(|i| i.name.to_string_lossy())
+        .collect::<Vec<_>>();
+    let mut slop = vec![OsString::from(function)];
+    for arg in args {
+        let arg = arg.to_string_lossy();
+        let (name, value) = arg
+            .split_once('=')
+            .ok_or_else(|| Error::InvalidNamedArg(arg.to_string()))?;
+        if !inputs
+            .iter()
+            .any(|i| i == name || i.to_kebab_case() == name)
+        {
+            return Err(Error::UnknownNamedArg {
+                function: function.to_string(),
+                arg: name.to_string(),
+                expected: inputs.join(", "),
+            });
+        }
+        slop.push(format!("--{name}").into());
+        slop.push(value.into());
+    }
+    Ok(slop)
+}
+
+/// Read argument names and values from a JSON or TOML object. String values are passed as
+/// is, anything else as JSON.
+fn read_args_file(path: &Path) -> Result<Vec<(String, String)>, Error> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| Error::CannotReadArgsFile(path.to_path_buf(), e))?;
+    let value = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str::<toml::Value>(&contents)
+            .map_err(|e| e.to_string())
+            .and_then(|v| serde_json::to_value(v).map_err(|e| e.to_string()))
+    } else {
+        serde_json::from_str::<serde_json::Value>(&contents).map_err(|e| e.to_string())
+    }
+    .map_err(|e| Error::CannotParseArgsFile(path.to_path_buf(), e))?;
+    let serde_json::Value::Object(args) = value else {
+        return Err(Error::ArgsFileNotObject(path.to_path_buf()));
+    };
+    Ok(args
+        .into_iter()
+        .map(|(name, value)| match value {
+            serde_json::Value::String(s) => (name, s),
+            value => (name, value.to_string()),
+        })
+        .collect())
+}
+
 fn fmt_arg_file_name(name: &str) -> String {
     format!("{name}-file-path")
 }
@@ -1,34 +1,65 @@
+pub mod admin;
+pub mod alias;
+pub mod asset;
 pub mod bindings;
 pub mod build;
-pub mod bump;
 pub mod deploy;
+pub mod doc;
+pub mod extend;
 pub mod fetch;
+pub mod id;
 pub mod inspect;
 pub mod install;
 pub mod invoke;
+pub mod multi_invoke;
 pub mod optimize;
 pub mod read;
 pub mod restore;
+pub mod run;
+pub mod size_check;
+pub mod spec_diff;
+pub mod ttl;
+pub mod verify;
+
+use crate::commands::global;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
+    /// Report who controls a contract, by simulating common admin and owner getters
+    Admin(admin::Cmd),
+
+    /// Manage names for contract IDs, per network
+    #[command(subcommand)]
+    Alias(alias::Cmd),
+
+    /// Deploy and compute the IDs of Stellar Asset Contracts
+    #[command(subcommand)]
+    Asset(asset::Cmd),
+
     /// Generate code client bindings for a contract
     #[command(subcommand)]
     Bindings(bindings::Cmd),
 
     Build(build::Cmd),
 
-    /// Extend the expiry ledger of a contract-data ledger entry.
-    ///
-    /// If no keys are specified the contract itself is bumped.
-    Bump(bump::Cmd),
-
     /// Deploy a contract
     Deploy(deploy::Cmd),
 
+    /// Generate Markdown or HTML documentation of a contract's functions and types from its spec
+    Doc(doc::Cmd),
+
+    /// Extend the expiry ledger of contract-data ledger entries, or of contract code.
+    ///
+    /// If no keys are specified the contract instance itself is extended.
+    #[command(alias = "bump")]
+    Extend(extend::Cmd),
+
     /// Fetch a contract's Wasm binary from a network or local sandbox
     Fetch(fetch::Cmd),
 
+    /// Compute the ID of a contract deployed by an account with a salt, without deploying it
+    Id(id::Cmd),
+
     /// Inspect a WASM file listing contract functions, meta, etc
     Inspect(inspect::Cmd),
 
@@ -45,6 +76,11 @@ pub enum Cmd {
     ///     soroban contract invoke ... -- --help
     Invoke(invoke::Cmd),
 
+    /// Invoke several contract functions in order, stopping at the first that fails
+    ///
+    /// Each call is its own transaction, as a transaction can only invoke one function.
+    MultiInvoke(multi_invoke::Cmd),
+
     /// Optimize a WASM file
     Optimize(optimize::Cmd),
 
@@ -55,10 +91,41 @@ pub enum Cmd {
     ///
     /// If no keys are specificed the contract itself is restored.
     Restore(restore::Cmd),
+
+    /// Invoke a function of a WASM file in an in-process host, without a network or the
+    /// sandbox's ledger file, and print its result, events, and budget usage
+    Run(run::Cmd),
+
+    /// Check a WASM file against a size limit and growth over a baseline, for use in CI
+    ///
+    /// Prints the size of each section, and exits with an error naming the sections that
+    /// contributed most when a limit is exceeded.
+    SizeCheck(size_check::Cmd),
+
+    /// Report the functions and types added, removed, or changed between two versions of a
+    /// contract, and exit with an error if any change breaks clients of the old version
+    SpecDiff(spec_diff::Cmd),
+
+    /// Report until which ledger a contract's instance, code, and data entries live, and
+    /// roughly when they will be archived
+    Ttl(ttl::Cmd),
+
+    /// Check that a deployed contract runs the code of a local wasm file or source directory,
+    /// comparing their hashes and showing their meta
+    Verify(verify::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Admin(#[from] admin::Error),
+
+    #[error(transparent)]
+    Alias(#[from] alias::Error),
+
+    #[error(transparent)]
+    Asset(#[from] asset::Error),
+
     #[error(transparent)]
     Bindings(#[from] bindings::Error),
 
@@ -66,14 +133,20 @@ pub enum Error {
     Build(#[from] build::Error),
 
     #[error(transparent)]
-    Bump(#[from] bump::Error),
+    Deploy(#[from] deploy::Error),
 
     #[error(transparent)]
-    Deploy(#[from] deploy::Error),
+    Doc(#[from] doc::Error),
+
+    #[error(transparent)]
+    Extend(#[from] extend::Error),
 
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
 
+    #[error(transparent)]
+    Id(#[from] id::Error),
+
     #[error(transparent)]
     Inspect(#[from] inspect::Error),
 
@@ -83,6 +156,9 @@ pub enum Error {
     #[error(transparent)]
     Invoke(#[from] invoke::Error),
 
+    #[error(transparent)]
+    MultiInvoke(#[from] multi_invoke::Error),
+
     #[error(transparent)]
     Optimize(#[from] optimize::Error),
 
@@ -91,22 +167,48 @@ pub enum Error {
 
     #[error(transparent)]
     Restore(#[from] restore::Error),
+
+    #[error(transparent)]
+    Run(#[from] run::Error),
+
+    #[error(transparent)]
+    SizeCheck(#[from] size_check::Error),
+
+    #[error(transparent)]
+    SpecDiff(#[from] spec_diff::Error),
+
+    #[error(transparent)]
+    Ttl(#[from] ttl::Error),
+
+    #[error(transparent)]
+    Verify(#[from] verify::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
+            Cmd::Admin(admin) => admin.run().await?,
+            Cmd::Alias(alias) => alias.run(global_args)?,
+            Cmd::Asset(asset) => asset.run(global_args).await?,
             Cmd::Bindings(bindings) => bindings.run().await?,
             Cmd::Build(build) => build.run()?,
-            Cmd::Bump(bump) => bump.run().await?,
-            Cmd::Deploy(deploy) => deploy.run().await?,
-            Cmd::Inspect(inspect) => inspect.run()?,
-            Cmd::Install(install) => install.run().await?,
-            Cmd::Invoke(invoke) => invoke.run().await?,
+            Cmd::Deploy(deploy) => deploy.run(global_args).await?,
+            Cmd::Doc(doc) => doc.run()?,
+            Cmd::Extend(extend) => extend.run(global_args).await?,
+            Cmd::Id(id) => id.run(global_args)?,
+            Cmd::Inspect(inspect) => inspect.run().await?,
+            Cmd::Install(install) => install.run(global_args).await?,
+            Cmd::Invoke(invoke) => invoke.run(global_args).await?,
+            Cmd::MultiInvoke(multi_invoke) => multi_invoke.run(global_args).await?,
             Cmd::Optimize(optimize) => optimize.run()?,
             Cmd::Fetch(fetch) => fetch.run().await?,
             Cmd::Read(read) => read.run().await?,
-            Cmd::Restore(restore) => restore.run().await?,
+            Cmd::Restore(restore) => restore.run(global_args).await?,
+            Cmd::Run(run) => run.run()?,
+            Cmd::SizeCheck(size_check) => size_check.run()?,
+            Cmd::SpecDiff(spec_diff) => spec_diff.run().await?,
+            Cmd::Ttl(ttl) => ttl.run(global_args).await?,
+            Cmd::Verify(verify) => verify.run().await?,
         }
         Ok(())
     }
@@ -137,4 +239,9 @@ pub enum SpecOutput {
     XdrBase64Array,
     /// Pretty print of contract spec entries
     Docs,
+    /// JSON of contract spec entries, with their docs, as by `contract bindings json`
+    Json,
+    /// Host functions imported, by what they let the contract do, and the ones each exported
+    /// function may call
+    Imports,
 }
@@ -0,0 +1,139 @@
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::{config, contract::invoke, global, HEADING_RPC},
+    rpc::Outcome,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// A call to make, as the contract ID or alias, the function, and its arguments, e.g.
+    /// `--call "token approve --from me --spender C... --amount 100"`. Repeat for each call,
+    /// they are made in the order given once all of them have been simulated. Simulation
+    /// doesn't see the effects of earlier calls, so calls that depend on them may fail it
+    #[arg(long = "call", required = true)]
+    pub calls: Vec<String>,
+
+    /// Whether to submit the transactions. With `no` each call is only simulated
+    #[arg(long, value_enum, default_value_t)]
+    pub send: invoke::SendMode,
+
+    /// Refuse to make the calls if their fees, estimated by simulating each before any is
+    /// submitted, total more than this, in stroops
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub max_total_fee: Option<u64>,
+
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub fee: crate::fee::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(
+        "cannot parse call {0:?}, expected a contract followed by a function and its arguments"
+    )]
+    InvalidCall(String),
+    #[error("call {index} ({call:?}) failed in simulation, no calls were made: {error}")]
+    Simulation {
+        index: usize,
+        call: String,
+        error: Box<invoke::Error>,
+    },
+    #[error("call {index} ({call:?}) failed, calls before it were already made: {error}")]
+    Call {
+        index: usize,
+        call: String,
+        error: Box<invoke::Error>,
+    },
+    #[error("estimated total fee of {total} stroops is over --max-total-fee {max}")]
+    MaxTotalFeeExceeded { total: u64, max: u64 },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let Outcome::Done(results) = self.invoke(global_args).await? else {
+            return Ok(());
+        };
+        for result in results {
+            invoke::print_result(global_args, &result)?;
+        }
+        Ok(())
+    }
+
+    /// Make each call in turn, stopping at the first that fails. On a network every call is
+    /// simulated first, and nothing is submitted unless all of them succeed within
+    /// `--max-total-fee`. A dry run stops at the first call, as the others may depend on it.
+    ///
+    /// A Soroban transaction holds exactly one `InvokeHostFunction` operation, so the calls
+    /// are separate transactions and are not atomic. Flows that must be all-or-nothing need a
+    /// contract that makes the calls itself.
+    pub async fn invoke(&self, global_args: &global::Args) -> Result<Outcome<Vec<String>>, Error> {
+        let cmds = self
+            .calls
+            .iter()
+            .map(|call| self.invoke_cmd(call))
+            .collect::<Result<Vec<_>, _>>()?;
+        if self.send == invoke::SendMode::Yes && !self.config.is_no_network() {
+            self.simulate(&cmds).await?;
+        }
+        let mut results = vec![];
+        for (index, (call, cmd)) in self.calls.iter().zip(cmds).enumerate() {
+            let outcome = cmd.invoke(global_args).await.map_err(|error| Error::Call {
+                index: index + 1,
+                call: call.clone(),
+                error: Box::new(error),
+            })?;
+            let Outcome::Done(result) = outcome else {
+                return Ok(Outcome::DryRun);
+            };
+            results.push(result);
+        }
+        Ok(Outcome::Done(results))
+    }
+
+    /// Simulate every call, so one that would fail is caught before any is submitted, and
+    /// print the estimated fees. Each is simulated against the network's current state,
+    /// without the effects of the calls before it.
+    async fn simulate(&self, cmds: &[invoke::Cmd]) -> Result<(), Error> {
+        eprintln!("Simulating the calls:");
+        let mut total = 0;
+        for (index, (call, cmd)) in self.calls.iter().zip(cmds).enumerate() {
+            let simulation = cmd.simulate().await.map_err(|error| Error::Simulation {
+                index: index + 1,
+                call: call.clone(),
+                error: Box::new(error),
+            })?;
+            let fee = serde_json::from_str::<serde_json::Value>(&simulation)?["fee"]
+                .as_u64()
+                .unwrap_or_default();
+            eprintln!("  call {}: {fee} stroops", index + 1);
+            total += fee;
+        }
+        eprintln!("Estimated total fee: {total} stroops");
+        match self.max_total_fee {
+            Some(max) if total > max => Err(Error::MaxTotalFeeExceeded { total, max }),
+            _ => Ok(()),
+        }
+    }
+
+    fn invoke_cmd(&self, call: &str) -> Result<invoke::Cmd, Error> {
+        let mut words = shlex::split(call)
+            .filter(|words| words.len() >= 2)
+            .ok_or_else(|| Error::InvalidCall(call.to_string()))?
+            .into_iter();
+        let contract_id = words.next().unwrap_or_default();
+        Ok(invoke::Cmd {
+            contract_id,
+            slop: words.map(Into::into).collect(),
+            send: self.send,
+            config: self.config.clone(),
+            fee: self.fee.clone(),
+            ..Default::default()
+        })
+    }
+}
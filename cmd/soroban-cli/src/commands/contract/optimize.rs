@@ -1,10 +1,18 @@
-use clap::{arg, command, Parser};
-use std::fmt::Debug;
+use clap::{arg, command, Parser, ValueEnum};
+use soroban_env_host::xdr::{self, ScMetaEntry, ScMetaV0, WriteXdr};
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
 #[cfg(feature = "opt")]
 use wasm_opt::{Feature, OptimizationError, OptimizationOptions};
 
 use crate::wasm;
 
+/// The custom sections that `--strip-meta` keeps, as bindings and the host read them.
+const CONTRACT_SECTIONS: &[&str] = &["contractspecv0", "contractenvmetav0"];
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
@@ -13,28 +21,74 @@ pub struct Cmd {
     /// Path to write the optimized WASM file to (defaults to same location as --wasm with .optimized.wasm suffix)
     #[arg(long)]
     wasm_out: Option<std::path::PathBuf>,
+
+    /// Remove the custom sections other than the contract's spec and environment meta, such as
+    /// debug info, names, producers, and the contract meta
+    #[arg(long)]
+    strip_meta: bool,
+
+    /// Add an entry to the contract meta, e.g. `--add-meta commit=$(git rev-parse HEAD)`. Can be
+    /// repeated
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_meta)]
+    add_meta: Vec<(String, String)>,
+
+    /// How hard to optimize
+    #[arg(long, value_enum, default_value = "aggressive")]
+    profile: Profile,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Profile {
+    /// Optimize for size as much as possible, and with the built-in optimizer also remove the
+    /// names and debug info
+    Aggressive,
+    /// Optimize for size without the slowest passes, and with the built-in optimizer only
+    /// remove the unused functions, globals, and data
+    Safe,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("not a wasm module: {0:?}")]
+    NotWasm(PathBuf),
     #[cfg(feature = "opt")]
     #[error("optimization error: {0}")]
     OptimizationError(OptimizationError),
     #[cfg(not(feature = "opt"))]
-    #[error("Must install with \"opt\" feature, e.g. `cargo install soroban-cli --features opt")]
-    Install,
+    #[error("optimization error: {0}")]
+    Builtin(String),
 }
 
 impl Cmd {
-    #[cfg(not(feature = "opt"))]
     pub fn run(&self) -> Result<(), Error> {
-        Err(Error::Install)
+        let wasm_out = self.wasm_out.as_ref().cloned().unwrap_or_else(|| {
+            let mut wasm_out = self.wasm.wasm.clone();
+            wasm_out.set_extension("optimized.wasm");
+            wasm_out
+        });
+        self.optimize(&wasm_out)?;
+        if self.strip_meta || !self.add_meta.is_empty() {
+            let wasm = fs::read(&wasm_out)?;
+            let wasm = self
+                .edit_custom_sections(&wasm)
+                .ok_or_else(|| Error::NotWasm(wasm_out.clone()))??;
+            fs::write(&wasm_out, &wasm)?;
+            println!(
+                "Updated custom sections: {} ({} bytes)",
+                wasm_out.to_string_lossy(),
+                wasm.len()
+            );
+        }
+        Ok(())
     }
 
-    #[cfg(feature = "opt")]
-    pub fn run(&self) -> Result<(), Error> {
+    fn optimize(&self, wasm_out: &Path) -> Result<(), Error> {
         let wasm_size = self.wasm.len()?;
 
         println!(
@@ -42,16 +96,43 @@ impl Cmd {
             self.wasm.wasm.to_string_lossy(),
             wasm_size
         );
-
-        let wasm_out = self.wasm_out.as_ref().cloned().unwrap_or_else(|| {
-            let mut wasm_out = self.wasm.wasm.clone();
-            wasm_out.set_extension("optimized.wasm");
-            wasm_out
-        });
         println!("Writing to: {}...", wasm_out.to_string_lossy());
 
-        let mut options = OptimizationOptions::new_optimize_for_size_aggressively();
-        options.converge = true;
+        self.run_optimizer(wasm_out)?;
+
+        let wasm_out_size = wasm::len(wasm_out)?;
+        println!(
+            "Optimized: {} ({} bytes)",
+            wasm_out.to_string_lossy(),
+            wasm_out_size
+        );
+
+        Ok(())
+    }
+
+    /// Without the "opt" feature, which builds binaryen's wasm-opt in, a pure Rust optimizer
+    /// removes what the contract doesn't use, without rewriting the code.
+    #[cfg(not(feature = "opt"))]
+    fn run_optimizer(&self, wasm_out: &Path) -> Result<(), Error> {
+        let aggressive = self.profile == Profile::Aggressive;
+        let mut module = walrus::ModuleConfig::new()
+            .generate_name_section(!aggressive)
+            .generate_producers_section(!aggressive)
+            .generate_dwarf(!aggressive)
+            .parse(&self.wasm.read()?)
+            .map_err(|e| Error::Builtin(e.to_string()))?;
+        walrus::passes::gc::run(&mut module);
+        fs::write(wasm_out, module.emit_wasm())?;
+        Ok(())
+    }
+
+    #[cfg(feature = "opt")]
+    fn run_optimizer(&self, wasm_out: &Path) -> Result<(), Error> {
+        let mut options = match self.profile {
+            Profile::Aggressive => OptimizationOptions::new_optimize_for_size_aggressively(),
+            Profile::Safe => OptimizationOptions::new_optimize_for_size(),
+        };
+        options.converge = self.profile == Profile::Aggressive;
 
         // Explicitly set to MVP + sign-ext + mutable-globals, which happens to
         // also be the default featureset, but just to be extra clear we set it
@@ -65,16 +146,137 @@ impl Cmd {
         options.enable_feature(Feature::SignExt);
 
         options
-            .run(&self.wasm.wasm, &wasm_out)
+            .run(&self.wasm.wasm, wasm_out)
             .map_err(Error::OptimizationError)?;
+        Ok(())
+    }
 
-        let wasm_out_size = wasm::len(&wasm_out)?;
-        println!(
-            "Optimized: {} ({} bytes)",
-            wasm_out.to_string_lossy(),
-            wasm_out_size
-        );
+    /// The module with the custom sections stripped and the meta added, or `None` if it is not
+    /// a wasm module.
+    fn edit_custom_sections(&self, wasm: &[u8]) -> Option<Result<Vec<u8>, Error>> {
+        let mut out = wasm.get(..8)?.to_vec();
+        let mut meta = vec![];
+        for (id, section) in sections(wasm)? {
+            if id == 0 {
+                let (name, data) = custom_section(section)?;
+                if name == "contractmetav0" && !self.strip_meta {
+                    // Merged with the added entries into one section at the end
+                    meta.extend_from_slice(data);
+                    continue;
+                }
+                if self.strip_meta && !CONTRACT_SECTIONS.contains(&name) {
+                    continue;
+                }
+            }
+            out.push(id);
+            write_leb128(&mut out, section.len());
+            out.extend_from_slice(section);
+        }
+        for (key, val) in &self.add_meta {
+            let entry = (|| {
+                ScMetaEntry::ScMetaV0(ScMetaV0 {
+                    key: key.as_str().try_into()?,
+                    val: val.as_str().try_into()?,
+                })
+                .to_xdr()
+            })();
+            match entry {
+                Ok(entry) => meta.extend(entry),
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+        if !meta.is_empty() {
+            let name = b"contractmetav0";
+            let mut section = vec![];
+            write_leb128(&mut section, name.len());
+            section.extend_from_slice(name);
+            section.extend(meta);
+            out.push(0);
+            write_leb128(&mut out, section.len());
+            out.extend(section);
+        }
+        Some(Ok(out))
+    }
+}
 
-        Ok(())
+fn parse_meta(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, val)| (key.to_string(), val.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got {s:?}"))
+}
+
+/// The id and contents of each section of the module, or `None` if it is malformed.
+fn sections(wasm: &[u8]) -> Option<Vec<(u8, &[u8])>> {
+    let mut sections = vec![];
+    let mut rest = wasm.get(8..)?;
+    while let Some((&id, after_id)) = rest.split_first() {
+        let (len, after_len) = read_leb128(after_id)?;
+        sections.push((id, after_len.get(..len)?));
+        rest = after_len.get(len..)?;
+    }
+    Some(sections)
+}
+
+/// The name and data of a custom section.
+fn custom_section(section: &[u8]) -> Option<(&str, &[u8])> {
+    let (len, rest) = read_leb128(section)?;
+    let name = std::str::from_utf8(rest.get(..len)?).ok()?;
+    Some((name, rest.get(len..)?))
+}
+
+fn read_leb128(bytes: &[u8]) -> Option<(usize, &[u8])> {
+    let mut value = 0usize;
+    for (i, byte) in bytes.iter().enumerate().take(5) {
+        value |= usize::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+fn write_leb128(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_and_adds_meta() {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        for name in ["name", "contractspecv0"] {
+            wasm.push(0);
+            wasm.push(u8::try_from(name.len() + 2).unwrap());
+            wasm.push(u8::try_from(name.len()).unwrap());
+            wasm.extend_from_slice(name.as_bytes());
+            wasm.push(0xff);
+        }
+        let cmd = Cmd {
+            wasm: wasm::Args {
+                wasm: PathBuf::new(),
+            },
+            wasm_out: None,
+            strip_meta: true,
+            add_meta: vec![("commit".to_string(), "abc".to_string())],
+            profile: Profile::Safe,
+        };
+        let out = cmd.edit_custom_sections(&wasm).unwrap().unwrap();
+        let names = sections(&out)
+            .unwrap()
+            .into_iter()
+            .map(|(_, section)| custom_section(section).unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["contractspecv0", "contractmetav0"]);
     }
 }
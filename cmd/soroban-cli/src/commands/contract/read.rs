@@ -2,17 +2,22 @@ use std::{
     convert::Into,
     fmt::Debug,
     io::{self, stdout},
+    rc::Rc,
 };
 
 use clap::{command, Parser, ValueEnum};
+use serde_json::Value;
 use soroban_env_host::{
+    storage::Storage,
     xdr::{
         self, ContractDataDurability, ContractDataEntry, ContractDataEntryBody,
         ContractDataEntryData, ContractEntryBodyType, Error as XdrError, Hash, LedgerEntryData,
-        LedgerKey, LedgerKeyContractData, ReadXdr, ScAddress, ScSpecTypeDef, ScVal, WriteXdr,
+        LedgerKey, LedgerKeyContractData, ReadXdr, ScAddress, ScContractInstance, ScSpecEntry,
+        ScSpecTypeDef, ScSpecUdtUnionCaseV0, ScVal, WriteXdr,
     },
     HostError,
 };
+use soroban_ledger_snapshot::LedgerSnapshot;
 
 use crate::{
     commands::config,
@@ -27,15 +32,24 @@ pub struct Cmd {
     /// Contract ID to invoke
     #[arg(long = "id")]
     contract_id: String,
-    /// Storage key (symbols only)
-    #[arg(long = "key", conflicts_with = "key_xdr")]
+    /// Storage key, as a symbol or as the JSON form of an ScVal, e.g.
+    /// '{"vec":[{"symbol":"Balance"},{"address":"G..."}]}'
+    #[arg(long = "key", conflicts_with_all = ["key_xdr", "all"])]
     key: Option<String>,
     /// Storage key (base64-encoded XDR ScVal)
-    #[arg(long = "key-xdr", conflicts_with = "key")]
+    #[arg(long = "key-xdr", conflicts_with_all = ["key", "all"])]
     key_xdr: Option<String>,
+    /// Read every entry of the contract. The RPC cannot list a contract's entries, so against a
+    /// network this is the contract's instance storage
+    #[arg(long)]
+    all: bool,
     /// Storage entry durability
     #[arg(long, value_enum)]
     durability: Option<Durability>,
+    /// Read the local sandbox entries as they were at this ledger. Past states are only kept
+    /// for ledgers written with `--keep-history`; the RPC only serves the latest state
+    #[arg(long, conflicts_with_all = ["network", "rpc_url"])]
+    at_ledger: Option<u32>,
 
     /// Type of output to generate
     #[arg(long, value_enum, default_value("string"))]
@@ -85,10 +99,12 @@ pub enum Error {
     CannotPrintFlush { error: io::Error },
     #[error(transparent)]
     Config(#[from] config::Error),
-    #[error("either `--key` or `--key-xdr` are required when querying a network")]
+    #[error("one of `--key`, `--key-xdr` or `--all` is required when querying a network")]
     KeyIsRequired,
     #[error(transparent)]
     Rpc(#[from] rpc::Error),
+    #[error("{0} is not a contract instance")]
+    NotAContractInstance(String),
     #[error("xdr processing error: {0}")]
     Xdr(#[from] XdrError),
     #[error(transparent)]
@@ -99,22 +115,22 @@ pub enum Error {
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
-        let contract_id: [u8; 32] =
-            utils::contract_id_from_str(&self.contract_id).map_err(|e| {
-                Error::CannotParseContractId {
-                    contract_id: self.contract_id.clone(),
-                    error: e,
-                }
-            })?;
+        let contract_id = self.config.resolve_contract_id(&self.contract_id)?;
+        let contract_id: [u8; 32] = utils::contract_id_from_str(&contract_id).map_err(|e| {
+            Error::CannotParseContractId {
+                contract_id: contract_id.clone(),
+                error: e,
+            }
+        })?;
         let key = if let Some(key) = &self.key {
-            Some(
-                soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol).map_err(
-                    |e| Error::CannotParseKey {
+            Some(match serde_json::from_str::<ScVal>(key) {
+                Ok(key) => key,
+                Err(_) => soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol)
+                    .map_err(|e| Error::CannotParseKey {
                         key: key.clone(),
                         error: e,
-                    },
-                )?,
-            )
+                    })?,
+            })
         } else if let Some(key) = &self.key_xdr {
             Some(
                 ScVal::from_xdr_base64(key).map_err(|e| Error::CannotParseXdrKey {
@@ -126,12 +142,69 @@ impl Cmd {
             None
         };
 
-        let entries = if self.config.is_no_network() {
-            self.run_in_sandbox(contract_id, &key)?
+        let entries = if self.config.is_no_network() || self.at_ledger.is_some() {
+            key_vals(&self.run_in_sandbox(contract_id, &key)?)
+        } else if self.all {
+            self.instance_storage(contract_id).await?
+        } else {
+            key_vals(&self.run_against_rpc_server(contract_id, key).await?)
+        };
+        let spec = if self.output == Output::Json {
+            self.spec(contract_id).await
+        } else {
+            vec![]
+        };
+        self.output_entries(&entries, &spec)
+    }
+
+    async fn instance_storage(&self, contract_id: [u8; 32]) -> Result<Vec<(ScVal, ScVal)>, Error> {
+        let network = self.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
+        match client.get_contract_data(&contract_id).await? {
+            ContractDataEntry {
+                body:
+                    ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                        val: ScVal::ContractInstance(ScContractInstance { storage, .. }),
+                        ..
+                    }),
+                ..
+            } => Ok(storage
+                .map(|storage| {
+                    storage
+                        .iter()
+                        .map(|entry| (entry.key.clone(), entry.val.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()),
+            _ => Err(Error::NotAContractInstance(
+                stellar_strkey::Contract(contract_id).to_string(),
+            )),
+        }
+    }
+
+    /// The contract's spec, used to name union cases in the output. Contracts without one, such
+    /// as the built-in token, are printed without it.
+    async fn spec(&self, contract_id: [u8; 32]) -> Vec<ScSpecEntry> {
+        let spec = if self.config.is_no_network() || self.at_ledger.is_some() {
+            self.state().ok().and_then(|state| {
+                let mut storage = Storage::with_recording_footprint(Rc::new(state.clone()));
+                utils::get_contract_spec_from_storage(
+                    &mut storage,
+                    &state.sequence_number,
+                    contract_id,
+                )
+                .ok()
+            })
         } else {
-            self.run_against_rpc_server(contract_id, key).await?
+            match self.config.get_network() {
+                Ok(network) => match Client::new(&network.rpc_url) {
+                    Ok(client) => client.get_remote_contract_spec(&contract_id).await.ok(),
+                    Err(_) => None,
+                },
+                Err(_) => None,
+            }
         };
-        self.output_entries(&entries)
+        spec.unwrap_or_default()
     }
 
     async fn run_against_rpc_server(
@@ -194,7 +267,7 @@ impl Cmd {
         contract_id: [u8; 32],
         key: &Option<ScVal>,
     ) -> Result<Vec<(LedgerKey, LedgerEntryData)>, Error> {
-        let state = self.config.get_state()?;
+        let state = self.state()?;
         let ledger_entries = &state.ledger_entries;
 
         let contract = ScAddress::Contract(xdr::Hash(contract_id));
@@ -245,23 +318,18 @@ impl Cmd {
             .collect::<Vec<_>>())
     }
 
-    fn output_entries(&self, raw_entries: &[(LedgerKey, LedgerEntryData)]) -> Result<(), Error> {
-        let entries = raw_entries
-            .iter()
-            .filter_map(|(_k, data)| {
-                if let LedgerEntryData::ContractData(ContractDataEntry {
-                    key,
-                    body: ContractDataEntryBody::DataEntry(ContractDataEntryData { val, .. }),
-                    ..
-                }) = &data
-                {
-                    Some((key.clone(), val.clone()))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
+    fn state(&self) -> Result<LedgerSnapshot, Error> {
+        Ok(match self.at_ledger {
+            Some(ledger) => self.config.get_state_at(ledger)?,
+            None => self.config.get_state()?,
+        })
+    }
 
+    fn output_entries(
+        &self,
+        entries: &[(ScVal, ScVal)],
+        spec: &[ScSpecEntry],
+    ) -> Result<(), Error> {
         let mut out = csv::Writer::from_writer(stdout());
         for (key, val) in entries {
             let output = match self.output {
@@ -276,13 +344,13 @@ impl Cmd {
                     })?,
                 ],
                 Output::Json => [
-                    serde_json::to_string_pretty(&key).map_err(|e| {
+                    serde_json::to_string_pretty(&to_json(spec, key)?).map_err(|e| {
                         Error::CannotPrintJsonResult {
                             result: key.clone(),
                             error: e,
                         }
                     })?,
-                    serde_json::to_string_pretty(&val).map_err(|e| {
+                    serde_json::to_string_pretty(&to_json(spec, val)?).map_err(|e| {
                         Error::CannotPrintJsonResult {
                             result: val.clone(),
                             error: e,
@@ -300,3 +368,68 @@ impl Cmd {
         Ok(())
     }
 }
+
+fn key_vals(entries: &[(LedgerKey, LedgerEntryData)]) -> Vec<(ScVal, ScVal)> {
+    entries
+        .iter()
+        .filter_map(|(_k, data)| {
+            if let LedgerEntryData::ContractData(ContractDataEntry {
+                key,
+                body: ContractDataEntryBody::DataEntry(ContractDataEntryData { val, .. }),
+                ..
+            }) = &data
+            {
+                Some((key.clone(), val.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Decode a stored value to JSON. Values shaped like a case of one of the spec's unions, as
+/// storage keys usually are, print as `{"Case": args}`, or `"Case"` when it has none.
+fn to_json(spec: &[ScSpecEntry], val: &ScVal) -> Result<Value, Error> {
+    if let ScVal::Vec(Some(vec)) = val {
+        let vals = vec.to_vec();
+        if let [ScVal::Symbol(name), args @ ..] = vals.as_slice() {
+            let is_case = spec.iter().any(|entry| match entry {
+                ScSpecEntry::UdtUnionV0(union) => union.cases.iter().any(|case| match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                        args.is_empty() && case.name.as_vec() == name.as_vec()
+                    }
+                    ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                        case.type_.len() == args.len() && case.name.as_vec() == name.as_vec()
+                    }
+                }),
+                _ => false,
+            });
+            if is_case {
+                let name = name.to_string_lossy();
+                return Ok(match args {
+                    [] => Value::String(name),
+                    [arg] => Value::Object([(name, to_json(spec, arg)?)].into_iter().collect()),
+                    args => Value::Object(
+                        [(
+                            name,
+                            Value::Array(
+                                args.iter()
+                                    .map(|arg| to_json(spec, arg))
+                                    .collect::<Result<_, _>>()?,
+                            ),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                });
+            }
+        }
+    }
+    match soroban_spec_tools::to_json(val) {
+        Ok(json) => Ok(json),
+        Err(_) => serde_json::to_value(val).map_err(|e| Error::CannotPrintJsonResult {
+            result: val.clone(),
+            error: e,
+        }),
+    }
+}
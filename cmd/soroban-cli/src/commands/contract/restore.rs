@@ -16,8 +16,11 @@ use soroban_env_host::xdr::{
 use stellar_strkey::DecodeError;
 
 use crate::{
-    commands::config::{self, locator},
-    rpc::{self, Client},
+    commands::{
+        config::{self, locator},
+        global,
+    },
+    rpc::{self, Client, Outcome},
     utils, wasm, Pwd,
 };
 
@@ -106,50 +109,55 @@ pub enum Error {
     Rpc(#[from] rpc::Error),
     #[error(transparent)]
     Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
-    pub async fn run(&self) -> Result<(), Error> {
-        let expiration_ledger_seq = if self.config.is_no_network() {
-            self.run_in_sandbox()?
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let outcome = if self.config.is_no_network() {
+            Outcome::Done(self.run_in_sandbox()?)
         } else {
-            self.run_against_rpc_server().await?
+            self.run_against_rpc_server(global_args).await?
+        };
+        let Outcome::Done(expiration_ledger_seq) = outcome else {
+            return Ok(());
         };
 
-        println!("New expiration ledger: {expiration_ledger_seq}");
+        global_args.formatter().print(
+            format!("New expiration ledger: {expiration_ledger_seq}"),
+            &serde_json::json!({ "expirationLedger": expiration_ledger_seq }),
+        )?;
 
         Ok(())
     }
 
-    pub async fn run_against_rpc_server(&self) -> Result<u32, Error> {
+    pub async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+    ) -> Result<Outcome<u32>, Error> {
         let network = self.config.get_network()?;
         tracing::trace!(?network);
-        let entry_keys = if let Some(wasm) = &self.wasm {
-            vec![crate::wasm::Args { wasm: wasm.clone() }.try_into()?]
-        } else if let Some(wasm_hash) = &self.wasm_hash {
-            vec![LedgerKey::ContractCode(LedgerKeyContractCode {
-                hash: Hash(
-                    utils::contract_id_from_str(wasm_hash)
-                        .map_err(|e| Error::CannotParseContractId(wasm_hash.clone(), e))?,
-                ),
-                body_type: ContractEntryBodyType::DataEntry,
-            })]
-        } else {
-            let contract_id = self.contract_id()?;
-            self.parse_keys(contract_id)?
-        };
+        self.restore(global_args, self.entry_keys()?).await
+    }
+
+    /// Restore the archived entries of `entry_keys`, returning their new expiration ledger.
+    pub async fn restore(
+        &self,
+        global_args: &global::Args,
+        entry_keys: Vec<LedgerKey>,
+    ) -> Result<Outcome<u32>, Error> {
         let network = &self.config.get_network()?;
         let client = Client::new(&network.rpc_url)?;
-        let key = self.config.key_pair()?;
+        let key = self.config.public_key()?;
 
         // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
-        let account_details = client.get_account(&public_strkey).await?;
+        let account_details = client.get_account(&key.to_string()).await?;
         let sequence: i64 = account_details.seq_num.into();
 
         let tx = Transaction {
-            source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+            source_account: MuxedAccount::Ed25519(Uint256(key.0)),
             fee: self.fee.fee,
             seq_num: SequenceNumber(sequence + 1),
             cond: Preconditions::None,
@@ -177,9 +185,20 @@ impl Cmd {
             }),
         };
 
-        let (result, meta, events) = client
-            .prepare_and_send_transaction(&tx, &key, &network.network_passphrase, None)
-            .await?;
+        let tx = self
+            .fee
+            .apply_to_assembled_tx(client.prepare_transaction(&tx, None).await?);
+        let Outcome::Done((result, meta, events)) = client
+            .send_transaction(
+                &self
+                    .config
+                    .sign_for_submission(&tx, self.fee.fee, &network.network_passphrase)?,
+                &global_args.submission(&network.network_passphrase),
+            )
+            .await?
+        else {
+            return Ok(Outcome::DryRun);
+        };
 
         tracing::trace!(?result);
         tracing::trace!(?meta);
@@ -206,18 +225,65 @@ impl Cmd {
                 operations[0].changes.len()
             );
         }
-        parse_operations(&operations).ok_or(Error::MissingOperationResult)
+        parse_operations(&operations)
+            .map(Outcome::Done)
+            .ok_or(Error::MissingOperationResult)
     }
 
+    /// The sandbox never evicts entries, so restoring one only renews its expiration, as the
+    /// network would, if it has passed.
     pub fn run_in_sandbox(&self) -> Result<u32, Error> {
-        // TODO: Implement this. This means we need to store ledger entries somewhere, and handle
-        // eviction, and restoration with that evicted state store.
-        todo!("Restoring ledger entries is not supported in the local sandbox mode");
+        let entry_keys = self.entry_keys()?;
+        let mut state = self.config.get_state()?;
+        let current_ledger = state.sequence_number;
+        let restored_expiration = current_ledger + state.min_persistent_entry_expiration - 1;
+        let mut expiration_ledger_seq = None;
+        for (key, entry) in &mut state.ledger_entries {
+            if !entry_keys.contains(&**key) {
+                continue;
+            }
+            if let LedgerEntryData::ContractData(ContractDataEntry {
+                expiration_ledger_seq: expiration,
+                ..
+            })
+            | LedgerEntryData::ContractCode(ContractCodeEntry {
+                expiration_ledger_seq: expiration,
+                ..
+            }) = &mut entry.data
+            {
+                if *expiration < current_ledger {
+                    *expiration = restored_expiration;
+                }
+                expiration_ledger_seq = Some(*expiration);
+            }
+        }
+        self.config.set_state(&mut state)?;
+        expiration_ledger_seq.ok_or(Error::LedgerEntryNotFound)
+    }
+
+    fn entry_keys(&self) -> Result<Vec<LedgerKey>, Error> {
+        Ok(if let Some(wasm) = &self.wasm {
+            vec![crate::wasm::Args { wasm: wasm.clone() }.try_into()?]
+        } else if let Some(wasm_hash) = &self.wasm_hash {
+            vec![LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: Hash(
+                    utils::contract_id_from_str(wasm_hash)
+                        .map_err(|e| Error::CannotParseContractId(wasm_hash.clone(), e))?,
+                ),
+                body_type: ContractEntryBodyType::DataEntry,
+            })]
+        } else {
+            let contract_id = self.contract_id()?;
+            self.parse_keys(contract_id)?
+        })
     }
 
     fn contract_id(&self) -> Result<[u8; 32], Error> {
-        utils::contract_id_from_str(self.contract_id.as_ref().unwrap())
-            .map_err(|e| Error::CannotParseContractId(self.contract_id.clone().unwrap(), e))
+        let contract_id = self
+            .config
+            .resolve_contract_id(self.contract_id.as_ref().unwrap())?;
+        utils::contract_id_from_str(&contract_id)
+            .map_err(|e| Error::CannotParseContractId(contract_id.clone(), e))
     }
 
     fn parse_keys(&self, contract_id: [u8; 32]) -> Result<Vec<LedgerKey>, Error> {
@@ -0,0 +1,115 @@
+use std::{ffi::OsString, fmt::Debug, path::PathBuf};
+
+use clap::{command, Parser};
+use soroban_env_host::{xdr::ContractEventType, HostError};
+use soroban_ledger_snapshot::LedgerSnapshot;
+
+use super::invoke::{self, HostInvocation};
+use crate::{
+    commands::config::{self, locator},
+    output::Output,
+    utils,
+};
+
+/// Invoke a function of a WASM file in an in-process host, without a network or the sandbox's
+/// ledger file, and print its result, events, and budget usage.
+///
+/// The ledger starts empty, or as in `--snapshot`, and is thrown away after the invocation.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// WASM file of the contract
+    #[arg(long)]
+    wasm: PathBuf,
+
+    /// Function to invoke
+    #[arg(long = "fn")]
+    function: String,
+
+    /// Argument to pass as `name=value`, e.g. `--arg to=alice`. Can be repeated
+    #[arg(long = "arg", value_name = "NAME=VALUE")]
+    args: Vec<String>,
+
+    /// Ledger snapshot to run against instead of an empty ledger, such as one written by
+    /// `snapshot create` or a sandbox ledger file. It is not changed
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+
+    /// Contract ID, or alias saved for the sandbox, to deploy the WASM as, e.g. to replace the
+    /// code of a contract in `--snapshot`
+    #[arg(long = "id")]
+    contract_id: Option<String>,
+
+    /// Account that invokes the function. Can be an identity, a secret key, or a seed phrase.
+    /// Default: `identity generate --default-seed`
+    #[arg(long, alias = "source")]
+    source_account: Option<String>,
+
+    /// Run with an unlimited budget
+    #[arg(long)]
+    unlimited_budget: bool,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error(transparent)]
+    Host(#[from] HostError),
+    #[error("reading snapshot {0:?}: {1}")]
+    CannotReadSnapshot(PathBuf, soroban_ledger_snapshot::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut state = match &self.snapshot {
+            Some(path) => LedgerSnapshot::read_file(path)
+                .map_err(|e| Error::CannotReadSnapshot(path.clone(), e))?,
+            None => utils::default_ledger_snapshot(),
+        };
+        let config = config::Args {
+            source_account: self.source_account.clone(),
+            locator: self.locator.clone(),
+            ..Default::default()
+        };
+        let contract_id = match &self.contract_id {
+            Some(alias_or_id) => config.resolve_contract_id(alias_or_id)?,
+            None => stellar_strkey::Contract([0; 32]).to_string(),
+        };
+        let invoke = invoke::Cmd {
+            contract_id,
+            wasm: Some(self.wasm.clone()),
+            unlimited_budget: self.unlimited_budget,
+            function: Some(self.function.clone()),
+            slop: self.args.iter().map(OsString::from).collect(),
+            config,
+            ..Default::default()
+        };
+        let HostInvocation {
+            output,
+            events,
+            budget,
+        } = invoke.invoke_in_host(&mut state, Output::Text)?;
+
+        println!("{output}");
+        let events = events
+            .iter()
+            .filter(|e| e.event.type_ != ContractEventType::Diagnostic)
+            .collect::<Vec<_>>();
+        if !events.is_empty() {
+            eprintln!("Events:");
+            for event in events {
+                eprintln!(" • {event}");
+            }
+        }
+        eprintln!("Budget:");
+        eprintln!(" • CPU instructions: {}", budget.get_cpu_insns_consumed()?);
+        eprintln!(" • Memory bytes: {}", budget.get_mem_bytes_consumed()?);
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use std::{collections::BTreeMap, fmt::Write, path::PathBuf, str::FromStr};
+
+use clap::{arg, command, Parser};
+
+use crate::wasm;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    wasm: wasm::Args,
+    /// Fail if the WASM file is larger than this many bytes. The network limits contract code
+    /// to 65536 bytes
+    #[arg(long)]
+    max_bytes: Option<u64>,
+    /// WASM file to compare against, e.g. the build from the main branch
+    #[arg(long, requires = "max_growth")]
+    baseline: Option<PathBuf>,
+    /// Fail if the WASM file grew by more than this over `--baseline`, as a percentage, e.g.
+    /// `5%`
+    #[arg(long, requires = "baseline")]
+    max_growth: Option<Percent>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error("invalid percentage {0:?}, expected a number like 5%")]
+    InvalidPercent(String),
+    #[error("cannot parse wasm sections of {0:?}")]
+    InvalidWasm(PathBuf),
+    #[error("size check failed:\n{0}")]
+    LimitsExceeded(String),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Percent(f64);
+
+impl FromStr for Percent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim_end_matches('%')
+            .parse()
+            .ok()
+            .filter(|p: &f64| p.is_finite() && *p >= 0.0)
+            .map(Percent)
+            .ok_or_else(|| Error::InvalidPercent(s.to_string()))
+    }
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let wasm = self.wasm.read()?;
+        let sections =
+            section_sizes(&wasm).ok_or_else(|| Error::InvalidWasm(self.wasm.wasm.clone()))?;
+        let baseline = self
+            .baseline
+            .as_ref()
+            .map(|path| {
+                let baseline = wasm::Args::from(path).read()?;
+                let sections =
+                    section_sizes(&baseline).ok_or_else(|| Error::InvalidWasm(path.clone()))?;
+                Ok::<_, Error>((baseline.len(), sections))
+            })
+            .transpose()?;
+
+        println!("{}", report(wasm.len(), &sections, baseline.as_ref()));
+
+        let mut failures = String::new();
+        let size = self.wasm.len()?;
+        if let Some(max_bytes) = self.max_bytes.filter(|max| size > *max) {
+            let _ = writeln!(
+                failures,
+                "  {size} bytes is over the limit of {max_bytes} bytes by {} bytes",
+                size - max_bytes
+            );
+        }
+        if let (Some((baseline_size, _)), Some(Percent(max_growth))) = (&baseline, self.max_growth)
+        {
+            let growth = growth(*baseline_size, wasm.len());
+            if growth > max_growth {
+                let _ = writeln!(
+                    failures,
+                    "  grew {growth:.2}% over the baseline, more than the allowed {max_growth}%"
+                );
+            }
+        }
+        if failures.is_empty() {
+            return Ok(());
+        }
+        let largest = largest_sections(&sections, baseline.as_ref().map(|(_, s)| s));
+        if !largest.is_empty() {
+            let _ = writeln!(failures, "  largest contributors: {}", largest.join(", "));
+        }
+        Err(Error::LimitsExceeded(failures.trim_end().to_string()))
+    }
+}
+
+/// Sizes of each section by name, with custom sections named `custom:<name>`. Sections that
+/// occur more than once have their sizes summed.
+fn section_sizes(wasm: &[u8]) -> Option<BTreeMap<String, usize>> {
+    let mut sections = BTreeMap::new();
+    let mut rest = wasm.strip_prefix(b"\0asm")?.get(4..)?;
+    while let Some((&id, after_id)) = rest.split_first() {
+        let (size, after_size) = read_leb128(after_id)?;
+        let contents = after_size.get(..size)?;
+        let name = match id {
+            0 => {
+                let (len, name) = read_leb128(contents)?;
+                format!("custom:{}", String::from_utf8_lossy(name.get(..len)?))
+            }
+            1 => "type".to_string(),
+            2 => "import".to_string(),
+            3 => "function".to_string(),
+            4 => "table".to_string(),
+            5 => "memory".to_string(),
+            6 => "global".to_string(),
+            7 => "export".to_string(),
+            8 => "start".to_string(),
+            9 => "element".to_string(),
+            10 => "code".to_string(),
+            11 => "data".to_string(),
+            12 => "data count".to_string(),
+            id => format!("unknown({id})"),
+        };
+        *sections.entry(name).or_default() += size;
+        rest = &after_size[size..];
+    }
+    Some(sections)
+}
+
+fn read_leb128(bytes: &[u8]) -> Option<(usize, &[u8])> {
+    let mut value = 0usize;
+    for (i, byte) in bytes.iter().enumerate().take(5) {
+        value |= usize::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn growth(from: usize, to: usize) -> f64 {
+    if from == 0 {
+        return if to == 0 { 0.0 } else { f64::INFINITY };
+    }
+    (to as f64 - from as f64) / from as f64 * 100.0
+}
+
+fn report(
+    size: usize,
+    sections: &BTreeMap<String, usize>,
+    baseline: Option<&(usize, BTreeMap<String, usize>)>,
+) -> String {
+    let mut out = String::new();
+    let row = |out: &mut String, name: &str, size: usize, baseline: Option<usize>| {
+        let _ = match baseline {
+            Some(baseline) => writeln!(
+                out,
+                "{name:<32} {size:>8} {baseline:>8} {:>+8}",
+                i64::try_from(size).unwrap_or(i64::MAX) - i64::try_from(baseline).unwrap_or(0)
+            ),
+            None => writeln!(out, "{name:<32} {size:>8}"),
+        };
+    };
+    if baseline.is_some() {
+        let _ = writeln!(
+            out,
+            "{:<32} {:>8} {:>8} {:>8}",
+            "section", "bytes", "baseline", "change"
+        );
+    } else {
+        let _ = writeln!(out, "{:<32} {:>8}", "section", "bytes");
+    }
+    let mut names = sections.keys().collect::<Vec<_>>();
+    if let Some((_, baseline)) = baseline {
+        names.extend(baseline.keys().filter(|name| !sections.contains_key(*name)));
+    }
+    for name in names {
+        row(
+            &mut out,
+            name,
+            sections.get(name).copied().unwrap_or_default(),
+            baseline.map(|(_, b)| b.get(name).copied().unwrap_or_default()),
+        );
+    }
+    row(&mut out, "total", size, baseline.map(|(size, _)| *size));
+    out.trim_end().to_string()
+}
+
+/// The three sections that grew the most, or without a baseline the three largest.
+fn largest_sections(
+    sections: &BTreeMap<String, usize>,
+    baseline: Option<&BTreeMap<String, usize>>,
+) -> Vec<String> {
+    let mut sizes = sections
+        .iter()
+        .map(|(name, size)| {
+            let from = baseline.map_or(0, |b| b.get(name).copied().unwrap_or_default());
+            (
+                name,
+                i64::try_from(*size).unwrap_or(i64::MAX) - i64::try_from(from).unwrap_or(0),
+            )
+        })
+        .filter(|(_, change)| *change > 0)
+        .collect::<Vec<_>>();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1));
+    sizes
+        .into_iter()
+        .take(3)
+        .map(|(name, change)| format!("{name} ({change:+} bytes)"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_of_minimal_module() {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // A custom section named "a" holding two bytes, and an empty type section.
+        wasm.extend([0, 4, 1, b'a', 0, 0]);
+        wasm.extend([1, 1, 0]);
+        let sections = section_sizes(&wasm).unwrap();
+        assert_eq!(sections["custom:a"], 4);
+        assert_eq!(sections["type"], 1);
+        assert!(section_sizes(&wasm[..wasm.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn percent() {
+        assert!((Percent::from_str("5%").unwrap().0 - 5.0).abs() < f64::EPSILON);
+        assert!(Percent::from_str("-1").is_err());
+        assert!((growth(100, 105) - 5.0).abs() < 1e-9);
+    }
+}
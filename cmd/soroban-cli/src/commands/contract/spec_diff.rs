@@ -0,0 +1,345 @@
+use std::{collections::BTreeMap, fmt::Debug, path::PathBuf};
+
+use clap::{command, Parser};
+use soroban_env_host::xdr::{ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtUnionCaseV0};
+
+use super::doc::type_name;
+use crate::{
+    commands::{
+        config::{ledger_file, locator, network},
+        contract::fetch,
+    },
+    utils::contract_spec::{self, ContractSpec},
+    wasm,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Wasm files to compare, the old one then the new one. With `--id`, only the new one
+    #[arg(num_args = 1..=2, required = true)]
+    wasm: Vec<PathBuf>,
+
+    /// Compare the contract deployed with this ID, or alias, instead of an old wasm file, with
+    /// the new wasm file
+    #[arg(long = "id")]
+    contract_id: Option<String>,
+
+    #[command(flatten)]
+    locator: locator::Args,
+
+    #[command(flatten)]
+    network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Fetch(#[from] fetch::Error),
+    #[error(transparent)]
+    Spec(#[from] contract_spec::Error),
+    #[error("expected {expected} wasm file(s), got {got}")]
+    WasmCount { expected: usize, got: usize },
+    #[error("{0} breaking change(s)")]
+    Breaking(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change {
+    pub kind: Kind,
+    /// Whether clients generated from the old spec may fail with the new one
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let expected = if self.contract_id.is_some() { 1 } else { 2 };
+        if self.wasm.len() != expected {
+            return Err(Error::WasmCount {
+                expected,
+                got: self.wasm.len(),
+            });
+        }
+        let parse = |path: &PathBuf| wasm::Args::from(path).parse().map(|wasm| wasm.spec);
+        let old = if let Some(contract_id) = &self.contract_id {
+            let contract_id = self
+                .locator
+                .resolve_contract_id(contract_id, &self.network.alias_key(&self.locator)?)?;
+            let fetch = fetch::Cmd {
+                contract_id: Some(contract_id),
+                wasm_hash: None,
+                out_file: None,
+                locator: self.locator.clone(),
+                network: self.network.clone(),
+                ledger_file: ledger_file::Args::default(),
+            };
+            ContractSpec::new(&fetch.get_bytes().await?)?.spec
+        } else {
+            parse(&self.wasm[0])?
+        };
+        let new = parse(&self.wasm[expected - 1])?;
+        let changes = diff(&old, &new);
+        if changes.is_empty() {
+            println!("No changes");
+            return Ok(());
+        }
+        for Change {
+            kind,
+            breaking,
+            description,
+        } in &changes
+        {
+            let sign = match kind {
+                Kind::Added => '+',
+                Kind::Removed => '-',
+                Kind::Changed => '~',
+            };
+            let breaking = if *breaking { " (breaking)" } else { "" };
+            println!("{sign} {description}{breaking}");
+        }
+        match changes.iter().filter(|change| change.breaking).count() {
+            0 => Ok(()),
+            breaking => Err(Error::Breaking(breaking)),
+        }
+    }
+}
+
+/// A named part of an entry, e.g. a struct field, and what the entry's clients rely on it being.
+type Members = Vec<(String, String)>;
+
+/// The kind of an entry, e.g. `struct`, its members, and whether adding members breaks clients.
+fn members(entry: &ScSpecEntry) -> (&'static str, Members, bool) {
+    match entry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 { inputs, .. }) => (
+            "fn",
+            inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| {
+                    (
+                        input.name.to_string_lossy(),
+                        format!("#{i}: {}", type_name(&input.type_)),
+                    )
+                })
+                .collect(),
+            true,
+        ),
+        ScSpecEntry::UdtStructV0(strukt) => (
+            "struct",
+            strukt
+                .fields
+                .iter()
+                .map(|field| (field.name.to_string_lossy(), type_name(&field.type_)))
+                .collect(),
+            true,
+        ),
+        ScSpecEntry::UdtUnionV0(union) => (
+            "union",
+            union
+                .cases
+                .iter()
+                .map(|case| match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                        (case.name.to_string_lossy(), String::new())
+                    }
+                    ScSpecUdtUnionCaseV0::TupleV0(case) => (
+                        case.name.to_string_lossy(),
+                        case.type_
+                            .iter()
+                            .map(type_name)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                })
+                .collect(),
+            false,
+        ),
+        ScSpecEntry::UdtEnumV0(enum_) => (
+            "enum",
+            enum_
+                .cases
+                .iter()
+                .map(|case| (case.name.to_string_lossy(), case.value.to_string()))
+                .collect(),
+            false,
+        ),
+        ScSpecEntry::UdtErrorEnumV0(enum_) => (
+            "error enum",
+            enum_
+                .cases
+                .iter()
+                .map(|case| (case.name.to_string_lossy(), case.value.to_string()))
+                .collect(),
+            false,
+        ),
+    }
+}
+
+fn entry_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtStructV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtUnionV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtEnumV0(x) => x.name.to_string_lossy(),
+        ScSpecEntry::UdtErrorEnumV0(x) => x.name.to_string_lossy(),
+    }
+}
+
+fn output(entry: &ScSpecEntry) -> Option<String> {
+    match entry {
+        ScSpecEntry::FunctionV0(function) => Some(
+            function
+                .outputs
+                .first()
+                .map_or_else(|| type_name(&ScSpecTypeDef::Void), type_name),
+        ),
+        _ => None,
+    }
+}
+
+/// The changes from the `old` spec to the `new` one. Removing or changing anything is breaking,
+/// as is adding function arguments or struct fields, but adding entries, or union or enum cases,
+/// is not. Docs are ignored.
+pub fn diff(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<Change> {
+    let by_name = |spec: &[ScSpecEntry]| {
+        spec.iter()
+            .map(|entry| (entry_name(entry), entry))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (old, new) = (by_name(old), by_name(new));
+    let mut changes = vec![];
+    for (name, entry) in &old {
+        let (kind, _, _) = members(entry);
+        if !new.contains_key(name) {
+            changes.push(Change {
+                kind: Kind::Removed,
+                breaking: true,
+                description: format!("{kind} {name}"),
+            });
+        }
+    }
+    for (name, entry) in &new {
+        let (kind, new_members, adding_breaks) = members(entry);
+        let Some(old_entry) = old.get(name) else {
+            changes.push(Change {
+                kind: Kind::Added,
+                breaking: false,
+                description: format!("{kind} {name}"),
+            });
+            continue;
+        };
+        let (old_kind, old_members, _) = members(old_entry);
+        if old_kind != kind {
+            changes.push(Change {
+                kind: Kind::Changed,
+                breaking: true,
+                description: format!("{name} from {old_kind} to {kind}"),
+            });
+            continue;
+        }
+        let old_members = old_members.into_iter().collect::<BTreeMap<_, _>>();
+        let new_members = new_members.into_iter().collect::<BTreeMap<_, _>>();
+        for member in old_members.keys().filter(|m| !new_members.contains_key(*m)) {
+            changes.push(Change {
+                kind: Kind::Removed,
+                breaking: true,
+                description: format!("{kind} {name}: {member}"),
+            });
+        }
+        for (member, value) in &new_members {
+            match old_members.get(member) {
+                None => changes.push(Change {
+                    kind: Kind::Added,
+                    breaking: adding_breaks,
+                    description: format!("{kind} {name}: {member}"),
+                }),
+                Some(old_value) if old_value != value => changes.push(Change {
+                    kind: Kind::Changed,
+                    breaking: true,
+                    description: format!("{kind} {name}: {member} from {old_value} to {value}"),
+                }),
+                Some(_) => {}
+            }
+        }
+        if let (Some(old_output), Some(new_output)) = (output(old_entry), output(entry)) {
+            if old_output != new_output {
+                changes.push(Change {
+                    kind: Kind::Changed,
+                    breaking: true,
+                    description: format!("{kind} {name}: output from {old_output} to {new_output}"),
+                });
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::ScSpecFunctionInputV0;
+
+    fn function(name: &str, inputs: &[(&str, ScSpecTypeDef)]) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            inputs: inputs
+                .iter()
+                .map(|(name, type_)| ScSpecFunctionInputV0 {
+                    doc: "".try_into().unwrap(),
+                    name: (*name).try_into().unwrap(),
+                    type_: type_.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: vec![].try_into().unwrap(),
+        })
+    }
+
+    #[test]
+    fn flags_breaking_changes() {
+        let old = [
+            function("hello", &[("to", ScSpecTypeDef::Symbol)]),
+            function("bye", &[]),
+        ];
+        let new = [
+            function("hello", &[("to", ScSpecTypeDef::String)]),
+            function("ping", &[]),
+        ];
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change {
+                    kind: Kind::Removed,
+                    breaking: true,
+                    description: "fn bye".to_string(),
+                },
+                Change {
+                    kind: Kind::Changed,
+                    breaking: true,
+                    description: "fn hello: to from #0: Symbol to #0: String".to_string(),
+                },
+                Change {
+                    kind: Kind::Added,
+                    breaking: false,
+                    description: "fn ping".to_string(),
+                },
+            ]
+        );
+    }
+}
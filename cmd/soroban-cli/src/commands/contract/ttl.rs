@@ -0,0 +1,236 @@
+use std::fmt::Debug;
+
+use chrono::{Duration, Utc};
+use clap::{command, Parser};
+use soroban_env_host::xdr::{
+    self, ContractCodeEntry, ContractDataEntry, ContractDataEntryBody, ContractDataEntryData,
+    ContractEntryBodyType, ContractExecutable, Hash, LedgerEntryData, LedgerKey,
+    LedgerKeyContractCode, LedgerKeyContractData, ReadXdr, ScAddress, ScContractInstance,
+    ScSpecTypeDef, ScVal, WriteXdr,
+};
+
+use crate::{
+    commands::{
+        config, contract::Durability, global, lab::ledger::now::TARGET_CLOSE_INTERVAL_SECS,
+    },
+    rpc::{self, Client},
+    utils,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias whose entries to report
+    #[arg(long = "id")]
+    contract_id: String,
+    /// Storage key, as a symbol or as the JSON form of an ScVal, can be repeated. The
+    /// contract's instance and code are always reported
+    #[arg(long = "key")]
+    key: Vec<String>,
+    /// Storage key (base64-encoded XDR ScVal), can be repeated
+    #[arg(long = "key-xdr")]
+    key_xdr: Vec<String>,
+    /// Durability of the entries of `--key` and `--key-xdr`
+    #[arg(long, value_enum, default_value = "persistent")]
+    durability: Durability,
+
+    #[command(flatten)]
+    config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("parsing key {key}: {error}")]
+    CannotParseKey {
+        key: String,
+        error: soroban_spec_tools::Error,
+    },
+    #[error("parsing XDR key {key}: {error}")]
+    CannotParseXdrKey { key: String, error: xdr::Error },
+    #[error("cannot parse contract ID {0}: {1}")]
+    CannotParseContractId(String, stellar_strkey::DecodeError),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] config::locator::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("latest ledger {0} is not a number")]
+    InvalidLatestLedger(String),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let contract_id = self.config.resolve_contract_id(&self.contract_id)?;
+        let contract_id = utils::contract_id_from_str(&contract_id)
+            .map_err(|e| Error::CannotParseContractId(contract_id.clone(), e))?;
+        let instance_key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash(contract_id)),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: xdr::ContractDataDurability::Persistent,
+            body_type: ContractEntryBodyType::DataEntry,
+        });
+        let (instance, _) = self.fetch(&[instance_key.clone()]).await?;
+        let mut keys = vec![instance_key];
+        if let Some((
+            _,
+            LedgerEntryData::ContractData(ContractDataEntry {
+                body:
+                    ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                        val:
+                            ScVal::ContractInstance(ScContractInstance {
+                                executable: ContractExecutable::Wasm(hash),
+                                ..
+                            }),
+                        ..
+                    }),
+                ..
+            }),
+        )) = instance.first()
+        {
+            keys.push(LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: hash.clone(),
+                body_type: ContractEntryBodyType::DataEntry,
+            }));
+        }
+        keys.extend(self.data_keys(contract_id)?);
+
+        let (entries, latest_ledger) = self.fetch(&keys).await?;
+        let format = self.config.locator.read_settings()?.format;
+        let now = Utc::now();
+        let mut text = format!("Latest ledger: {latest_ledger}\n");
+        let mut json_entries = Vec::with_capacity(keys.len());
+        for key in &keys {
+            let expiration = entries.iter().find_map(|(k, data)| match data {
+                LedgerEntryData::ContractData(ContractDataEntry {
+                    expiration_ledger_seq,
+                    ..
+                })
+                | LedgerEntryData::ContractCode(ContractCodeEntry {
+                    expiration_ledger_seq,
+                    ..
+                }) if k == key => Some(*expiration_ledger_seq),
+                _ => None,
+            });
+            let status = match expiration {
+                None => "not found".to_string(),
+                Some(expiration) if expiration < latest_ledger => format!(
+                    "live until ledger {expiration}, archived {} ledgers ago",
+                    latest_ledger - expiration
+                ),
+                Some(expiration) => {
+                    let ledgers = expiration - latest_ledger;
+                    let expires_at =
+                        now + Duration::seconds(i64::from(ledgers) * TARGET_CLOSE_INTERVAL_SECS);
+                    format!(
+                        "live until ledger {expiration}, {ledgers} ledgers from now, around {}",
+                        format.timestamp(expires_at)
+                    )
+                }
+            };
+            text.push_str(&format!("{}: {status}\n", describe(key)));
+            json_entries.push(serde_json::json!({
+                "key": key.to_xdr_base64()?,
+                "description": describe(key),
+                "expirationLedger": expiration,
+            }));
+        }
+        global_args.formatter().print(
+            text,
+            &serde_json::json!({ "latestLedger": latest_ledger, "entries": json_entries }),
+        )?;
+        Ok(())
+    }
+
+    /// The entries of `keys` that exist, and the latest ledger.
+    async fn fetch(
+        &self,
+        keys: &[LedgerKey],
+    ) -> Result<(Vec<(LedgerKey, LedgerEntryData)>, u32), Error> {
+        if self.config.is_no_network() {
+            let state = self.config.get_state()?;
+            let entries = state
+                .ledger_entries
+                .iter()
+                .filter(|(k, _)| keys.contains(&**k))
+                .map(|(k, v)| ((**k).clone(), v.data.clone()))
+                .collect();
+            return Ok((entries, state.sequence_number));
+        }
+        let network = self.config.get_network()?;
+        let response = Client::new(&network.rpc_url)?
+            .get_ledger_entries(keys.to_vec())
+            .await?;
+        let latest_ledger = response
+            .latest_ledger
+            .parse()
+            .map_err(|_| Error::InvalidLatestLedger(response.latest_ledger.clone()))?;
+        let entries = response
+            .entries
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| {
+                Ok((
+                    LedgerKey::from_xdr_base64(&entry.key)?,
+                    LedgerEntryData::from_xdr_base64(&entry.xdr)?,
+                ))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok((entries, latest_ledger))
+    }
+
+    fn data_keys(&self, contract_id: [u8; 32]) -> Result<Vec<LedgerKey>, Error> {
+        let mut keys = vec![];
+        for key in &self.key {
+            keys.push(match serde_json::from_str::<ScVal>(key) {
+                Ok(key) => key,
+                Err(_) => soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol)
+                    .map_err(|e| Error::CannotParseKey {
+                        key: key.clone(),
+                        error: e,
+                    })?,
+            });
+        }
+        for key in &self.key_xdr {
+            keys.push(
+                ScVal::from_xdr_base64(key).map_err(|e| Error::CannotParseXdrKey {
+                    key: key.clone(),
+                    error: e,
+                })?,
+            );
+        }
+        Ok(keys
+            .into_iter()
+            .map(|key| {
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: ScAddress::Contract(Hash(contract_id)),
+                    key,
+                    durability: self.durability.into(),
+                    body_type: ContractEntryBodyType::DataEntry,
+                })
+            })
+            .collect())
+    }
+}
+
+fn describe(key: &LedgerKey) -> String {
+    match key {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            key: ScVal::LedgerKeyContractInstance,
+            ..
+        }) => "Instance".to_string(),
+        LedgerKey::ContractCode(LedgerKeyContractCode { hash, .. }) => {
+            format!("Code {}", hex::encode(hash.0))
+        }
+        LedgerKey::ContractData(LedgerKeyContractData { key, .. }) => format!(
+            "Key {}",
+            soroban_spec_tools::to_string(key)
+                .unwrap_or_else(|_| key.to_xdr_base64().unwrap_or_default())
+        ),
+        key => format!("{key:?}"),
+    }
+}
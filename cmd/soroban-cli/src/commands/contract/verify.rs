@@ -0,0 +1,137 @@
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use clap::{command, Parser};
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{ScMetaEntry, ScMetaV0};
+
+use crate::{
+    commands::{
+        config::{ledger_file, locator, network},
+        contract::{build, fetch},
+    },
+    utils::contract_spec::{self, meta_key_name, ContractSpec},
+};
+
+/// Check that a deployed contract runs the code of a local wasm file, or of the contract built
+/// from a source directory.
+///
+/// Builds are only reproducible with the same toolchain, SDK version, and build options, and
+/// contracts are usually optimized before being deployed, so compare with the optimized file
+/// when that is what was deployed.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// ID of the deployed contract, or an alias saved for it
+    #[arg(long = "id", env = "SOROBAN_CONTRACT_ID")]
+    contract_id: String,
+
+    /// Local wasm file to compare with the deployed code
+    #[arg(
+        long,
+        required_unless_present = "source_dir",
+        conflicts_with = "source_dir"
+    )]
+    wasm: Option<PathBuf>,
+
+    /// Directory of the contract's cargo manifest, to build with `contract build` and compare the
+    /// result with the deployed code
+    #[arg(long)]
+    source_dir: Option<PathBuf>,
+
+    /// Package to build in `--source-dir`, when it contains several contracts
+    #[arg(long, requires = "source_dir")]
+    package: Option<String>,
+
+    #[command(flatten)]
+    locator: locator::Args,
+
+    #[command(flatten)]
+    network: network::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Fetch(#[from] fetch::Error),
+    #[error(transparent)]
+    Build(#[from] build::Error),
+    #[error(transparent)]
+    Spec(#[from] contract_spec::Error),
+    #[error("reading file {0:?}: {1}")]
+    CannotReadWasm(PathBuf, std::io::Error),
+    #[error("{0:?} builds {1} contracts, pick one with --package")]
+    AmbiguousPackage(PathBuf, usize),
+    #[error("the deployed code does not match {0:?}")]
+    Mismatch(PathBuf),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let wasm = self.local_wasm()?;
+        let local = fs::read(&wasm).map_err(|e| Error::CannotReadWasm(wasm.clone(), e))?;
+        let contract_id = self
+            .locator
+            .resolve_contract_id(&self.contract_id, &self.network.alias_key(&self.locator)?)?;
+        let fetch = fetch::Cmd {
+            contract_id: Some(contract_id.clone()),
+            wasm_hash: None,
+            out_file: None,
+            locator: self.locator.clone(),
+            network: self.network.clone(),
+            ledger_file: ledger_file::Args::default(),
+        };
+        let deployed = fetch.get_bytes().await?;
+
+        let hash = |bytes: &[u8]| hex::encode(Sha256::digest(bytes));
+        let (deployed_hash, local_hash) = (hash(&deployed), hash(&local));
+        println!("Contract: {contract_id}");
+        println!("Deployed: {deployed_hash}");
+        println!("Local:    {local_hash} ({})", wasm.to_string_lossy());
+        print_meta("Deployed meta", &ContractSpec::new(&deployed)?);
+        if deployed_hash == local_hash {
+            println!("Verdict: match");
+            return Ok(());
+        }
+        print_meta("Local meta", &ContractSpec::new(&local)?);
+        println!("Verdict: mismatch");
+        Err(Error::Mismatch(wasm))
+    }
+
+    /// The `--wasm` file, or the wasm file built from `--source-dir`.
+    fn local_wasm(&self) -> Result<PathBuf, Error> {
+        if let Some(wasm) = &self.wasm {
+            return Ok(wasm.clone());
+        }
+        let source_dir = self.source_dir.clone().unwrap_or_default();
+        let build = build::Cmd {
+            manifest_path: source_dir.join("Cargo.toml"),
+            package: self.package.clone(),
+            profile: "release".to_string(),
+            features: None,
+            all_features: false,
+            no_default_features: false,
+            out_dir: None,
+            print_commands_only: false,
+        };
+        let mut wasm_files = build.wasm_files()?;
+        if wasm_files.len() != 1 {
+            return Err(Error::AmbiguousPackage(source_dir, wasm_files.len()));
+        }
+        build.run()?;
+        Ok(wasm_files.remove(0))
+    }
+}
+
+fn print_meta(title: &str, spec: &ContractSpec) {
+    println!("{title}:");
+    if spec.meta.is_empty() {
+        println!(" • None");
+    }
+    for ScMetaEntry::ScMetaV0(ScMetaV0 { key, val }) in &spec.meta {
+        println!(" • {}: {val}", meta_key_name(&key.to_string_lossy()));
+    }
+}
@@ -1,10 +1,22 @@
 use clap::{arg, command, Parser};
-use std::io;
+use std::{collections::HashMap, fs, io, path::PathBuf, time::Duration};
 
 use soroban_env_host::xdr::{self, ReadXdr};
 
-use super::config::{events_file, locator, network};
-use crate::{rpc, toid, utils};
+use super::{
+    config::{events_file, locator, network},
+    global,
+};
+use crate::{format::Format, output::Output, rpc, toid, utils};
+
+pub mod decode;
+pub mod filter;
+pub mod notify;
+
+/// How long `--follow` waits before polling again once it has caught up.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(5);
+/// The longest to wait before retrying after failing to reach the RPC server.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -15,7 +27,18 @@ pub struct Cmd {
     #[arg(long, conflicts_with = "cursor", required_unless_present = "cursor")]
     start_ledger: Option<u32>,
 
-    /// The cursor corresponding to the start of the event range.
+    /// The last ledger sequence number in the range to pull events, to page through all the
+    /// events up to it, `--count` at a time, instead of fetching a single page
+    #[arg(long, conflicts_with_all = ["follow", "networks"])]
+    end_ledger: Option<u32>,
+
+    /// The most requests per second to make while paging through events with
+    /// `--end-ledger`
+    #[arg(long, default_value = "5", requires = "end_ledger")]
+    requests_per_second: u32,
+
+    /// The cursor corresponding to the start of the event range, or `now` to start at the
+    /// latest ledger.
     #[arg(
         long,
         conflicts_with = "start_ledger",
@@ -23,9 +46,16 @@ pub struct Cmd {
     )]
     cursor: Option<String>,
 
-    /// Output formatting options for event stream
-    #[arg(long, value_enum, default_value = "pretty")]
-    output: OutputFormat,
+    /// Keep polling for new events, printing them as they arrive, until interrupted. Failed
+    /// polls are retried with an increasing backoff. With `--networks`, each network is polled
+    /// from after its own last event.
+    #[arg(long)]
+    follow: bool,
+
+    /// Output formatting options for event stream. Default: `ndjson` with the global
+    /// `--output-format json`, `pretty` otherwise
+    #[arg(long, value_enum)]
+    output: Option<OutputFormat>,
 
     /// The maximum number of events to display (specify "0" to show all events
     /// when using sandbox, or to defer to the server-defined limit if using
@@ -33,10 +63,14 @@ pub struct Cmd {
     #[arg(short, long, default_value = "10")]
     count: usize,
 
-    /// A set of (up to 5) contract IDs to filter events on. This parameter can
+    /// A set of (up to 5) contract IDs, or aliases, to filter events on. This parameter can
     /// be passed multiple times, e.g. `--id abc --id def`, or passed with
     /// multiple parameters, e.g. `--id abd def`.
     ///
+    /// The events of these contracts are decoded with their contract specs, naming their
+    /// topics and data after the arguments of the function their first topic names, e.g.
+    /// `transfer(from, to, amount)`. Not with `--networks`.
+    ///
     /// Though the specification supports multiple filter objects (i.e.
     /// combinations of type, IDs, and topics), only one set can be specified on
     /// the command-line today, though that set can have multiple IDs/topics.
@@ -68,6 +102,17 @@ pub struct Cmd {
     )]
     topic_filters: Vec<String>,
 
+    /// Only show events whose topic at an index has a value, as `INDEX:VALUE`, e.g.
+    /// `--topic-eq 0:transfer --topic-eq 1:GABC...`. Values are compared with the topics
+    /// decoded to JSON. Without `--topic`, symbol and address values are also sent to the RPC
+    /// server as topic filters. Can be repeated
+    #[arg(long, value_name = "INDEX:VALUE", help_heading = "FILTERS")]
+    topic_eq: Vec<filter::TopicEq>,
+
+    /// Only show events whose data, decoded to JSON, contains this text
+    #[arg(long, help_heading = "FILTERS")]
+    data_contains: Option<String>,
+
     /// Specifies which type of contract events to display.
     #[arg(
         long = "type",
@@ -77,6 +122,23 @@ pub struct Cmd {
     )]
     event_type: rpc::EventType,
 
+    /// Fetch events from several configured networks at once, e.g. `--networks
+    /// testnet,futurenet`, tagging each event with its network. Ledger numbers differ between
+    /// networks, so `--start-ledger` should be no later than the earliest ledger of interest
+    /// on any of them.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        conflicts_with_all = ["network", "rpc_url", "cursor"],
+    )]
+    networks: Vec<String>,
+
+    /// JSON file mapping network names to the contract IDs to filter on for that network, to
+    /// follow the same contracts deployed to each of `--networks`, e.g.
+    /// `{"testnet": ["C..."], "futurenet": ["C..."]}`. Networks not in the map use `--id`.
+    #[arg(long, requires = "networks", help_heading = "FILTERS")]
+    id_map: Option<PathBuf>,
+
     #[command(flatten)]
     locator: locator::Args,
 
@@ -85,6 +147,9 @@ pub struct Cmd {
 
     #[command(flatten)]
     events_file: events_file::Args,
+
+    #[command(flatten)]
+    notify: notify::Args,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -123,6 +188,9 @@ pub enum Error {
     #[error("invalid timestamp in event: {ts}")]
     InvalidTimestamp { ts: String },
 
+    #[error("invalid ledger in event: {ledger}")]
+    InvalidLedger { ledger: String },
+
     #[error("missing start_ledger and cursor")]
     MissingStartLedgerAndCursor,
     #[error("missing target")]
@@ -151,6 +219,21 @@ pub enum Error {
 
     #[error(transparent)]
     Locator(#[from] locator::Error),
+
+    #[error(transparent)]
+    Notify(#[from] notify::Error),
+
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+
+    #[error(transparent)]
+    Filter(#[from] filter::Error),
+
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+
+    #[error("cannot print as csv: {error}")]
+    CannotPrintAsCsv { error: csv::Error },
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum)]
@@ -161,10 +244,43 @@ pub enum OutputFormat {
     Plain,
     /// JSONified console output
     Json,
+    /// One JSON object per line, for jq and other JSON-lines tools
+    Ndjson,
+    /// Comma-separated values, with a header row, with the topics and value decoded to JSON
+    Csv,
+}
+
+/// Where to fetch the events of one of `--networks` from.
+#[derive(Clone, Debug)]
+struct Feed {
+    name: String,
+    rpc_url: String,
+    contract_ids: Vec<String>,
+    start: rpc::EventStart,
 }
 
+/// Columns of `--output csv`.
+const CSV_HEADER: [&str; 9] = [
+    "network",
+    "id",
+    "type",
+    "ledger",
+    "ledger_closed_at",
+    "contract_id",
+    "topics",
+    "value",
+    "decoded",
+];
+
 impl Cmd {
-    pub async fn run(&mut self) -> Result<(), Error> {
+    pub async fn run(&mut self, global_args: &global::Args) -> Result<(), Error> {
+        // Events are a stream, so JSON results are one event per line, as with other commands.
+        // Quiet output would print nothing at all, so it keeps the default.
+        self.output.get_or_insert(match global_args.output_format {
+            Output::Json => OutputFormat::Ndjson,
+            Output::Text | Output::Quiet => OutputFormat::Pretty,
+        });
+
         // Validate that topics are made up of segments.
         for topic in &self.topic_filters {
             for (i, segment) in topic.split(',').enumerate() {
@@ -186,45 +302,379 @@ impl Cmd {
             }
         }
 
-        // Validate contract_ids
-        for id in &mut self.contract_ids {
-            utils::contract_id_from_str(id).map_err(|e| Error::InvalidContractId {
-                contract_id: id.clone(),
-                error: e,
-            })?;
+        // With `--networks`, aliases are resolved for each network
+        if self.networks.is_empty() {
+            let alias_key = self.network.alias_key(&self.locator)?;
+            self.contract_ids = self.resolve_contract_ids(&self.contract_ids, &alias_key)?;
         }
 
-        let response = if self.network.is_no_network() {
+        if self.topic_filters.is_empty() {
+            if let Some(topics) = filter::server_topics(&self.topic_eq) {
+                self.topic_filters = topics;
+            }
+        }
+
+        if self.output() == OutputFormat::Csv {
+            print_csv_row(CSV_HEADER)?;
+        }
+
+        if !self.networks.is_empty() {
+            return self.run_against_networks().await;
+        }
+
+        if self.cursor.as_deref() == Some("now") {
+            self.start_ledger = Some(self.latest_ledger().await?);
+            self.cursor = None;
+        }
+
+        let decoder =
+            decode::Decoder::fetch(&self.contract_ids, &self.locator, &self.network).await;
+        if self.follow {
+            return self.follow(&decoder).await;
+        }
+        if let Some(end_ledger) = self.end_ledger {
+            return self.backfill(end_ledger, &decoder).await;
+        }
+
+        let response = self.fetch().await?;
+
+        let format = self.locator.read_settings()?.format;
+        for event in &response.events {
+            self.show(event, None, &decoder, &format).await?;
+        }
+        self.print_latest_ledger(&format!("Latest Ledger: {}", response.latest_ledger));
+
+        Ok(())
+    }
+
+    async fn fetch(&self) -> Result<rpc::GetEventsResponse, Error> {
+        if self.network.is_no_network() {
             self.run_in_sandbox()
         } else {
             self.run_against_rpc_server().await
-        }?;
+        }
+    }
 
-        for event in &response.events {
-            match self.output {
-                // Should we pretty-print the JSON like we're doing here or just
-                // dump an event in raw JSON on each line? The latter is easier
-                // to consume programmatically.
-                OutputFormat::Json => {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&event).map_err(|e| {
-                            Error::InvalidJson {
-                                debug: format!("{event:#?}"),
-                                error: e,
-                            }
-                        })?,
-                    );
+    /// Fetch, retrying with an increasing backoff while the RPC server fails, e.g. because it
+    /// is unreachable or limits the request rate.
+    async fn fetch_with_retry(&self) -> Result<rpc::GetEventsResponse, Error> {
+        let mut backoff = FOLLOW_INTERVAL;
+        loop {
+            match self.fetch().await {
+                Err(Error::Rpc(e)) => {
+                    eprintln!("warning: {e}, retrying in {}s", backoff.as_secs());
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
                 }
-                OutputFormat::Plain => println!("{event}"),
-                OutputFormat::Pretty => event.pretty_print()?,
+                response => return response,
             }
         }
-        println!("Latest Ledger: {}", response.latest_ledger);
+    }
+
+    /// Continue from after the last of the events.
+    fn advance(&mut self, events: &[rpc::Event]) {
+        if let Some(event) = events.last() {
+            self.start_ledger = None;
+            self.cursor = Some(event.paging_token.clone());
+        }
+    }
+
+    /// Poll for events after the last one printed, until interrupted.
+    async fn follow(&mut self, decoder: &decode::Decoder) -> Result<(), Error> {
+        let format = self.locator.read_settings()?.format;
+        loop {
+            let response = self.fetch_with_retry().await?;
+            for event in &response.events {
+                self.show(event, None, decoder, &format).await?;
+            }
+            self.advance(&response.events);
+            // A full page means more events may already be waiting
+            if self.count == 0 || response.events.len() < self.count {
+                tokio::time::sleep(FOLLOW_INTERVAL).await;
+            }
+        }
+    }
 
+    /// Page through the events up to `end_ledger`, keeping under `--requests-per-second`.
+    async fn backfill(&mut self, end_ledger: u32, decoder: &decode::Decoder) -> Result<(), Error> {
+        let format = self.locator.read_settings()?.format;
+        let interval = Duration::from_secs(1) / self.requests_per_second.max(1);
+        loop {
+            let response = self.fetch_with_retry().await?;
+            for event in &response.events {
+                let ledger: u32 = event.ledger.parse().map_err(|_| Error::InvalidLedger {
+                    ledger: event.ledger.clone(),
+                })?;
+                if ledger > end_ledger {
+                    self.print_latest_ledger(&format!("Latest Ledger: {}", response.latest_ledger));
+                    return Ok(());
+                }
+                self.show(event, None, decoder, &format).await?;
+            }
+            // A page that is not full is the last, as is an empty one when the server decides
+            // the page size
+            if response.events.is_empty() || (self.count != 0 && response.events.len() < self.count)
+            {
+                self.print_latest_ledger(&format!("Latest Ledger: {}", response.latest_ledger));
+                return Ok(());
+            }
+            self.advance(&response.events);
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// The latest ledger of the RPC server, or of the sandbox's events file.
+    async fn latest_ledger(&self) -> Result<u32, Error> {
+        if self.network.is_no_network() {
+            let file = self.events_file.read(&self.locator.config_dir()?)?;
+            Ok(file.latest_ledger)
+        } else {
+            let network = self.network.get(&self.locator)?;
+            let client = rpc::Client::new(&network.rpc_url)?;
+            Ok(client.get_latest_ledger().await?.sequence)
+        }
+    }
+
+    /// Print and notify about the event, if it matches `--topic-eq` and `--data-contains`.
+    async fn show(
+        &self,
+        event: &rpc::Event,
+        network: Option<&str>,
+        decoder: &decode::Decoder,
+        format: &Format,
+    ) -> Result<(), Error> {
+        let decoded = decoder.decode(event)?;
+        if !filter::matches(
+            event,
+            decoded.as_ref(),
+            &self.topic_eq,
+            self.data_contains.as_deref(),
+        )? {
+            return Ok(());
+        }
+        self.print_event(event, network, decoded.as_ref(), format)?;
+        self.notify.notify(event).await?;
+        Ok(())
+    }
+
+    fn print_event(
+        &self,
+        event: &rpc::Event,
+        network: Option<&str>,
+        decoded: Option<&decode::Decoded>,
+        format: &Format,
+    ) -> Result<(), Error> {
+        match self.output() {
+            // Should we pretty-print the JSON like we're doing here or just
+            // dump an event in raw JSON on each line? The latter is easier
+            // to consume programmatically.
+            OutputFormat::Json => {
+                let value = event_json(event, network, decoded)?;
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            }
+            OutputFormat::Ndjson => {
+                let value = event_json(event, network, decoded)?;
+                println!("{}", serde_json::to_string(&value)?);
+            }
+            OutputFormat::Csv => {
+                let topics = event
+                    .topic
+                    .iter()
+                    .map(|topic| xdr_to_json(topic))
+                    .collect::<Vec<_>>();
+                print_csv_row([
+                    network.unwrap_or_default(),
+                    event.id.as_str(),
+                    event.event_type.as_str(),
+                    event.ledger.as_str(),
+                    event.ledger_closed_at.as_str(),
+                    event.contract_id.as_str(),
+                    serde_json::Value::Array(topics).to_string().as_str(),
+                    xdr_to_json(&event.value.xdr).to_string().as_str(),
+                    decoded
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                        .as_str(),
+                ])?;
+            }
+            OutputFormat::Plain => {
+                if let Some(network) = network {
+                    println!("Network: {network}");
+                }
+                print!("{}", event.formatted(format));
+                if let Some(decoded) = decoded {
+                    println!("  Decoded:  {decoded}");
+                }
+                println!();
+            }
+            OutputFormat::Pretty => {
+                if let Some(network) = network {
+                    println!("Network: {network}");
+                }
+                event.formatted(format).pretty_print()?;
+                if let Some(decoded) = decoded {
+                    println!("  Decoded:  {decoded}");
+                }
+            }
+        }
         Ok(())
     }
 
+    /// `--output`, defaulted from the global output format by `run`.
+    fn output(&self) -> OutputFormat {
+        self.output.unwrap_or(OutputFormat::Pretty)
+    }
+
+    /// Print the latest ledger after the events, to stderr when the events are printed in a
+    /// format for other tools to read.
+    fn print_latest_ledger(&self, line: &str) {
+        match self.output() {
+            OutputFormat::Ndjson | OutputFormat::Csv => eprintln!("{line}"),
+            OutputFormat::Pretty | OutputFormat::Plain | OutputFormat::Json => println!("{line}"),
+        }
+    }
+
+    /// Fetch from each of `--networks` concurrently, printing the events of all of them in
+    /// the order they closed. With `--follow`, keep polling each of them from after its last
+    /// event.
+    async fn run_against_networks(&self) -> Result<(), Error> {
+        let start = self.start()?;
+        let id_map = self.read_id_map()?;
+        let mut feeds = vec![];
+        for name in &self.networks {
+            let network = network::Args {
+                network: Some(name.clone()),
+                ..Default::default()
+            }
+            .get(&self.locator)?;
+            let contract_ids = self.resolve_contract_ids(
+                id_map.get(name).unwrap_or(&self.contract_ids),
+                &network.network_passphrase,
+            )?;
+            rpc::Client::new(&network.rpc_url)?
+                .verify_network_passphrase(Some(&network.network_passphrase))
+                .await?;
+            feeds.push(Feed {
+                name: name.clone(),
+                rpc_url: network.rpc_url,
+                contract_ids,
+                start: start.clone(),
+            });
+        }
+        let format = self.locator.read_settings()?.format;
+        let decoder = decode::Decoder::default();
+        loop {
+            let responses = self.fetch_feeds(&feeds).await?;
+            let mut events = responses
+                .iter()
+                .flat_map(|(i, response)| response.events.iter().map(|e| (*i, e)))
+                .collect::<Vec<_>>();
+            events.sort_by(|(_, a), (_, b)| a.ledger_closed_at.cmp(&b.ledger_closed_at));
+            for (i, event) in events {
+                self.show(event, Some(feeds[i].name.as_str()), &decoder, &format)
+                    .await?;
+            }
+            if !self.follow {
+                let mut latest_ledgers = responses
+                    .iter()
+                    .map(|(i, response)| (&feeds[*i].name, response.latest_ledger))
+                    .collect::<Vec<_>>();
+                latest_ledgers.sort();
+                for (name, latest_ledger) in latest_ledgers {
+                    self.print_latest_ledger(&format!("Latest Ledger ({name}): {latest_ledger}"));
+                }
+                return Ok(());
+            }
+            let mut full_page = false;
+            for (i, response) in &responses {
+                if let Some(event) = response.events.last() {
+                    feeds[*i].start = rpc::EventStart::Cursor(event.paging_token.clone());
+                }
+                full_page |= self.count != 0 && response.events.len() >= self.count;
+            }
+            // A full page means more events may already be waiting
+            if !full_page {
+                tokio::time::sleep(FOLLOW_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Fetch a page of events of each feed concurrently, by the feed's index. With `--follow`,
+    /// failed fetches are retried with an increasing backoff.
+    async fn fetch_feeds(
+        &self,
+        feeds: &[Feed],
+    ) -> Result<Vec<(usize, rpc::GetEventsResponse)>, Error> {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, feed) in feeds.iter().enumerate() {
+            let feed = feed.clone();
+            let (event_type, topics, count, follow) = (
+                self.event_type,
+                self.topic_filters.clone(),
+                self.count,
+                self.follow,
+            );
+            tasks.spawn(async move {
+                let client = rpc::Client::new(&feed.rpc_url)?;
+                let mut backoff = FOLLOW_INTERVAL;
+                loop {
+                    let response = client
+                        .get_events(
+                            feed.start.clone(),
+                            Some(event_type),
+                            &feed.contract_ids,
+                            &topics,
+                            Some(count),
+                        )
+                        .await;
+                    match response {
+                        Err(e) if follow => {
+                            eprintln!(
+                                "warning: {}: {e}, retrying in {}s",
+                                feed.name,
+                                backoff.as_secs()
+                            );
+                            tokio::time::sleep(backoff).await;
+                            backoff = (backoff * 2).min(MAX_BACKOFF);
+                        }
+                        response => return Ok::<_, rpc::Error>((i, response?)),
+                    }
+                }
+            });
+        }
+        let mut responses = vec![];
+        while let Some(result) = tasks.join_next().await {
+            responses.push(result??);
+        }
+        Ok(responses)
+    }
+
+    fn read_id_map(&self) -> Result<HashMap<String, Vec<String>>, Error> {
+        let Some(path) = &self.id_map else {
+            return Ok(HashMap::new());
+        };
+        let contents = fs::read_to_string(path).map_err(|e| Error::CannotReadFile {
+            path: path.display().to_string(),
+            error: e.to_string(),
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Resolve the contract aliases among `ids` saved for the network with `alias_key`, and
+    /// check that they are all contract IDs.
+    fn resolve_contract_ids(&self, ids: &[String], alias_key: &str) -> Result<Vec<String>, Error> {
+        ids.iter()
+            .map(|id| {
+                let id = self.locator.resolve_contract_id(id, alias_key)?;
+                utils::contract_id_from_str(&id).map_err(|e| Error::InvalidContractId {
+                    contract_id: id.clone(),
+                    error: e,
+                })?;
+                Ok(id)
+            })
+            .collect()
+    }
+
     async fn run_against_rpc_server(&self) -> Result<rpc::GetEventsResponse, Error> {
         let start = self.start()?;
         let network = self.network.get(&self.locator)?;
@@ -286,6 +736,42 @@ impl Cmd {
     }
 }
 
+/// The event as JSON, with the network it is from and its decoding, if any.
+fn event_json(
+    event: &rpc::Event,
+    network: Option<&str>,
+    decoded: Option<&decode::Decoded>,
+) -> Result<serde_json::Value, Error> {
+    let mut value = serde_json::to_value(event).map_err(|e| Error::InvalidJson {
+        debug: format!("{event:#?}"),
+        error: e,
+    })?;
+    if let (Some(network), Some(object)) = (network, value.as_object_mut()) {
+        object.insert("network".to_string(), network.into());
+    }
+    if let (Some(decoded), Some(object)) = (decoded, value.as_object_mut()) {
+        object.insert("decoded".to_string(), decoded.to_json());
+    }
+    Ok(value)
+}
+
+/// Base64 XDR of an ScVal decoded to JSON, or as it is if it cannot be.
+fn xdr_to_json(xdr: &str) -> serde_json::Value {
+    xdr::ScVal::from_xdr_base64(xdr)
+        .ok()
+        .and_then(|val| soroban_spec_tools::to_json(&val).ok())
+        .unwrap_or_else(|| xdr.into())
+}
+
+/// Print a CSV row, flushing it so rows are printed as events arrive.
+fn print_csv_row<'a>(row: impl IntoIterator<Item = &'a str>) -> Result<(), Error> {
+    let mut out = csv::Writer::from_writer(io::stdout());
+    out.write_record(row)
+        .map_err(|e| Error::CannotPrintAsCsv { error: e })?;
+    out.flush()?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path;
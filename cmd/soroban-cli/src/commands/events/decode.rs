@@ -0,0 +1,166 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde_json::Value;
+use soroban_env_host::xdr::{self, ReadXdr, ScSpecTypeDef as ScType, ScVal};
+use soroban_spec_tools::Spec;
+
+use crate::{
+    commands::{
+        config::{ledger_file, locator, network},
+        contract::fetch,
+    },
+    rpc, utils,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Fetch(#[from] fetch::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+/// The specs of the contracts whose events are decoded.
+///
+/// Contract specs do not describe events, so an event is decoded after the function its first
+/// topic names, if there is one that takes as many arguments as the event has other topics and
+/// data. The token interface's events, e.g. `transfer(from, to, amount)`, follow this. Other
+/// events get their topics and data decoded without names.
+#[derive(Clone, Default)]
+pub struct Decoder {
+    specs: HashMap<[u8; 32], Spec>,
+}
+
+/// An event's name, and its topics after the name and its data, named if they could be.
+#[derive(Debug, Clone)]
+pub struct Decoded {
+    pub name: String,
+    pub args: Vec<(String, Value)>,
+}
+
+impl Decoder {
+    /// Fetch the specs of `contract_ids` from the network, or from the sandbox's ledger file.
+    /// Contracts without one, such as Stellar Asset Contracts, are warned about and their events
+    /// are not decoded.
+    pub async fn fetch(
+        contract_ids: &[String],
+        locator: &locator::Args,
+        network: &network::Args,
+    ) -> Self {
+        let mut specs = HashMap::new();
+        for id in contract_ids {
+            let Ok(contract_id) = utils::contract_id_from_str(id) else {
+                continue;
+            };
+            match fetch_spec(id, locator, network).await {
+                Ok(spec) => {
+                    specs.insert(contract_id, spec);
+                }
+                Err(e) => eprintln!("warning: not decoding the events of {id}: {e}"),
+            }
+        }
+        Self { specs }
+    }
+
+    /// Decode the event, if it is from one of the contracts with a spec.
+    pub fn decode(&self, event: &rpc::Event) -> Result<Option<Decoded>, Error> {
+        let Some(spec) = utils::contract_id_from_str(&event.contract_id)
+            .ok()
+            .and_then(|id| self.specs.get(&id))
+        else {
+            return Ok(None);
+        };
+        let mut topics = event
+            .topic
+            .iter()
+            .map(ScVal::from_xdr_base64)
+            .collect::<Result<Vec<_>, _>>()?;
+        let name = match topics.first() {
+            Some(ScVal::Symbol(name)) => Some(name.to_string_lossy()),
+            _ => None,
+        };
+        if name.is_some() {
+            topics.remove(0);
+        }
+        let values = topics.len();
+        topics.push(ScVal::from_xdr_base64(&event.value.xdr)?);
+
+        let inputs = name
+            .as_ref()
+            .and_then(|name| spec.find_function(name).ok())
+            .filter(|function| function.inputs.len() == topics.len())
+            .map(|function| function.inputs.to_vec());
+        let args = topics
+            .iter()
+            .enumerate()
+            .map(|(i, val)| {
+                let (arg, value) = match inputs.as_ref().map(|inputs| &inputs[i]) {
+                    Some(input) => (
+                        input.name.to_string_lossy(),
+                        to_json(spec, val, &input.type_),
+                    ),
+                    None if i == values => ("data".to_string(), soroban_spec_tools::to_json(val)),
+                    None => (format!("topic{}", i + 1), soroban_spec_tools::to_json(val)),
+                };
+                Ok((arg, value?))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Some(Decoded {
+            name: name.unwrap_or_else(|| "event".to_string()),
+            args,
+        }))
+    }
+}
+
+impl Decoded {
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "name": self.name,
+            "args": self.args.iter().cloned().collect::<serde_json::Map<_, _>>(),
+        })
+    }
+}
+
+impl Display for Decoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = self
+            .args
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}({})", self.name, args.join(", "))
+    }
+}
+
+async fn fetch_spec(
+    contract_id: &str,
+    locator: &locator::Args,
+    network: &network::Args,
+) -> Result<Spec, Error> {
+    let wasm = fetch::Cmd {
+        contract_id: Some(contract_id.to_string()),
+        wasm_hash: None,
+        out_file: None,
+        locator: locator.clone(),
+        network: network.clone(),
+        ledger_file: ledger_file::Args::default(),
+    }
+    .get_bytes()
+    .await?;
+    Ok(Spec::from_wasm(&wasm)?)
+}
+
+/// The value as JSON of its type in the spec. Only user-defined types decode differently from
+/// untyped values, and the spec's decoding panics on some mismatched types, so it is only used
+/// for those.
+fn to_json(spec: &Spec, val: &ScVal, type_: &ScType) -> Result<Value, soroban_spec_tools::Error> {
+    match (val, type_) {
+        (ScVal::Map(Some(_)) | ScVal::Vec(Some(_)) | ScVal::U32(_), ScType::Udt(_)) => spec
+            .xdr_to_json(val, type_)
+            .or_else(|_| soroban_spec_tools::to_json(val)),
+        _ => soroban_spec_tools::to_json(val),
+    }
+}
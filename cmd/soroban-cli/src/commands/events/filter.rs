@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+use soroban_env_host::xdr::{
+    self, AccountId, Hash, PublicKey, ReadXdr, ScAddress, ScSpecTypeDef, ScVal, Uint256, WriteXdr,
+};
+
+use super::decode::Decoded;
+use crate::rpc;
+
+/// Topics have at most 4 segments.
+const MAX_TOPICS: usize = 4;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+}
+
+/// A topic, by its index in the event's topics, and the value it must have.
+#[derive(Clone, Debug)]
+pub struct TopicEq {
+    pub index: usize,
+    pub value: String,
+}
+
+impl FromStr for TopicEq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("{s} is not of the form INDEX:VALUE"))?;
+        let index = index
+            .parse()
+            .ok()
+            .filter(|index| *index < MAX_TOPICS)
+            .ok_or_else(|| format!("topic index {index} is not between 0 and 3"))?;
+        Ok(Self {
+            index,
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Whether the event's topics have the values of `topic_eq`, and its data, decoded to JSON,
+/// contains `data_contains`.
+pub fn matches(
+    event: &rpc::Event,
+    decoded: Option<&Decoded>,
+    topic_eq: &[TopicEq],
+    data_contains: Option<&str>,
+) -> Result<bool, Error> {
+    for TopicEq { index, value } in topic_eq {
+        let Some(topic) = event.topic.get(*index) else {
+            return Ok(false);
+        };
+        if !is_equal(
+            &soroban_spec_tools::to_json(&ScVal::from_xdr_base64(topic)?)?,
+            value,
+        ) {
+            return Ok(false);
+        }
+    }
+    if let Some(text) = data_contains {
+        let data = match decoded.and_then(|decoded| decoded.args.last()) {
+            Some((_, data)) => data.clone(),
+            None => soroban_spec_tools::to_json(&ScVal::from_xdr_base64(&event.value.xdr)?)?,
+        };
+        if !data.to_string().contains(text) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn is_equal(json: &Value, value: &str) -> bool {
+    match json {
+        Value::String(s) => s == value,
+        json => serde_json::from_str::<Value>(value).is_ok_and(|value| value == *json),
+    }
+}
+
+/// Topic filters for the RPC server that match the events `topic_eq` can, if all its values are
+/// symbols or addresses. Filters match events with as many topics as they have segments, so
+/// there is one for each number of topics the events can have.
+pub fn server_topics(topic_eq: &[TopicEq]) -> Option<Vec<String>> {
+    let mut segments = vec!["*".to_string(); MAX_TOPICS];
+    for TopicEq { index, value } in topic_eq {
+        segments[*index] = to_scval(value)?.to_xdr_base64().ok()?;
+    }
+    let min_topics = topic_eq.iter().map(|t| t.index + 1).max()?;
+    Some(
+        (min_topics..=MAX_TOPICS)
+            .map(|topics| segments[..topics].join(","))
+            .collect(),
+    )
+}
+
+/// The value as an address if it is one, or as a symbol. Other values, such as numbers, could be
+/// one of several types, so they are not converted.
+fn to_scval(value: &str) -> Option<ScVal> {
+    if let Ok(key) = stellar_strkey::ed25519::PublicKey::from_string(value) {
+        return Some(ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+        ))));
+    }
+    if let Ok(contract) = stellar_strkey::Contract::from_string(value) {
+        return Some(ScVal::Address(ScAddress::Contract(Hash(contract.0))));
+    }
+    if serde_json::from_str::<Value>(value).is_ok() {
+        return None;
+    }
+    soroban_spec_tools::from_string_primitive(value, &ScSpecTypeDef::Symbol).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_topics_cover_each_topic_count() {
+        let topic_eq = ["1:transfer".parse::<TopicEq>().unwrap()];
+        let symbol = soroban_spec_tools::from_string_primitive("transfer", &ScSpecTypeDef::Symbol)
+            .unwrap()
+            .to_xdr_base64()
+            .unwrap();
+        assert_eq!(
+            server_topics(&topic_eq).unwrap(),
+            [
+                format!("*,{symbol}"),
+                format!("*,{symbol},*"),
+                format!("*,{symbol},*,*"),
+            ]
+        );
+        assert!(server_topics(&["0:5".parse::<TopicEq>().unwrap()]).is_none());
+    }
+}
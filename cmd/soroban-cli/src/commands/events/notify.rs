@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use clap::arg;
+use serde_json::{json, Value};
+use soroban_env_host::xdr::{self, ReadXdr, ScVal};
+
+use crate::rpc;
+
+pub const DEFAULT_TEMPLATE: &str =
+    "{type} event from {contract_id} in ledger {ledger}\ntopics: {topics}\nvalue: {value}";
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Post each event to this Slack incoming webhook URL
+    #[arg(long, help_heading = "NOTIFICATIONS")]
+    pub slack_webhook: Option<String>,
+
+    /// Post each event to this Discord webhook URL
+    #[arg(long, help_heading = "NOTIFICATIONS")]
+    pub discord_webhook: Option<String>,
+
+    /// Message posted to webhooks. `{contract_id}`, `{ledger}`, `{ledger_closed_at}`,
+    /// `{type}`, `{id}`, `{topics}`, and `{value}` are replaced with the event's fields,
+    /// topics and value decoded from XDR to JSON
+    #[arg(long, default_value = DEFAULT_TEMPLATE, help_heading = "NOTIFICATIONS")]
+    pub message_template: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid webhook URL {0}")]
+    InvalidUrl(String),
+    #[error("webhook {url} responded with {status}")]
+    WebhookFailed {
+        url: String,
+        status: http::StatusCode,
+    },
+    #[error("https webhooks are not supported on windows: {0}")]
+    WindowsNotSupported(String),
+    #[error(transparent)]
+    Hyper(#[from] hyper::Error),
+    #[error(transparent)]
+    Http(#[from] http::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+}
+
+impl Args {
+    /// Post the event to each configured webhook.
+    pub async fn notify(&self, event: &rpc::Event) -> Result<(), Error> {
+        if self.slack_webhook.is_none() && self.discord_webhook.is_none() {
+            return Ok(());
+        }
+        let message = self.render(event)?;
+        if let Some(url) = &self.slack_webhook {
+            post(url, &json!({ "text": message })).await?;
+        }
+        if let Some(url) = &self.discord_webhook {
+            post(url, &json!({ "content": message })).await?;
+        }
+        Ok(())
+    }
+
+    pub fn render(&self, event: &rpc::Event) -> Result<String, Error> {
+        let topics = event
+            .topic
+            .iter()
+            .map(|topic| decode(topic))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = decode(&event.value.xdr)?;
+        Ok(self
+            .message_template
+            .replace("{contract_id}", &event.contract_id)
+            .replace("{ledger_closed_at}", &event.ledger_closed_at)
+            .replace("{ledger}", &event.ledger)
+            .replace("{type}", &event.event_type)
+            .replace("{id}", &event.id)
+            .replace("{topics}", &Value::Array(topics).to_string())
+            .replace("{value}", &value.to_string()))
+    }
+}
+
+fn decode(xdr: &str) -> Result<Value, Error> {
+    Ok(soroban_spec_tools::to_json(&ScVal::from_xdr_base64(xdr)?)?)
+}
+
+async fn post(url: &str, body: &Value) -> Result<(), Error> {
+    let uri = http::Uri::from_str(url).map_err(|_| Error::InvalidUrl(url.to_string()))?;
+    let request = hyper::Request::post(uri.clone())
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(hyper::Body::from(body.to_string()))?;
+    let response = match uri.scheme_str() {
+        Some("http") => hyper::Client::new().request(request).await?,
+        Some("https") => {
+            #[cfg(target_os = "windows")]
+            {
+                return Err(Error::WindowsNotSupported(url.to_string()));
+            }
+            #[cfg(not(target_os = "windows"))]
+            {
+                let https = hyper_tls::HttpsConnector::new();
+                hyper::Client::builder()
+                    .build::<_, hyper::Body>(https)
+                    .request(request)
+                    .await?
+            }
+        }
+        _ => return Err(Error::InvalidUrl(url.to_string())),
+    };
+    if !response.status().is_success() {
+        return Err(Error::WebhookFailed {
+            url: url.to_string(),
+            status: response.status(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use soroban_env_host::xdr::WriteXdr;
+
+    use super::*;
+
+    #[test]
+    fn renders_decoded_fields() {
+        let event = rpc::Event {
+            event_type: "contract".to_string(),
+            ledger: "12".to_string(),
+            ledger_closed_at: "2023-09-01T00:00:00Z".to_string(),
+            id: "1".to_string(),
+            paging_token: "1".to_string(),
+            contract_id: "CABC".to_string(),
+            topic: vec![ScVal::U32(1).to_xdr_base64().unwrap()],
+            value: rpc::EventValue {
+                xdr: ScVal::Bool(true).to_xdr_base64().unwrap(),
+            },
+        };
+        let args = Args {
+            message_template: "{contract_id}@{ledger} {topics} {value}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(args.render(&event).unwrap(), "CABC@12 [1] true");
+    }
+}
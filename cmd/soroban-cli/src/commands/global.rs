@@ -2,6 +2,10 @@ use clap::arg;
 use std::path::PathBuf;
 
 use super::config;
+use crate::{
+    output::{Formatter, Output},
+    rpc::Submission,
+};
 
 #[derive(Debug, clap::Args, Clone, Default)]
 #[group(skip)]
@@ -15,17 +19,39 @@ pub struct Args {
     pub filter_logs: Vec<String>,
 
     /// Do not write logs to stderr including `INFO`
-    #[arg(long, short = 'q')]
+    #[arg(long, short = 'q', conflicts_with_all = ["verbose", "very_verbose"])]
     pub quiet: bool,
 
-    /// Log DEBUG events
-    #[arg(long, short = 'v')]
-    pub verbose: bool,
+    /// Log DEBUG events, including RPC requests and responses, and submitted transactions.
+    /// Repeat, `-vv`, to also log TRACE events
+    #[arg(long, short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
 
-    /// Log DEBUG and TRACE events
+    /// Log DEBUG and TRACE events. Same as `-vv`
     #[arg(long, alias = "vv")]
     pub very_verbose: bool,
 
+    /// Also write logs to this file, appending to it. The file gets DEBUG events at least,
+    /// whatever the level on stderr, for looking into failures after the fact
+    #[arg(long, env = "SOROBAN_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// How to print results: as text, as JSON for scripts, or not at all. `events`, which
+    /// streams events in formats of its own, takes its `--output` default from this
+    #[arg(long, value_enum, default_value_t, env = "SOROBAN_OUTPUT_FORMAT")]
+    pub output_format: Output,
+
+    /// Print the transactions that would be submitted, decoded with their fees, instead of
+    /// submitting them. Stops at the first transaction that later ones depend on, e.g. the
+    /// install before a deploy
+    #[arg(long, env = "SOROBAN_DRY_RUN")]
+    pub dry_run: bool,
+
+    /// Don't ask for confirmation before submitting transactions to the public network or
+    /// deploying a manifest
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+
     /// List installed plugins. E.g. `soroban-hello`
     #[arg(long)]
     pub list: bool,
@@ -47,12 +73,26 @@ pub enum Error {
 }
 
 impl Args {
+    pub fn formatter(&self) -> Formatter {
+        Formatter::new(self.output_format)
+    }
+
+    /// How to submit transactions to the network with `network_passphrase`.
+    pub fn submission<'a>(&self, network_passphrase: &'a str) -> Submission<'a> {
+        Submission {
+            dry_run: self.dry_run,
+            yes: self.yes,
+            output: self.output_format,
+            network_passphrase,
+        }
+    }
+
     pub fn log_level(&self) -> Option<tracing::Level> {
         if self.quiet {
             None
-        } else if self.very_verbose {
+        } else if self.very_verbose || self.verbose > 1 {
             Some(tracing::Level::TRACE)
-        } else if self.verbose {
+        } else if self.verbose == 1 {
             Some(tracing::Level::DEBUG)
         } else {
             Some(tracing::Level::INFO)
@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use clap::{command, Parser};
+
+use crate::commands::{config::locator, global};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    History(#[from] Box<super::Error>),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let entries = super::read(&self.locator).map_err(Box::new)?;
+        let format = self.locator.read_settings()?.format;
+        let mut text = String::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let _ = writeln!(
+                text,
+                "{:>4}  {}  {}  {}",
+                i + 1,
+                entry.time(&format),
+                entry.network,
+                entry.command_line()
+            );
+        }
+        global_args.formatter().print(text, &entries)?;
+        Ok(())
+    }
+}
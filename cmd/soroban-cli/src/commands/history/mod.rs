@@ -0,0 +1,210 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use chrono::{TimeZone, Utc};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use soroban_env_host::xdr::{MuxedAccount, Transaction};
+
+use crate::{format::Format, utils};
+
+use super::{
+    config::{self, locator, network::Network},
+    global,
+};
+
+pub mod ls;
+pub mod replay;
+pub mod show;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// List the commands that changed a network's state
+    Ls(ls::Cmd),
+    /// Print a command of the history, with its transaction and contracts
+    Show(show::Cmd),
+    /// Run a command of the history again
+    Replay(replay::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+    #[error(transparent)]
+    Show(#[from] show::Error),
+    #[error(transparent)]
+    Replay(#[from] replay::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("reading history {path:?}: {error}")]
+    CannotReadHistory { path: PathBuf, error: io::Error },
+    #[error("parsing line {line} of history {path:?}: {error}")]
+    CannotParseHistory {
+        path: PathBuf,
+        line: usize,
+        error: serde_json::Error,
+    },
+    #[error("no command {0} in the history, see `soroban history ls`")]
+    NotFound(usize),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Ls(cmd) => cmd.run(global_args)?,
+            Cmd::Show(cmd) => cmd.run(global_args)?,
+            Cmd::Replay(cmd) => cmd.run().await?,
+        };
+        Ok(())
+    }
+}
+
+/// History of the commands that changed a network's state, one JSON entry per line, in the
+/// config directory.
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// A command that changed a network's state, e.g. `contract deploy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub time: i64,
+    /// The user who ran the command, as the OS knows them
+    pub user: Option<String>,
+    /// Arguments of the command, without the leading `soroban`
+    pub command: Vec<String>,
+    /// Name of the network, or its RPC URL if it wasn't given a name
+    pub network: String,
+    pub network_passphrase: String,
+    /// Account that submitted the transaction
+    pub source: String,
+    /// Hash of the submitted transaction, in hex
+    pub tx_hash: String,
+    pub contract_ids: Vec<String>,
+    /// Hash of the Wasm installed, or extended, in hex
+    pub wasm_hash: Option<String>,
+}
+
+impl Entry {
+    pub fn time(&self, format: &Format) -> String {
+        match Utc.timestamp_opt(self.time, 0).single() {
+            Some(time) => format.timestamp(time),
+            None => self.time.to_string(),
+        }
+    }
+
+    /// The command, as it can be typed into a shell.
+    pub fn command_line(&self) -> String {
+        format!(
+            "soroban {}",
+            shlex::join(self.command.iter().map(String::as_str))
+        )
+    }
+}
+
+/// The arguments of the command being run, when they aren't the process's, e.g. in a repl.
+static COMMAND: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Record `command` as the arguments of commands run from now on, instead of the process's.
+pub fn set_command(command: Vec<String>) {
+    *COMMAND.lock().unwrap() = Some(command);
+}
+
+fn command() -> Vec<String> {
+    COMMAND
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| std::env::args().skip(1).collect())
+}
+
+/// Record that the command being run submitted `tx`. The command has already succeeded by
+/// then, so failing to write the history is a warning.
+pub fn record(
+    config: &config::Args,
+    network: &Network,
+    tx: &Transaction,
+    contract_ids: &[[u8; 32]],
+    wasm_hash: Option<&[u8; 32]>,
+) {
+    let source = match &tx.source_account {
+        MuxedAccount::Ed25519(source) => source,
+        MuxedAccount::MuxedEd25519(muxed) => &muxed.ed25519,
+    };
+    let tx_hash = match utils::transaction_hash(tx, &network.network_passphrase) {
+        Ok(hash) => hash,
+        Err(e) => {
+            eprintln!("warning: not recorded in the history: {e}");
+            return;
+        }
+    };
+    let entry = Entry {
+        time: Utc::now().timestamp(),
+        user: std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok(),
+        command: command(),
+        network: config
+            .network
+            .network
+            .clone()
+            .unwrap_or_else(|| network.rpc_url.clone()),
+        network_passphrase: network.network_passphrase.clone(),
+        source: stellar_strkey::ed25519::PublicKey(source.0).to_string(),
+        tx_hash: hex::encode(tx_hash),
+        contract_ids: contract_ids
+            .iter()
+            .map(|id| stellar_strkey::Contract(*id).to_string())
+            .collect(),
+        wasm_hash: wasm_hash.map(hex::encode),
+    };
+    if let Err(e) = append(&config.locator, &entry) {
+        eprintln!("warning: not recorded in the history: {e}");
+    }
+}
+
+fn append(locator: &locator::Args, entry: &Entry) -> Result<(), io::Error> {
+    let dir = locator
+        .config_dir()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    fs::create_dir_all(&dir)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(HISTORY_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+/// The entries of the history, oldest first.
+pub fn read(locator: &locator::Args) -> Result<Vec<Entry>, Error> {
+    let path = locator.config_dir()?.join(HISTORY_FILE);
+    let history = match fs::read_to_string(&path) {
+        Ok(history) => history,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(Error::CannotReadHistory { path, error }),
+    };
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|error| Error::CannotParseHistory {
+                path: path.clone(),
+                line: i + 1,
+                error,
+            })
+        })
+        .collect()
+}
+
+/// The `n`th entry of the history, counting from 1 as `history ls` does.
+pub fn get(locator: &locator::Args, n: usize) -> Result<Entry, Error> {
+    let entries = read(locator)?;
+    n.checked_sub(1)
+        .and_then(|i| entries.into_iter().nth(i))
+        .ok_or(Error::NotFound(n))
+}
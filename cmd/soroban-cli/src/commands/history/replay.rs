@@ -0,0 +1,42 @@
+use std::{future::Future, iter, pin::Pin};
+
+use clap::{command, Parser};
+
+use crate::commands::{self, config::locator, Root};
+
+/// Run a command of the history again, with the arguments it was first run with. Anything
+/// else it depends on, e.g. the environment variables it read, is as it is now.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Number of the command, as `history ls` lists it
+    n: usize,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    History(#[from] Box<super::Error>),
+    #[error("parsing the command: {0}")]
+    Clap(#[from] clap::Error),
+    #[error(transparent)]
+    Command(#[from] Box<commands::Error>),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let entry = super::get(&self.locator, self.n).map_err(Box::new)?;
+        eprintln!("{}", entry.command_line());
+        let mut root =
+            Root::try_parse_from(iter::once("soroban".to_string()).chain(entry.command.clone()))?;
+        super::set_command(entry.command);
+        // Boxed, as the command runs from within a command.
+        let run: Pin<Box<dyn Future<Output = Result<(), commands::Error>> + '_>> =
+            Box::pin(root.run());
+        run.await.map_err(Box::new)?;
+        Ok(())
+    }
+}
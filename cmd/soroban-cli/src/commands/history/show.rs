@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+use clap::{command, Parser};
+
+use crate::commands::{config::locator, global};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Number of the command, as `history ls` lists it
+    n: usize,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    History(#[from] Box<super::Error>),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let entry = super::get(&self.locator, self.n).map_err(Box::new)?;
+        let format = self.locator.read_settings()?.format;
+        let mut text = String::new();
+        let _ = writeln!(text, "Command: {}", entry.command_line());
+        let _ = writeln!(text, "Time: {}", entry.time(&format));
+        if let Some(user) = &entry.user {
+            let _ = writeln!(text, "User: {user}");
+        }
+        let _ = writeln!(text, "Network: {}", entry.network);
+        let _ = writeln!(text, "Network passphrase: {}", entry.network_passphrase);
+        let _ = writeln!(text, "Source account: {}", entry.source);
+        let _ = writeln!(text, "Transaction: {}", entry.tx_hash);
+        for contract_id in &entry.contract_ids {
+            let _ = writeln!(text, "Contract: {contract_id}");
+        }
+        if let Some(wasm_hash) = &entry.wasm_hash {
+            let _ = writeln!(text, "Wasm hash: {wasm_hash}");
+        }
+        global_args.formatter().print(text, &entry)?;
+        Ok(())
+    }
+}
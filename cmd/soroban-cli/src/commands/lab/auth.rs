@@ -0,0 +1,59 @@
+use clap::{Parser, Subcommand};
+use soroban_env_host::xdr;
+
+use crate::{
+    commands::{
+        config::{locator, network, secret},
+        tx::sign_auth,
+    },
+    encoding,
+};
+
+pub mod inspect;
+pub mod sign;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    sub: SubCmd,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SubCmd {
+    /// Explain an authorization entry: who authorizes which invocations, with which nonce, until
+    /// which ledger, and whether it is signed
+    Inspect(inspect::Cmd),
+    /// Sign an authorization entry with an identity
+    Sign(sign::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    SignAuth(#[from] sign_auth::Error),
+    #[error("the entry is authorized by the transaction's source account, not by a signature")]
+    SourceAccountCredentials,
+    #[error("the entry must be signed by {0}, not {1}")]
+    WrongSigner(String, String),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match &self.sub {
+            SubCmd::Inspect(cmd) => cmd.run()?,
+            SubCmd::Sign(cmd) => cmd.run()?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use clap::{arg, Parser};
+use soroban_env_host::xdr::{
+    ReadXdr, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanCredentials,
+};
+
+use super::Error;
+use crate::{encoding, log};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Authorization entry, as base64 or hex, or a path to a file holding either or the raw bytes
+    #[arg(long)]
+    entry: String,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let entry = SorobanAuthorizationEntry::from_xdr(encoding::decode(&self.entry)?)?;
+        print!("{}", log::explain_auth(&[entry.clone()]));
+        if let SorobanCredentials::Address(SorobanAddressCredentials {
+            nonce,
+            signature_expiration_ledger,
+            signature,
+            ..
+        }) = &entry.credentials
+        {
+            println!("Nonce: {nonce}");
+            println!("Signature expires after ledger {signature_expiration_ledger}");
+            if matches!(signature, ScVal::Void) {
+                println!("Not signed");
+            } else {
+                println!(
+                    "Signed: {}",
+                    soroban_spec_tools::to_json(signature)
+                        .map_or_else(|_| format!("{signature:?}"), |v| v.to_string())
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    AccountId, PublicKey, ReadXdr, ScAddress, ScBytes, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanCredentials, Uint256, WriteXdr,
+};
+
+use super::Error;
+use crate::{
+    commands::{
+        config::{locator, network, secret::Secret},
+        tx::sign_auth,
+    },
+    encoding,
+};
+
+/// Sign an authorization entry, e.g. one simulation returned for an address other than the
+/// source account, and print it signed.
+///
+/// Entries of an account are signed as its built-in `__check_auth` expects. Entries of a contract,
+/// i.e. a custom account, are signed with the raw 64 byte signature, which custom accounts that
+/// take a single ed25519 signature expect.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Authorization entry, as base64 or hex, or a path to a file holding either or the raw bytes
+    #[arg(long)]
+    entry: String,
+
+    /// Identity to sign with, or a secret key or seed phrase
+    #[arg(long, alias = "source-account", env = "SOROBAN_ACCOUNT")]
+    identity: String,
+
+    /// If using a seed phrase, which hierarchical deterministic path to use, e.g.
+    /// `m/44'/148'/{hd_path}`. Default: `0`
+    #[arg(long)]
+    hd_path: Option<usize>,
+
+    /// Ledger after which the signature is no longer valid. Default: the entry's
+    #[arg(long)]
+    expiration_ledger: Option<u32>,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+
+    #[command(flatten)]
+    encoding: encoding::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut entry = SorobanAuthorizationEntry::from_xdr(encoding::decode(&self.entry)?)?;
+        let network = self.network.get(&self.locator)?;
+        let secret = match self.locator.read_identity(&self.identity) {
+            Ok(secret) => secret,
+            Err(_) => self.identity.parse::<Secret>()?,
+        };
+        let public_key = secret.public_key(self.hd_path)?;
+        let SorobanCredentials::Address(SorobanAddressCredentials {
+            address,
+            nonce,
+            signature_expiration_ledger,
+            signature,
+        }) = &mut entry.credentials
+        else {
+            return Err(Error::SourceAccountCredentials);
+        };
+        if let Some(expiration_ledger) = self.expiration_ledger {
+            *signature_expiration_ledger = expiration_ledger;
+        }
+        let payload = sign_auth::authorization_payload(
+            &network.network_passphrase,
+            *nonce,
+            *signature_expiration_ledger,
+            &entry.root_invocation,
+        )?;
+        let ed25519_signature = secret.sign(self.hd_path, &payload)?.to_bytes();
+        *signature = match address {
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))))
+                if *key == public_key.0 =>
+            {
+                sign_auth::account_signature(&public_key.0, &ed25519_signature)?
+            }
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+                return Err(Error::WrongSigner(
+                    stellar_strkey::ed25519::PublicKey(*key).to_string(),
+                    public_key.to_string(),
+                ))
+            }
+            ScAddress::Contract(_) => ScVal::Bytes(ScBytes(ed25519_signature.to_vec().try_into()?)),
+        };
+        self.encoding.print(&entry.to_xdr()?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use clap::{Parser, Subcommand};
+use soroban_env_host::xdr;
+
+use crate::{
+    commands::{
+        config::{locator, network},
+        contract::deploy,
+        global,
+    },
+    encoding,
+    output::Formatter,
+    utils::parsing,
+};
+
+pub mod address;
+pub mod asset;
+pub mod preimage;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    sub: SubCmd,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SubCmd {
+    /// ID of a contract deployed by an address with a salt
+    Address(address::Cmd),
+    /// ID of the Stellar Asset Contract of an asset
+    Asset(asset::Cmd),
+    /// ID hashed from a `HashIdPreimage`, as built by hand
+    Preimage(preimage::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Deploy(#[from] deploy::Error),
+    #[error(transparent)]
+    Parsing(#[from] parsing::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0} is neither an address nor an identity")]
+    InvalidAddress(String),
+    #[error("{0} is not a contract ID preimage")]
+    NotContractId(String),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let formatter = global_args.formatter();
+        match &self.sub {
+            SubCmd::Address(cmd) => cmd.run(formatter)?,
+            SubCmd::Asset(cmd) => cmd.run(formatter)?,
+            SubCmd::Preimage(cmd) => cmd.run(formatter)?,
+        };
+        Ok(())
+    }
+}
+
+/// Print the contract ID as a strkey, and in hex.
+pub fn print(formatter: Formatter, contract_id: &xdr::Hash) -> Result<(), Error> {
+    let strkey = stellar_strkey::Contract(contract_id.0).to_string();
+    let hex = hex::encode(contract_id.0);
+    formatter.print(
+        format!("{strkey}\n{hex}"),
+        &serde_json::json!({ "contractId": strkey, "hex": hex }),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    AccountId, ContractIdPreimage, ContractIdPreimageFromAddress, Hash, PublicKey, ScAddress,
+    Uint256,
+};
+
+use super::{print, Error};
+use crate::{
+    commands::{
+        config::{locator, network},
+        contract::deploy,
+    },
+    output::Formatter,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Deployer, as a G account, a C contract, or an identity
+    #[arg(long)]
+    address: String,
+
+    /// Salt, in hex, as passed to `contract deploy --salt`
+    #[arg(long)]
+    salt: String,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, formatter: Formatter) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: self.address()?,
+            salt: Uint256(deploy::parse_salt(&self.salt)?),
+        });
+        print(
+            formatter,
+            &deploy::get_contract_id(preimage, &network.network_passphrase)?,
+        )
+    }
+
+    fn address(&self) -> Result<ScAddress, Error> {
+        let key = if let Ok(key) = stellar_strkey::ed25519::PublicKey::from_string(&self.address) {
+            key
+        } else if let Ok(contract) = stellar_strkey::Contract::from_string(&self.address) {
+            return Ok(ScAddress::Contract(Hash(contract.0)));
+        } else {
+            self.locator
+                .read_identity(&self.address)
+                .ok()
+                .and_then(|secret| secret.public_key(None).ok())
+                .ok_or_else(|| Error::InvalidAddress(self.address.clone()))?
+        };
+        Ok(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+        )))
+    }
+}
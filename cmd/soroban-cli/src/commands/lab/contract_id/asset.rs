@@ -0,0 +1,33 @@
+use clap::{arg, command, Parser};
+
+use super::{print, Error};
+use crate::{
+    commands::config::{locator, network},
+    output::Formatter,
+    utils::{self, parsing},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Asset, as `native` or `CODE:ISSUER`
+    #[arg(long)]
+    asset: String,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, formatter: Formatter) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let asset = parsing::parse_asset(&self.asset)?;
+        print(
+            formatter,
+            &utils::contract_id_hash_from_asset(&asset, &network.network_passphrase)?,
+        )
+    }
+}
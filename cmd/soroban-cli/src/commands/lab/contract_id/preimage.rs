@@ -0,0 +1,26 @@
+use clap::{arg, Parser};
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{Hash, HashIdPreimage, ReadXdr, WriteXdr};
+
+use super::{print, Error};
+use crate::{encoding, output::Formatter};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// `HashIdPreimage` of the contract ID, with the network ID, as base64 or hex, or a path to a
+    /// file holding either or the raw bytes. `lab xdr encode --type HashIdPreimage` builds one
+    /// from JSON
+    #[arg(long)]
+    xdr: String,
+}
+
+impl Cmd {
+    pub fn run(&self, formatter: Formatter) -> Result<(), Error> {
+        let preimage = HashIdPreimage::from_xdr(encoding::decode(&self.xdr)?)?;
+        if !matches!(preimage, HashIdPreimage::ContractId(_)) {
+            return Err(Error::NotContractId(self.xdr.clone()));
+        }
+        print(formatter, &Hash(Sha256::digest(preimage.to_xdr()?).into()))
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt::Debug;
+
+use clap::{Parser, Subcommand};
+
+pub mod now;
+
+#[derive(Parser, Debug)]
+pub struct Root {
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Print the latest ledger of the network, and estimate the ledger at a future time
+    Now(now::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Now(#[from] now::Error),
+}
+
+impl Root {
+    pub async fn run(&self) -> Result<(), Error> {
+        match &self.cmd {
+            Cmd::Now(now) => now.run().await?,
+        }
+        Ok(())
+    }
+}
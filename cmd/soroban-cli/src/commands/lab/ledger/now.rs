@@ -0,0 +1,91 @@
+use chrono::{DateTime, TimeZone, Utc};
+use clap::{arg, command, Parser};
+
+use crate::{
+    commands::config::{locator, network},
+    rpc::{self, Client},
+};
+
+/// Ledgers close about every 5 seconds on every network.
+pub const TARGET_CLOSE_INTERVAL_SECS: i64 = 5;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Estimate the ledger that will be current at this time, as an RFC 3339 timestamp
+    /// (2024-01-31T12:00:00Z) or seconds since the Unix epoch
+    #[arg(long, value_parser = parse_timestamp)]
+    at: Option<DateTime<Utc>>,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let client = Client::new(&network.rpc_url)?;
+        let format = self.locator.read_settings()?.format;
+        let latest = client.get_latest_ledger().await?;
+        // The RPC doesn't report when the ledger closed, it closed at most one interval ago.
+        let now = Utc::now();
+        println!("Ledger: {}", latest.sequence);
+        println!("Protocol version: {}", latest.protocol_version);
+        println!("Target close interval: {TARGET_CLOSE_INTERVAL_SECS}s");
+        println!("Observed at: {}", format.timestamp(now));
+        if let Some(at) = self.at {
+            println!(
+                "Estimated ledger at {}: {}",
+                format.timestamp(at),
+                estimate_ledger(latest.sequence, now, at)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Estimate the ledger current at `at`, given the ledger current at `now`.
+pub fn estimate_ledger(sequence: u32, now: DateTime<Utc>, at: DateTime<Utc>) -> i64 {
+    i64::from(sequence) + (at - now).num_seconds() / TARGET_CLOSE_INTERVAL_SECS
+}
+
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(secs) = s.parse::<i64>() {
+        return Utc
+            .timestamp_opt(secs, 0)
+            .single()
+            .ok_or_else(|| format!("timestamp out of range: {s}"));
+    }
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| format!("invalid timestamp {s}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_future_ledger() {
+        let now = parse_timestamp("2024-01-31T12:00:00Z").unwrap();
+        let at = parse_timestamp("2024-02-01T12:00:00Z").unwrap();
+        assert_eq!(estimate_ledger(1000, now, at), 1000 + 17280);
+        assert_eq!(
+            parse_timestamp("1706702400").unwrap(),
+            parse_timestamp("2024-01-31T12:00:00+00:00").unwrap()
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use clap::arg;
+use sha2::{Digest, Sha256};
+
+use crate::commands::config::secret;
+
+pub mod sign;
+pub mod verify;
+
+/// Prefix of signed messages, so that a message signature can never be a valid signature of a
+/// transaction or anything else, as SEP-53 specifies.
+const PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error("{0} is neither an identity nor a public key")]
+    InvalidAddress(String),
+    #[error("message is not valid base64: {0}")]
+    InvalidMessage(base64::DecodeError),
+    #[error("signature is not 64 bytes of base64")]
+    InvalidSignatureEncoding,
+    #[error("signature is not valid for {0}")]
+    InvalidSignature(String),
+}
+
+/// The message, as text or, with `--base64`, as the bytes it encodes.
+#[derive(clap::Args, Debug, Clone)]
+#[group(skip)]
+pub struct Message {
+    /// Message to sign
+    #[arg(long)]
+    message: String,
+
+    /// Decode the message from base64, to sign binary data
+    #[arg(long)]
+    base64: bool,
+}
+
+impl Message {
+    /// The hash that is signed: SHA-256 of the prefix followed by the message.
+    pub fn hash(&self) -> Result<[u8; 32], Error> {
+        let message = if self.base64 {
+            base64::decode(&self.message).map_err(Error::InvalidMessage)?
+        } else {
+            self.message.as_bytes().to_vec()
+        };
+        Ok(hash(&message))
+    }
+}
+
+pub fn hash(message: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(PREFIX)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Verifier;
+
+    #[test]
+    fn signatures_verify() {
+        let secret = secret::Secret::test_seed_phrase().unwrap();
+        let message = Message {
+            message: "Hello, World!".to_string(),
+            base64: false,
+        };
+        let signature = secret.sign(None, &message.hash().unwrap()).unwrap();
+        let key_pair = secret.key_pair(None).unwrap();
+        assert!(key_pair
+            .public
+            .verify(&hash(b"Hello, World!"), &signature)
+            .is_ok());
+        assert!(key_pair
+            .public
+            .verify(&hash(b"Hello, World?"), &signature)
+            .is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use clap::{arg, command, Parser};
+
+use super::{Error, Message};
+use crate::commands::config::{locator, secret::Secret};
+
+/// Sign a message with an identity, as SEP-53 specifies, to prove to off-chain services, such as
+/// dapp logins, that the identity holds its key. Prints the signature in base64.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    message: Message,
+
+    /// Identity to sign with, or a secret key or seed phrase
+    #[arg(long, alias = "source-account", env = "SOROBAN_ACCOUNT")]
+    identity: String,
+
+    /// If using a seed phrase, which hierarchical deterministic path to use, e.g.
+    /// `m/44'/148'/{hd_path}`. Default: `0`
+    #[arg(long)]
+    hd_path: Option<usize>,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let secret = match self.locator.read_identity(&self.identity) {
+            Ok(secret) => secret,
+            Err(_) => self.identity.parse::<Secret>()?,
+        };
+        let signature = secret.sign(self.hd_path, &self.message.hash()?)?;
+        eprintln!("Signed by {}", secret.public_key(self.hd_path)?);
+        println!("{}", base64::encode(signature.to_bytes()));
+        Ok(())
+    }
+}
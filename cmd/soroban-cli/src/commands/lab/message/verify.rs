@@ -0,0 +1,50 @@
+use clap::{arg, command, Parser};
+use ed25519_dalek::Verifier;
+
+use super::{Error, Message};
+use crate::commands::config::locator;
+
+/// Verify a SEP-53 message signature, as `lab sign-message` prints. Fails unless the signature
+/// is valid.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    message: Message,
+
+    /// Signature, in base64
+    #[arg(long)]
+    signature: String,
+
+    /// Public key of the signer, or an identity
+    #[arg(long)]
+    address: String,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let public_key = match stellar_strkey::ed25519::PublicKey::from_string(&self.address) {
+            Ok(public_key) => public_key,
+            Err(_) => self
+                .locator
+                .read_identity(&self.address)
+                .map_err(|_| Error::InvalidAddress(self.address.clone()))?
+                .public_key(None)?,
+        };
+        let signature = base64::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| ed25519_dalek::Signature::try_from(bytes.as_slice()).ok())
+            .ok_or(Error::InvalidSignatureEncoding)?;
+        let hash = self.message.hash()?;
+        let valid = ed25519_dalek::PublicKey::from_bytes(&public_key.0)
+            .is_ok_and(|key| key.verify(&hash, &signature).is_ok());
+        if !valid {
+            return Err(Error::InvalidSignature(public_key.to_string()));
+        }
+        println!("Valid signature by {public_key}");
+        Ok(())
+    }
+}
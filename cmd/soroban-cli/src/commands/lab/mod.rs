@@ -1,29 +1,77 @@
 use clap::Subcommand;
 
+use super::global;
+
+pub mod auth;
+pub mod contract_id;
+pub mod ledger;
+pub mod message;
+pub mod scval;
 pub mod token;
+pub mod tx;
 pub mod xdr;
 
 #[derive(Debug, Subcommand)]
 pub enum Cmd {
+    /// Sign and inspect Soroban authorization entries
+    Auth(auth::Cmd),
+
+    /// Compute contract IDs from a deployer and salt, an asset, or a preimage
+    ContractId(contract_id::Cmd),
+
+    /// Inspect the ledgers of a network
+    Ledger(ledger::Root),
+
+    /// Convert values between JSON and ScVal XDR
+    Scval(scval::Cmd),
+
+    /// Sign a message with an identity, as SEP-53 specifies
+    SignMessage(message::sign::Cmd),
+
     /// Wrap, create, and manage token contracts
     Token(token::Root),
 
-    /// Decode xdr
+    /// Build, decode, and explain transactions
+    Tx(tx::Root),
+
+    /// Verify a SEP-53 message signature
+    VerifyMessage(message::verify::Cmd),
+
+    /// Decode and encode xdr
     Xdr(xdr::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Auth(#[from] auth::Error),
+    #[error(transparent)]
+    ContractId(#[from] contract_id::Error),
+    #[error(transparent)]
+    Ledger(#[from] ledger::Error),
+    #[error(transparent)]
+    Message(#[from] message::Error),
+    #[error(transparent)]
+    Scval(#[from] scval::Error),
     #[error(transparent)]
     Token(#[from] token::Error),
     #[error(transparent)]
+    Tx(#[from] tx::Error),
+    #[error(transparent)]
     Xdr(#[from] xdr::Error),
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
-            Cmd::Token(token) => token.run().await?,
+            Cmd::Auth(auth) => auth.run()?,
+            Cmd::ContractId(contract_id) => contract_id.run(global_args)?,
+            Cmd::Ledger(ledger) => ledger.run().await?,
+            Cmd::Scval(scval) => scval.run()?,
+            Cmd::SignMessage(sign) => sign.run()?,
+            Cmd::Token(token) => token.run(global_args).await?,
+            Cmd::Tx(tx) => tx.run().await?,
+            Cmd::VerifyMessage(verify) => verify.run()?,
             Cmd::Xdr(xdr) => xdr.run()?,
         }
         Ok(())
@@ -0,0 +1,183 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use soroban_env_host::xdr::{
+    self, ScSpecTypeBytesN, ScSpecTypeDef as ScType, ScSpecTypeMap, ScSpecTypeOption,
+    ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec,
+};
+use soroban_spec_tools::Spec;
+
+use crate::encoding;
+
+pub mod from_json;
+pub mod to_json;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    sub: SubCmd,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SubCmd {
+    /// Convert a value, given as `contract invoke` takes arguments, to ScVal XDR
+    FromJson(from_json::Cmd),
+    /// Convert ScVal XDR to JSON, as `contract invoke` prints results
+    ToJson(to_json::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid type {0}")]
+    InvalidType(String),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error("reading {path:?}: {error}")]
+    CannotReadWasm {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match &self.sub {
+            SubCmd::FromJson(cmd) => cmd.run()?,
+            SubCmd::ToJson(cmd) => cmd.run()?,
+        };
+        Ok(())
+    }
+}
+
+/// The spec of the contract in `wasm`, for its user-defined types, or an empty one.
+pub fn read_spec(wasm: Option<&PathBuf>) -> Result<Spec, Error> {
+    let Some(path) = wasm else {
+        return Ok(Spec::default());
+    };
+    let wasm = std::fs::read(path).map_err(|error| Error::CannotReadWasm {
+        path: path.clone(),
+        error,
+    })?;
+    Ok(Spec::from_wasm(&wasm)?)
+}
+
+/// Parse a type as `contract invoke --help` names them, e.g. `u32`, `i128`, `Address`,
+/// `32_hex_bytes` or `BytesN<32>`, `Option<u32>`, `Vec<Symbol>` or `Array<Symbol>`,
+/// `Map<Symbol, i128>`, `Tuple<u32, bool>`. Other names are user-defined types, of the contract
+/// in `spec`.
+pub fn parse_type(s: &str, spec: &Spec) -> Result<ScType, Error> {
+    let invalid = || Error::InvalidType(s.to_string());
+    let s = s.trim();
+    let (name, params) = match s.split_once('<') {
+        Some((name, rest)) => {
+            let params = rest.strip_suffix('>').ok_or_else(invalid)?;
+            (name.trim(), split_params(params))
+        }
+        None => (s, vec![]),
+    };
+    let param = |i: usize| -> Result<Box<ScType>, Error> {
+        Ok(Box::new(parse_type(
+            params.get(i).ok_or_else(invalid)?,
+            spec,
+        )?))
+    };
+    let type_ = match (name.to_lowercase().as_str(), params.len()) {
+        ("u32", 0) => ScType::U32,
+        ("i32", 0) => ScType::I32,
+        ("u64", 0) => ScType::U64,
+        ("i64", 0) => ScType::I64,
+        ("u128", 0) => ScType::U128,
+        ("i128", 0) => ScType::I128,
+        ("u256", 0) => ScType::U256,
+        ("i256", 0) => ScType::I256,
+        ("bool", 0) => ScType::Bool,
+        ("symbol", 0) => ScType::Symbol,
+        ("string", 0) => ScType::String,
+        ("bytes" | "hex_bytes", 0) => ScType::Bytes,
+        ("address", 0) => ScType::Address,
+        ("void" | "null", 0) => ScType::Void,
+        ("timepoint", 0) => ScType::Timepoint,
+        ("duration", 0) => ScType::Duration,
+        ("bytesn", 1) => ScType::BytesN(ScSpecTypeBytesN {
+            n: params[0].parse().map_err(|_| invalid())?,
+        }),
+        ("option", 1) => ScType::Option(Box::new(ScSpecTypeOption {
+            value_type: param(0)?,
+        })),
+        ("vec" | "array", 1) => ScType::Vec(Box::new(ScSpecTypeVec {
+            element_type: param(0)?,
+        })),
+        ("map", 2) => ScType::Map(Box::new(ScSpecTypeMap {
+            key_type: param(0)?,
+            value_type: param(1)?,
+        })),
+        ("tuple", _) => ScType::Tuple(Box::new(ScSpecTypeTuple {
+            value_types: (0..params.len())
+                .map(|i| param(i).map(|type_| *type_))
+                .collect::<Result<Vec<_>, _>>()?
+                .try_into()
+                .map_err(|_| invalid())?,
+        })),
+        (_, 0) => match name.strip_suffix("_hex_bytes").map(str::parse) {
+            Some(Ok(n)) => ScType::BytesN(ScSpecTypeBytesN { n }),
+            _ => {
+                spec.find(name).map_err(|_| invalid())?;
+                ScType::Udt(ScSpecTypeUdt {
+                    name: name.try_into().map_err(|_| invalid())?,
+                })
+            }
+        },
+        _ => return Err(invalid()),
+    };
+    Ok(type_)
+}
+
+/// Split type parameters at the commas that are not inside nested parameters.
+fn split_params(params: &str) -> Vec<&str> {
+    let mut split = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in params.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(params[start..].trim());
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_types() {
+        let spec = Spec::default();
+        assert_eq!(
+            parse_type("Map<Symbol, Vec<i128>>", &spec).unwrap(),
+            ScType::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(ScType::Symbol),
+                value_type: Box::new(ScType::Vec(Box::new(ScSpecTypeVec {
+                    element_type: Box::new(ScType::I128),
+                }))),
+            }))
+        );
+        assert_eq!(
+            parse_type("32_hex_bytes", &spec).unwrap(),
+            ScType::BytesN(ScSpecTypeBytesN { n: 32 })
+        );
+        assert!(parse_type("Unknown", &spec).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::WriteXdr;
+
+use super::{parse_type, read_spec, Error};
+use crate::encoding;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Type of the value, e.g. `i128`, `Address`, `Vec<u32>`, `Map<Symbol, i128>`, or a type
+    /// defined by the contract of `--wasm`
+    #[arg(long)]
+    r#type: String,
+    /// Value, as JSON or, for strings, symbols, addresses, bytes and large integers, as is
+    value: String,
+    /// WASM file of the contract that defines the type
+    #[arg(long)]
+    wasm: Option<PathBuf>,
+    #[command(flatten)]
+    encoding: encoding::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let spec = read_spec(self.wasm.as_ref())?;
+        let type_ = parse_type(&self.r#type, &spec)?;
+        let val = spec.from_string(&self.value, &type_)?;
+        self.encoding.print(&val.to_xdr()?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::{arg, Parser};
+use soroban_env_host::xdr::{ReadXdr, ScVal};
+
+use super::{parse_type, read_spec, Error};
+use crate::encoding;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// ScVal XDR, as base64 or hex, or a path to a file holding either or the raw bytes
+    xdr: String,
+    /// Type of the value, to convert it as a contract's result of that type. Without it, the
+    /// value is converted by its XDR alone, so e.g. structs are maps
+    #[arg(long)]
+    r#type: Option<String>,
+    /// WASM file of the contract that defines the type
+    #[arg(long, requires = "type")]
+    wasm: Option<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let val = ScVal::from_xdr(encoding::decode(&self.xdr)?)?;
+        let json = match &self.r#type {
+            Some(type_) => {
+                let spec = read_spec(self.wasm.as_ref())?;
+                spec.xdr_to_json(&val, &parse_type(type_, &spec)?)?
+            }
+            None => soroban_spec_tools::to_json(&val)?,
+        };
+        println!("{json}");
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use clap::{arg, command, Parser};
+use soroban_env_host::xdr::Hash;
 
-use crate::commands::config;
+use crate::commands::{config, global};
 
-use crate::utils::contract_id_hash_from_asset;
+use crate::network::sandbox_network_id;
 use crate::utils::parsing::parse_asset;
+use crate::utils::{contract_id_hash_from_asset, contract_id_hash_from_asset_and_network_id};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -23,14 +25,25 @@ pub enum Error {
     ConfigError(#[from] crate::commands::config::Error),
     #[error(transparent)]
     Xdr(#[from] soroban_env_host::xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         let asset = parse_asset(&self.asset)?;
-        let network = self.config.get_network()?;
-        let contract_id = contract_id_hash_from_asset(&asset, &network.network_passphrase)?;
+        // The ID only depends on the network passphrase, so a network saved with `config
+        // network add` works offline. Without a network the ID is the sandbox's.
+        let contract_id = if self.config.is_no_network() {
+            contract_id_hash_from_asset_and_network_id(&asset, Hash(sandbox_network_id()))?
+        } else {
+            let network = self.config.get_network()?;
+            contract_id_hash_from_asset(&asset, &network.network_passphrase)?
+        };
         let strkey_contract_id = stellar_strkey::Contract(contract_id.0).to_string();
-        println!("{strkey_contract_id}");
+        global_args.formatter().print(
+            &strkey_contract_id,
+            &serde_json::json!({ "contractId": strkey_contract_id }),
+        )?;
         Ok(())
     }
 }
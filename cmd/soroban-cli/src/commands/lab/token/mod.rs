@@ -2,6 +2,8 @@ use std::fmt::Debug;
 
 use clap::{Parser, Subcommand};
 
+use crate::commands::global;
+
 pub mod id;
 pub mod wrap;
 
@@ -28,10 +30,10 @@ pub enum Error {
 }
 
 impl Root {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self.cmd {
-            Cmd::Wrap(wrap) => wrap.run().await?,
-            Cmd::Id(id) => id.run()?,
+            Cmd::Wrap(wrap) => wrap.run(global_args).await?,
+            Cmd::Id(id) => id.run(global_args)?,
         }
         Ok(())
     }
@@ -15,8 +15,8 @@ use std::convert::Infallible;
 use std::{array::TryFromSliceError, fmt::Debug, num::ParseIntError, rc::Rc};
 
 use crate::{
-    commands::config,
-    rpc::{Client, Error as SorobanRpcError},
+    commands::{config, global},
+    rpc::{Client, Error as SorobanRpcError, Outcome},
     utils::{contract_id_hash_from_asset, parsing::parse_asset},
 };
 
@@ -38,6 +38,8 @@ pub enum Error {
     Config(#[from] config::Error),
     #[error(transparent)]
     ParseAssetError(#[from] crate::utils::parsing::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 impl From<Infallible> for Error {
@@ -60,16 +62,22 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub async fn run(&self) -> Result<(), Error> {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         // Parse asset
         let asset = parse_asset(&self.asset)?;
 
-        let res_str = if self.config.is_no_network() {
-            self.run_in_sandbox(&asset)?
+        let outcome = if self.config.is_no_network() {
+            Outcome::Done(self.run_in_sandbox(&asset)?)
         } else {
-            self.run_against_rpc_server(asset).await?
+            self.run_against_rpc_server(global_args, asset).await?
+        };
+        let Outcome::Done(contract_id) = outcome else {
+            return Ok(());
         };
-        println!("{res_str}");
+        global_args.formatter().print(
+            &contract_id,
+            &serde_json::json!({ "contractId": contract_id }),
+        )?;
         Ok(())
     }
 
@@ -101,18 +109,21 @@ impl Cmd {
         Ok(stellar_strkey::Contract(contract_id.0).to_string())
     }
 
-    async fn run_against_rpc_server(&self, asset: Asset) -> Result<String, Error> {
+    async fn run_against_rpc_server(
+        &self,
+        global_args: &global::Args,
+        asset: Asset,
+    ) -> Result<Outcome<String>, Error> {
         let network = self.config.get_network()?;
         let client = Client::new(&network.rpc_url)?;
         client
             .verify_network_passphrase(Some(&network.network_passphrase))
             .await?;
-        let key = self.config.key_pair()?;
+        let key = self.config.public_key()?;
 
         // Get the account sequence number
-        let public_strkey = stellar_strkey::ed25519::PublicKey(key.public.to_bytes()).to_string();
         // TODO: use symbols for the method names (both here and in serve)
-        let account_details = client.get_account(&public_strkey).await?;
+        let account_details = client.get_account(&key.to_string()).await?;
         let sequence: i64 = account_details.seq_num.into();
         let network_passphrase = &network.network_passphrase;
         let contract_id = contract_id_hash_from_asset(&asset, network_passphrase)?;
@@ -125,11 +136,18 @@ impl Cmd {
             &key,
         )?;
 
-        client
-            .prepare_and_send_transaction(&tx, &key, network_passphrase, None)
-            .await?;
-
-        Ok(stellar_strkey::Contract(contract_id.0).to_string())
+        let tx = self
+            .fee
+            .apply_to_assembled_tx(client.prepare_transaction(&tx, None).await?);
+        Ok(client
+            .send_transaction(
+                &self
+                    .config
+                    .sign_for_submission(&tx, self.fee.fee, network_passphrase)?,
+                &global_args.submission(network_passphrase),
+            )
+            .await?
+            .map(|_| stellar_strkey::Contract(contract_id.0).to_string()))
     }
 }
 
@@ -154,7 +172,7 @@ fn build_wrap_token_tx(
     sequence: i64,
     fee: u32,
     _network_passphrase: &str,
-    key: &ed25519_dalek::Keypair,
+    key: &stellar_strkey::ed25519::PublicKey,
 ) -> Result<Transaction, Error> {
     let contract = ScAddress::Contract(contract_id.clone());
     let mut read_write = vec![
@@ -196,7 +214,7 @@ fn build_wrap_token_tx(
     };
 
     Ok(Transaction {
-        source_account: MuxedAccount::Ed25519(Uint256(key.public.to_bytes())),
+        source_account: MuxedAccount::Ed25519(Uint256(key.0)),
         fee,
         seq_num: SequenceNumber(sequence),
         cond: Preconditions::None,
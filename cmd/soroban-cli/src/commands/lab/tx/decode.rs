@@ -0,0 +1,404 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    self, AccountId, ContractDataEntry, ContractDataEntryBody, ContractDataEntryData,
+    ContractExecutable, CreateAccountOp, FeeBumpTransactionInnerTx, Hash, HostFunction,
+    InvokeContractArgs, InvokeHostFunctionOp, LedgerKey, LedgerKeyAccount, LedgerKeyContractCode,
+    LedgerKeyContractData, MuxedAccount, MuxedAccountMed25519, Operation, OperationBody, PaymentOp,
+    PublicKey, ReadXdr, ScAddress, ScContractInstance, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, Transaction, TransactionEnvelope, TransactionExt, Uint256, WriteXdr,
+};
+
+use crate::{
+    commands::config::{locator, network},
+    encoding,
+    rpc::{self, Client},
+    utils,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Transaction envelope, as base64 or hex, or a path to a file holding either or the raw
+    /// bytes
+    envelope: String,
+
+    /// Also report, from the network, whether the accounts involved exist, which contracts are
+    /// involved and under which aliases, the wasm they run, and whether the footprint's entries
+    /// currently exist
+    #[arg(long)]
+    with_context: bool,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error("parsing transaction envelope: {0}")]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+}
+
+/// The accounts, contracts and ledger entries a transaction refers to.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Involved {
+    pub accounts: BTreeSet<String>,
+    pub contracts: BTreeSet<[u8; 32]>,
+    /// Footprint keys, and whether each is written
+    pub footprint: Vec<(LedgerKey, bool)>,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let envelope = TransactionEnvelope::from_xdr(encoding::decode(&self.envelope)?)?;
+        println!("{}", serde_json::to_string_pretty(&envelope)?);
+        if self.with_context {
+            println!();
+            self.print_context(&involved(&envelope)).await?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn print_context(&self, involved: &Involved) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let client = Client::new(&network.rpc_url)?;
+        let format = self.locator.read_settings()?.format;
+        let mut aliases = BTreeMap::<[u8; 32], Vec<String>>::new();
+        for (alias, contract_id) in self
+            .locator
+            .list_contract_aliases(&self.network.alias_key(&self.locator)?)?
+        {
+            if let Ok(contract_id) = utils::contract_id_from_str(&contract_id) {
+                aliases.entry(contract_id).or_default().push(alias);
+            }
+        }
+        let label = |contract_id: &[u8; 32]| {
+            let strkey = stellar_strkey::Contract(*contract_id).to_string();
+            match aliases.get(contract_id) {
+                Some(names) => format!("{strkey} ({})", names.join(", ")),
+                None => strkey,
+            }
+        };
+
+        println!("Accounts:");
+        for account in &involved.accounts {
+            match client.get_account(account).await {
+                Ok(entry) => println!(
+                    "  {account}: exists, sequence {}, balance {}",
+                    entry.seq_num.0,
+                    format.amount(entry.balance)
+                ),
+                Err(rpc::Error::NotFound(..)) => println!("  {account}: does not exist"),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        // Wasm hashes are named after the contracts in the transaction that run them.
+        let mut wasm_names = BTreeMap::<[u8; 32], Vec<String>>::new();
+        println!("Contracts:");
+        for contract_id in &involved.contracts {
+            let executable = match client.get_contract_data(contract_id).await {
+                Ok(ContractDataEntry {
+                    body:
+                        ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                            val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
+                            ..
+                        }),
+                    ..
+                }) => executable,
+                Ok(_) => {
+                    println!("  {}: not a contract instance", label(contract_id));
+                    continue;
+                }
+                Err(rpc::Error::NotFound(..)) => {
+                    println!("  {}: not deployed", label(contract_id));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            match executable {
+                ContractExecutable::Wasm(Hash(hash)) => {
+                    println!("  {}: runs wasm {}", label(contract_id), hex::encode(hash));
+                    wasm_names
+                        .entry(hash)
+                        .or_default()
+                        .push(aliases.get(contract_id).map_or_else(
+                            || stellar_strkey::Contract(*contract_id).to_string(),
+                            |names| names.join(", "),
+                        ));
+                }
+                ContractExecutable::Token => {
+                    println!("  {}: built-in token", label(contract_id));
+                }
+            }
+        }
+
+        if involved.footprint.is_empty() {
+            return Ok(());
+        }
+        let keys = involved
+            .footprint
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        let present = client
+            .get_ledger_entries(keys)
+            .await?
+            .entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect::<BTreeSet<_>>();
+        println!("Footprint:");
+        for (key, read_write) in &involved.footprint {
+            let access = if *read_write {
+                "read-write"
+            } else {
+                "read-only"
+            };
+            let state = if present.contains(&key.to_xdr_base64()?) {
+                "exists"
+            } else {
+                "missing, never created or archived"
+            };
+            let description = match key {
+                LedgerKey::Account(LedgerKeyAccount { account_id }) => {
+                    format!("account {}", account_to_string(account_id))
+                }
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: ScAddress::Contract(Hash(contract_id)),
+                    key,
+                    durability,
+                    ..
+                }) => format!(
+                    "{durability:?} data of {} at {}",
+                    label(contract_id),
+                    serde_json::to_string(key)?
+                ),
+                LedgerKey::ContractCode(LedgerKeyContractCode {
+                    hash: Hash(hash), ..
+                }) => match wasm_names.get(hash) {
+                    Some(names) => format!("wasm {} of {}", hex::encode(hash), names.join(", ")),
+                    None => format!("wasm {}", hex::encode(hash)),
+                },
+                key => serde_json::to_string(key)?,
+            };
+            println!("  {access} {description}: {state}");
+        }
+        Ok(())
+    }
+}
+
+/// Collect what the envelope refers to: source, fee and destination accounts, contracts
+/// invoked, authorized or passed as arguments, and the footprint.
+pub fn involved(envelope: &TransactionEnvelope) -> Involved {
+    let mut involved = Involved::default();
+    match envelope {
+        TransactionEnvelope::TxV0(envelope) => {
+            let Uint256(key) = &envelope.tx.source_account_ed25519;
+            involved
+                .accounts
+                .insert(stellar_strkey::ed25519::PublicKey(*key).to_string());
+            for op in envelope.tx.operations.iter() {
+                involved.add_operation(op);
+            }
+        }
+        TransactionEnvelope::Tx(envelope) => involved.add_transaction(&envelope.tx),
+        TransactionEnvelope::TxFeeBump(envelope) => {
+            involved.add_muxed(&envelope.tx.fee_source);
+            let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+            involved.add_transaction(&inner.tx);
+        }
+    }
+    involved
+}
+
+impl Involved {
+    fn add_transaction(&mut self, tx: &Transaction) {
+        self.add_muxed(&tx.source_account);
+        for op in tx.operations.iter() {
+            self.add_operation(op);
+        }
+        if let TransactionExt::V1(data) = &tx.ext {
+            let footprint = &data.resources.footprint;
+            for (keys, read_write) in [(&footprint.read_only, false), (&footprint.read_write, true)]
+            {
+                for key in keys.iter() {
+                    match key {
+                        LedgerKey::Account(LedgerKeyAccount { account_id }) => {
+                            self.accounts.insert(account_to_string(account_id));
+                        }
+                        LedgerKey::ContractData(LedgerKeyContractData { contract, .. }) => {
+                            self.add_address(contract);
+                        }
+                        _ => {}
+                    }
+                    self.footprint.push((key.clone(), read_write));
+                }
+            }
+        }
+    }
+
+    fn add_operation(&mut self, op: &Operation) {
+        if let Some(source) = &op.source_account {
+            self.add_muxed(source);
+        }
+        match &op.body {
+            OperationBody::CreateAccount(CreateAccountOp { destination, .. }) => {
+                self.accounts.insert(account_to_string(destination));
+            }
+            OperationBody::Payment(PaymentOp { destination, .. }) => self.add_muxed(destination),
+            OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function,
+                auth,
+            }) => {
+                if let HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address,
+                    args,
+                    ..
+                }) = host_function
+                {
+                    self.add_address(contract_address);
+                    for arg in args.iter() {
+                        self.add_val(arg);
+                    }
+                }
+                for SorobanAuthorizationEntry {
+                    credentials,
+                    root_invocation,
+                } in auth.iter()
+                {
+                    if let SorobanCredentials::Address(SorobanAddressCredentials {
+                        address, ..
+                    }) = credentials
+                    {
+                        self.add_address(address);
+                    }
+                    self.add_invocation(root_invocation);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_invocation(&mut self, invocation: &SorobanAuthorizedInvocation) {
+        if let SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address, ..
+        }) = &invocation.function
+        {
+            self.add_address(contract_address);
+        }
+        for sub in invocation.sub_invocations.iter() {
+            self.add_invocation(sub);
+        }
+    }
+
+    fn add_val(&mut self, val: &ScVal) {
+        match val {
+            ScVal::Address(address) => self.add_address(address),
+            ScVal::Vec(Some(vec)) => {
+                for val in vec.iter() {
+                    self.add_val(val);
+                }
+            }
+            ScVal::Map(Some(map)) => {
+                for entry in map.iter() {
+                    self.add_val(&entry.key);
+                    self.add_val(&entry.val);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_address(&mut self, address: &ScAddress) {
+        match address {
+            ScAddress::Account(account_id) => {
+                self.accounts.insert(account_to_string(account_id));
+            }
+            ScAddress::Contract(Hash(contract_id)) => {
+                self.contracts.insert(*contract_id);
+            }
+        }
+    }
+
+    fn add_muxed(&mut self, account: &MuxedAccount) {
+        let (MuxedAccount::Ed25519(Uint256(key))
+        | MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+            ed25519: Uint256(key),
+            ..
+        })) = account;
+        self.accounts
+            .insert(stellar_strkey::ed25519::PublicKey(*key).to_string());
+    }
+}
+
+fn account_to_string(
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))): &AccountId,
+) -> String {
+    stellar_strkey::ed25519::PublicKey(*key).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{
+        Memo, Preconditions, ScSymbol, SequenceNumber, TransactionV1Envelope, VecM,
+    };
+
+    #[test]
+    fn involved_collects_sources_and_invoked_contracts() {
+        let account = [1; 32];
+        let contract = [2; 32];
+        let other = [3; 32];
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256(account)),
+                fee: 100,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![Operation {
+                    source_account: None,
+                    body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                        host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                            contract_address: ScAddress::Contract(Hash(contract)),
+                            function_name: ScSymbol("hello".try_into().unwrap()),
+                            args: vec![ScVal::Address(ScAddress::Contract(Hash(other)))]
+                                .try_into()
+                                .unwrap(),
+                        }),
+                        auth: VecM::default(),
+                    }),
+                }]
+                .try_into()
+                .unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        });
+        let involved = involved(&envelope);
+        assert_eq!(
+            involved.accounts,
+            BTreeSet::from([stellar_strkey::ed25519::PublicKey(account).to_string()])
+        );
+        assert_eq!(involved.contracts, BTreeSet::from([contract, other]));
+        assert!(involved.footprint.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+
+use clap::{Parser, Subcommand};
+
+pub mod decode;
+pub mod new;
+
+#[derive(Parser, Debug)]
+pub struct Root {
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Decode a transaction envelope, optionally enriched with the current state of the network
+    Decode(decode::Cmd),
+    /// Build an unsigned transaction envelope from operations given as flags or JSON
+    New(new::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error(transparent)]
+    New(#[from] new::Error),
+}
+
+impl Root {
+    pub async fn run(&self) -> Result<(), Error> {
+        match &self.cmd {
+            Cmd::Decode(decode) => decode.run().await?,
+            Cmd::New(new) => new.run().await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+use std::{fs, path::PathBuf};
+
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    self, BumpFootprintExpirationOp, CreateAccountOp, ExtensionPoint, Hash, HostFunction,
+    InvokeContractArgs, InvokeHostFunctionOp, LedgerFootprint, LedgerKey, Memo, MuxedAccount,
+    Operation, OperationBody, PaymentOp, Preconditions, ReadXdr, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, SorobanResources, SorobanTransactionData, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+
+use crate::{
+    commands::config,
+    encoding,
+    rpc::{self, Client},
+    utils::{self, parsing},
+};
+
+/// Stroops in one lumen.
+const STROOPS_PER_XLM: i64 = 10_000_000;
+
+/// Build an unsigned transaction, to sign with `tx sign` and submit with `tx submit`.
+///
+/// A transaction has either any number of classic operations, or a single Soroban one:
+/// `--invoke-id` or `--extend-ttl`. Soroban transactions need their footprint and resources from
+/// simulation, with `--simulate`, to be valid.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Sequence number of the transaction. Default: one more than the source account's, fetched
+    /// from the network
+    #[arg(long)]
+    sequence: Option<i64>,
+
+    /// Create an account, as `DESTINATION:XLM`. Can be repeated
+    #[arg(long, value_name = "DESTINATION:XLM", help_heading = "OPERATIONS")]
+    create_account: Vec<String>,
+
+    /// Pay an account, as `DESTINATION:STROOPS`, or `DESTINATION:STROOPS:CODE:ISSUER` for an
+    /// asset other than XLM. Can be repeated
+    #[arg(
+        long,
+        value_name = "DESTINATION:STROOPS[:ASSET]",
+        help_heading = "OPERATIONS"
+    )]
+    payment: Vec<String>,
+
+    /// Contract to invoke
+    #[arg(long, requires = "invoke_fn", help_heading = "OPERATIONS")]
+    invoke_id: Option<String>,
+
+    /// Function to invoke
+    #[arg(long, requires = "invoke_id", help_heading = "OPERATIONS")]
+    invoke_fn: Option<String>,
+
+    /// Argument of the function (base64-encoded XDR ScVal), e.g. from `lab scval from-json`.
+    /// Can be repeated
+    #[arg(long, requires = "invoke_id", help_heading = "OPERATIONS")]
+    invoke_arg_xdr: Vec<String>,
+
+    /// Extend the time to live of the entries of `--footprint-key-xdr` by this many ledgers
+    #[arg(
+        long,
+        value_name = "LEDGERS",
+        conflicts_with = "invoke_id",
+        help_heading = "OPERATIONS"
+    )]
+    extend_ttl: Option<u32>,
+
+    /// Ledger key (base64-encoded XDR) of the read-only footprint. Can be repeated
+    #[arg(long, help_heading = "OPERATIONS")]
+    footprint_key_xdr: Vec<String>,
+
+    /// File with a JSON array of operations, in the form `lab xdr decode --output json` prints
+    #[arg(long, help_heading = "OPERATIONS")]
+    ops_json: Option<PathBuf>,
+
+    /// Simulate the transaction to set its footprint, resources, and resource fee
+    #[arg(long)]
+    simulate: bool,
+
+    #[command(flatten)]
+    config: config::Args,
+
+    #[command(flatten)]
+    fee: crate::fee::Args,
+
+    #[command(flatten)]
+    encoding: encoding::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Parsing(#[from] parsing::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error("cannot parse contract ID {0}: {1}")]
+    CannotParseContractId(String, stellar_strkey::DecodeError),
+    #[error("reading {path:?}: {error}")]
+    CannotReadFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("invalid operation {0}")]
+    InvalidOperation(String),
+    #[error("no operations, see --help for the operations a transaction can have")]
+    NoOperations,
+    #[error("a transaction with a Soroban operation cannot have other operations")]
+    SorobanNotAlone,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        // Unsigned transactions only need the source account's public key
+        let source = match self
+            .config
+            .source_account
+            .as_deref()
+            .map(stellar_strkey::ed25519::PublicKey::from_string)
+        {
+            Some(Ok(key)) => key,
+            _ => self.config.public_key()?,
+        };
+        let operations = self.operations()?;
+        let soroban = operations.iter().any(|op| {
+            matches!(
+                op.body,
+                OperationBody::InvokeHostFunction(_)
+                    | OperationBody::BumpFootprintExpiration(_)
+                    | OperationBody::RestoreFootprint(_)
+            )
+        });
+        if operations.is_empty() {
+            return Err(Error::NoOperations);
+        }
+        if soroban && operations.len() > 1 {
+            return Err(Error::SorobanNotAlone);
+        }
+        let ext = if soroban {
+            TransactionExt::V1(SorobanTransactionData {
+                ext: ExtensionPoint::V0,
+                resources: SorobanResources {
+                    footprint: LedgerFootprint {
+                        read_only: self.footprint()?.try_into()?,
+                        read_write: VecM::default(),
+                    },
+                    instructions: 0,
+                    read_bytes: 0,
+                    write_bytes: 0,
+                    extended_meta_data_size_bytes: 0,
+                },
+                refundable_fee: 0,
+            })
+        } else {
+            TransactionExt::V0
+        };
+
+        let client = if self.sequence.is_none() || self.simulate {
+            Some(Client::new(&self.config.get_network()?.rpc_url)?)
+        } else {
+            None
+        };
+        let sequence = match (self.sequence, &client) {
+            (Some(sequence), _) => sequence,
+            (None, Some(client)) => {
+                let account = client.get_account(&source.to_string()).await?;
+                i64::from(account.seq_num) + 1
+            }
+            (None, None) => unreachable!("a client is made when there is no --sequence"),
+        };
+        let mut tx = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(source.0)),
+            fee: self.fee.fee,
+            seq_num: SequenceNumber(sequence),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: operations.try_into()?,
+            ext,
+        };
+        if let (true, Some(client)) = (self.simulate, &client) {
+            tx = self
+                .fee
+                .apply_to_assembled_tx(client.prepare_transaction(&tx, None).await?);
+        }
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: VecM::default(),
+        });
+        self.encoding.print(&envelope.to_xdr()?)?;
+        Ok(())
+    }
+
+    fn operations(&self) -> Result<Vec<Operation>, Error> {
+        let mut operations = vec![];
+        for op in &self.create_account {
+            let (destination, xlm) = op
+                .split_once(':')
+                .ok_or_else(|| Error::InvalidOperation(op.clone()))?;
+            let starting_balance = xlm
+                .parse::<i64>()
+                .ok()
+                .and_then(|xlm| xlm.checked_mul(STROOPS_PER_XLM))
+                .ok_or_else(|| Error::InvalidOperation(op.clone()))?;
+            operations.push(OperationBody::CreateAccount(CreateAccountOp {
+                destination: parsing::parse_account_id(destination)?,
+                starting_balance,
+            }));
+        }
+        for op in &self.payment {
+            let mut parts = op.splitn(3, ':');
+            let (Some(destination), Some(amount)) = (parts.next(), parts.next()) else {
+                return Err(Error::InvalidOperation(op.clone()));
+            };
+            let xdr::PublicKey::PublicKeyTypeEd25519(destination) =
+                parsing::parse_account_id(destination)?.0;
+            operations.push(OperationBody::Payment(PaymentOp {
+                destination: MuxedAccount::Ed25519(destination),
+                asset: parsing::parse_asset(parts.next().unwrap_or("native"))?,
+                amount: amount
+                    .parse()
+                    .map_err(|_| Error::InvalidOperation(op.clone()))?,
+            }));
+        }
+        if let (Some(id), Some(function)) = (&self.invoke_id, &self.invoke_fn) {
+            let id = self.config.resolve_contract_id(id)?;
+            let contract_id = utils::contract_id_from_str(&id)
+                .map_err(|e| Error::CannotParseContractId(id.clone(), e))?;
+            let args = self
+                .invoke_arg_xdr
+                .iter()
+                .map(ScVal::from_xdr_base64)
+                .collect::<Result<Vec<_>, _>>()?;
+            operations.push(OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address: ScAddress::Contract(Hash(contract_id)),
+                    function_name: ScSymbol(function.as_str().try_into()?),
+                    args: args.try_into()?,
+                }),
+                auth: VecM::default(),
+            }));
+        }
+        if let Some(ledgers_to_expire) = self.extend_ttl {
+            operations.push(OperationBody::BumpFootprintExpiration(
+                BumpFootprintExpirationOp {
+                    ext: ExtensionPoint::V0,
+                    ledgers_to_expire,
+                },
+            ));
+        }
+        let mut operations = operations
+            .into_iter()
+            .map(|body| Operation {
+                source_account: None,
+                body,
+            })
+            .collect::<Vec<_>>();
+        if let Some(path) = &self.ops_json {
+            let json = fs::read_to_string(path).map_err(|error| Error::CannotReadFile {
+                path: path.clone(),
+                error,
+            })?;
+            operations.extend(serde_json::from_str::<Vec<Operation>>(&json)?);
+        }
+        Ok(operations)
+    }
+
+    fn footprint(&self) -> Result<Vec<LedgerKey>, Error> {
+        Ok(self
+            .footprint_key_xdr
+            .iter()
+            .map(LedgerKey::from_xdr_base64)
+            .collect::<Result<_, _>>()?)
+    }
+}
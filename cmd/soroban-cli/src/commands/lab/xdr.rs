@@ -1,4 +1,5 @@
 mod decode;
+mod encode;
 
 use std::fmt::Debug;
 
@@ -14,13 +15,19 @@ pub struct Cmd {
 #[derive(Subcommand, Debug, Clone)]
 enum SubCmd {
     /// Decode XDR
+    #[command(visible_alias = "decode")]
     Dec(decode::Cmd),
+    /// Encode XDR from JSON
+    #[command(visible_alias = "encode")]
+    Enc(encode::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("decode: {0}")]
     Decode(#[from] decode::Error),
+    #[error("encode: {0}")]
+    Encode(#[from] encode::Error),
 }
 
 impl Cmd {
@@ -28,6 +35,7 @@ impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
         match &self.sub {
             SubCmd::Dec(d) => d.run()?,
+            SubCmd::Enc(e) => e.run()?,
         };
         Ok(())
     }
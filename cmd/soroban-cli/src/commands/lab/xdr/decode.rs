@@ -6,20 +6,48 @@ use clap::{
 use core::str::FromStr;
 use soroban_env_host::xdr;
 
+use crate::encoding;
+
+/// Types `--type auto` tries first, in order, as the ones most likely to be decoded.
+const COMMON_TYPES: &[xdr::TypeVariant] = &[
+    xdr::TypeVariant::TransactionEnvelope,
+    xdr::TypeVariant::TransactionResult,
+    xdr::TypeVariant::TransactionMeta,
+    xdr::TypeVariant::ScVal,
+    xdr::TypeVariant::LedgerEntryData,
+    xdr::TypeVariant::LedgerEntry,
+    xdr::TypeVariant::LedgerKey,
+    xdr::TypeVariant::SorobanAuthorizationEntry,
+    xdr::TypeVariant::SorobanTransactionData,
+    xdr::TypeVariant::ContractEvent,
+    xdr::TypeVariant::DiagnosticEvent,
+    xdr::TypeVariant::ScSpecEntry,
+];
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
-    /// XDR type to decode to
+    /// XDR type to decode to, or `auto` to try the common types, reporting which matched, and
+    /// then every other type
     #[arg(
         long,
+        default_value = "auto",
         value_parser =
-            PossibleValuesParser::new(xdr::TypeVariant::VARIANTS_STR)
-                .try_map(|s| xdr::TypeVariant::from_str(&s))
+            PossibleValuesParser::new(
+                std::iter::once("auto").chain(xdr::TypeVariant::VARIANTS_STR.iter().copied())
+            )
+            .try_map(|s| TypeArg::from_str(&s))
     )]
-    r#type: xdr::TypeVariant,
-    /// XDR (base64 encoded) to decode
-    #[arg(long)]
-    xdr: String,
+    r#type: TypeArg,
+    /// XDR to decode, as base64 or hex, or a path to a file holding either or the raw bytes
+    #[arg(long, conflicts_with = "input", required_unless_present_any = ["input", "list"])]
+    xdr: Option<String>,
+    /// XDR to decode, the same as `--xdr`
+    #[arg(conflicts_with = "list")]
+    input: Option<String>,
+    /// List the types XDR can be decoded to
+    #[arg(long, conflicts_with = "xdr")]
+    list: bool,
     /// Type of output
     #[arg(long, value_enum, default_value_t)]
     output: Output,
@@ -39,18 +67,51 @@ impl Default for Output {
     }
 }
 
+/// The type to decode XDR to, if it is not to be guessed.
+#[derive(Clone, Copy, Debug)]
+pub enum TypeArg {
+    Auto,
+    Type(xdr::TypeVariant),
+}
+
+impl FromStr for TypeArg {
+    type Err = xdr::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auto" {
+            Ok(Self::Auto)
+        } else {
+            xdr::TypeVariant::from_str(s).map(Self::Type)
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("parsing xdr: {0}")]
     Xdr(#[from] xdr::Error),
+    #[error("the xdr is not any known type")]
+    UnknownType,
     #[error("generating json: {0}")]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        let value =
-            xdr::Type::from_xdr_base64(self.r#type, self.xdr.clone()).map_err(Error::Xdr)?;
+        if self.list {
+            for name in xdr::TypeVariant::VARIANTS_STR {
+                println!("{name}");
+            }
+            return Ok(());
+        }
+        let input = self.input.as_ref().or(self.xdr.as_ref());
+        let bytes = encoding::decode(input.map_or("", String::as_str))?;
+        let value = match self.r#type {
+            TypeArg::Type(type_) => xdr::Type::from_xdr(type_, bytes).map_err(Error::Xdr)?,
+            TypeArg::Auto => guess(&bytes)?,
+        };
 
         match self.output {
             Output::Default => println!("{value:#?}"),
@@ -63,3 +124,23 @@ impl Cmd {
         Ok(())
     }
 }
+
+/// Decode the XDR as the first of the common types it is, reporting each common type it could
+/// be on stderr, or, if none, as the first type in the XDR definitions it is.
+fn guess(bytes: &[u8]) -> Result<xdr::Type, Error> {
+    let matched = COMMON_TYPES
+        .iter()
+        .filter_map(|type_| xdr::Type::from_xdr(*type_, bytes).ok())
+        .collect::<Vec<_>>();
+    if !matched.is_empty() {
+        let names = matched.iter().map(xdr::Type::name).collect::<Vec<_>>();
+        eprintln!("Matched: {}", names.join(", "));
+        return matched.into_iter().next().ok_or(Error::UnknownType);
+    }
+    let value = xdr::TypeVariant::VARIANTS
+        .iter()
+        .find_map(|type_| xdr::Type::from_xdr(*type_, bytes).ok())
+        .ok_or(Error::UnknownType)?;
+    eprintln!("Matched: {}", value.name());
+    Ok(value)
+}
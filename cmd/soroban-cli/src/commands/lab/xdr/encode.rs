@@ -0,0 +1,84 @@
+use std::{fs, path::Path};
+
+use clap::{arg, builder::PossibleValuesParser, command, Parser};
+use soroban_env_host::xdr::{self, WriteXdr};
+
+use crate::encoding;
+
+macro_rules! types {
+    ($($type_:ident),* $(,)?) => {
+        /// The types XDR can be encoded from.
+        const TYPES: &[&str] = &[$(stringify!($type_)),*];
+
+        fn to_xdr(type_: &str, json: &str) -> Result<Vec<u8>, Error> {
+            match type_ {
+                $(
+                    stringify!($type_) => Ok(serde_json::from_str::<xdr::$type_>(json)?.to_xdr()?),
+                )*
+                _ => unreachable!("--type only accepts the types in TYPES"),
+            }
+        }
+    };
+}
+
+types!(
+    TransactionEnvelope,
+    Transaction,
+    TransactionResult,
+    TransactionMeta,
+    ScVal,
+    ScAddress,
+    LedgerEntryData,
+    LedgerEntry,
+    LedgerKey,
+    SorobanAuthorizationEntry,
+    SorobanAuthorizedInvocation,
+    SorobanTransactionData,
+    HostFunction,
+    Operation,
+    ContractEvent,
+    DiagnosticEvent,
+    ScSpecEntry,
+    HashIdPreimage,
+);
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// XDR type to encode
+    #[arg(long, value_parser = PossibleValuesParser::new(TYPES))]
+    r#type: String,
+    /// JSON to encode, in the form `lab xdr decode --output json` prints, or a path to a file
+    /// holding it
+    input: String,
+    #[command(flatten)]
+    encoding: encoding::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("encoding xdr: {0}")]
+    Xdr(#[from] xdr::Error),
+    #[error("parsing json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("reading {path}: {error}")]
+    CannotReadFile { path: String, error: std::io::Error },
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let path = Path::new(&self.input);
+        let json = if path.is_file() {
+            fs::read_to_string(path).map_err(|error| Error::CannotReadFile {
+                path: self.input.clone(),
+                error,
+            })?
+        } else {
+            self.input.clone()
+        };
+        self.encoding.print(&to_xdr(&self.r#type, &json)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{TimeZone, Utc};
+use clap::{arg, command, Parser};
+
+use crate::commands::{
+    config::{ledger_file, locator},
+    lab::ledger::now::TARGET_CLOSE_INTERVAL_SECS,
+};
+
+/// Entries whose TTL ends before the new ledger are archived, as they would be on a network.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Number of ledgers to advance by
+    #[arg(long, default_value = "1")]
+    ledgers: u32,
+
+    /// Number of seconds to advance the ledger's time by [default: 5 per ledger]
+    #[arg(long)]
+    seconds: Option<u64>,
+
+    #[command(flatten)]
+    ledger_file: ledger_file::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    LedgerFile(#[from] ledger_file::Error),
+    #[error("cannot advance past ledger {}", u32::MAX)]
+    Overflow,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let pwd = self.locator.config_dir()?;
+        let mut state = self.ledger_file.read(&pwd)?;
+        state.sequence_number = state
+            .sequence_number
+            .checked_add(self.ledgers)
+            .ok_or(Error::Overflow)?;
+        let seconds = self
+            .seconds
+            .unwrap_or(u64::from(self.ledgers) * TARGET_CLOSE_INTERVAL_SECS.unsigned_abs());
+        state.timestamp = state.timestamp.saturating_add(seconds);
+        self.ledger_file.write(&mut state, &pwd)?;
+
+        let format = self.locator.read_settings()?.format;
+        println!("Ledger: {}", state.sequence_number);
+        if let Some(time) = i64::try_from(state.timestamp)
+            .ok()
+            .and_then(|t| Utc.timestamp_opt(t, 0).single())
+        {
+            println!("Time: {}", format.timestamp(time));
+        }
+        Ok(())
+    }
+}
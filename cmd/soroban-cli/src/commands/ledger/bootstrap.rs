@@ -0,0 +1,213 @@
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    AccountId, Asset, ContractDataDurability, ContractEntryBodyType, Hash, LedgerEntry,
+    LedgerEntryData, LedgerEntryExt, LedgerKey, LedgerKeyAccount, LedgerKeyContractData,
+    LedgerKeyTrustLine, PublicKey, ScAddress, ScVal, TrustLineAsset, TrustLineEntry,
+    TrustLineEntryExt, TrustLineFlags, Uint256,
+};
+use soroban_ledger_snapshot::LedgerSnapshot;
+
+use crate::{
+    commands::{
+        config::{self, ledger_file, locator, secret::Secret},
+        lab::token::wrap,
+    },
+    network::SANDBOX_NETWORK_PASSPHRASE,
+    utils::{self, parsing},
+};
+
+/// Stroops in one lumen.
+const STROOPS_PER_XLM: i64 = 10_000_000;
+
+/// Accounts that are not identities yet are generated as identities, with a random seed. The
+/// issuers of `--asset` are funded too, and the other accounts trust their assets.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identities or public keys of the accounts to fund, comma separated
+    #[arg(long, value_delimiter = ',', required = true)]
+    accounts: Vec<String>,
+
+    /// Balance in XLM of each account
+    #[arg(long, default_value = "10000")]
+    native_balance: i64,
+
+    /// Asset to add trustlines for and wrap in its Stellar Asset Contract, as `CODE:ISSUER`,
+    /// where the issuer is an identity or public key. Can be repeated
+    #[arg(long)]
+    asset: Vec<String>,
+
+    /// Balance in stroops of each account's trustlines
+    #[arg(long, default_value = "0")]
+    asset_balance: i64,
+
+    #[command(flatten)]
+    ledger_file: ledger_file::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] config::secret::Error),
+    #[error(transparent)]
+    LedgerFile(#[from] ledger_file::Error),
+    #[error(transparent)]
+    Asset(#[from] parsing::Error),
+    #[error(transparent)]
+    Wrap(#[from] wrap::Error),
+    #[error(transparent)]
+    Xdr(#[from] soroban_env_host::xdr::Error),
+    #[error("native balance of {0} XLM is too large")]
+    BalanceTooLarge(i64),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let pwd = self.locator.config_dir()?;
+        let mut state = self.ledger_file.read(&pwd)?;
+        let balance = self
+            .native_balance
+            .checked_mul(STROOPS_PER_XLM)
+            .ok_or(Error::BalanceTooLarge(self.native_balance))?;
+
+        let mut accounts = vec![];
+        for name in &self.accounts {
+            let account = self.account_id(name)?;
+            fund(&mut state, &account, balance);
+            println!("{name}: {}", strkey(&account));
+            accounts.push(account);
+        }
+        let mut assets = vec![];
+        for asset in &self.asset {
+            let (code, issuer) = asset.split_once(':').unwrap_or((asset, ""));
+            let issuer = self.account_id(issuer)?;
+            fund(&mut state, &issuer, balance);
+            let asset = parsing::parse_asset(&format!("{code}:{}", strkey(&issuer)))?;
+            for account in accounts.iter().filter(|account| **account != issuer) {
+                trust(&mut state, account, &asset, self.asset_balance);
+            }
+            assets.push(asset);
+        }
+        self.ledger_file.write(&mut state, &pwd)?;
+
+        // Wrapping runs the host against the ledger file, so it goes after the accounts are
+        // written.
+        let wrap = wrap::Cmd {
+            asset: String::new(),
+            config: config::Args {
+                ledger_file: self.ledger_file.clone(),
+                locator: self.locator.clone(),
+                ..Default::default()
+            },
+            fee: crate::fee::Args::default(),
+        };
+        for (name, asset) in self.asset.iter().zip(&assets) {
+            let contract_id =
+                utils::contract_id_hash_from_asset(asset, SANDBOX_NETWORK_PASSPHRASE)?;
+            let contract_id = if is_deployed(&state, &contract_id) {
+                stellar_strkey::Contract(contract_id.0).to_string()
+            } else {
+                wrap.run_in_sandbox(asset)?
+            };
+            println!("{name}: {contract_id}");
+        }
+        Ok(())
+    }
+
+    /// The account of a public key, or of an identity, generating the identity if it doesn't
+    /// exist.
+    fn account_id(&self, name: &str) -> Result<AccountId, Error> {
+        let key = if let Ok(key) = stellar_strkey::ed25519::PublicKey::from_string(name) {
+            key
+        } else {
+            let secret = match self.locator.read_identity(name) {
+                Err(locator::Error::ConfigMissing(_, _)) => {
+                    let secret = Secret::from_seed(None, false)?;
+                    self.locator.write_identity(name, &secret)?;
+                    secret
+                }
+                secret => secret?,
+            };
+            secret.public_key(None)?
+        };
+        Ok(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))))
+    }
+}
+
+fn strkey(account: &AccountId) -> String {
+    let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))) = account;
+    stellar_strkey::ed25519::PublicKey(*key).to_string()
+}
+
+/// The index of the entry of `key`, adding the `new` entry if there is none.
+fn entry(state: &mut LedgerSnapshot, key: LedgerKey, new: impl FnOnce() -> LedgerEntry) -> usize {
+    if let Some(i) = state.ledger_entries.iter().position(|(k, _)| **k == key) {
+        return i;
+    }
+    state.ledger_entries.push((Box::new(key), Box::new(new())));
+    state.ledger_entries.len() - 1
+}
+
+fn fund(state: &mut LedgerSnapshot, account_id: &AccountId, balance: i64) {
+    let key = LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id.clone(),
+    });
+    let i = entry(state, key, || {
+        utils::default_account_ledger_entry(account_id.clone())
+    });
+    if let LedgerEntryData::Account(account) = &mut state.ledger_entries[i].1.data {
+        account.balance = balance;
+    }
+}
+
+/// Add a trustline to `asset`, authorized and with `balance`, unless the account has one.
+fn trust(state: &mut LedgerSnapshot, account_id: &AccountId, asset: &Asset, balance: i64) {
+    let asset = match asset {
+        Asset::Native => return,
+        Asset::CreditAlphanum4(asset) => TrustLineAsset::CreditAlphanum4(asset.clone()),
+        Asset::CreditAlphanum12(asset) => TrustLineAsset::CreditAlphanum12(asset.clone()),
+    };
+    let key = LedgerKey::Trustline(LedgerKeyTrustLine {
+        account_id: account_id.clone(),
+        asset: asset.clone(),
+    });
+    let entries = state.ledger_entries.len();
+    let sequence = state.sequence_number;
+    entry(state, key, || LedgerEntry {
+        last_modified_ledger_seq: sequence,
+        data: LedgerEntryData::Trustline(TrustLineEntry {
+            account_id: account_id.clone(),
+            asset,
+            balance,
+            limit: i64::MAX,
+            flags: TrustLineFlags::AuthorizedFlag as u32,
+            ext: TrustLineEntryExt::V0,
+        }),
+        ext: LedgerEntryExt::V0,
+    });
+    if state.ledger_entries.len() > entries {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: account_id.clone(),
+        });
+        if let Some((_, entry)) = state.ledger_entries.iter_mut().find(|(k, _)| **k == key) {
+            if let LedgerEntryData::Account(account) = &mut entry.data {
+                account.num_sub_entries += 1;
+            }
+        }
+    }
+}
+
+fn is_deployed(state: &LedgerSnapshot, contract_id: &Hash) -> bool {
+    let key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::Contract(contract_id.clone()),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+        body_type: ContractEntryBodyType::DataEntry,
+    });
+    state.ledger_entries.iter().any(|(k, _)| **k == key)
+}
@@ -0,0 +1,24 @@
+use clap::Subcommand;
+
+pub mod get;
+
+#[derive(Debug, Subcommand)]
+pub enum Cmd {
+    /// Fetch ledger entries from the network by their keys, and print them as JSON
+    Get(get::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Get(#[from] get::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        match &self {
+            Cmd::Get(get) => get.run().await?,
+        }
+        Ok(())
+    }
+}
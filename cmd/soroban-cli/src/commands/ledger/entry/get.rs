@@ -0,0 +1,209 @@
+use clap::{arg, command, Parser, ValueEnum};
+use serde_json::json;
+use soroban_env_host::xdr::{
+    self, AccountId, Asset, ContractDataDurability, ContractEntryBodyType, Hash, LedgerEntryData,
+    LedgerKey, LedgerKeyAccount, LedgerKeyContractCode, LedgerKeyContractData, LedgerKeyTrustLine,
+    PublicKey, ReadXdr, ScAddress, ScVal, TrustLineAsset, Uint256,
+};
+
+use crate::{
+    commands::config,
+    encoding,
+    rpc::{self, Client},
+    utils::{self, parsing},
+};
+
+/// Fetch ledger entries by their keys, in a single request, and print them as a JSON array of
+/// their keys, entries and the ledgers they were last modified in.
+///
+/// Keys can be given as XDR or JSON, or built from the flags for the common kinds of entries.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Ledger key, as base64 or hex XDR, or as JSON in the form `lab xdr decode --output json`
+    /// prints. Can be repeated
+    #[arg(long)]
+    key: Vec<String>,
+
+    /// Account, as a public key or an identity. Can be repeated
+    #[arg(long)]
+    account: Vec<String>,
+
+    /// Trustline of an account to an asset, as `ACCOUNT:CODE:ISSUER`. Can be repeated
+    #[arg(long, value_name = "ACCOUNT:CODE:ISSUER")]
+    trustline: Vec<String>,
+
+    /// Instance of a contract, by ID or alias. Can be repeated
+    #[arg(long)]
+    contract_instance: Vec<String>,
+
+    /// Data of a contract, as `CONTRACT:KEY` where `KEY` is the storage key as base64 ScVal
+    /// XDR, e.g. from `lab scval from-json`. Can be repeated
+    #[arg(long, value_name = "CONTRACT:KEY")]
+    contract_data: Vec<String>,
+
+    /// Durability of the `--contract-data` entries
+    #[arg(long, value_enum, default_value_t)]
+    durability: Durability,
+
+    /// Wasm, by its hash in hex. Can be repeated
+    #[arg(long)]
+    wasm_hash: Vec<String>,
+
+    #[command(flatten)]
+    config: config::Args,
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Durability {
+    #[default]
+    Persistent,
+    Temporary,
+}
+
+impl From<Durability> for ContractDataDurability {
+    fn from(durability: Durability) -> Self {
+        match durability {
+            Durability::Persistent => ContractDataDurability::Persistent,
+            Durability::Temporary => ContractDataDurability::Temporary,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Secret(#[from] config::secret::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error(transparent)]
+    Parsing(#[from] parsing::Error),
+    #[error("invalid key {0}")]
+    InvalidKey(String),
+    #[error("cannot parse contract ID {0}: {1}")]
+    CannotParseContractId(String, stellar_strkey::DecodeError),
+    #[error("no keys, see --help for the ways to give them")]
+    NoKeys,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let keys = self.keys()?;
+        if keys.is_empty() {
+            return Err(Error::NoKeys);
+        }
+        let network = self.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
+        let response = client.get_ledger_entries(keys.clone()).await?;
+        let mut found = vec![];
+        for entry in response.entries.unwrap_or_default() {
+            found.push(json!({
+                "key": LedgerKey::from_xdr_base64(&entry.key)?,
+                "entry": LedgerEntryData::from_xdr_base64(&entry.xdr)?,
+                "lastModifiedLedger": entry.last_modified_ledger,
+            }));
+        }
+        let found_keys = found.iter().map(|f| &f["key"]).collect::<Vec<_>>();
+        for key in &keys {
+            if !found_keys.contains(&&serde_json::to_value(key)?) {
+                eprintln!(
+                    "warning: not found, never created or archived: {}",
+                    serde_json::to_string(key)?
+                );
+            }
+        }
+        println!("{}", serde_json::to_string_pretty(&found)?);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<LedgerKey>, Error> {
+        let mut keys = vec![];
+        for key in &self.key {
+            keys.push(if key.trim_start().starts_with('{') {
+                serde_json::from_str(key)?
+            } else {
+                LedgerKey::from_xdr(encoding::decode(key)?)?
+            });
+        }
+        for account in &self.account {
+            keys.push(LedgerKey::Account(LedgerKeyAccount {
+                account_id: self.account_id(account)?,
+            }));
+        }
+        for trustline in &self.trustline {
+            let (account, asset) = trustline
+                .split_once(':')
+                .ok_or_else(|| Error::InvalidKey(trustline.clone()))?;
+            let asset = match parsing::parse_asset(asset)? {
+                Asset::CreditAlphanum4(asset) => TrustLineAsset::CreditAlphanum4(asset),
+                Asset::CreditAlphanum12(asset) => TrustLineAsset::CreditAlphanum12(asset),
+                Asset::Native => return Err(Error::InvalidKey(trustline.clone())),
+            };
+            keys.push(LedgerKey::Trustline(LedgerKeyTrustLine {
+                account_id: self.account_id(account)?,
+                asset,
+            }));
+        }
+        for contract in &self.contract_instance {
+            keys.push(self.contract_data_key(
+                contract,
+                ScVal::LedgerKeyContractInstance,
+                ContractDataDurability::Persistent,
+            )?);
+        }
+        for data in &self.contract_data {
+            let (contract, key) = data
+                .split_once(':')
+                .ok_or_else(|| Error::InvalidKey(data.clone()))?;
+            keys.push(self.contract_data_key(
+                contract,
+                ScVal::from_xdr_base64(key)?,
+                self.durability.into(),
+            )?);
+        }
+        for hash in &self.wasm_hash {
+            let hash = hex::decode(hash)
+                .ok()
+                .and_then(|hash| hash.try_into().ok())
+                .ok_or_else(|| Error::InvalidKey(hash.clone()))?;
+            keys.push(LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: Hash(hash),
+                body_type: ContractEntryBodyType::DataEntry,
+            }));
+        }
+        Ok(keys)
+    }
+
+    fn account_id(&self, account: &str) -> Result<AccountId, Error> {
+        let key = match stellar_strkey::ed25519::PublicKey::from_string(account) {
+            Ok(key) => key,
+            Err(_) => self.config.account(account)?.public_key(None)?,
+        };
+        Ok(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))))
+    }
+
+    fn contract_data_key(
+        &self,
+        contract: &str,
+        key: ScVal,
+        durability: ContractDataDurability,
+    ) -> Result<LedgerKey, Error> {
+        let id = self.config.resolve_contract_id(contract)?;
+        let contract_id = utils::contract_id_from_str(&id)
+            .map_err(|e| Error::CannotParseContractId(id.clone(), e))?;
+        Ok(LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash(contract_id)),
+            key,
+            durability,
+            body_type: ContractEntryBodyType::DataEntry,
+        }))
+    }
+}
@@ -0,0 +1,191 @@
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::PathBuf};
+
+use clap::{arg, command, Parser, ValueEnum};
+use soroban_env_host::xdr::{
+    ContractCodeEntry, ContractCodeEntryBody, ContractDataDurability, ContractDataEntry,
+    ContractDataEntryBody, ContractDataEntryData, ContractExecutable, Hash, LedgerEntryData,
+    LedgerKey, LedgerKeyContractData, ScAddress, ScContractInstance, ScVal, WriteXdr,
+};
+
+use crate::commands::config::{ledger_file, locator};
+
+/// Contract IDs depend on the deployer, so the deployed contracts get new IDs, which the script
+/// prints. Contract data can only be written by the contracts themselves, so it is listed in
+/// comments, to recreate by invoking the contracts.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// What to export the ledger as
+    #[arg(long, value_enum, default_value = "script")]
+    format: Format,
+
+    /// Directory to write the export to, with the contracts' wasm files in `wasm/`
+    #[arg(long)]
+    out_dir: PathBuf,
+
+    #[command(flatten)]
+    ledger_file: ledger_file::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// A shell script, `deploy.sh`, of the soroban commands that install and deploy the
+    /// contracts, using the network and source account of `SOROBAN_NETWORK` and
+    /// `SOROBAN_ACCOUNT`
+    Script,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    LedgerFile(#[from] ledger_file::Error),
+    #[error("writing {0:?}: {1}")]
+    Write(PathBuf, std::io::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let pwd = self.locator.config_dir()?;
+        let state = self.ledger_file.read(&pwd)?;
+
+        let mut code = BTreeMap::new();
+        let mut instances = BTreeMap::new();
+        let mut data = BTreeMap::<_, Vec<_>>::new();
+        for (key, entry) in &state.ledger_entries {
+            match (&**key, &entry.data) {
+                (
+                    LedgerKey::ContractCode(_),
+                    LedgerEntryData::ContractCode(ContractCodeEntry {
+                        hash,
+                        body: ContractCodeEntryBody::DataEntry(wasm),
+                        ..
+                    }),
+                ) => {
+                    code.insert(hex::encode(hash.0), wasm.to_vec());
+                }
+                (
+                    LedgerKey::ContractData(LedgerKeyContractData {
+                        contract: ScAddress::Contract(contract),
+                        key,
+                        durability,
+                        ..
+                    }),
+                    LedgerEntryData::ContractData(ContractDataEntry {
+                        body: ContractDataEntryBody::DataEntry(ContractDataEntryData { val, .. }),
+                        ..
+                    }),
+                ) => {
+                    let id = stellar_strkey::Contract(contract.0).to_string();
+                    if let ScVal::ContractInstance(ScContractInstance {
+                        executable,
+                        storage,
+                    }) = val
+                    {
+                        instances.insert(id.clone(), executable.clone());
+                        for entry in storage.iter().flat_map(|storage| storage.0.iter()) {
+                            data.entry(id.clone()).or_default().push(format!(
+                                "Instance {} = {}",
+                                value(&entry.key),
+                                value(&entry.val)
+                            ));
+                        }
+                    } else {
+                        let durability = match durability {
+                            ContractDataDurability::Persistent => "Persistent",
+                            ContractDataDurability::Temporary => "Temporary",
+                        };
+                        data.entry(id).or_default().push(format!(
+                            "{durability} {} = {}",
+                            value(key),
+                            value(val)
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let wasm_dir = self.out_dir.join("wasm");
+        fs::create_dir_all(&wasm_dir).map_err(|e| Error::Write(wasm_dir.clone(), e))?;
+        for (hash, wasm) in &code {
+            let path = wasm_dir.join(format!("{hash}.wasm"));
+            fs::write(&path, wasm).map_err(|e| Error::Write(path, e))?;
+        }
+        let script = match self.format {
+            Format::Script => script(state.sequence_number, &code, &instances, &data),
+        };
+        let path = self.out_dir.join("deploy.sh");
+        fs::write(&path, script).map_err(|e| Error::Write(path.clone(), e))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| Error::Write(path.clone(), e))?;
+        }
+        println!(
+            "Exported {} contracts and {} wasm files to {}",
+            instances.len(),
+            code.len(),
+            path.to_string_lossy()
+        );
+        Ok(())
+    }
+}
+
+fn script(
+    ledger: u32,
+    code: &BTreeMap<String, Vec<u8>>,
+    instances: &BTreeMap<String, ContractExecutable>,
+    data: &BTreeMap<String, Vec<String>>,
+) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n\
+         # The contracts of the sandbox ledger at ledger {ledger}. Run with the network and\n\
+         # account to deploy with, e.g. SOROBAN_NETWORK=testnet SOROBAN_ACCOUNT=alice ./deploy.sh\n\
+         set -e\n\
+         cd \"$(dirname \"$0\")\"\n"
+    );
+    if !code.is_empty() {
+        script.push('\n');
+    }
+    for hash in code.keys() {
+        let _ = writeln!(script, "soroban contract install --wasm wasm/{hash}.wasm");
+    }
+    for (i, (id, executable)) in instances.iter().enumerate() {
+        script.push('\n');
+        match executable {
+            ContractExecutable::Wasm(Hash(hash)) => {
+                let var = format!("CONTRACT_{}", i + 1);
+                let _ = writeln!(
+                    script,
+                    "{var}=$(soroban contract deploy --wasm-hash {})",
+                    hex::encode(hash)
+                );
+                let _ = writeln!(script, "echo \"{id}: ${var}\"");
+            }
+            ContractExecutable::Token => {
+                let _ = writeln!(
+                    script,
+                    "# {id} is a Stellar Asset Contract, wrap its asset with\n\
+                     # soroban lab token wrap --asset CODE:ISSUER"
+                );
+            }
+        }
+        if let Some(data) = data.get(id) {
+            let _ = writeln!(script, "# Data of {id}, to recreate by invoking it:");
+            for entry in data {
+                let _ = writeln!(script, "#   {entry}");
+            }
+        }
+    }
+    script
+}
+
+fn value(val: &ScVal) -> String {
+    soroban_spec_tools::to_string(val).unwrap_or_else(|_| val.to_xdr_base64().unwrap_or_default())
+}
@@ -0,0 +1,45 @@
+use clap::Subcommand;
+
+pub mod advance;
+pub mod bootstrap;
+pub mod entry;
+pub mod export;
+
+#[derive(Debug, Subcommand)]
+pub enum Cmd {
+    /// Advance the sandbox ledger's sequence and time, e.g. to test TTLs and time-based logic
+    Advance(advance::Cmd),
+    /// Fund accounts in the sandbox ledger, generating identities for them as needed, and add
+    /// trustlines to and wrap classic assets
+    Bootstrap(bootstrap::Cmd),
+    /// Fetch ledger entries from the network
+    #[command(subcommand)]
+    Entry(entry::Cmd),
+    /// Export the contracts in the sandbox ledger as the commands that install and deploy them
+    /// on a network
+    Export(export::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Advance(#[from] advance::Error),
+    #[error(transparent)]
+    Bootstrap(#[from] bootstrap::Error),
+    #[error(transparent)]
+    Entry(#[from] entry::Error),
+    #[error(transparent)]
+    Export(#[from] export::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        match &self {
+            Cmd::Advance(advance) => advance.run()?,
+            Cmd::Bootstrap(bootstrap) => bootstrap.run()?,
+            Cmd::Entry(entry) => entry.run().await?,
+            Cmd::Export(export) => export.run()?,
+        }
+        Ok(())
+    }
+}
@@ -2,13 +2,19 @@ use std::str::FromStr;
 
 use clap::{command, CommandFactory, FromArgMatches, Parser};
 
+pub mod account;
 pub mod completion;
 pub mod config;
 pub mod contract;
 pub mod events;
 pub mod global;
+pub mod history;
 pub mod lab;
+pub mod ledger;
 pub mod plugin;
+pub mod repl;
+pub mod snapshot;
+pub mod tx;
 pub mod version;
 
 pub const HEADING_SANDBOX: &str = "Options (Sandbox)";
@@ -72,13 +78,21 @@ impl Root {
         Self::from_arg_matches_mut(&mut Self::command().get_matches_from(itr))
     }
     pub async fn run(&mut self) -> Result<(), Error> {
+        let global_args = &self.global_args;
         match &mut self.cmd {
-            Cmd::Completion(completion) => completion.run(),
-            Cmd::Config(config) => config.run().await?,
-            Cmd::Contract(contract) => contract.run().await?,
-            Cmd::Events(events) => events.run().await?,
-            Cmd::Lab(lab) => lab.run().await?,
-            Cmd::Version(version) => version.run(),
+            Cmd::Account(account) => account.run(global_args).await?,
+            Cmd::Completion(completion) => completion.run().await?,
+            Cmd::Config(config) => config.run(global_args).await?,
+            Cmd::Contract(contract) => contract.run(global_args).await?,
+            Cmd::Events(events) => events.run(global_args).await?,
+            Cmd::History(history) => history.run(global_args).await?,
+            Cmd::Lab(lab) => lab.run(global_args).await?,
+            Cmd::Ledger(ledger) => ledger.run().await?,
+            Cmd::Plugin(plugin) => plugin.run(global_args)?,
+            Cmd::Repl(repl) => repl.run().await?,
+            Cmd::Snapshot(snapshot) => snapshot.run().await?,
+            Cmd::Tx(tx) => tx.run(global_args).await?,
+            Cmd::Version(version) => version.run(global_args),
         };
         Ok(())
     }
@@ -94,6 +108,9 @@ impl FromStr for Root {
 
 #[derive(Parser, Debug)]
 pub enum Cmd {
+    /// Inspect accounts on the network
+    #[command(subcommand)]
+    Account(account::Cmd),
     /// Print shell completion code for the specified shell.
     #[command(long_about = completion::LONG_ABOUT)]
     Completion(completion::Cmd),
@@ -105,9 +122,27 @@ pub enum Cmd {
     Config(config::Cmd),
     /// Watch the network for contract events
     Events(events::Cmd),
+    /// List, show and replay the commands that deployed, installed, invoked, or extended
+    /// contracts on a network
+    #[command(subcommand)]
+    History(history::Cmd),
     /// Experiment with early features and expert tools
     #[command(subcommand)]
     Lab(lab::Cmd),
+    /// Manage the sandbox's ledger file, and fetch ledger entries from a network
+    #[command(subcommand)]
+    Ledger(ledger::Cmd),
+    /// List plugins. Any `soroban-NAME` executable on PATH runs as `soroban NAME`
+    #[command(subcommand)]
+    Plugin(plugin::Cmd),
+    /// Start an interactive session that keeps a network and source account between commands
+    Repl(repl::Cmd),
+    /// Capture ledger entries from a network to run contracts against offline
+    #[command(subcommand)]
+    Snapshot(snapshot::Cmd),
+    /// Inspect and compare transactions
+    #[command(subcommand)]
+    Tx(tx::Cmd),
     /// Print version information
     Version(version::Cmd),
 }
@@ -116,12 +151,28 @@ pub enum Cmd {
 pub enum Error {
     // TODO: stop using Debug for displaying errors
     #[error(transparent)]
+    Account(#[from] account::Error),
+    #[error(transparent)]
+    Completion(#[from] completion::Error),
+    #[error(transparent)]
     Contract(#[from] contract::Error),
     #[error(transparent)]
     Events(#[from] events::Error),
+    #[error(transparent)]
+    History(#[from] history::Error),
 
     #[error(transparent)]
     Lab(#[from] lab::Error),
     #[error(transparent)]
+    Ledger(#[from] ledger::Error),
+    #[error(transparent)]
     Config(#[from] config::Error),
+    #[error(transparent)]
+    Plugin(#[from] plugin::Error),
+    #[error(transparent)]
+    Repl(#[from] repl::Error),
+    #[error(transparent)]
+    Snapshot(#[from] snapshot::Error),
+    #[error(transparent)]
+    Tx(#[from] tx::Error),
 }
@@ -0,0 +1,25 @@
+use crate::commands::global;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Plugin(#[from] Box<super::Error>),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let mut plugins = super::list().map_err(Box::new)?;
+        plugins.sort();
+        plugins.dedup();
+        global_args
+            .formatter()
+            .print(plugins.join("\n"), &plugins)?;
+        Ok(())
+    }
+}
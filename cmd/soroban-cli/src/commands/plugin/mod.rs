@@ -1,8 +1,20 @@
 use std::process::Command;
+
+use clap::Parser;
 use which::which;
 
 use crate::utils;
 
+use super::{config::locator, global};
+
+pub mod ls;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// List the plugins on PATH, i.e. the `soroban-NAME` executables that run as `soroban NAME`
+    Ls(ls::Cmd),
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Plugin not provided. Should be `soroban plugin` for a binary `soroban-plugin`")]
@@ -12,13 +24,27 @@ pub enum Error {
     #[error(
         r#"no such command: `{0}`
         
-        {1}View all installed plugins with `soroban --list`"#
+        {1}View all installed plugins with `soroban plugin ls`"#
     )]
     ExecutableNotFound(String, String),
     #[error(transparent)]
     Which(#[from] which::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
 }
 
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Ls(cmd) => cmd.run(global_args)?,
+        };
+        Ok(())
+    }
+}
+
+/// Run `soroban NAME ARGS...` as `soroban-NAME ARGS...`, the way cargo runs its plugins. The
+/// plugin gets the config directory and the network's RPC URL and passphrase in the same
+/// environment variables soroban reads them from, so it can work with the same config.
 pub fn run() -> Result<(), Error> {
     let (name, args) = {
         let mut args = std::env::args().skip(1);
@@ -47,14 +73,28 @@ pub fn run() -> Result<(), Error> {
         };
         Error::ExecutableNotFound(name, suggestion)
     })?;
-    std::process::exit(
-        Command::new(bin)
-            .args(args)
-            .spawn()?
-            .wait()?
-            .code()
-            .unwrap(),
-    );
+    let mut command = Command::new(bin);
+    command.args(args).envs(context());
+    // A plugin killed by a signal has no exit code
+    std::process::exit(command.spawn()?.wait()?.code().unwrap_or(1));
+}
+
+/// Environment variables for plugins, that aren't already set.
+fn context() -> Vec<(&'static str, String)> {
+    let locator = locator::Args::default();
+    let mut vars = vec![];
+    if let Ok(dir) = locator.config_dir() {
+        vars.push(("SOROBAN_CONFIG_DIR", dir.display().to_string()));
+    }
+    if let Some(network) = std::env::var("SOROBAN_NETWORK")
+        .ok()
+        .and_then(|name| locator.read_network(&name).ok())
+    {
+        vars.push(("SOROBAN_RPC_URL", network.rpc_url));
+        vars.push(("SOROBAN_NETWORK_PASSPHRASE", network.network_passphrase));
+    }
+    vars.retain(|(var, _)| std::env::var_os(var).is_none());
+    vars
 }
 
 const MAX_HEX_LENGTH: usize = 10;
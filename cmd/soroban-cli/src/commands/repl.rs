@@ -0,0 +1,158 @@
+use std::{
+    future::Future,
+    io::{self, BufRead, Write},
+    pin::Pin,
+};
+
+use clap::{arg, CommandFactory, FromArgMatches, Parser};
+
+use super::Root;
+
+const HELP: &str = "\
+Any soroban command can be run without the leading `soroban`, e.g.
+  contract invoke --id token -- balance --id alice
+using the session's network and source account unless it is given others. Also:
+  invoke CONTRACT.FUNCTION [NAME=VALUE]...  invoke a function, e.g. invoke token.balance id=alice
+  decode TYPE XDR                           decode XDR, e.g. decode TransactionResult AAAA...
+  use network NAME                          switch the session's network
+  use source NAME                           switch the session's source account
+  help                                      print this help
+  exit                                      end the session";
+
+/// Start an interactive session that runs commands with a network and source account that
+/// persist between them, and can be switched without retyping them on every command.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Network to start the session with
+    #[arg(long, env = "SOROBAN_NETWORK")]
+    network: Option<String>,
+
+    /// Source account to start the session with
+    #[arg(long, alias = "source", env = "SOROBAN_ACCOUNT")]
+    source_account: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading input: {0}")]
+    Stdin(io::Error),
+}
+
+/// The session's context is kept in the environment variables that the `--network` and
+/// `--source-account` flags of every command default to.
+const NETWORK_VAR: &str = "SOROBAN_NETWORK";
+const SOURCE_VAR: &str = "SOROBAN_ACCOUNT";
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        if let Some(network) = &self.network {
+            std::env::set_var(NETWORK_VAR, network);
+        }
+        if let Some(source) = &self.source_account {
+            std::env::set_var(SOURCE_VAR, source);
+        }
+        eprintln!("Type `help` for the commands, `exit` to end the session.");
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        loop {
+            eprint!("{}", prompt());
+            let _ = io::stderr().flush();
+            let Some(line) = lines.next() else {
+                eprintln!();
+                return Ok(());
+            };
+            let line = line.map_err(Error::Stdin)?;
+            let words = match shlex::split(line.trim()) {
+                Some(words) if words.is_empty() => continue,
+                Some(words) => words,
+                None => {
+                    eprintln!("error: unbalanced quotes");
+                    continue;
+                }
+            };
+            match words.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+                ["exit" | "quit"] => return Ok(()),
+                ["help"] => println!("{HELP}"),
+                ["use", "network", name] => std::env::set_var(NETWORK_VAR, name),
+                ["use", "source", name] => std::env::set_var(SOURCE_VAR, name),
+                ["repl", ..] => eprintln!("error: already in a session"),
+                _ => {
+                    let words = expand(words.clone());
+                    super::history::set_command(words.clone());
+                    if let Err(e) = run_line(words).await {
+                        eprintln!("error: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn prompt() -> String {
+    let network = std::env::var(NETWORK_VAR).unwrap_or_else(|_| "sandbox".to_string());
+    match std::env::var(SOURCE_VAR) {
+        Ok(source) => format!("soroban ({network}, {source})> "),
+        Err(_) => format!("soroban ({network})> "),
+    }
+}
+
+/// Expand the session's shorthands into the commands they stand for.
+fn expand(words: Vec<String>) -> Vec<String> {
+    match &words[..] {
+        [invoke, target, args @ ..] if invoke == "invoke" => match target.split_once('.') {
+            Some((contract, function)) => [
+                "contract", "invoke", "--id", contract, "--fn", function, "--",
+            ]
+            .into_iter()
+            .map(String::from)
+            .chain(args.iter().cloned())
+            .collect(),
+            None => words,
+        },
+        [decode, type_, xdr] if decode == "decode" => {
+            ["lab", "xdr", "decode", "--type", type_, xdr]
+                .into_iter()
+                .map(String::from)
+                .collect()
+        }
+        _ => words,
+    }
+}
+
+async fn run_line(words: Vec<String>) -> Result<(), super::Error> {
+    let parsed = Root::command()
+        .no_binary_name(true)
+        .try_get_matches_from(words)
+        .and_then(|mut matches| Root::from_arg_matches_mut(&mut matches));
+    let mut root = match parsed {
+        Ok(root) => root,
+        // Help and usage errors print as they do outside the session.
+        Err(e) => {
+            let _ = e.print();
+            return Ok(());
+        }
+    };
+    // Boxed, as the session runs commands from within a command.
+    let run: Pin<Box<dyn Future<Output = Result<(), super::Error>> + '_>> = Box::pin(root.run());
+    run.await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_shorthands() {
+        let words = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            expand(words("invoke token.balance id=alice")),
+            words("contract invoke --id token --fn balance -- id=alice")
+        );
+        assert_eq!(
+            expand(words("decode ScVal AAAAAQ==")),
+            words("lab xdr decode --type ScVal AAAAAQ==")
+        );
+        assert_eq!(expand(words("events --count 1")), words("events --count 1"));
+    }
+}
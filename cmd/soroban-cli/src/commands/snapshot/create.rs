@@ -0,0 +1,209 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use chrono::Utc;
+use clap::{command, Parser};
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{
+    self, ConfigSettingEntry, ConfigSettingId, ContractDataDurability, ContractDataEntry,
+    ContractDataEntryBody, ContractDataEntryData, ContractEntryBodyType, ContractExecutable, Hash,
+    LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey, LedgerKeyContractCode,
+    LedgerKeyContractData, ReadXdr, ScAddress, ScContractInstance, ScSpecTypeDef, ScVal,
+};
+
+use crate::{
+    commands::{config, contract::Durability},
+    rpc::{self, Client},
+    utils,
+};
+
+/// The RPC cannot list a contract's data entries, so besides the instance, with its instance
+/// storage, and the code, only the entries of `--key` and `--key-xdr` are included.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Contract ID or alias whose entries to write
+    #[arg(long = "id")]
+    contract_id: String,
+    /// Storage key, as a symbol or as the JSON form of an ScVal, can be repeated
+    #[arg(long = "key")]
+    key: Vec<String>,
+    /// Storage key (base64-encoded XDR ScVal), can be repeated
+    #[arg(long = "key-xdr")]
+    key_xdr: Vec<String>,
+    /// Durability of the entries of `--key` and `--key-xdr`
+    #[arg(long, value_enum, default_value = "persistent")]
+    durability: Durability,
+    /// File to write the snapshot to
+    #[arg(long, short = 'o')]
+    out: PathBuf,
+
+    #[command(flatten)]
+    config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("parsing key {key}: {error}")]
+    CannotParseKey {
+        key: String,
+        error: soroban_spec_tools::Error,
+    },
+    #[error("parsing XDR key {key}: {error}")]
+    CannotParseXdrKey { key: String, error: xdr::Error },
+    #[error("cannot parse contract ID {0}: {1}")]
+    CannotParseContractId(String, stellar_strkey::DecodeError),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("ledger {0} is not a number")]
+    InvalidLedger(String),
+    #[error("contract {0} not found")]
+    ContractNotFound(String),
+    #[error("writing snapshot {0:?}: {1}")]
+    CannotWriteSnapshot(PathBuf, soroban_ledger_snapshot::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let contract_id = self.config.resolve_contract_id(&self.contract_id)?;
+        let contract_id = utils::contract_id_from_str(&contract_id)
+            .map_err(|e| Error::CannotParseContractId(contract_id.clone(), e))?;
+        let network = self.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
+
+        let instance_key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash(contract_id)),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+            body_type: ContractEntryBodyType::DataEntry,
+        });
+        let mut entries = fetch(&client, &[instance_key]).await?;
+        let Some((
+            _,
+            LedgerEntry {
+                data:
+                    LedgerEntryData::ContractData(ContractDataEntry {
+                        body:
+                            ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                                val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
+                                ..
+                            }),
+                        ..
+                    }),
+                ..
+            },
+        )) = entries.first()
+        else {
+            return Err(Error::ContractNotFound(self.contract_id.clone()));
+        };
+        let mut keys = self.data_keys(contract_id)?;
+        if let ContractExecutable::Wasm(hash) = executable {
+            keys.push(LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: hash.clone(),
+                body_type: ContractEntryBodyType::DataEntry,
+            }));
+        }
+        let found = fetch(&client, &keys).await?;
+        if found.len() < keys.len() {
+            eprintln!(
+                "warning: {} of the requested entries were not found",
+                keys.len() - found.len()
+            );
+        }
+        entries.extend(found);
+
+        let mut snapshot = utils::default_ledger_snapshot();
+        let latest_ledger = client.get_latest_ledger().await?;
+        snapshot.protocol_version = latest_ledger.protocol_version;
+        snapshot.sequence_number = latest_ledger.sequence;
+        snapshot.timestamp = u64::try_from(Utc::now().timestamp()).unwrap_or_default();
+        snapshot.network_id = Sha256::digest(network.network_passphrase.as_bytes()).into();
+        for setting in client
+            .get_config_settings(&[ConfigSettingId::StateExpiration])
+            .await?
+        {
+            if let ConfigSettingEntry::StateExpiration(settings) = setting {
+                snapshot.min_persistent_entry_expiration = settings.min_persistent_entry_expiration;
+                snapshot.min_temp_entry_expiration = settings.min_temp_entry_expiration;
+                snapshot.max_entry_expiration = settings.max_entry_expiration;
+            }
+        }
+        snapshot.ledger_entries = entries
+            .into_iter()
+            .map(|(key, entry)| (Box::new(key), Box::new(entry)))
+            .collect();
+        snapshot
+            .write_file(&self.out)
+            .map_err(|e| Error::CannotWriteSnapshot(self.out.clone(), e))?;
+        println!(
+            "Wrote {} entries at ledger {} to {}",
+            snapshot.ledger_entries.len(),
+            snapshot.sequence_number,
+            self.out.to_string_lossy()
+        );
+        Ok(())
+    }
+
+    fn data_keys(&self, contract_id: [u8; 32]) -> Result<Vec<LedgerKey>, Error> {
+        let mut keys = vec![];
+        for key in &self.key {
+            keys.push(match serde_json::from_str::<ScVal>(key) {
+                Ok(key) => key,
+                Err(_) => soroban_spec_tools::from_string_primitive(key, &ScSpecTypeDef::Symbol)
+                    .map_err(|e| Error::CannotParseKey {
+                        key: key.clone(),
+                        error: e,
+                    })?,
+            });
+        }
+        for key in &self.key_xdr {
+            keys.push(
+                ScVal::from_xdr_base64(key).map_err(|e| Error::CannotParseXdrKey {
+                    key: key.clone(),
+                    error: e,
+                })?,
+            );
+        }
+        Ok(keys
+            .into_iter()
+            .map(|key| {
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: ScAddress::Contract(Hash(contract_id)),
+                    key,
+                    durability: self.durability.into(),
+                    body_type: ContractEntryBodyType::DataEntry,
+                })
+            })
+            .collect())
+    }
+}
+
+/// The entries of `keys` that exist.
+async fn fetch(
+    client: &Client,
+    keys: &[LedgerKey],
+) -> Result<Vec<(LedgerKey, LedgerEntry)>, Error> {
+    client
+        .get_ledger_entries(keys.to_vec())
+        .await?
+        .entries
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| {
+            Ok((
+                LedgerKey::from_xdr_base64(&entry.key)?,
+                LedgerEntry {
+                    last_modified_ledger_seq: entry
+                        .last_modified_ledger
+                        .parse()
+                        .map_err(|_| Error::InvalidLedger(entry.last_modified_ledger.clone()))?,
+                    data: LedgerEntryData::from_xdr_base64(&entry.xdr)?,
+                    ext: LedgerEntryExt::V0,
+                },
+            ))
+        })
+        .collect()
+}
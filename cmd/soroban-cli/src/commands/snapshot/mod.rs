@@ -0,0 +1,25 @@
+use clap::Subcommand;
+
+pub mod create;
+
+#[derive(Debug, Subcommand)]
+pub enum Cmd {
+    /// Write a contract's ledger entries from a network to a snapshot file, to run the contract
+    /// against offline with `contract run --snapshot` or `contract invoke --snapshot`
+    Create(create::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Create(#[from] create::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        match &self {
+            Cmd::Create(create) => create.run().await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use clap::{arg, Parser};
+use serde_json::Value;
+use soroban_env_host::xdr::{self, ReadXdr, TransactionEnvelope};
+
+use crate::encoding;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// First transaction envelope, as base64 or hex, or a path to a file holding either or the
+    /// raw bytes
+    #[arg(value_name = "A")]
+    a: String,
+    /// Second transaction envelope, in the same forms as the first
+    #[arg(value_name = "B")]
+    b: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error("parsing transaction envelope {input}: {error}")]
+    Xdr { input: String, error: xdr::Error },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let changes = diff(&to_json(&self.a)?, &to_json(&self.b)?);
+        if changes.is_empty() {
+            println!("transactions are identical");
+        }
+        for change in changes {
+            println!("{change}");
+        }
+        Ok(())
+    }
+}
+
+fn to_json(input: &str) -> Result<Value, Error> {
+    let envelope =
+        TransactionEnvelope::from_xdr(encoding::decode(input)?).map_err(|error| Error::Xdr {
+            input: input.to_string(),
+            error,
+        })?;
+    Ok(serde_json::to_value(envelope)?)
+}
+
+/// Lines describing where `b` differs from `a`, one per differing leaf, prefixed by its path
+/// in the envelope: `~` for a changed value, `-` for one only in `a`, `+` for one only in `b`.
+pub fn diff(a: &Value, b: &Value) -> Vec<String> {
+    let mut changes = vec![];
+    diff_at("", a, b, &mut changes);
+    changes
+}
+
+fn diff_at(path: &str, a: &Value, b: &Value, changes: &mut Vec<String>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, a_value) in a {
+                let path = join(path, key);
+                match b.get(key) {
+                    Some(b_value) => diff_at(&path, a_value, b_value, changes),
+                    None => changes.push(format!("- {path}: {a_value}")),
+                }
+            }
+            for (key, b_value) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                changes.push(format!("+ {}: {b_value}", join(path, key)));
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let path = format!("{path}[{i}]");
+                match (a.get(i), b.get(i)) {
+                    (Some(a), Some(b)) => diff_at(&path, a, b, changes),
+                    (Some(a), None) => changes.push(format!("- {path}: {a}")),
+                    (None, Some(b)) => changes.push(format!("+ {path}: {b}")),
+                    (None, None) => {}
+                }
+            }
+        }
+        (a, b) if a != b => changes.push(format!("~ {path}: {a} -> {b}")),
+        _ => {}
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_paths() {
+        let a = json!({"tx": {"fee": 100, "operations": [1, 2], "memo": "none"}});
+        let b = json!({"tx": {"fee": 200, "operations": [1], "cond": "none"}});
+        assert_eq!(
+            diff(&a, &b),
+            vec![
+                "~ tx.fee: 100 -> 200",
+                "- tx.memo: \"none\"",
+                "- tx.operations[1]: 2",
+                "+ tx.cond: \"none\"",
+            ]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use std::{fmt::Write, fs, path::PathBuf};
+
+use clap::{arg, command, Parser};
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{
+    AccountId, Asset, BumpFootprintExpirationOp, ContractExecutable, CreateAccountOp,
+    CreateContractArgs, DecoratedSignature, FeeBumpTransactionInnerTx, Hash, HostFunction,
+    InvokeContractArgs, InvokeHostFunctionOp, LedgerKey, LedgerKeyAccount, LedgerKeyContractCode,
+    LedgerKeyContractData, Memo, MuxedAccount, MuxedAccountMed25519, Operation, OperationBody,
+    PaymentOp, Preconditions, PublicKey, ScAddress, ScVal, Transaction, TransactionEnvelope,
+    TransactionExt, Uint256,
+};
+use soroban_spec_tools::Spec;
+
+use super::sign;
+use crate::{commands::config::locator, format::Format, log};
+
+/// Explain what a transaction envelope will do before it is signed or submitted: its
+/// operations, the contract functions it invokes and their arguments, who must authorize what,
+/// the ledger entries it reads and writes, and its fees.
+///
+/// Nothing is fetched from the network, so it works on the offline machine that signs.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Transaction envelope, as base64 or hex, or a path to a file holding either or the raw
+    /// bytes. Default: read from stdin
+    envelope: Option<String>,
+
+    /// Wasm of the invoked contract, to name the arguments of the function after its spec
+    #[arg(long)]
+    wasm: Option<PathBuf>,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error("reading {0}: {1}")]
+    CannotReadWasm(PathBuf, std::io::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let envelope = sign::read_envelope(self.envelope.as_deref())?;
+        let spec = match &self.wasm {
+            Some(path) => Some(Spec::from_wasm(
+                &fs::read(path).map_err(|e| Error::CannotReadWasm(path.clone(), e))?,
+            )?),
+            None => None,
+        };
+        let format = self.locator.read_settings()?.format;
+        print!("{}", explain(&envelope, spec.as_ref(), &format)?);
+        Ok(())
+    }
+}
+
+/// Describe the envelope in plain language, naming function arguments after `spec` if given.
+pub fn explain(
+    envelope: &TransactionEnvelope,
+    spec: Option<&Spec>,
+    format: &Format,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    let signatures = match envelope {
+        TransactionEnvelope::TxV0(envelope) => {
+            let Uint256(key) = &envelope.tx.source_account_ed25519;
+            let _ = writeln!(out, "Source: {}", stellar_strkey::ed25519::PublicKey(*key));
+            let _ = writeln!(out, "Sequence: {}", envelope.tx.seq_num.0);
+            let _ = writeln!(out, "Max fee: {}", format.amount(envelope.tx.fee.into()));
+            explain_memo(&mut out, &envelope.tx.memo)?;
+            explain_operations(&mut out, envelope.tx.operations.as_slice(), spec, format)?;
+            envelope.signatures.as_slice()
+        }
+        TransactionEnvelope::Tx(envelope) => {
+            explain_transaction(&mut out, &envelope.tx, spec, format)?;
+            envelope.signatures.as_slice()
+        }
+        TransactionEnvelope::TxFeeBump(envelope) => {
+            let _ = writeln!(
+                out,
+                "Fee bump by {}, paying up to {}",
+                muxed_to_string(&envelope.tx.fee_source),
+                format.amount(envelope.tx.fee)
+            );
+            let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+            let _ = writeln!(out, "Inner transaction:");
+            explain_transaction(&mut out, &inner.tx, spec, format)?;
+            let _ = writeln!(
+                out,
+                "Inner signatures: {}",
+                signatures_to_string(inner.signatures.as_slice())
+            );
+            envelope.signatures.as_slice()
+        }
+    };
+    let _ = writeln!(out, "Signatures: {}", signatures_to_string(signatures));
+    Ok(out)
+}
+
+fn explain_transaction(
+    out: &mut String,
+    tx: &Transaction,
+    spec: Option<&Spec>,
+    format: &Format,
+) -> Result<(), Error> {
+    let _ = writeln!(out, "Source: {}", muxed_to_string(&tx.source_account));
+    let _ = writeln!(out, "Sequence: {}", tx.seq_num.0);
+    match &tx.ext {
+        TransactionExt::V1(data) => {
+            let _ = writeln!(
+                out,
+                "Max fee: {}, of which {} is refundable",
+                format.amount(tx.fee.into()),
+                format.amount(data.refundable_fee)
+            );
+        }
+        TransactionExt::V0 => {
+            let _ = writeln!(out, "Max fee: {}", format.amount(tx.fee.into()));
+        }
+    }
+    if !matches!(tx.cond, Preconditions::None) {
+        let _ = writeln!(out, "Preconditions: {}", serde_json::to_string(&tx.cond)?);
+    }
+    explain_memo(out, &tx.memo)?;
+    explain_operations(out, tx.operations.as_slice(), spec, format)?;
+    if let TransactionExt::V1(data) = &tx.ext {
+        let resources = &data.resources;
+        let _ = writeln!(
+            out,
+            "Resources: {} instructions, {} bytes read, {} bytes written",
+            format.number(resources.instructions),
+            format.number(resources.read_bytes),
+            format.number(resources.write_bytes)
+        );
+        let footprint = &resources.footprint;
+        for (keys, access) in [
+            (&footprint.read_only, "Reads"),
+            (&footprint.read_write, "Writes"),
+        ] {
+            if keys.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "{access}:");
+            for key in keys.iter() {
+                let _ = writeln!(out, "  - {}", key_to_string(key)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn explain_memo(out: &mut String, memo: &Memo) -> Result<(), Error> {
+    if !matches!(memo, Memo::None) {
+        let _ = writeln!(out, "Memo: {}", serde_json::to_string(memo)?);
+    }
+    Ok(())
+}
+
+fn explain_operations(
+    out: &mut String,
+    operations: &[Operation],
+    spec: Option<&Spec>,
+    format: &Format,
+) -> Result<(), Error> {
+    let _ = writeln!(out, "Operations:");
+    for (i, op) in operations.iter().enumerate() {
+        let source = op
+            .source_account
+            .as_ref()
+            .map(|source| format!(" (as {})", muxed_to_string(source)))
+            .unwrap_or_default();
+        let _ = write!(out, "  {}.{source} ", i + 1);
+        match &op.body {
+            OperationBody::CreateAccount(CreateAccountOp {
+                destination,
+                starting_balance,
+            }) => {
+                let _ = writeln!(
+                    out,
+                    "Create account {} with {}",
+                    account_to_string(destination),
+                    format.amount(*starting_balance)
+                );
+            }
+            OperationBody::Payment(PaymentOp {
+                destination,
+                asset,
+                amount,
+            }) => {
+                let amount = match asset {
+                    Asset::Native => format.amount(*amount),
+                    asset => format!("{} of {}", format.number(*amount), asset_to_string(asset)),
+                };
+                let _ = writeln!(out, "Pay {amount} to {}", muxed_to_string(destination));
+            }
+            OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function,
+                auth,
+            }) => {
+                explain_host_function(out, host_function, spec)?;
+                for line in log::explain_auth(auth.as_slice()).lines() {
+                    let _ = writeln!(out, "     {line}");
+                }
+            }
+            OperationBody::BumpFootprintExpiration(BumpFootprintExpirationOp {
+                ledgers_to_expire,
+                ..
+            }) => {
+                let _ = writeln!(
+                    out,
+                    "Extend the time to live of the footprint's entries by {ledgers_to_expire} \
+                     ledgers"
+                );
+            }
+            OperationBody::RestoreFootprint(_) => {
+                let _ = writeln!(out, "Restore the footprint's archived entries");
+            }
+            body => {
+                let _ = writeln!(out, "{}", serde_json::to_string(body)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn explain_host_function(
+    out: &mut String,
+    host_function: &HostFunction,
+    spec: Option<&Spec>,
+) -> Result<(), Error> {
+    match host_function {
+        HostFunction::InvokeContract(InvokeContractArgs {
+            contract_address,
+            function_name,
+            args,
+        }) => {
+            let function_name = function_name.to_string_lossy();
+            let _ = writeln!(
+                out,
+                "Invoke {function_name} on {}",
+                address_to_string(contract_address)
+            );
+            let inputs = spec
+                .and_then(|spec| spec.find_function(&function_name).ok())
+                .filter(|function| function.inputs.len() == args.len())
+                .map(|function| {
+                    function
+                        .inputs
+                        .iter()
+                        .map(|input| input.name.to_string_lossy())
+                        .collect::<Vec<_>>()
+                });
+            for (i, arg) in args.iter().enumerate() {
+                let name = inputs
+                    .as_ref()
+                    .map_or_else(|| format!("arg{}", i + 1), |inputs| inputs[i].clone());
+                let _ = writeln!(out, "     {name}: {}", value_to_string(arg));
+            }
+        }
+        HostFunction::CreateContract(CreateContractArgs { executable, .. }) => {
+            let executable = match executable {
+                ContractExecutable::Wasm(Hash(hash)) => format!("wasm {}", hex::encode(hash)),
+                ContractExecutable::Token => "the built-in token".to_string(),
+            };
+            let _ = writeln!(out, "Create a contract from {executable}");
+        }
+        HostFunction::UploadContractWasm(wasm) => {
+            let _ = writeln!(
+                out,
+                "Upload {} bytes of wasm, with hash {}",
+                wasm.len(),
+                hex::encode(Sha256::digest(wasm.as_slice()))
+            );
+        }
+    }
+    Ok(())
+}
+
+fn key_to_string(key: &LedgerKey) -> Result<String, Error> {
+    Ok(match key {
+        LedgerKey::Account(LedgerKeyAccount { account_id }) => {
+            format!("account {}", account_to_string(account_id))
+        }
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract,
+            key: ScVal::LedgerKeyContractInstance,
+            ..
+        }) => format!("instance of {}", address_to_string(contract)),
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract,
+            key,
+            durability,
+            ..
+        }) => format!(
+            "{durability:?} data of {} at {}",
+            address_to_string(contract),
+            value_to_string(key)
+        ),
+        LedgerKey::ContractCode(LedgerKeyContractCode {
+            hash: Hash(hash), ..
+        }) => format!("wasm {}", hex::encode(hash)),
+        key => serde_json::to_string(key)?,
+    })
+}
+
+fn signatures_to_string(signatures: &[DecoratedSignature]) -> String {
+    if signatures.is_empty() {
+        return "none".to_string();
+    }
+    let hints = signatures
+        .iter()
+        .map(|signature| format!("…{}", hex::encode(signature.hint.0)))
+        .collect::<Vec<_>>();
+    format!(
+        "{}, from keys ending in {}",
+        signatures.len(),
+        hints.join(", ")
+    )
+}
+
+fn asset_to_string(asset: &Asset) -> String {
+    match asset {
+        Asset::Native => "XLM".to_string(),
+        Asset::CreditAlphanum4(asset) => format!(
+            "{}:{}",
+            String::from_utf8_lossy(&asset.asset_code.0).trim_end_matches('\0'),
+            account_to_string(&asset.issuer)
+        ),
+        Asset::CreditAlphanum12(asset) => format!(
+            "{}:{}",
+            String::from_utf8_lossy(&asset.asset_code.0).trim_end_matches('\0'),
+            account_to_string(&asset.issuer)
+        ),
+    }
+}
+
+fn muxed_to_string(account: &MuxedAccount) -> String {
+    match account {
+        MuxedAccount::Ed25519(Uint256(key)) => stellar_strkey::ed25519::PublicKey(*key).to_string(),
+        MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+            ed25519: Uint256(key),
+            id,
+        }) => stellar_strkey::ed25519::MuxedAccount {
+            ed25519: *key,
+            id: *id,
+        }
+        .to_string(),
+    }
+}
+
+fn account_to_string(
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))): &AccountId,
+) -> String {
+    stellar_strkey::ed25519::PublicKey(*key).to_string()
+}
+
+fn address_to_string(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(account_id) => account_to_string(account_id),
+        ScAddress::Contract(Hash(hash)) => stellar_strkey::Contract(*hash).to_string(),
+    }
+}
+
+fn value_to_string(value: &ScVal) -> String {
+    soroban_spec_tools::to_json(value).map_or_else(|_| format!("{value:?}"), |v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScSymbol, SequenceNumber, TransactionV1Envelope, VecM};
+
+    #[test]
+    fn explains_payment_and_invocation() {
+        let source = [1; 32];
+        let contract = [2; 32];
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256(source)),
+                fee: 100,
+                seq_num: SequenceNumber(7),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![
+                    Operation {
+                        source_account: None,
+                        body: OperationBody::Payment(PaymentOp {
+                            destination: MuxedAccount::Ed25519(Uint256(source)),
+                            asset: Asset::Native,
+                            amount: 10_000_000,
+                        }),
+                    },
+                    Operation {
+                        source_account: None,
+                        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                                contract_address: ScAddress::Contract(Hash(contract)),
+                                function_name: ScSymbol("hello".try_into().unwrap()),
+                                args: vec![ScVal::U32(5)].try_into().unwrap(),
+                            }),
+                            auth: VecM::default(),
+                        }),
+                    },
+                ]
+                .try_into()
+                .unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        });
+        let explained = explain(&envelope, None, &Format::default()).unwrap();
+        let source = stellar_strkey::ed25519::PublicKey(source).to_string();
+        let contract = stellar_strkey::Contract(contract).to_string();
+        assert!(explained.contains(&format!("1. Pay 1.0000000 XLM to {source}")));
+        assert!(explained.contains(&format!("2. Invoke hello on {contract}")));
+        assert!(explained.contains("arg1: 5"));
+        assert!(explained.contains("Signatures: none"));
+    }
+}
@@ -0,0 +1,59 @@
+use clap::Subcommand;
+
+use super::global;
+
+pub mod diff;
+pub mod inspect;
+pub mod sign;
+pub mod sign_auth;
+pub mod submit;
+pub mod watch;
+
+#[derive(Debug, Subcommand)]
+pub enum Cmd {
+    /// Show what differs between two transaction envelopes, field by field
+    Diff(diff::Cmd),
+    /// Explain what a transaction envelope will do, to review it before signing
+    Inspect(inspect::Cmd),
+    /// Add the source account's signature to a transaction envelope
+    Sign(sign::Cmd),
+    /// Sign the authorization entries written by `contract invoke --auth-out` that the source
+    /// account must authorize, so they can be passed back with `--auth-in`
+    SignAuth(sign_auth::Cmd),
+    /// Wait for a transaction, e.g. one submitted by a wallet, to succeed or fail, and print
+    /// its result, fee, and events
+    Watch(watch::Cmd),
+    /// Submit a signed transaction envelope and wait for it to succeed or fail, printing its
+    /// result, fee, and events
+    Submit(submit::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
+    #[error(transparent)]
+    Inspect(#[from] inspect::Error),
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+    #[error(transparent)]
+    SignAuth(#[from] sign_auth::Error),
+    #[error(transparent)]
+    Watch(#[from] watch::Error),
+    #[error(transparent)]
+    Submit(#[from] submit::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match &self {
+            Cmd::Diff(diff) => diff.run()?,
+            Cmd::Inspect(inspect) => inspect.run()?,
+            Cmd::Sign(sign) => sign.run()?,
+            Cmd::SignAuth(sign_auth) => sign_auth.run()?,
+            Cmd::Watch(watch) => watch.run(global_args).await?,
+            Cmd::Submit(submit) => submit.run(global_args).await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Read};
+
+use clap::{arg, command, Parser};
+use soroban_env_host::xdr::{
+    self, FeeBumpTransactionEnvelope, ReadXdr, TransactionEnvelope, TransactionV1Envelope, WriteXdr,
+};
+
+use crate::{commands::config, encoding, utils};
+
+/// Add the source account's signature to a transaction envelope, e.g. one built with
+/// `lab tx new`. Run it once per signer to collect the signatures of a multisig account.
+///
+/// Nothing is fetched from the network, which only gives the passphrase, so it can sign on an
+/// offline machine, with a network configured with `config network add`.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Transaction envelope, as base64 or hex, or a path to a file holding either or the raw
+    /// bytes. Default: read from stdin
+    #[arg(long = "env")]
+    envelope: Option<String>,
+
+    #[command(flatten)]
+    config: config::Args,
+
+    #[command(flatten)]
+    encoding: encoding::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Encoding(#[from] encoding::Error),
+    #[error("reading the envelope from stdin: {0}")]
+    Stdin(io::Error),
+    #[error("v0 transaction envelopes cannot be signed, rebuild the transaction")]
+    V0Envelope,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let envelope = read_envelope(self.envelope.as_deref())?;
+        let network = self.config.get_network()?;
+        let signed = self.sign(envelope, &network.network_passphrase)?;
+        self.encoding.print(&signed.to_xdr()?)?;
+        Ok(())
+    }
+
+    /// Append the source account's signature, unless the envelope already has it.
+    pub fn sign(
+        &self,
+        envelope: TransactionEnvelope,
+        network_passphrase: &str,
+    ) -> Result<TransactionEnvelope, Error> {
+        let secret = self.config.source_secret()?;
+        Ok(match envelope {
+            TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) => {
+                let hash = utils::transaction_hash(&tx, network_passphrase)?;
+                let signature = config::decorated_signature(&secret, self.config.hd_path, &hash)?;
+                TransactionEnvelope::Tx(TransactionV1Envelope {
+                    tx,
+                    signatures: append(signatures.into(), signature)?,
+                })
+            }
+            TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }) => {
+                let hash = utils::fee_bump_transaction_hash(&tx, network_passphrase)?;
+                let signature = config::decorated_signature(&secret, self.config.hd_path, &hash)?;
+                TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+                    tx,
+                    signatures: append(signatures.into(), signature)?,
+                })
+            }
+            TransactionEnvelope::TxV0(_) => return Err(Error::V0Envelope),
+        })
+    }
+}
+
+fn append<T: PartialEq, const MAX: u32>(
+    mut signatures: Vec<T>,
+    signature: T,
+) -> Result<xdr::VecM<T, MAX>, xdr::Error> {
+    if !signatures.contains(&signature) {
+        signatures.push(signature);
+    }
+    signatures.try_into()
+}
+
+/// Decode the transaction envelope, given as `encoding::decode` takes it, or read from stdin.
+pub fn read_envelope(envelope: Option<&str>) -> Result<TransactionEnvelope, Error> {
+    let input = match envelope {
+        Some(envelope) => envelope.to_string(),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(Error::Stdin)?;
+            input
+        }
+    };
+    Ok(TransactionEnvelope::from_xdr(encoding::decode(&input)?)?)
+}
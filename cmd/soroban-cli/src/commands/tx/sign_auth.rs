@@ -0,0 +1,134 @@
+use std::{fs, path::PathBuf};
+
+use clap::{arg, command, Parser};
+use sha2::{Digest, Sha256};
+use soroban_env_host::xdr::{
+    self, AccountId, Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, PublicKey, ReadXdr,
+    ScAddress, ScBytes, ScMap, ScMapEntry, ScSymbol, ScVal, ScVec, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedInvocation, SorobanCredentials, Uint256, WriteXdr,
+};
+
+use crate::commands::config;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Directory of authorization entries, as written by `contract invoke --auth-out`. The
+    /// entries the source account must authorize are signed in place
+    #[arg(long)]
+    pub auth: PathBuf,
+
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Secret(#[from] config::secret::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("reading {0}: {1}")]
+    CannotReadAuthEntry(PathBuf, std::io::Error),
+    #[error("writing {0}: {1}")]
+    CannotWriteAuthEntry(PathBuf, std::io::Error),
+    #[error("no authorization entries in {0} need a signature from {1}")]
+    NothingToSign(PathBuf, String),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let network = self.config.get_network()?;
+        let secret = self.config.source_secret()?;
+        let public_key = self.config.public_key()?;
+        let signer = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            public_key.0,
+        ))));
+
+        let mut paths = fs::read_dir(&self.auth)
+            .and_then(|entries| {
+                entries
+                    .map(|e| e.map(|e| e.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| Error::CannotReadAuthEntry(self.auth.clone(), e))?;
+        paths.retain(|p| p.extension().is_some_and(|ext| ext == "xdr"));
+        paths.sort();
+
+        let mut signed = 0;
+        for path in paths {
+            let data = fs::read_to_string(&path)
+                .map_err(|e| Error::CannotReadAuthEntry(path.clone(), e))?;
+            let mut entry = SorobanAuthorizationEntry::from_xdr_base64(data.trim())?;
+            let SorobanCredentials::Address(SorobanAddressCredentials {
+                address,
+                nonce,
+                signature_expiration_ledger,
+                signature,
+            }) = &mut entry.credentials
+            else {
+                continue;
+            };
+            if *address != signer {
+                continue;
+            }
+            let payload = authorization_payload(
+                &network.network_passphrase,
+                *nonce,
+                *signature_expiration_ledger,
+                &entry.root_invocation,
+            )?;
+            let ed25519_signature = secret.sign(self.config.hd_path, &payload)?;
+            *signature = account_signature(&public_key.0, &ed25519_signature.to_bytes())?;
+            fs::write(&path, entry.to_xdr_base64()?)
+                .map_err(|e| Error::CannotWriteAuthEntry(path.clone(), e))?;
+            println!("{}: signed by {public_key}", path.display());
+            signed += 1;
+        }
+        if signed == 0 {
+            return Err(Error::NothingToSign(
+                self.auth.clone(),
+                public_key.to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The hash an address signs to authorize `invocation` on the network, for the nonce and until
+/// the ledger its signature expires at.
+pub fn authorization_payload(
+    network_passphrase: &str,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    invocation: &SorobanAuthorizedInvocation,
+) -> Result<[u8; 32], xdr::Error> {
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        nonce,
+        signature_expiration_ledger,
+        invocation: invocation.clone(),
+    });
+    Ok(Sha256::digest(preimage.to_xdr()?).into())
+}
+
+/// The signature an account's built-in `__check_auth` expects: a vector of maps holding each
+/// signer's public key and signature.
+pub fn account_signature(public_key: &[u8; 32], signature: &[u8; 64]) -> Result<ScVal, Error> {
+    let entry = |key: &str, bytes: &[u8]| -> Result<ScMapEntry, Error> {
+        Ok(ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(key.try_into()?)),
+            val: ScVal::Bytes(ScBytes(bytes.to_vec().try_into()?)),
+        })
+    };
+    let map = ScVal::Map(Some(ScMap(
+        vec![
+            entry("public_key", public_key)?,
+            entry("signature", signature)?,
+        ]
+        .try_into()?,
+    )));
+    Ok(ScVal::Vec(Some(ScVec(vec![map].try_into()?))))
+}
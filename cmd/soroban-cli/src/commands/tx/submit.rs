@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use clap::{arg, command, Parser};
+
+use super::{sign, watch};
+use crate::{
+    commands::{
+        config::{locator, network},
+        global,
+    },
+    rpc::{self, Client, Outcome},
+};
+
+/// Submit a signed transaction envelope, e.g. one from `tx sign`, wait for it to succeed or
+/// fail, and print its result, fee, and events.
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Transaction envelope, as base64 or hex, or a path to a file holding either or the raw
+    /// bytes. Default: read from stdin
+    envelope: Option<String>,
+
+    /// Seconds to wait for the transaction to be included in a ledger
+    #[arg(long, default_value = "300")]
+    timeout: u64,
+
+    /// Also print the transaction's result meta, as JSON
+    #[arg(long)]
+    meta: bool,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+    #[error(transparent)]
+    Watch(#[from] watch::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let envelope = sign::read_envelope(self.envelope.as_deref())?;
+        let network = self.network.get(&self.locator)?;
+        let client = Client::new(&network.rpc_url)?;
+        client
+            .verify_network_passphrase(Some(&network.network_passphrase))
+            .await?;
+        let submission = global_args.submission(&network.network_passphrase);
+        let Outcome::Done((hash, _)) = client.submit_transaction(&envelope, &submission).await?
+        else {
+            return Ok(());
+        };
+        let response = watch::wait(&client, &hash, Duration::from_secs(self.timeout)).await?;
+        Ok(watch::print(
+            global_args.formatter(),
+            &hash,
+            &response,
+            self.meta,
+        )?)
+    }
+}
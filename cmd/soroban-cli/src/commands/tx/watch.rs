@@ -0,0 +1,167 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use clap::{arg, command, Parser};
+use serde_json::json;
+use soroban_env_host::xdr::{
+    self, ReadXdr, SorobanTransactionMeta, TransactionMeta, TransactionMetaV3, TransactionResult,
+};
+
+use crate::{
+    commands::{
+        config::{locator, network},
+        global,
+    },
+    log,
+    output::Formatter,
+    rpc::{self, Client, GetTransactionResponse},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Hash of the transaction, in hex
+    hash: String,
+
+    /// Seconds to wait for the transaction to be included in a ledger
+    #[arg(long, default_value = "300")]
+    timeout: u64,
+
+    /// Also print the transaction's result meta, as JSON
+    #[arg(long)]
+    meta: bool,
+
+    #[command(flatten)]
+    network: network::Args,
+
+    #[command(flatten)]
+    locator: locator::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error("transaction {0} was not found within {1} seconds")]
+    Timeout(String, u64),
+    #[error("transaction {0} failed")]
+    Failed(String),
+    #[error("unexpected transaction status {0}")]
+    UnexpectedStatus(String),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let client = Client::new(&network.rpc_url)?;
+        let response = wait(&client, &self.hash, Duration::from_secs(self.timeout)).await?;
+        print(global_args.formatter(), &self.hash, &response, self.meta)
+    }
+}
+
+/// Poll for the transaction until it has succeeded or failed, reporting each change of its
+/// status on stderr.
+pub async fn wait(
+    client: &Client,
+    hash: &str,
+    timeout: Duration,
+) -> Result<GetTransactionResponse, Error> {
+    let start = Instant::now();
+    let mut last_status = String::new();
+    loop {
+        let response = client.get_transaction(hash).await?;
+        if response.status != last_status {
+            eprintln!(
+                "{}: {} ({}s)",
+                hash,
+                response.status,
+                start.elapsed().as_secs()
+            );
+            last_status = response.status.clone();
+        }
+        match response.status.as_str() {
+            "SUCCESS" | "FAILED" => return Ok(response),
+            "NOT_FOUND" => {}
+            status => return Err(Error::UnexpectedStatus(status.to_string())),
+        }
+        if start.elapsed() > timeout {
+            return Err(Error::Timeout(hash.to_string(), timeout.as_secs()));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Print the ledger, fee, result, return value and events of a transaction that has succeeded
+/// or failed, and its result meta if `meta`. Failed transactions are an error after printing.
+pub fn print(
+    formatter: Formatter,
+    hash: &str,
+    response: &GetTransactionResponse,
+    meta: bool,
+) -> Result<(), Error> {
+    let mut text = format!("Status: {}\n", response.status);
+    let mut json = json!({ "hash": hash, "status": response.status, "ledger": response.ledger });
+    if let Some(ledger) = response.ledger {
+        let _ = writeln!(text, "Ledger: {ledger}");
+    }
+    if let Some(result) = &response.result_xdr {
+        let result = TransactionResult::from_xdr_base64(result)?;
+        let _ = writeln!(text, "Fee charged: {} stroops", result.fee_charged);
+        let _ = writeln!(text, "Result: {}", serde_json::to_string(&result.result)?);
+        json["feeCharged"] = result.fee_charged.into();
+        json["result"] = serde_json::to_value(&result.result)?;
+    }
+    if let Some(result_meta) = &response.result_meta_xdr {
+        let result_meta = TransactionMeta::from_xdr_base64(result_meta)?;
+        if let TransactionMeta::V3(TransactionMetaV3 {
+            soroban_meta:
+                Some(SorobanTransactionMeta {
+                    return_value,
+                    events,
+                    diagnostic_events,
+                    ..
+                }),
+            ..
+        }) = &result_meta
+        {
+            if response.status == "SUCCESS" {
+                let value = soroban_spec_tools::to_json(return_value)?;
+                let _ = writeln!(text, "Return value: {value}");
+                json["returnValue"] = value;
+            }
+            if !events.is_empty() {
+                let _ = writeln!(text, "Events:");
+                for event in events.iter() {
+                    let _ = writeln!(text, " • {}", serde_json::to_string(event)?);
+                }
+            }
+            json["events"] = serde_json::to_value(events)?;
+            text += &log::diagnostic_events(diagnostic_events, None);
+        }
+        if meta {
+            let _ = writeln!(
+                text,
+                "Result meta: {}",
+                serde_json::to_string_pretty(&result_meta)?
+            );
+            json["meta"] = serde_json::to_value(&result_meta)?;
+        }
+    }
+    formatter.print(text, &json)?;
+    if response.status == "FAILED" {
+        return Err(Error::Failed(hash.to_string()));
+    }
+    Ok(())
+}
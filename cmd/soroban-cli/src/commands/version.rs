@@ -1,7 +1,10 @@
 use clap::Parser;
+use serde_json::json;
 use soroban_env_host::meta;
 use std::fmt::Debug;
 
+use super::global;
+
 const GIT_REVISION: &str = env!("GIT_REVISION");
 
 #[derive(Parser, Debug, Clone)]
@@ -10,8 +13,21 @@ pub struct Cmd;
 
 impl Cmd {
     #[allow(clippy::unused_self)]
-    pub fn run(&self) {
-        println!("soroban {}", long());
+    pub fn run(&self, global_args: &global::Args) {
+        let env = soroban_env_host::VERSION;
+        let json = json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "revision": GIT_REVISION,
+            "sorobanEnv": env.pkg,
+            "sorobanEnvRevision": env.rev,
+            "interfaceVersion": meta::INTERFACE_VERSION,
+            "stellarXdr": env.xdr.pkg,
+            "stellarXdrRevision": env.xdr.rev,
+        });
+        global_args
+            .formatter()
+            .print(format!("soroban {}", long()), &json)
+            .expect("json values always serialize");
     }
 }
 
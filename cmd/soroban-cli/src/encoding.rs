@@ -0,0 +1,95 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading {path}: {error}")]
+    CannotReadFile {
+        path: std::path::PathBuf,
+        error: io::Error,
+    },
+    #[error("input is not base64, hex, or a path to a file")]
+    UnknownEncoding,
+    #[error("writing output: {0}")]
+    Write(#[from] io::Error),
+}
+
+/// How XDR is written out
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, clap::ValueEnum)]
+pub enum Encoding {
+    /// Base64 encoded text
+    #[default]
+    Base64,
+    /// Hex encoded text
+    Hex,
+    /// Raw bytes, for redirecting to a file
+    RawFile,
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Encoding of XDR output
+    #[arg(long, value_enum, default_value_t)]
+    pub encoding: Encoding,
+}
+
+impl Args {
+    /// Write `bytes` to stdout in the chosen encoding, followed by a newline unless raw.
+    pub fn print(&self, bytes: &[u8]) -> Result<(), Error> {
+        let mut stdout = io::stdout().lock();
+        match self.encoding {
+            Encoding::RawFile => stdout.write_all(bytes)?,
+            Encoding::Base64 | Encoding::Hex => writeln!(stdout, "{}", self.encode(bytes))?,
+        }
+        Ok(())
+    }
+
+    /// Encode `bytes` as text. Raw output has no text form and falls back to base64.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self.encoding {
+            Encoding::Hex => hex::encode(bytes),
+            Encoding::Base64 | Encoding::RawFile => base64::encode(bytes),
+        }
+    }
+}
+
+/// Decode XDR given as base64, hex, or a path to a file holding any of those or raw bytes.
+pub fn decode(input: &str) -> Result<Vec<u8>, Error> {
+    let path = Path::new(input);
+    if path.is_file() {
+        let bytes = fs::read(path).map_err(|error| Error::CannotReadFile {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        return Ok(match std::str::from_utf8(&bytes) {
+            Ok(text) => decode_text(text.trim()).unwrap_or(bytes),
+            Err(_) => bytes,
+        });
+    }
+    decode_text(input.trim()).ok_or(Error::UnknownEncoding)
+}
+
+/// Hex is tried first, it's a subset of the base64 alphabet and an XDR value that
+/// happens to be valid hex as base64 is far less likely than the reverse.
+fn decode_text(text: &str) -> Option<Vec<u8>> {
+    if text.is_empty() {
+        return None;
+    }
+    hex::decode(text).ok().or_else(|| base64::decode(text).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_encoding() {
+        assert_eq!(decode("0000000a").unwrap(), [0, 0, 0, 10]);
+        assert_eq!(decode("AAAACg==").unwrap(), [0, 0, 0, 10]);
+        assert!(decode("not xdr!").is_err());
+    }
+}
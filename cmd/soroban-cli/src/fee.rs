@@ -1,4 +1,5 @@
 use clap::arg;
+use soroban_env_host::xdr::{Transaction, TransactionExt};
 
 use crate::commands::HEADING_RPC;
 
@@ -8,10 +9,57 @@ pub struct Args {
     /// fee amount for transaction, in stroops. 1 stroop = 0.0000001 xlm
     #[arg(long, default_value = "100", env = "SOROBAN_FEE", help_heading = HEADING_RPC)]
     pub fee: u32,
+    /// Print the resources simulation estimated for the transaction, and the fee it will pay,
+    /// to stderr
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub print_cost: bool,
+    /// Number of CPU instructions the transaction may use, overriding the simulated estimate.
+    /// The resource fee is not recomputed for them, so it must be given with `--resource-fee`
+    #[arg(long, requires = "resource_fee", help_heading = HEADING_RPC)]
+    pub instructions: Option<u32>,
+    /// Resource fee to pay in stroops, on top of `--fee`, overriding the simulated minimum
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub resource_fee: Option<u32>,
 }
 
 impl Default for Args {
     fn default() -> Self {
-        Self { fee: 100 }
+        Self {
+            fee: 100,
+            print_cost: false,
+            instructions: None,
+            resource_fee: None,
+        }
+    }
+}
+
+impl Args {
+    /// Apply the resource overrides to a transaction assembled from simulation, printing its
+    /// resources if asked to.
+    pub fn apply_to_assembled_tx(&self, mut tx: Transaction) -> Transaction {
+        if let Some(resource_fee) = self.resource_fee {
+            tx.fee = self.fee.saturating_add(resource_fee);
+        }
+        if let TransactionExt::V1(data) = &mut tx.ext {
+            if let Some(instructions) = self.instructions {
+                data.resources.instructions = instructions;
+            }
+            if self.print_cost {
+                let resources = &data.resources;
+                eprintln!("CPU instructions: {}", resources.instructions);
+                eprintln!("Read bytes: {}", resources.read_bytes);
+                eprintln!("Write bytes: {}", resources.write_bytes);
+                eprintln!(
+                    "Ledger entries read: {}, written: {}",
+                    resources.footprint.read_only.len() + resources.footprint.read_write.len(),
+                    resources.footprint.read_write.len()
+                );
+                eprintln!("Refundable fee: {}", data.refundable_fee);
+            }
+        }
+        if self.print_cost {
+            eprintln!("Fee: {}", tx.fee);
+        }
+        tx
     }
 }
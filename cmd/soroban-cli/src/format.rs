@@ -0,0 +1,125 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How human readable output renders timestamps, numbers and amounts, set with
+/// `soroban config set format.<setting> <value>`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Format {
+    pub timestamps: Timestamps,
+    pub thousands_separator: bool,
+    pub amounts: Amounts,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Timestamps {
+    /// RFC 3339 in UTC
+    #[default]
+    Utc,
+    /// RFC 3339 in the local time zone
+    Local,
+    /// Seconds since the Unix epoch
+    Unix,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Amounts {
+    /// Lumens, with all seven decimals
+    #[default]
+    Xlm,
+    /// Stroops, 1 stroop = 0.0000001 XLM
+    Stroops,
+}
+
+impl Format {
+    pub fn timestamp(&self, at: DateTime<Utc>) -> String {
+        match self.timestamps {
+            Timestamps::Utc => at.to_rfc3339(),
+            Timestamps::Local => at.with_timezone(&Local).to_rfc3339(),
+            Timestamps::Unix => at.timestamp().to_string(),
+        }
+    }
+
+    pub fn number(&self, n: impl Into<i128>) -> String {
+        let n: i128 = n.into();
+        if !self.thousands_separator {
+            return n.to_string();
+        }
+        let digits = n.unsigned_abs().to_string();
+        let mut out = String::with_capacity(digits.len() * 4 / 3 + 1);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(',');
+            }
+            out.push(digit);
+        }
+        if n < 0 {
+            out.insert(0, '-');
+        }
+        out
+    }
+
+    pub fn amount(&self, stroops: i64) -> String {
+        match self.amounts {
+            Amounts::Stroops => format!("{} stroops", self.number(stroops)),
+            Amounts::Xlm => {
+                let sign = if stroops < 0 { "-" } else { "" };
+                let stroops = i128::from(stroops).abs();
+                format!(
+                    "{sign}{}.{:07} XLM",
+                    self.number(stroops / 10_000_000),
+                    stroops % 10_000_000
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        let format = Format {
+            thousands_separator: true,
+            ..Format::default()
+        };
+        assert_eq!(format.number(0), "0");
+        assert_eq!(format.number(999), "999");
+        assert_eq!(format.number(1_000), "1,000");
+        assert_eq!(format.number(-1_234_567), "-1,234,567");
+        assert_eq!(Format::default().number(1_234_567), "1234567");
+    }
+
+    #[test]
+    fn amounts() {
+        let format = Format {
+            thousands_separator: true,
+            ..Format::default()
+        };
+        assert_eq!(format.amount(12_345_678_901), "1,234.5678901 XLM");
+        assert_eq!(format.amount(-5), "-0.0000005 XLM");
+        let format = Format {
+            amounts: Amounts::Stroops,
+            ..format
+        };
+        assert_eq!(format.amount(12_345), "12,345 stroops");
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        let format = Format {
+            timestamps: Timestamps::Unix,
+            ..Format::default()
+        };
+        let at = DateTime::parse_from_rfc3339("2024-01-31T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(format.timestamp(at), "1706702400");
+        assert_eq!(Format::default().timestamp(at), "2024-01-31T12:00:00+00:00");
+    }
+}
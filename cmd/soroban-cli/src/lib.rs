@@ -4,9 +4,12 @@
     clippy::missing_panics_doc
 )]
 pub mod commands;
+pub mod encoding;
 pub mod fee;
+pub mod format;
 pub mod log;
 pub mod network;
+pub mod output;
 pub mod rpc;
 pub mod toid;
 pub mod utils;
@@ -15,6 +18,9 @@ pub mod wasm;
 use std::path::Path;
 
 pub use commands::Root;
+// Used by generated Rust bindings, so they convert values with the same versions as the CLI
+pub use soroban_env_host::xdr;
+pub use soroban_spec_tools;
 
 pub fn parse_cmd<T>(s: &str) -> Result<T, clap::Error>
 where
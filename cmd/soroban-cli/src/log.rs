@@ -1,9 +1,15 @@
 pub mod auth;
 pub mod budget;
+pub mod diagnostic_event;
 pub mod event;
 pub mod footprint;
+pub mod profile;
+pub mod state_diff;
 
 pub use auth::*;
 pub use budget::*;
+pub use diagnostic_event::*;
 pub use event::*;
 pub use footprint::*;
+pub use profile::*;
+pub use state_diff::*;
@@ -1,7 +1,96 @@
-use soroban_env_host::xdr::{SorobanAuthorizationEntry, VecM};
+use std::fmt::Write;
+
+use soroban_env_host::xdr::{
+    AccountId, ContractExecutable, ContractIdPreimage, CreateContractArgs, Hash,
+    InvokeContractArgs, PublicKey, ScAddress, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, Uint256, VecM,
+};
 
 pub fn auth(auth: &[VecM<SorobanAuthorizationEntry>]) {
     if !auth.is_empty() {
         tracing::debug!(?auth);
     }
 }
+
+/// Describe in plain language who has to authorize what, e.g.
+///
+/// ```text
+/// GABC... must authorize:
+///   - calling swap on CDEF... with ["GABC...", 100]
+///     - which calls transfer on CGHI... with ["GABC...", "CDEF...", 100]
+/// ```
+pub fn explain_auth(entries: &[SorobanAuthorizationEntry]) -> String {
+    if entries.is_empty() {
+        return "No authorization required\n".to_string();
+    }
+    let mut out = String::new();
+    for entry in entries {
+        let signer = match &entry.credentials {
+            SorobanCredentials::SourceAccount => "The source account".to_string(),
+            SorobanCredentials::Address(SorobanAddressCredentials { address, .. }) => {
+                address_to_string(address)
+            }
+        };
+        let _ = writeln!(out, "{signer} must authorize:");
+        explain_invocation(&mut out, &entry.root_invocation, 1, "calling");
+    }
+    out
+}
+
+fn explain_invocation(
+    out: &mut String,
+    invocation: &SorobanAuthorizedInvocation,
+    depth: usize,
+    verb: &str,
+) {
+    let indent = "  ".repeat(depth);
+    let _ = match &invocation.function {
+        SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address,
+            function_name,
+            args,
+        }) => writeln!(
+            out,
+            "{indent}- {verb} {} on {} with [{}]",
+            function_name.to_string_lossy(),
+            address_to_string(contract_address),
+            args.iter()
+                .map(value_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        SorobanAuthorizedFunction::CreateContractHostFn(CreateContractArgs {
+            contract_id_preimage,
+            executable,
+        }) => {
+            let executable = match executable {
+                ContractExecutable::Wasm(hash) => format!("wasm {}", hex::encode(hash.0)),
+                ContractExecutable::Token => "the built-in token".to_string(),
+            };
+            let from = match contract_id_preimage {
+                ContractIdPreimage::Address(preimage) => {
+                    format!(" on behalf of {}", address_to_string(&preimage.address))
+                }
+                ContractIdPreimage::Asset(_) => String::new(),
+            };
+            writeln!(out, "{indent}- creating a contract from {executable}{from}")
+        }
+    };
+    for sub in invocation.sub_invocations.iter() {
+        explain_invocation(out, sub, depth + 1, "which calls");
+    }
+}
+
+fn address_to_string(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+            stellar_strkey::ed25519::PublicKey(*key).to_string()
+        }
+        ScAddress::Contract(Hash(hash)) => stellar_strkey::Contract(*hash).to_string(),
+    }
+}
+
+fn value_to_string(value: &ScVal) -> String {
+    soroban_spec_tools::to_json(value).map_or_else(|_| format!("{value:?}"), |v| v.to_string())
+}
@@ -0,0 +1,118 @@
+use itertools::Itertools;
+use soroban_env_host::xdr::{
+    ContractEventBody, ContractEventV0, DiagnosticEvent, Hash, ScError, ScErrorCode, ScVal,
+};
+use soroban_spec_tools::Spec;
+
+/// Render diagnostic events one per line, e.g.
+///
+/// ```text
+///   CDEF... fn_call transfer: ["G...", "G...", 100]
+///   CDEF... (failed) error contract error #3 (InsufficientBalance): "escalating error"
+/// ```
+///
+/// Contract error codes are named after the cases of the contract's `Error` enum when its spec
+/// is given.
+pub fn diagnostic_events(events: &[DiagnosticEvent], spec: Option<&Spec>) -> String {
+    if events.is_empty() {
+        return String::new();
+    }
+    let mut out = "\nDiagnostic events:\n".to_string();
+    for event in events {
+        let ContractEventBody::V0(ContractEventV0 { topics, data }) = &event.event.body;
+        let contract = event
+            .event
+            .contract_id
+            .as_ref()
+            .map_or("-".to_string(), |Hash(id)| {
+                stellar_strkey::Contract(*id).to_string()
+            });
+        let failed = if event.in_successful_contract_call {
+            ""
+        } else {
+            " (failed)"
+        };
+        let topics = topics.iter().map(|topic| value(topic, spec)).join(" ");
+        out.push_str(&format!(
+            "  {contract}{failed} {topics}: {}\n",
+            value(data, spec)
+        ));
+    }
+    out
+}
+
+fn value(val: &ScVal, spec: Option<&Spec>) -> String {
+    match val {
+        ScVal::Symbol(symbol) => symbol.to_string_lossy(),
+        ScVal::Error(error) => error_to_string(error, spec),
+        ScVal::Vec(Some(vals)) => format!("[{}]", vals.iter().map(|v| value(v, spec)).join(", ")),
+        val => soroban_spec_tools::to_json(val)
+            .map_or_else(|_| format!("{val:?}"), |json| json.to_string()),
+    }
+}
+
+/// Describe an error, e.g. `contract error #3 (InsufficientBalance)` or
+/// `Auth(InvalidAction): invalid action`.
+pub fn error_to_string(error: &ScError, spec: Option<&Spec>) -> String {
+    let code = match error {
+        ScError::Contract(code) => {
+            return match spec.and_then(|spec| spec.find_error_type(*code).ok()) {
+                Some(case) => format!("contract error #{code} ({})", case.name.to_string_lossy()),
+                None => format!("contract error #{code}"),
+            }
+        }
+        ScError::WasmVm(code)
+        | ScError::Context(code)
+        | ScError::Storage(code)
+        | ScError::Object(code)
+        | ScError::Crypto(code)
+        | ScError::Events(code)
+        | ScError::Budget(code)
+        | ScError::Value(code)
+        | ScError::Auth(code) => code,
+    };
+    let description = match code {
+        ScErrorCode::ArithDomain => "arithmetic overflow or value out of domain",
+        ScErrorCode::IndexBounds => "index out of bounds",
+        ScErrorCode::InvalidInput => "invalid input",
+        ScErrorCode::MissingValue => "missing value",
+        ScErrorCode::ExistingValue => "value already exists",
+        ScErrorCode::ExceededLimit => "limit exceeded",
+        ScErrorCode::InvalidAction => "invalid action",
+        ScErrorCode::InternalError => "internal error",
+        ScErrorCode::UnexpectedType => "unexpected type",
+        ScErrorCode::UnexpectedSize => "unexpected size",
+    };
+    format!("{error:?}: {description}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ContractEvent, ContractEventType, ExtensionPoint};
+
+    #[test]
+    fn failed_call() {
+        let event = DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: None,
+                type_: ContractEventType::Diagnostic,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: vec![
+                        ScVal::Symbol("error".try_into().unwrap()),
+                        ScVal::Error(ScError::Auth(ScErrorCode::InvalidAction)),
+                    ]
+                    .try_into()
+                    .unwrap(),
+                    data: ScVal::U32(7),
+                }),
+            },
+        };
+        assert_eq!(
+            diagnostic_events(&[event], None),
+            "\nDiagnostic events:\n  - (failed) error Auth(InvalidAction): invalid action: 7\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::format::Format;
+
+const BAR_WIDTH: u64 = 20;
+
+/// Describe how much of each resource an invocation uses, against the network's
+/// per-transaction limit when known, one line per resource, e.g.
+///
+/// ```text
+/// instructions     1234567 / 100000000  [                    ]   1.2%
+/// ```
+pub fn profile(resources: &[(&str, u64, Option<u64>)], format: &Format) -> String {
+    let mut out = String::new();
+    for (name, used, limit) in resources {
+        let used_str = format.number(*used);
+        let line = match limit {
+            Some(limit) if *limit > 0 => {
+                let filled = (used * BAR_WIDTH / limit).min(BAR_WIDTH);
+                let permille = used * 1000 / limit;
+                format!(
+                    "{name:<24} {used_str:>13} / {:<13} [{}{}] {:>3}.{}%",
+                    format.number(*limit),
+                    "#".repeat(usize::try_from(filled).unwrap_or_default()),
+                    " ".repeat(usize::try_from(BAR_WIDTH - filled).unwrap_or_default()),
+                    permille / 10,
+                    permille % 10,
+                )
+            }
+            _ => format!("{name:<24} {used_str:>13}"),
+        };
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars() {
+        let report = profile(
+            &[("read bytes", 500, Some(1000)), ("memory", 7, None)],
+            &Format::default(),
+        );
+        assert_eq!(
+            report,
+            "read bytes                         500 / 1000          [##########          ]  50.0%\n\
+             memory                               7\n"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use soroban_env_host::xdr::{
+    ContractDataEntry, ContractDataEntryBody, ContractDataEntryData, Hash, LedgerEntryData,
+    LedgerKey, LedgerKeyContractData, ScAddress, ScVal,
+};
+
+/// Describe how contract data changed between two sets of ledger entries, one line per key:
+/// `+` for an added entry, `-` for a removed one, `~` for a changed value, e.g.
+///
+/// ```text
+/// ~ CDEF... persistent "Counter": 1 -> 2
+/// ```
+pub fn state_diff(
+    before: &[(LedgerKey, LedgerEntryData)],
+    after: &[(LedgerKey, LedgerEntryData)],
+) -> Vec<String> {
+    let before = contract_data(before);
+    let after = contract_data(after);
+    let mut changes = vec![];
+    for (key, old) in &before {
+        match after.get(key) {
+            Some(new) if new != old => changes.push(format!(
+                "~ {}: {} -> {}",
+                key_to_string(key),
+                value_to_string(old),
+                value_to_string(new)
+            )),
+            Some(_) => {}
+            None => changes.push(format!("- {}", key_to_string(key))),
+        }
+    }
+    for (key, new) in after.iter().filter(|(key, _)| !before.contains_key(*key)) {
+        changes.push(format!(
+            "+ {}: {}",
+            key_to_string(key),
+            value_to_string(new)
+        ));
+    }
+    changes
+}
+
+fn contract_data(entries: &[(LedgerKey, LedgerEntryData)]) -> BTreeMap<LedgerKey, ScVal> {
+    entries
+        .iter()
+        .filter_map(|(key, data)| match (key, data) {
+            (
+                LedgerKey::ContractData(_),
+                LedgerEntryData::ContractData(ContractDataEntry {
+                    body: ContractDataEntryBody::DataEntry(ContractDataEntryData { val, .. }),
+                    ..
+                }),
+            ) => Some((key.clone(), val.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn key_to_string(key: &LedgerKey) -> String {
+    let LedgerKey::ContractData(LedgerKeyContractData {
+        contract,
+        key,
+        durability,
+        ..
+    }) = key
+    else {
+        return format!("{key:?}");
+    };
+    let contract = match contract {
+        ScAddress::Contract(Hash(hash)) => stellar_strkey::Contract(*hash).to_string(),
+        ScAddress::Account(account_id) => format!("{account_id:?}"),
+    };
+    let key = match key {
+        ScVal::LedgerKeyContractInstance => "instance".to_string(),
+        key => value_to_string(key),
+    };
+    let durability = format!("{durability:?}").to_lowercase();
+    format!("{contract} {durability} {key}")
+}
+
+fn value_to_string(value: &ScVal) -> String {
+    soroban_spec_tools::to_json(value).map_or_else(|_| format!("{value:?}"), |v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ContractDataDurability, ContractEntryBodyType};
+
+    fn entry(key: &str, val: u32) -> (LedgerKey, LedgerEntryData) {
+        let contract = ScAddress::Contract(Hash([0; 32]));
+        let key = ScVal::Symbol(key.try_into().unwrap());
+        (
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: contract.clone(),
+                key: key.clone(),
+                durability: ContractDataDurability::Persistent,
+                body_type: ContractEntryBodyType::DataEntry,
+            }),
+            LedgerEntryData::ContractData(ContractDataEntry {
+                contract,
+                key,
+                durability: ContractDataDurability::Persistent,
+                body: ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                    flags: 0,
+                    val: ScVal::U32(val),
+                }),
+                expiration_ledger_seq: 0,
+            }),
+        )
+    }
+
+    #[test]
+    fn diff() {
+        let contract = stellar_strkey::Contract([0; 32]);
+        let changes = state_diff(
+            &[entry("a", 1), entry("b", 1), entry("c", 1)],
+            &[entry("a", 2), entry("b", 1), entry("d", 1)],
+        );
+        assert_eq!(
+            changes,
+            [
+                format!("~ {contract} persistent \"a\": 1 -> 2"),
+                format!("- {contract} persistent \"c\""),
+                format!("+ {contract} persistent \"d\": 1"),
+            ]
+        );
+    }
+}
@@ -2,6 +2,9 @@ use sha2::{Digest, Sha256};
 
 pub static SANDBOX_NETWORK_PASSPHRASE: &str = "Local Sandbox Stellar Network ; September 2022";
 
+/// Passphrase of the public network, mainnet, where transactions cost real money.
+pub static PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
 #[must_use]
 pub fn sandbox_network_id() -> [u8; 32] {
     Sha256::digest(SANDBOX_NETWORK_PASSPHRASE.as_bytes()).into()
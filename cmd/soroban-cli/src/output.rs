@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How commands print their results, chosen with the global `--output-format`. `events`,
+/// which streams events in formats of its own, only takes its `--output` default from it, and
+/// commands that print generated code or scripts, e.g. `contract bindings` or `completion`,
+/// always print them as they are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// Human readable text, which can change between releases
+    #[default]
+    Text,
+    /// One JSON value per result, on one line, so streams of results are newline-delimited
+    /// JSON. Field names and types are stable across releases
+    Json,
+    /// Nothing but errors
+    Quiet,
+}
+
+/// Prints each result of a command in an [`Output`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Formatter {
+    output: Output,
+}
+
+impl Formatter {
+    pub fn new(output: Output) -> Self {
+        Self { output }
+    }
+
+    pub fn output(&self) -> Output {
+        self.output
+    }
+
+    /// Print a result, as `text` for people or as `json` for scripts. Trailing newlines of
+    /// `text` are trimmed and one is printed, unless `text` is empty, e.g. an empty list.
+    pub fn print(
+        &self,
+        text: impl Display,
+        json: &impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        match self.output {
+            Output::Text => {
+                let text = text.to_string();
+                let text = text.trim_end_matches('\n');
+                if !text.is_empty() {
+                    println!("{text}");
+                }
+            }
+            Output::Json => println!("{}", serde_json::to_string(json)?),
+            Output::Quiet => {}
+        }
+        Ok(())
+    }
+}
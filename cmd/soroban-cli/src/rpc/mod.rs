@@ -1,30 +1,40 @@
 use http::{uri::Authority, Uri};
 use itertools::Itertools;
 use jsonrpsee_core::params::ObjectParams;
-use jsonrpsee_core::{self, client::ClientT, rpc_params};
+use jsonrpsee_core::{self, client::ClientT, rpc_params, traits::ToRpcParams};
 use jsonrpsee_http_client::{HeaderMap, HttpClient, HttpClientBuilder};
+use serde::de::DeserializeOwned;
 use serde_aux::prelude::{deserialize_default_from_null, deserialize_number_from_string};
+use serde_json::value::RawValue;
 use soroban_env_host::xdr::DepthLimitedRead;
 use soroban_env_host::{
     budget::Budget,
     events::HostEvent,
     xdr::{
-        self, AccountEntry, AccountId, ContractDataEntry, DiagnosticEvent, Error as XdrError,
-        LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyAccount, PublicKey, ReadXdr,
-        SorobanAuthorizationEntry, Transaction, TransactionEnvelope, TransactionMeta,
-        TransactionMetaV3, TransactionResult, TransactionV1Envelope, Uint256, VecM, WriteXdr,
+        self, AccountEntry, AccountId, ConfigSettingEntry, ConfigSettingId, ContractDataEntry,
+        DiagnosticEvent, Error as XdrError, LedgerEntryData, LedgerFootprint, LedgerKey,
+        LedgerKeyAccount, LedgerKeyConfigSetting, PublicKey, ReadXdr, SorobanAuthorizationEntry,
+        Transaction, TransactionEnvelope, TransactionMeta, TransactionMetaV3, TransactionResult,
+        TransactionV1Envelope, Uint256, VecM, WriteXdr,
     },
 };
 use soroban_sdk::token;
 use std::{
+    collections::HashMap,
     fmt::Display,
+    io::Write,
     str::FromStr,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use termcolor::{Color, ColorChoice, StandardStream, WriteColor};
 use termcolor_output::colored;
-use tokio::time::sleep;
+use tokio::{sync::OnceCell, time::sleep};
 
+use crate::format::Format;
+use crate::log;
+use crate::network::PUBLIC_NETWORK_PASSPHRASE;
+use crate::output::{Formatter, Output};
 use crate::utils::{self, contract_spec};
 
 mod transaction;
@@ -32,6 +42,64 @@ use transaction::assemble;
 
 const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
 
+/// Whether and how transactions are submitted, chosen with the global `--dry-run` and
+/// `--yes`.
+#[derive(Clone, Copy, Debug)]
+pub struct Submission<'a> {
+    /// Print transactions instead of submitting them
+    pub dry_run: bool,
+    /// Submit to the public network without asking
+    pub yes: bool,
+    /// How to print the transactions of a dry run
+    pub output: Output,
+    /// Passphrase of the network the transactions are for, as configured
+    pub network_passphrase: &'a str,
+}
+
+impl Submission<'_> {
+    /// Print the transaction instead in a dry run, and ask before submitting it to the public
+    /// network. Returns whether to submit it.
+    fn check(&self, tx: &TransactionEnvelope) -> Result<bool, Error> {
+        if self.dry_run {
+            let explained = crate::commands::tx::inspect::explain(tx, None, &Format::default())
+                .unwrap_or_else(|e| format!("cannot explain the transaction: {e}\n"));
+            let xdr = tx.to_xdr_base64()?;
+            Formatter::new(self.output).print(
+                format!("{explained}Envelope: {xdr}"),
+                &serde_json::json!({ "envelope_xdr": xdr, "envelope": tx }),
+            )?;
+            return Ok(false);
+        }
+        if self.yes || self.network_passphrase != PUBLIC_NETWORK_PASSPHRASE {
+            return Ok(true);
+        }
+        if utils::confirm("You are about to submit a transaction to the PUBLIC network. Continue?")
+            .map_err(Error::Confirmation)?
+        {
+            Ok(true)
+        } else {
+            Err(Error::NotConfirmed)
+        }
+    }
+}
+
+/// What came of a command that submits transactions: its result, or nothing in a dry run,
+/// which prints the transactions instead of submitting them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Done(T),
+    DryRun,
+}
+
+impl<T> Outcome<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        match self {
+            Outcome::Done(value) => Outcome::Done(f(value)),
+            Outcome::DryRun => Outcome::DryRun,
+        }
+    }
+}
+
 pub type LogEvents = fn(
     footprint: &LedgerFootprint,
     auth: &[VecM<SorobanAuthorizationEntry>],
@@ -59,16 +127,28 @@ pub enum Error {
     JsonRpc(#[from] jsonrpsee_core::Error),
     #[error("json decoding error: {0}")]
     Serde(#[from] serde_json::Error),
-    #[error("transaction failed: {0}")]
-    TransactionFailed(String),
+    #[error("transaction failed: {result}{}", log::diagnostic_events(.events, None))]
+    TransactionFailed {
+        result: String,
+        events: Vec<DiagnosticEvent>,
+    },
     #[error("transaction submission failed: {0}")]
     TransactionSubmissionFailed(String),
     #[error("expected transaction status: {0}")]
     UnexpectedTransactionStatus(String),
     #[error("transaction submission timeout")]
     TransactionSubmissionTimeout,
-    #[error("transaction simulation failed: {0}")]
-    TransactionSimulationFailed(String),
+    #[error("transaction not submitted to the public network, confirm it or pass --yes")]
+    NotConfirmed,
+    #[error("reading confirmation: {0}")]
+    Confirmation(std::io::Error),
+    #[error("transaction sequence number was already used or is too far ahead (txBAD_SEQ)")]
+    TransactionBadSequence,
+    #[error("transaction simulation failed: {error}{}", log::diagnostic_events(.events, None))]
+    TransactionSimulationFailed {
+        error: String,
+        events: Vec<DiagnosticEvent>,
+    },
     #[error("{0} not found: {1}")]
     NotFound(String, String),
     #[error("Missing result in successful response")]
@@ -134,7 +214,22 @@ pub struct GetTransactionResponse {
         default
     )]
     pub result_meta_xdr: Option<String>,
-    // TODO: add ledger info and application order
+    /// Ledger the transaction was included in
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ledger: Option<u32>,
+    // TODO: add application order
+}
+
+/// How long to wait for a submitted transaction to be included in a ledger, unless told otherwise.
+pub const DEFAULT_SUBMISSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How a submitted transaction made it into a ledger.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SendMetrics {
+    /// Ledger the transaction was included in, if the server reported it
+    pub ledger: Option<u32>,
+    /// Number of times the transaction status was polled before it was final
+    pub polls: u32,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
@@ -168,6 +263,73 @@ pub struct GetNetworkResponse {
     pub protocol_version: u32,
 }
 
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub struct GetLatestLedgerResponse {
+    pub id: String,
+    #[serde(
+        rename = "protocolVersion",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub protocol_version: u32,
+    pub sequence: u32,
+}
+
+/// Inclusion fees, in stroops, bid by the transactions of the last ledgers.
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub struct FeeDistribution {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub min: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub mode: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p10: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p20: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p30: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p40: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p50: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p60: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p70: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p80: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p90: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p95: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub p99: u64,
+    #[serde(
+        rename = "transactionCount",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub transaction_count: u32,
+    #[serde(
+        rename = "ledgerCount",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub ledger_count: u32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub struct GetFeeStatsResponse {
+    #[serde(rename = "sorobanInclusionFee")]
+    pub soroban_inclusion_fee: FeeDistribution,
+    #[serde(rename = "inclusionFee")]
+    pub inclusion_fee: FeeDistribution,
+    #[serde(
+        rename = "latestLedger",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub latest_ledger: u32,
+}
+
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
 pub struct Cost {
     #[serde(
@@ -294,6 +456,16 @@ impl Event {
         parse_cursor(&self.id)
     }
 
+    /// Copy of the event with its close time rendered as `format` asks, for display.
+    #[must_use]
+    pub fn formatted(&self, format: &Format) -> Self {
+        let mut event = self.clone();
+        if let Ok(closed_at) = chrono::DateTime::parse_from_rfc3339(&self.ledger_closed_at) {
+            event.ledger_closed_at = format.timestamp(closed_at.with_timezone(&chrono::Utc));
+        }
+        event
+    }
+
     pub fn pretty_print(&self) -> Result<(), Box<dyn std::error::Error>> {
         let mut stdout = StandardStream::stdout(ColorChoice::Auto);
         if !stdout.supports_color() {
@@ -381,8 +553,25 @@ pub enum EventStart {
     Cursor(String),
 }
 
+/// Requests in flight, keyed by method and params, with the response once it arrives, or
+/// `None` if the request failed.
+type InFlight = HashMap<String, Arc<OnceCell<Option<serde_json::Value>>>>;
+
+/// Clones of a client share the requests in flight, so identical requests made at the same
+/// time by concurrent tasks are sent once and the response is handed to each.
+#[derive(Clone)]
 pub struct Client {
     base_url: String,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+/// Params that have already been serialized.
+struct RawParams(Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, jsonrpsee_core::Error> {
+        Ok(self.0)
+    }
 }
 
 impl Client {
@@ -413,6 +602,7 @@ impl Client {
         tracing::trace!(?uri);
         Ok(Self {
             base_url: uri.to_string(),
+            in_flight: Arc::default(),
         })
     }
 
@@ -427,6 +617,62 @@ impl Client {
             .build(url)?)
     }
 
+    /// Make a request, joining an identical one already in flight instead of sending another.
+    /// Only for requests without side effects. Errors are not shared: when the request joined
+    /// fails, it is made again, so each caller gets the error of its own request.
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl ToRpcParams,
+    ) -> Result<R, Error> {
+        let params = params.to_rpc_params()?;
+        let key = format!("{method}{}", params.as_ref().map_or("", |p| p.get()));
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let mut own = None;
+        let made = &mut own;
+        let first = params.clone();
+        let joined = cell
+            .get_or_init(|| async move {
+                let response = self.send_request(method, first).await;
+                // Requests made from now on get a fresh response.
+                self.in_flight.lock().unwrap().remove(&key);
+                let shared = response.as_ref().ok().cloned();
+                *made = Some(response);
+                shared
+            })
+            .await;
+        let response = match (own, joined) {
+            (Some(response), _) => response?,
+            (None, Some(response)) => response.clone(),
+            (None, None) => self.send_request(method, params).await?,
+        };
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Send a request, logging it and its response at debug level.
+    async fn send_request(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<serde_json::Value, Error> {
+        tracing::debug!(method, params = params.as_ref().map_or("", |p| p.get()));
+        let response = self
+            .client()?
+            .request::<serde_json::Value, _>(method, RawParams(params))
+            .await;
+        match &response {
+            Ok(response) => tracing::debug!(method, %response),
+            Err(error) => tracing::debug!(method, %error),
+        }
+        Ok(response?)
+    }
+
     pub async fn friendbot_url(&self) -> Result<String, Error> {
         let network = self.get_network().await?;
         tracing::trace!("{network:#?}");
@@ -451,7 +697,16 @@ impl Client {
 
     pub async fn get_network(&self) -> Result<GetNetworkResponse, Error> {
         tracing::trace!("Getting network");
-        Ok(self.client()?.request("getNetwork", rpc_params![]).await?)
+        self.request("getNetwork", rpc_params![]).await
+    }
+
+    pub async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, Error> {
+        self.request("getLatestLedger", rpc_params![]).await
+    }
+
+    /// Inclusion fees bid recently, for setting competitive fees. Only newer servers have it.
+    pub async fn get_fee_stats(&self) -> Result<GetFeeStatsResponse, Error> {
+        self.request("getFeeStats", rpc_params![]).await
     }
 
     pub async fn get_account(&self, address: &str) -> Result<AccountEntry, Error> {
@@ -487,21 +742,31 @@ soroban config identity fund {address} --helper-url <url>"#
         }
     }
 
-    pub async fn send_transaction(
+    /// Submit the transaction without waiting for it, returning its hash and the status the
+    /// server accepted it with.
+    pub async fn submit_transaction(
         &self,
         tx: &TransactionEnvelope,
-    ) -> Result<(TransactionResult, TransactionMeta, Vec<DiagnosticEvent>), Error> {
+        submission: &Submission<'_>,
+    ) -> Result<Outcome<(String, String)>, Error> {
+        if !submission.check(tx)? {
+            return Ok(Outcome::DryRun);
+        }
         let client = self.client()?;
         tracing::trace!(?tx);
+        let tx_xdr = tx.to_xdr_base64()?;
+        tracing::debug!(method = "sendTransaction", params = tx_xdr);
+        let response: SendTransactionResponse = client
+            .request("sendTransaction", rpc_params![tx_xdr])
+            .await
+            .map_err(|err| Error::TransactionSubmissionFailed(format!("{err:#?}")))?;
+        tracing::debug!(method = "sendTransaction", ?response);
         let SendTransactionResponse {
             hash,
             error_result_xdr,
             status,
             ..
-        } = client
-            .request("sendTransaction", rpc_params![tx.to_xdr_base64()?])
-            .await
-            .map_err(|err| Error::TransactionSubmissionFailed(format!("{err:#?}")))?;
+        } = response;
 
         if status == "ERROR" {
             let error = error_result_xdr
@@ -515,14 +780,63 @@ soroban config identity fund {address} --helper-url <url>"#
                 })
                 .map(|r| r.result);
             tracing::error!(?error);
-            return Err(Error::TransactionSubmissionFailed(format!("{:#?}", error?)));
+            let error = error?;
+            if matches!(error, xdr::TransactionResultResult::TxBadSeq) {
+                return Err(Error::TransactionBadSequence);
+            }
+            return Err(Error::TransactionSubmissionFailed(format!("{error:#?}")));
         }
+        Ok(Outcome::Done((hash, status)))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub async fn send_transaction(
+        &self,
+        tx: &TransactionEnvelope,
+        submission: &Submission<'_>,
+    ) -> Result<Outcome<(TransactionResult, TransactionMeta, Vec<DiagnosticEvent>)>, Error> {
+        Ok(self
+            .send_transaction_with_metrics(tx, submission, DEFAULT_SUBMISSION_TIMEOUT, |_, _, _| ())
+            .await?
+            .map(|(result, meta, events, _)| (result, meta, events)))
+    }
+
+    /// Like `send_transaction`, also reporting the ledger the transaction was included in and
+    /// how many times its status was polled. Gives up after `timeout`, and calls `on_status`
+    /// with the status, ledger and time since submission each time the status changes.
+    #[allow(clippy::type_complexity)]
+    pub async fn send_transaction_with_metrics(
+        &self,
+        tx: &TransactionEnvelope,
+        submission: &Submission<'_>,
+        timeout: Duration,
+        mut on_status: impl FnMut(&str, Option<u32>, Duration),
+    ) -> Result<
+        Outcome<(
+            TransactionResult,
+            TransactionMeta,
+            Vec<DiagnosticEvent>,
+            SendMetrics,
+        )>,
+        Error,
+    > {
+        let Outcome::Done((hash, status)) = self.submit_transaction(tx, submission).await? else {
+            return Ok(Outcome::DryRun);
+        };
         // even if status == "success" we need to query the transaction status in order to get the result
 
         // Poll the transaction status
         let start = Instant::now();
+        on_status(&status, None, start.elapsed());
+        let mut last_status = status;
+        let mut polls = 0;
         loop {
             let response = self.get_transaction(&hash).await?;
+            polls += 1;
+            if response.status != last_status {
+                on_status(&response.status, response.ledger, start.elapsed());
+                last_status = response.status.clone();
+            }
             match response.status.as_str() {
                 "SUCCESS" => {
                     // TODO: the caller should probably be printing this
@@ -537,21 +851,39 @@ soroban config identity fund {address} --helper-url <url>"#
                             .ok_or(Error::MissingResult)?,
                     )?;
                     let events = extract_events(&meta);
-                    return Ok((result, meta, events));
+                    let metrics = SendMetrics {
+                        ledger: response.ledger,
+                        polls,
+                    };
+                    return Ok(Outcome::Done((result, meta, events, metrics)));
                 }
                 "FAILED" => {
                     tracing::error!(?response);
-                    // TODO: provide a more elaborate error
-                    return Err(Error::TransactionSubmissionFailed(format!("{response:#?}")));
+                    let result = match &response.result_xdr {
+                        Some(xdr) => {
+                            format!("{:?}", TransactionResult::from_xdr_base64(xdr)?.result)
+                        }
+                        None => response.status.clone(),
+                    };
+                    // All of them, not only those `extract_events` keeps for a successful call
+                    let events = match &response.result_meta_xdr {
+                        Some(xdr) => match TransactionMeta::from_xdr_base64(xdr)? {
+                            TransactionMeta::V3(TransactionMetaV3 {
+                                soroban_meta: Some(meta),
+                                ..
+                            }) => meta.diagnostic_events.into(),
+                            _ => vec![],
+                        },
+                        None => vec![],
+                    };
+                    return Err(Error::TransactionFailed { result, events });
                 }
                 "NOT_FOUND" => (),
                 _ => {
                     return Err(Error::UnexpectedTransactionStatus(response.status));
                 }
             };
-            let duration = start.elapsed();
-            // TODO: parameterize the timeout instead of using a magic constant
-            if duration.as_secs() > 10 {
+            if start.elapsed() > timeout {
                 return Err(Error::TransactionSubmissionTimeout);
             }
             sleep(Duration::from_secs(1)).await;
@@ -565,13 +897,19 @@ soroban config identity fund {address} --helper-url <url>"#
         tracing::trace!(?tx);
         let base64_tx = tx.to_xdr_base64()?;
         let response: SimulateTransactionResponse = self
-            .client()?
             .request("simulateTransaction", rpc_params![base64_tx])
             .await?;
         tracing::trace!(?response);
         match response.error {
             None => Ok(response),
-            Some(e) => Err(Error::TransactionSimulationFailed(e)),
+            Some(error) => Err(Error::TransactionSimulationFailed {
+                error,
+                events: response
+                    .events
+                    .iter()
+                    .filter_map(|event| DiagnosticEvent::from_xdr_base64(event).ok())
+                    .collect(),
+            }),
         }
     }
 
@@ -592,25 +930,35 @@ soroban config identity fund {address} --helper-url <url>"#
         assemble(tx, &sim_response, log_events)
     }
 
-    pub async fn prepare_and_send_transaction(
-        &self,
-        tx_without_preflight: &Transaction,
-        key: &ed25519_dalek::Keypair,
-        network_passphrase: &str,
-        log_events: Option<LogEvents>,
-    ) -> Result<(TransactionResult, TransactionMeta, Vec<DiagnosticEvent>), Error> {
-        let unsigned_tx = self
-            .prepare_transaction(tx_without_preflight, log_events)
-            .await?;
-        let tx = utils::sign_transaction(key, &unsigned_tx, network_passphrase)?;
-        self.send_transaction(&tx).await
+    pub async fn get_transaction(&self, tx_id: &str) -> Result<GetTransactionResponse, Error> {
+        self.request("getTransaction", rpc_params![tx_id]).await
     }
 
-    pub async fn get_transaction(&self, tx_id: &str) -> Result<GetTransactionResponse, Error> {
-        Ok(self
-            .client()?
-            .request("getTransaction", rpc_params![tx_id])
-            .await?)
+    /// The network's settings, e.g. its per-transaction resource limits.
+    pub async fn get_config_settings(
+        &self,
+        ids: &[ConfigSettingId],
+    ) -> Result<Vec<ConfigSettingEntry>, Error> {
+        let keys = ids
+            .iter()
+            .map(|id| {
+                LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+                    config_setting_id: *id,
+                })
+            })
+            .collect();
+        self.get_ledger_entries(keys)
+            .await?
+            .entries
+            .unwrap_or_default()
+            .iter()
+            .map(
+                |entry| match LedgerEntryData::from_xdr_base64(&entry.xdr)? {
+                    LedgerEntryData::ConfigSetting(setting) => Ok(setting),
+                    _ => Err(Error::InvalidResponse),
+                },
+            )
+            .collect()
     }
 
     pub async fn get_ledger_entries(
@@ -625,10 +973,8 @@ soroban config identity fund {address} --helper-url <url>"#
             }
             base64_keys.push(k.to_xdr_base64().unwrap());
         }
-        Ok(self
-            .client()?
-            .request("getLedgerEntries", rpc_params![base64_keys])
-            .await?)
+        self.request("getLedgerEntries", rpc_params![base64_keys])
+            .await
     }
 
     pub async fn get_events(
@@ -667,7 +1013,7 @@ soroban config identity fund {address} --helper-url <url>"#
         oparams.insert("filters", vec![filters])?;
         oparams.insert("pagination", pagination)?;
 
-        Ok(self.client()?.request("getEvents", oparams).await?)
+        self.request("getEvents", oparams).await
     }
 
     pub async fn get_contract_data(
@@ -964,4 +1310,84 @@ mod tests {
             }
         }
     }
+
+    /// Serve JSON-RPC requests on a local port, answering each after a delay with `result`, or
+    /// with an error without one. Returns the URL and the number of requests served.
+    fn slow_server(result: Option<serde_json::Value>) -> (String, Arc<Mutex<usize>>) {
+        use std::io::{BufRead, BufReader, Read};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let served = Arc::new(Mutex::new(0));
+        let count = served.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let (result, count) = (result.clone(), count.clone());
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim().to_ascii_lowercase();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some(value) = line.strip_prefix("content-length:") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+                    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    *count.lock().unwrap() += 1;
+                    std::thread::sleep(Duration::from_millis(200));
+                    let id = &request["id"];
+                    let response = match result {
+                        Some(result) => {
+                            serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result })
+                        }
+                        None => serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": -32603, "message": "unavailable" },
+                        }),
+                    };
+                    let body = response.to_string();
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    );
+                });
+            }
+        });
+        (url, served)
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_requests_are_sent_once() {
+        let (url, served) = slow_server(Some(
+            serde_json::json!({ "id": "a", "protocolVersion": 20, "sequence": 7 }),
+        ));
+        let client = Client::new(&url).unwrap();
+        let other = client.clone();
+        let (a, b) = tokio::join!(client.get_latest_ledger(), other.get_latest_ledger());
+        assert_eq!(a.unwrap().sequence, 7);
+        assert_eq!(b.unwrap().sequence, 7);
+        assert_eq!(*served.lock().unwrap(), 1);
+
+        // Nothing is cached once the response has arrived.
+        client.get_latest_ledger().await.unwrap();
+        assert_eq!(*served.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn each_request_joining_a_failed_one_gets_its_own_error() {
+        let (url, served) = slow_server(None);
+        let client = Client::new(&url).unwrap();
+        let (a, b) = tokio::join!(client.get_latest_ledger(), client.get_latest_ledger());
+        assert!(matches!(a, Err(Error::JsonRpc(_))), "{a:?}");
+        assert!(matches!(b, Err(Error::JsonRpc(_))), "{b:?}");
+        assert_eq!(*served.lock().unwrap(), 2);
+    }
 }
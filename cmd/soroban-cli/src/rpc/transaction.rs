@@ -64,7 +64,9 @@ pub fn assemble(
                     )
                 })
                 .collect::<Result<Vec<_>, _>>()?;
-            if !auths.is_empty() {
+            // Keep auth entries that were supplied up front, e.g. already signed by other
+            // parties, rather than replacing them with the unsigned ones from simulation.
+            if body.auth.is_empty() && !auths.is_empty() {
                 body.auth = auths[0].clone();
             }
             auths
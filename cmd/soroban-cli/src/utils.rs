@@ -1,6 +1,9 @@
-use std::{collections::HashMap, io::ErrorKind, path::Path};
+use std::{
+    collections::HashMap,
+    io::{BufRead, ErrorKind, IsTerminal, Write},
+    path::Path,
+};
 
-use ed25519_dalek::Signer;
 use sha2::{Digest, Sha256};
 
 use soroban_env_host::{
@@ -10,13 +13,12 @@ use soroban_env_host::{
     xdr::{
         AccountEntry, AccountEntryExt, AccountId, Asset, ContractCodeEntry, ContractCodeEntryBody,
         ContractDataDurability, ContractDataEntry, ContractDataEntryBody, ContractDataEntryData,
-        ContractEntryBodyType, ContractExecutable, ContractIdPreimage, DecoratedSignature,
-        Error as XdrError, ExtensionPoint, Hash, HashIdPreimage, HashIdPreimageContractId,
+        ContractEntryBodyType, ContractExecutable, ContractIdPreimage, Error as XdrError,
+        ExtensionPoint, FeeBumpTransaction, Hash, HashIdPreimage, HashIdPreimageContractId,
         LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerFootprint, LedgerKey,
         LedgerKeyContractCode, LedgerKeyContractData, ScAddress, ScContractInstance, ScSpecEntry,
-        ScVal, SequenceNumber, Signature, SignatureHint, String32, Thresholds, Transaction,
-        TransactionEnvelope, TransactionSignaturePayload,
-        TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, VecM, WriteXdr,
+        ScVal, SequenceNumber, String32, Thresholds, Transaction, TransactionSignaturePayload,
+        TransactionSignaturePayloadTaggedTransaction, VecM, WriteXdr,
     },
 };
 use soroban_ledger_snapshot::LedgerSnapshot;
@@ -26,8 +28,22 @@ use stellar_strkey::ed25519::PrivateKey;
 
 use crate::network::sandbox_network_id;
 
+pub mod contract_imports;
 pub mod contract_spec;
 
+/// Ask `question` on stderr and read the answer from stdin. Without a terminal to ask on, the
+/// answer is no.
+pub fn confirm(question: &str) -> std::io::Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    eprint!("{question} [y/N] ");
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
 /// # Errors
 ///
 /// Might return an error
@@ -44,22 +60,27 @@ pub fn ledger_snapshot_read_or_default(
     match LedgerSnapshot::read_file(p) {
         Ok(snapshot) => Ok(snapshot),
         Err(soroban_ledger_snapshot::Error::Io(e)) if e.kind() == ErrorKind::NotFound => {
-            Ok(LedgerSnapshot {
-                network_id: sandbox_network_id(),
-                // These three "defaults" are not part of the actual default definition in
-                // rs-soroban-sdk, but if we don't have them the sandbox doesn't work right.
-                // Oof.
-                // TODO: Remove this hacky workaround.
-                min_persistent_entry_expiration: 4096,
-                min_temp_entry_expiration: 16,
-                max_entry_expiration: 6_312_000,
-                ..Default::default()
-            })
+            Ok(default_ledger_snapshot())
         }
         Err(e) => Err(e),
     }
 }
 
+/// An empty ledger on the sandbox network.
+pub fn default_ledger_snapshot() -> LedgerSnapshot {
+    LedgerSnapshot {
+        network_id: sandbox_network_id(),
+        // These three "defaults" are not part of the actual default definition in
+        // rs-soroban-sdk, but if we don't have them the sandbox doesn't work right.
+        // Oof.
+        // TODO: Remove this hacky workaround.
+        min_persistent_entry_expiration: 4096,
+        min_temp_entry_expiration: 16,
+        max_entry_expiration: 6_312_000,
+        ..Default::default()
+    }
+}
+
 /// # Errors
 ///
 /// Might return an error
@@ -166,23 +187,15 @@ pub fn transaction_hash(tx: &Transaction, network_passphrase: &str) -> Result<[u
 /// # Errors
 ///
 /// Might return an error
-pub fn sign_transaction(
-    key: &ed25519_dalek::Keypair,
-    tx: &Transaction,
+pub fn fee_bump_transaction_hash(
+    tx: &FeeBumpTransaction,
     network_passphrase: &str,
-) -> Result<TransactionEnvelope, XdrError> {
-    let tx_hash = transaction_hash(tx, network_passphrase)?;
-    let tx_signature = key.sign(&tx_hash);
-
-    let decorated_signature = DecoratedSignature {
-        hint: SignatureHint(key.public.to_bytes()[28..].try_into()?),
-        signature: Signature(tx_signature.to_bytes().try_into()?),
+) -> Result<[u8; 32], XdrError> {
+    let signature_payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone()),
     };
-
-    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
-        tx: tx.clone(),
-        signatures: vec![decorated_signature].try_into()?,
-    }))
+    Ok(Sha256::digest(signature_payload.to_xdr()?).into())
 }
 
 /// # Errors
@@ -369,6 +382,16 @@ pub fn contract_id_hash_from_asset(
             .try_into()
             .unwrap(),
     );
+    contract_id_hash_from_asset_and_network_id(asset, network_id)
+}
+
+/// # Errors
+///
+/// Might return an error
+pub fn contract_id_hash_from_asset_and_network_id(
+    asset: &Asset,
+    network_id: Hash,
+) -> Result<Hash, XdrError> {
     let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
         network_id,
         contract_id_preimage: ContractIdPreimage::Asset(asset.clone()),
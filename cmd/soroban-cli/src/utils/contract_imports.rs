@@ -0,0 +1,234 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use wasmparser::{BinaryReaderError, ExternalKind, Operator, Parser, Payload, TypeRef};
+
+macro_rules! host_functions {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident $args:tt -> $ret:ty }
+                )*
+            }
+        )*
+    } => {
+        /// The module and field names each host function is imported with, and the names of its
+        /// module and itself in the environment interface.
+        const HOST_FUNCTIONS: &[(&str, &str, &str, &str)] = &[
+            $($(($mod_str, $fn_str, stringify!($mod_id), stringify!($fn_id)),)*)*
+        ];
+    };
+}
+
+soroban_env_host::call_macro_with_all_host_functions! { host_functions }
+
+/// What a host function lets a contract do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Capability {
+    LedgerWrite,
+    LedgerRead,
+    Event,
+    Crypto,
+    ContractCall,
+    Auth,
+    Other,
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Capability::LedgerWrite => "Ledger writes",
+            Capability::LedgerRead => "Ledger reads",
+            Capability::Event => "Events",
+            Capability::Crypto => "Crypto",
+            Capability::ContractCall => "Contract calls",
+            Capability::Auth => "Auth",
+            Capability::Other => "Other",
+        })
+    }
+}
+
+/// The host functions a contract imports, and the ones each of its exported functions may call.
+pub struct ContractImports {
+    /// Names of the imported host functions by what they let the contract do
+    pub host_functions: BTreeMap<Capability, BTreeSet<String>>,
+    /// Names of the host functions and other exported functions that each exported function
+    /// calls, directly or through internal functions. Indirect calls are not followed, so this
+    /// can miss some.
+    pub calls: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ContractImports {
+    pub fn new(wasm: &[u8]) -> Result<Self, BinaryReaderError> {
+        // Imported functions come first in the function index space, then the defined ones
+        let mut imports = vec![];
+        let mut exports = vec![];
+        let mut callees = vec![];
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        if let TypeRef::Func(_) = import.ty {
+                            imports.push(host_function(import.module, import.name));
+                        }
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        if export.kind == ExternalKind::Func {
+                            exports.push((export.name.to_string(), export.index as usize));
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let mut calls = BTreeSet::new();
+                    for operator in body.get_operators_reader()? {
+                        if let Operator::Call { function_index } = operator? {
+                            calls.insert(function_index as usize);
+                        }
+                    }
+                    callees.push(calls);
+                }
+                _ => {}
+            }
+        }
+
+        let mut host_functions = BTreeMap::<_, BTreeSet<_>>::new();
+        for (name, capability) in &imports {
+            host_functions
+                .entry(*capability)
+                .or_default()
+                .insert(name.clone());
+        }
+        let export_names = exports
+            .iter()
+            .map(|(name, index)| (*index, name.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let calls = exports
+            .iter()
+            .map(|(name, index)| {
+                let mut called = BTreeSet::new();
+                let mut seen = BTreeSet::from([*index]);
+                let mut stack = vec![*index];
+                while let Some(function) = stack.pop() {
+                    let Some(calls) = function
+                        .checked_sub(imports.len())
+                        .and_then(|i| callees.get(i))
+                    else {
+                        continue;
+                    };
+                    for &callee in calls {
+                        if let Some((host_function, _)) = imports.get(callee) {
+                            called.insert(host_function.clone());
+                        } else if let Some(export) = export_names.get(&callee) {
+                            called.insert(export.clone());
+                        } else if seen.insert(callee) {
+                            stack.push(callee);
+                        }
+                    }
+                }
+                (name.clone(), called)
+            })
+            .collect();
+        Ok(Self {
+            host_functions,
+            calls,
+        })
+    }
+}
+
+impl Display for ContractImports {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Host Functions:")?;
+        for (capability, names) in &self.host_functions {
+            writeln!(f, " • {capability}: {}", join(names))?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Call Graph (approximate, indirect calls are not followed):"
+        )?;
+        for (name, called) in &self.calls {
+            writeln!(f, " • Function: {name}")?;
+            if !called.is_empty() {
+                writeln!(f, "     Calls: {}", join(called))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn join(names: &BTreeSet<String>) -> String {
+    names.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// The name of the function a contract imports as `module` and `field`, and what it lets the
+/// contract do. Imports that are not host functions are named `module.field`.
+fn host_function(module: &str, field: &str) -> (String, Capability) {
+    HOST_FUNCTIONS
+        .iter()
+        .find(|(mod_str, fn_str, _, _)| *mod_str == module && *fn_str == field)
+        .map_or_else(
+            || (format!("{module}.{field}"), Capability::Other),
+            |(_, _, mod_id, fn_id)| ((*fn_id).to_string(), capability(mod_id, fn_id)),
+        )
+}
+
+fn capability(module: &str, function: &str) -> Capability {
+    const LEDGER_WRITES: &[&str] = &["put_", "del_", "create_", "upload_", "update_", "bump_"];
+    match (module, function) {
+        (_, "contract_event") => Capability::Event,
+        ("call", _) => Capability::ContractCall,
+        ("crypto" | "prng", _) => Capability::Crypto,
+        ("ledger", _) if LEDGER_WRITES.iter().any(|p| function.starts_with(p)) => {
+            Capability::LedgerWrite
+        }
+        ("ledger", _) => Capability::LedgerRead,
+        (_, _) if function.contains("auth") => Capability::Auth,
+        _ => Capability::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_calls_through_internal_functions() {
+        let (module, field, _, _) = HOST_FUNCTIONS[0];
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // A `() -> ()` type
+        wasm.extend([1, 4, 1, 0x60, 0, 0]);
+        // The host function is function 0
+        let mut import = vec![1, u8::try_from(module.len()).unwrap()];
+        import.extend(module.as_bytes());
+        import.push(u8::try_from(field.len()).unwrap());
+        import.extend(field.as_bytes());
+        import.extend([0, 0]);
+        wasm.extend([2, u8::try_from(import.len()).unwrap()]);
+        wasm.extend(import);
+        // Functions 1 and 2, the first exported as `hello`
+        wasm.extend([3, 3, 2, 0, 0]);
+        wasm.extend([7, 9, 1, 5]);
+        wasm.extend(b"hello");
+        wasm.extend([0, 1]);
+        // `hello` calls function 2, which calls the host function
+        wasm.extend([10, 11, 2, 4, 0, 0x10, 2, 0x0b, 4, 0, 0x10, 0, 0x0b]);
+
+        let imports = ContractImports::new(&wasm).unwrap();
+        let (name, capability) = host_function(module, field);
+        assert_eq!(
+            imports.host_functions[&capability],
+            BTreeSet::from([name.clone()])
+        );
+        assert_eq!(imports.calls["hello"], BTreeSet::from([name]));
+    }
+}
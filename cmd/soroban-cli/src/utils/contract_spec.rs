@@ -98,6 +98,14 @@ impl ContractSpec {
         })
     }
 
+    /// The protocol and pre-release versions of the environment interface the contract was
+    /// built against.
+    pub fn interface_version(&self) -> Option<(u32, u32)> {
+        self.env_meta.iter().find_map(|entry| match entry {
+            ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(v) => Some(split_interface_version(*v)),
+        })
+    }
+
     pub fn spec_as_json_array(&self) -> Result<String, Error> {
         let spec = self
             .spec
@@ -116,7 +124,11 @@ impl Display for ContractSpec {
             for env_meta_entry in &self.env_meta {
                 match env_meta_entry {
                     ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(v) => {
-                        writeln!(f, " • Interface Version: {v}")?;
+                        let (protocol, pre_release) = split_interface_version(*v);
+                        writeln!(
+                            f,
+                            " • Interface Version: {v} (protocol {protocol}, pre-release {pre_release})"
+                        )?;
                     }
                 }
             }
@@ -130,7 +142,7 @@ impl Display for ContractSpec {
             for meta_entry in &self.meta {
                 match meta_entry {
                     ScMetaEntry::ScMetaV0(ScMetaV0 { key, val }) => {
-                        writeln!(f, " • {key}: {val}")?;
+                        writeln!(f, " • {}: {val}", meta_key_name(&key.to_string_lossy()))?;
                     }
                 }
             }
@@ -157,6 +169,22 @@ impl Display for ContractSpec {
     }
 }
 
+/// Splits an environment interface version into its protocol version, in the high 32 bits, and
+/// pre-release version, in the low 32 bits.
+#[allow(clippy::cast_possible_truncation)]
+pub fn split_interface_version(version: u64) -> (u32, u32) {
+    ((version >> 32) as u32, version as u32)
+}
+
+/// Names for the meta keys the Rust SDK sets, other keys are custom.
+pub fn meta_key_name(key: &str) -> &str {
+    match key {
+        "rsver" => "Rust Version",
+        "rssdkver" => "Soroban SDK Version",
+        key => key,
+    }
+}
+
 fn write_func(f: &mut std::fmt::Formatter<'_>, func: &ScSpecFunctionV0) -> std::fmt::Result {
     writeln!(f, " • Function: {}", func.name.to_string_lossy())?;
     if func.doc.len() > 0 {